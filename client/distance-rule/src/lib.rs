@@ -0,0 +1,200 @@
+// Copyright 2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Distance Rule
+//!
+//! This crate computes the distance rule of the Duniter Web of Trust (WoT), independently of the
+//! way the WoT is fetched.
+//!
+//! It is used by:
+//! - the **distance-oracle**, which fetches the WoT from a node through RPC to evaluate the
+//!   identities of an evaluation pool,
+//! - the **node**, which reads the WoT from its own state to serve the `distance_precheck` RPC
+//!   method.
+//!
+//! ## Distance rule
+//!
+//! A member is a referee when it issued and received at least `ceil(N^(1/max_depth))`
+//! certifications, `N` being the number of members. The distance of an identity is the fraction
+//! of the referees from which it can be reached in at most `max_depth` certification steps,
+//! itself excluded.
+
+#[cfg(test)]
+mod tests;
+
+use fnv::{FnvHashMap, FnvHashSet};
+use log::{debug, info};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use sp_runtime::Perbill;
+
+pub type IdtyIndex = u32;
+
+/// Web of trust reduced to what the distance rule needs.
+pub struct Wot {
+    /// idty -> issuers of received certs
+    received_certs: FnvHashMap<IdtyIndex, Vec<IdtyIndex>>,
+    /// referee idty -> issued certs
+    referees: FnvHashMap<IdtyIndex, u32>,
+    max_depth: u32,
+}
+
+impl Wot {
+    /// Builds the web of trust from its members and, for each identity, the issuers of the
+    /// certifications it received.
+    pub fn new(
+        members: impl IntoIterator<Item = IdtyIndex>,
+        received_certs: impl IntoIterator<Item = (IdtyIndex, Vec<IdtyIndex>)>,
+        max_depth: u32,
+    ) -> Self {
+        // member idty -> issued certs
+        let mut members: FnvHashMap<IdtyIndex, u32> =
+            members.into_iter().map(|member| (member, 0)).collect();
+
+        let min_certs_for_referee =
+            (members.len() as f32).powf(1. / (max_depth as f32)).ceil() as u32;
+
+        // idty -> received certs
+        let mut received_certs_map = FnvHashMap::<IdtyIndex, Vec<IdtyIndex>>::default();
+        for (receiver, issuers) in received_certs {
+            if (issuers.len() as u32) < min_certs_for_referee {
+                // This member is not referee
+                members.remove(&receiver);
+            }
+            for issuer in issuers.iter() {
+                if let Some(issued_certs) = members.get_mut(issuer) {
+                    *issued_certs += 1;
+                }
+            }
+            received_certs_map.insert(receiver, issuers);
+        }
+
+        // Only retain referees
+        members.retain(|_idty, issued_certs| *issued_certs >= min_certs_for_referee);
+
+        Self {
+            received_certs: received_certs_map,
+            referees: members,
+            max_depth,
+        }
+    }
+
+    /// Number of referees of the web of trust.
+    pub fn referees_count(&self) -> u32 {
+        self.referees.len() as u32
+    }
+
+    /// Calculates the fraction of accessible referees to total referees for a given identity.
+    pub fn distance(&self, idty: IdtyIndex) -> Perbill {
+        debug!("Evaluating distance for idty {idty}");
+        let mut accessible_referees = FnvHashSet::<IdtyIndex>::with_capacity_and_hasher(
+            self.referees.len(),
+            Default::default(),
+        );
+        let mut known_idties = FnvHashMap::<IdtyIndex, u32>::with_capacity_and_hasher(
+            self.referees.len(),
+            Default::default(),
+        );
+        self.distance_rule_recursive(
+            idty,
+            &mut accessible_referees,
+            &mut known_idties,
+            self.max_depth,
+        );
+        let result = if self.referees.contains_key(&idty) {
+            Perbill::from_rational(
+                accessible_referees.len() as u32 - 1,
+                self.referees.len() as u32 - 1,
+            )
+        } else {
+            Perbill::from_rational(accessible_referees.len() as u32, self.referees.len() as u32)
+        };
+        info!(
+            "Distance for idty {}: {}/{} = {}%",
+            idty,
+            accessible_referees.len(),
+            self.referees.len(),
+            result.deconstruct() as f32 / 1_000_000_000f32 * 100f32
+        );
+        result
+    }
+
+    /// Calculates the distance of the given identities in parallel, in the same order.
+    pub fn distances(&self, idties: &[IdtyIndex]) -> Vec<Perbill> {
+        idties.par_iter().map(|idty| self.distance(*idty)).collect()
+    }
+
+    /// Recursively explores the certification graph to identify referees accessible within a given depth.
+    fn distance_rule_recursive(
+        &self,
+        idty: IdtyIndex,
+        accessible_referees: &mut FnvHashSet<IdtyIndex>,
+        known_idties: &mut FnvHashMap<IdtyIndex, u32>,
+        depth: u32,
+    ) {
+        // Do not re-explore identities that have already been explored at least as deeply
+        match known_idties.entry(idty) {
+            std::collections::hash_map::Entry::Occupied(mut entry) => {
+                if *entry.get() >= depth {
+                    return;
+                } else {
+                    *entry.get_mut() = depth;
+                }
+            }
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(depth);
+            }
+        }
+
+        // If referee, add it to the list
+        if self.referees.contains_key(&idty) {
+            accessible_referees.insert(idty);
+        }
+
+        // If reached the maximum distance, stop exploring
+        if depth == 0 {
+            return;
+        }
+
+        // Explore certifiers
+        for &certifier in self.received_certs.get(&idty).unwrap_or(&vec![]).iter() {
+            self.distance_rule_recursive(certifier, accessible_referees, known_idties, depth - 1);
+        }
+    }
+}
+
+/// Evaluates the distance of the given identities in a web of trust.
+///
+/// The web of trust is given by its members and, for each identity, the issuers of the
+/// certifications it received. The result is the fraction of referees accessible within
+/// `max_depth` steps, in the same order as `idties`.
+pub fn evaluate_distances(
+    members: impl IntoIterator<Item = IdtyIndex>,
+    received_certs: impl IntoIterator<Item = (IdtyIndex, Vec<IdtyIndex>)>,
+    max_depth: u32,
+    idties: &[IdtyIndex],
+) -> Vec<Perbill> {
+    Wot::new(members, received_certs, max_depth).distances(idties)
+}
+
+/// Decodes the identity index of a storage key of a map hashed with `Twox64Concat`, such as
+/// `Membership::Membership` or `Certification::CertsByReceiver`.
+pub fn idty_index_from_storage_key(storage_key: &[u8]) -> IdtyIndex {
+    u32::from_le_bytes(
+        storage_key[40..44]
+            .try_into()
+            .expect("Cannot convert StorageKey to IdtyIndex"),
+    )
+}
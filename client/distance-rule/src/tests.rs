@@ -0,0 +1,62 @@
+// Copyright 2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+
+#[test]
+fn test_evaluate_distances() {
+    // clique of 3 members, identity 4 certified by two of them, identity 5 isolated
+    let received_certs = vec![
+        (1, vec![2, 3]),
+        (2, vec![1, 3]),
+        (3, vec![1, 2]),
+        (4, vec![1, 2]),
+    ];
+    let results = evaluate_distances([1, 2, 3], received_certs, 5, &[1, 4, 5]);
+    assert_eq!(
+        results,
+        vec![Perbill::one(), Perbill::one(), Perbill::zero()]
+    );
+}
+
+// chain of members 1 <- 2 <- 3 <- 4 <- 1, all certified by and certifying member 5,
+// identity 6 certified by 1
+fn chain_received_certs() -> Vec<(IdtyIndex, Vec<IdtyIndex>)> {
+    vec![
+        (1, vec![2, 5]),
+        (2, vec![3, 5]),
+        (3, vec![4, 5]),
+        (4, vec![1, 5]),
+        (5, vec![1, 2, 3, 4]),
+        (6, vec![1]),
+    ]
+}
+
+#[test]
+fn test_distance_depth() {
+    // with a depth of 1, a referee must issue and receive 5 certs
+    let wot = Wot::new([1, 2, 3, 4, 5], chain_received_certs(), 1);
+    assert_eq!(wot.referees_count(), 0);
+
+    // with a depth of 2, a referee must issue and receive 3 certs
+    let wot = Wot::new([1, 2, 3, 4, 5], chain_received_certs(), 2);
+    assert_eq!(wot.referees_count(), 1);
+    // 6 reaches referee 5 in two steps, 7 is unknown
+    assert_eq!(
+        wot.distances(&[6, 7]),
+        vec![Perbill::one(), Perbill::zero()]
+    );
+}
@@ -33,11 +33,20 @@
 //! - Retrieves **period index** and **evaluation results** from the storage and file system.
 //! - Determines whether the computation results for the current period have already been published.
 //! - Reads and parses evaluation result files when available, providing the necessary data to the runtime.
+//!
+//! ## Embedded oracle
+//!
+//! A node can also run the oracle in its own process. The results are then shared in memory with
+//! this inherent data provider through [`EvaluationResults`], and take precedence over the files.
 
 use frame_support::pallet_prelude::*;
 use sc_client_api::{ProvideUncles, StorageKey, StorageProvider};
 use sp_runtime::{AccountId32, generic::BlockId, traits::Block as BlockT};
-use std::path::PathBuf;
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
 /// The file version that should match the distance oracle one.
 /// This ensures that the smith avoids accidentally submitting invalid data
@@ -53,12 +62,48 @@ pub enum Error<B: BlockT> {
     NoHashForBlockId(BlockId<B>),
 }
 
+/// Distance evaluation results computed in the node process, by period index of publication.
+#[derive(Clone, Default)]
+pub struct EvaluationResults(Arc<Mutex<BTreeMap<u32, sp_distance::ComputationResult>>>);
+
+impl EvaluationResults {
+    /// Stores the result to publish during the given period.
+    ///
+    /// The results of the periods before the previous one are dropped.
+    pub fn insert(&self, period_index: u32, result: sp_distance::ComputationResult) {
+        let mut results = self.0.lock().expect("poisoned lock");
+        results.retain(|&period, _| period + 1 >= period_index);
+        results.insert(period_index, result);
+    }
+
+    /// Whether a result is stored for the given period.
+    pub fn contains(&self, period_index: u32) -> bool {
+        self.0
+            .lock()
+            .expect("poisoned lock")
+            .contains_key(&period_index)
+    }
+
+    /// Returns the result to publish during the given period, if any.
+    pub fn get(&self, period_index: u32) -> Option<sp_distance::ComputationResult> {
+        self.0
+            .lock()
+            .expect("poisoned lock")
+            .get(&period_index)
+            .cloned()
+    }
+}
+
 /// Create a new [`sp_distance::InherentDataProvider`] at the given block.
+///
+/// The result of the embedded oracle is used if any, otherwise the result file of the
+/// distance oracle.
 pub fn create_distance_inherent_data_provider<B, C, Backend>(
     client: &C,
     parent: B::Hash,
     distance_dir: PathBuf,
     owner_keys: &[sp_core::sr25519::Public],
+    embedded_results: &EvaluationResults,
 ) -> sp_distance::InherentDataProvider<IdtyIndex>
 where
     B: BlockT,
@@ -155,6 +200,12 @@ where
         return sp_distance::InherentDataProvider::<IdtyIndex>::new(None);
     }
 
+    // Use the result of the embedded oracle, if any
+    if let Some(evaluation_result) = embedded_results.get(period_index) {
+        log::info!("🧙 [distance inherent] Providing evaluation result of the embedded oracle");
+        return sp_distance::InherentDataProvider::<IdtyIndex>::new(Some(evaluation_result));
+    }
+
     // Read evaluation result from file, if it exists
     log::debug!(
         "🧙 [distance inherent] Reading evaluation result from file {:?}",
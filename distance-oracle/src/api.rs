@@ -126,7 +126,7 @@ impl MemberIter {
             .next()
             .await
             .transpose()
-            .map(|i| i.map(|j| idty_id_from_storage_key(&j.key_bytes)))
+            .map(|i| i.map(|j| dc_distance_rule::idty_index_from_storage_key(&j.key_bytes)))
     }
 }
 
@@ -159,18 +159,13 @@ impl CertIter {
     pub async fn next(
         &mut self,
     ) -> Result<Option<(IdtyIndex, Vec<(IdtyIndex, u32)>)>, subxt::error::Error> {
-        self.0
-            .next()
-            .await
-            .transpose()
-            .map(|i| i.map(|j| (idty_id_from_storage_key(&j.key_bytes), j.value)))
+        self.0.next().await.transpose().map(|i| {
+            i.map(|j| {
+                (
+                    dc_distance_rule::idty_index_from_storage_key(&j.key_bytes),
+                    j.value,
+                )
+            })
+        })
     }
 }
-
-fn idty_id_from_storage_key(storage_key: &[u8]) -> IdtyIndex {
-    u32::from_le_bytes(
-        storage_key[40..44]
-            .try_into()
-            .expect("Cannot convert StorageKey to IdtyIndex"),
-    )
-}
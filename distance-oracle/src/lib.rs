@@ -25,9 +25,10 @@
 //! The Distance Oracle is organized into the following modules:
 //!
 //! 1. **`/distance-oracle/`**: Contains the main binary for executing the distance computation.
-//! 2. **`/primitives/distance/`**: Defines primitive types shared between the client and runtime.
-//! 3. **`/client/distance/`**: Exposes the `create_distance_inherent_data_provider`, which feeds data into the runtime through the Inherent Data Provider.
-//! 4. **`/pallets/distance/`**: A pallet that handles distance-related types, traits, storage, and hooks in the runtime, coordinating the interaction between the oracle, inherent data provider, and runtime.
+//! 2. **`/client/distance-rule/`**: Computes the distance rule on a web of trust, whatever its source. It is shared with the node, which serves the `distance_precheck` RPC method.
+//! 3. **`/primitives/distance/`**: Defines primitive types shared between the client and runtime.
+//! 4. **`/client/distance/`**: Exposes the `create_distance_inherent_data_provider`, which feeds data into the runtime through the Inherent Data Provider.
+//! 5. **`/pallets/distance/`**: A pallet that handles distance-related types, traits, storage, and hooks in the runtime, coordinating the interaction between the oracle, inherent data provider, and runtime.
 //!
 //! ## How it works
 //! - The **Distance Pallet** adds an evaluation request at period `i` in the runtime.
@@ -59,9 +60,7 @@ pub use mock as api;
 use api::{AccountId, EvaluationPool, H256, IdtyIndex};
 
 use codec::Encode;
-use fnv::{FnvHashMap, FnvHashSet};
 use log::{debug, info, warn};
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::{io::Write, path::PathBuf};
 
 /// The file version must match the version used by the inherent data provider.
@@ -216,58 +215,41 @@ pub async fn compute_distance_evaluation(
 
     let max_depth = api::max_referee_distance(client).await;
 
-    // member idty -> issued certs
-    let mut members = FnvHashMap::<IdtyIndex, u32>::default();
-
+    let mut members = Vec::new();
     let mut members_iter = api::member_iter(client, evaluation_block).await;
     while let Some(member_idty) = members_iter
         .next()
         .await
         .expect("Cannot fetch next members")
     {
-        members.insert(member_idty, 0);
+        members.push(member_idty);
     }
 
-    let min_certs_for_referee = (members.len() as f32).powf(1. / (max_depth as f32)).ceil() as u32;
-
     // idty -> received certs
-    let mut received_certs = FnvHashMap::<IdtyIndex, Vec<IdtyIndex>>::default();
-
+    let mut received_certs = Vec::new();
     let mut certs_iter = api::cert_iter(client, evaluation_block).await;
     while let Some((receiver, issuers)) = certs_iter
         .next()
         .await
         .expect("Cannot fetch next certification")
     {
-        if (issuers.len() as u32) < min_certs_for_referee {
-            // This member is not referee
-            members.remove(&receiver);
-        }
-        for (issuer, _removable_on) in issuers.iter() {
-            if let Some(issued_certs) = members.get_mut(issuer) {
-                *issued_certs += 1;
-            }
-        }
-        received_certs.insert(
+        received_certs.push((
             receiver,
             issuers
                 .into_iter()
                 .map(|(issuer, _removable_on)| issuer)
                 .collect(),
-        );
+        ));
     }
 
-    // Only retain referees
-    members.retain(|_idty, issued_certs| *issued_certs >= min_certs_for_referee);
-    let referees = members;
-
-    let evaluation = evaluation_pool
+    let idties: Vec<IdtyIndex> = evaluation_pool
         .evaluations
         .0
-        .as_slice()
-        .par_iter()
-        .map(|(idty, _)| distance_rule(&received_certs, &referees, max_depth, *idty))
+        .iter()
+        .map(|(idty, _)| *idty)
         .collect();
+    let evaluation =
+        dc_distance_rule::evaluate_distances(members, received_certs, max_depth, &idties);
 
     Some((evaluation, current_period_index, evaluation_result_path))
 }
@@ -336,87 +318,3 @@ async fn prepare_evaluation_context(
         evaluation_result_path,
     ))
 }
-
-/// Recursively explores the certification graph to identify referees accessible within a given depth.
-fn distance_rule_recursive(
-    received_certs: &FnvHashMap<IdtyIndex, Vec<IdtyIndex>>,
-    referees: &FnvHashMap<IdtyIndex, u32>,
-    idty: IdtyIndex,
-    accessible_referees: &mut FnvHashSet<IdtyIndex>,
-    known_idties: &mut FnvHashMap<IdtyIndex, u32>,
-    depth: u32,
-) {
-    // Do not re-explore identities that have already been explored at least as deeply
-    match known_idties.entry(idty) {
-        std::collections::hash_map::Entry::Occupied(mut entry) => {
-            if *entry.get() >= depth {
-                return;
-            } else {
-                *entry.get_mut() = depth;
-            }
-        }
-        std::collections::hash_map::Entry::Vacant(entry) => {
-            entry.insert(depth);
-        }
-    }
-
-    // If referee, add it to the list
-    if referees.contains_key(&idty) {
-        accessible_referees.insert(idty);
-    }
-
-    // If reached the maximum distance, stop exploring
-    if depth == 0 {
-        return;
-    }
-
-    // Explore certifiers
-    for &certifier in received_certs.get(&idty).unwrap_or(&vec![]).iter() {
-        distance_rule_recursive(
-            received_certs,
-            referees,
-            certifier,
-            accessible_referees,
-            known_idties,
-            depth - 1,
-        );
-    }
-}
-
-/// Calculates the fraction of accessible referees to total referees for a given identity.
-fn distance_rule(
-    received_certs: &FnvHashMap<IdtyIndex, Vec<IdtyIndex>>,
-    referees: &FnvHashMap<IdtyIndex, u32>,
-    depth: u32,
-    idty: IdtyIndex,
-) -> sp_runtime::Perbill {
-    debug!("Evaluating distance for idty {idty}");
-    let mut accessible_referees =
-        FnvHashSet::<IdtyIndex>::with_capacity_and_hasher(referees.len(), Default::default());
-    let mut known_idties =
-        FnvHashMap::<IdtyIndex, u32>::with_capacity_and_hasher(referees.len(), Default::default());
-    distance_rule_recursive(
-        received_certs,
-        referees,
-        idty,
-        &mut accessible_referees,
-        &mut known_idties,
-        depth,
-    );
-    let result = if referees.contains_key(&idty) {
-        sp_runtime::Perbill::from_rational(
-            accessible_referees.len() as u32 - 1,
-            referees.len() as u32 - 1,
-        )
-    } else {
-        sp_runtime::Perbill::from_rational(accessible_referees.len() as u32, referees.len() as u32)
-    };
-    info!(
-        "Distance for idty {}: {}/{} = {}%",
-        idty,
-        accessible_referees.len(),
-        referees.len(),
-        result.deconstruct() as f32 / 1_000_000_000f32 * 100f32
-    );
-    result
-}
@@ -0,0 +1,11 @@
+Feature: Multisig
+
+  Scenario: two of three signatories transfer from a multisig account
+    When charlie sends 5 ĞD to the multisig of alice, bob and charlie with threshold 2
+    Then the multisig of alice, bob and charlie with threshold 2 should have 5 ĞD
+    When alice approves a transfer of 3 ĞD to dave from the multisig of alice, bob and charlie with threshold 2
+    # The threshold is not reached yet
+    Then dave should have 0 ĞD
+    When bob approves a transfer of 3 ĞD to dave from the multisig of alice, bob and charlie with threshold 2
+    Then dave should have 3 ĞD
+    Then the multisig of alice, bob and charlie with threshold 2 should have 2 ĞD
@@ -0,0 +1,15 @@
+Feature: Proxy
+
+  Scenario: a proxy transfers on behalf of its delegator
+    When alice adds bob as transfer only proxy
+    When bob sends 3 ĞD to dave on behalf of alice
+    Then dave should have 3 ĞD
+    When alice removes bob as transfer only proxy
+    When bob sends 3 ĞD to dave on behalf of alice
+    # Bob is no longer a proxy of Alice
+    Then dave should have 3 ĞD
+
+  Scenario: a proxy can not dispatch calls out of its type
+    When alice adds bob as distance request proxy
+    When bob sends 3 ĞD to dave on behalf of alice
+    Then dave should have 0 ĞD
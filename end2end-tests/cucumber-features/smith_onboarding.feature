@@ -0,0 +1,33 @@
+@genesis.smith
+
+Feature: Smith onboarding
+#
+# Alice, Bob and Charlie are smiths, Alice being the only online authority.
+# Dave is a member of the WoT. A single smith certification is required to become smith.
+#
+# A session lasts 30 blocks. An authority going online or offline is taken into account at
+# the next session, and enters or leaves the validator set one session later.
+
+  Scenario: a member is invited as smith and joins the validators authoring blocks
+    When alice invites dave to become smith
+    Then dave smith status should be invited
+    When dave accepts the smith invitation
+    Then dave smith status should be pending
+    When alice issues a smith certification to dave
+    Then dave smith status should be smith
+    Then dave authority status should be offline
+    When dave sets his session keys
+    When dave goes online
+    Then dave authority status should be incoming
+    Then dave should not be a validator
+    When 30 blocks later
+    Then dave authority status should be online
+    Then dave should not be a validator
+    When 30 blocks later
+    # Dave is now part of the validator set, and is assigned BABE slots to author blocks
+    Then dave should be a validator
+    Then alice should be a validator
+    When dave goes offline
+    Then dave authority status should be outgoing
+    When 30 blocks later
+    Then dave authority status should be offline
@@ -0,0 +1,23 @@
+Feature: Technical committee
+#
+# Alice, Bob and Charlie are members of the technical committee.
+# Calls dispatched as root through the upgrade origin require two thirds of the committee.
+
+  Scenario: the technical committee approves a runtime call
+    When alice proposes to the technical committee to set the balance of dave to 50 ĞD
+    Then the technical committee should have 1 open proposal
+    # Alice vote is counted with her proposal
+    When bob votes yes on the last technical committee proposal
+    Then dave should have 0 ĞD
+    When charlie closes the last technical committee proposal
+    Then the technical committee should have 0 open proposals
+    Then dave should have 50 ĞD
+
+  Scenario: the technical committee rejects a runtime call
+    When alice proposes to the technical committee to set the balance of dave to 50 ĞD
+    When bob votes no on the last technical committee proposal
+    When charlie votes no on the last technical committee proposal
+    # The two thirds can not be reached anymore
+    When alice closes the last technical committee proposal
+    Then the technical committee should have 0 open proposals
+    Then dave should have 0 ĞD
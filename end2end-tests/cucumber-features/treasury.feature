@@ -0,0 +1,11 @@
+Feature: Treasury
+
+  Scenario: an account funds the treasury
+    Given dave has 10 ĞD
+    # The treasury is endowed with 1 ĞD by the treasury funder at genesis
+    Then treasury should contain 1 ĞD
+    When dave funds the treasury with 5 ĞD
+    # 1000 - 500 - 2(one transaction fee, not a member)
+    Then dave should have 498 cĞD
+    # 100 + 500 + 2(transaction fee of dave)
+    Then treasury should contain 602 cĞD
@@ -13,3 +13,15 @@ Feature: Universal Dividend
         # Not members
         Then eve should not be eligible to UD
         Then ferdie should not be eligible to UD
+
+    Scenario: Eligibility after several months
+        # the first block schedules the first UD one UD period later
+        When 1 block later
+        Then 0 universal dividends should have been created
+
+        # one UD is created per block
+        When 6 months later
+        Then 6 universal dividends should have been created
+
+        Then alice should be eligible to UD
+        Then eve should not be eligible to UD
@@ -0,0 +1,177 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{gdev, *};
+use crate::common::pair_signer::PairSigner;
+use sp_core::{Pair, ed25519};
+use sp_keyring::sr25519::Keyring;
+use subxt::ext::codec::Decode;
+
+type SessionKeys = gdev::runtime_types::gdev_runtime::opaque::SessionKeys;
+
+/// Status of a smith in the set of authorities.
+#[derive(Debug, PartialEq, Eq)]
+pub enum AuthorityStatus {
+    Incoming,
+    Online,
+    Outgoing,
+    Offline,
+}
+
+impl FromStr for AuthorityStatus {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<Self, String> {
+        match input {
+            "incoming" => Ok(Self::Incoming),
+            "online" => Ok(Self::Online),
+            "outgoing" => Ok(Self::Outgoing),
+            "offline" => Ok(Self::Offline),
+            _ => Err(format!("'{input}' does not match an authority status")),
+        }
+    }
+}
+
+// session keys derived from the keyring seed, in the order of the runtime opaque keys:
+// babe, grandpa, im_online, authority_discovery
+fn session_keys(who: Keyring) -> Result<SessionKeys> {
+    let sr25519_key = who.to_raw_public();
+    let ed25519_key = ed25519::Pair::from_string(&who.to_seed(), None)
+        .map_err(|e| anyhow::anyhow!("invalid seed: {e:?}"))?
+        .public();
+    let bytes = [
+        &sr25519_key[..],
+        ed25519_key.as_ref(),
+        &sr25519_key[..],
+        &sr25519_key[..],
+    ]
+    .concat();
+
+    Ok(SessionKeys::decode(&mut &bytes[..])?)
+}
+
+pub async fn set_session_keys(client: &FullClient, who: Keyring) -> Result<()> {
+    let signer = PairSigner::new(who.pair());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx()
+                    .authority_members()
+                    .set_session_keys(session_keys(who)?),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn go_online(client: &FullClient, who: Keyring) -> Result<()> {
+    let signer = PairSigner::new(who.pair());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().authority_members().go_online(),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn go_offline(client: &FullClient, who: Keyring) -> Result<()> {
+    let signer = PairSigner::new(who.pair());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().authority_members().go_offline(),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_authority_status(client: &FullClient, who: Keyring) -> Result<AuthorityStatus> {
+    let account: subxt::utils::AccountId32 = who.to_raw_public().into();
+    let storage = client.client.storage().at_latest().await?;
+    let member_id = storage
+        .fetch(
+            &gdev::storage()
+                .identity()
+                .identity_index_of(account.clone()),
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("identity {account} has no associated index"))?;
+
+    let status = if storage
+        .fetch_or_default(&gdev::storage().authority_members().incoming_authorities())
+        .await?
+        .contains(&member_id)
+    {
+        AuthorityStatus::Incoming
+    } else if storage
+        .fetch_or_default(&gdev::storage().authority_members().outgoing_authorities())
+        .await?
+        .contains(&member_id)
+    {
+        AuthorityStatus::Outgoing
+    } else if storage
+        .fetch_or_default(&gdev::storage().authority_members().online_authorities())
+        .await?
+        .contains(&member_id)
+    {
+        AuthorityStatus::Online
+    } else {
+        AuthorityStatus::Offline
+    };
+
+    Ok(status)
+}
+
+// whether the account is in the current validator set, taking part in block authoring
+pub async fn is_validator(client: &FullClient, who: Keyring) -> Result<bool> {
+    let account: subxt::utils::AccountId32 = who.to_raw_public().into();
+
+    Ok(client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch_or_default(&gdev::storage().session().validators())
+        .await?
+        .contains(&account))
+}
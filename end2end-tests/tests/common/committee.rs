@@ -0,0 +1,185 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    gdev,
+    gdev::runtime_types::{gdev_runtime::RuntimeCall, pallet_balances, pallet_upgrade_origin},
+    *,
+};
+use crate::common::pair_signer::PairSigner;
+use sp_keyring::sr25519::Keyring;
+use subxt::{
+    ext::codec::Encode,
+    utils::{H256, MultiAddress},
+};
+
+type Weight = gdev::runtime_types::sp_weights::weight_v2::Weight;
+
+// upper bound of the weight of the proposals executed on close
+const PROPOSAL_WEIGHT_BOUND: Weight = Weight {
+    ref_time: 100_000_000_000,
+    proof_size: 100_000,
+};
+
+/// Propose a call to be dispatched as root through the upgrade origin.
+///
+/// The threshold of the motion is the two thirds of the committee required by the upgrade
+/// origin, the proposer vote counting as the first aye.
+pub async fn propose_as_root(client: &FullClient, from: Keyring, call: RuntimeCall) -> Result<()> {
+    let signer = PairSigner::new(from.pair());
+    let members = client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch_or_default(&gdev::storage().technical_committee().members())
+        .await?;
+    let threshold = (2 * members.len() as u32).div_ceil(3);
+    let proposal =
+        RuntimeCall::UpgradeOrigin(pallet_upgrade_origin::pallet::Call::dispatch_as_root {
+            call: Box::new(call),
+        });
+    let length_bound = proposal.encode().len() as u32;
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx()
+                    .technical_committee()
+                    .propose(threshold, proposal, length_bound),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Propose to set the free balance of an account.
+pub async fn propose_set_balance(
+    client: &FullClient,
+    from: Keyring,
+    who: Keyring,
+    amount: u64,
+) -> Result<()> {
+    propose_as_root(
+        client,
+        from,
+        RuntimeCall::Balances(pallet_balances::pallet::Call::force_set_balance {
+            who: MultiAddress::Id(who.to_raw_public().into()),
+            new_free: amount,
+        }),
+    )
+    .await
+}
+
+pub async fn vote(client: &FullClient, from: Keyring, approve: bool) -> Result<()> {
+    let signer = PairSigner::new(from.pair());
+    let (proposal_hash, index) = last_proposal(client).await?;
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx()
+                    .technical_committee()
+                    .vote(proposal_hash, index, approve),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn close(client: &FullClient, from: Keyring) -> Result<()> {
+    let signer = PairSigner::new(from.pair());
+    let (proposal_hash, index) = last_proposal(client).await?;
+    let length_bound = client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(
+            &gdev::storage()
+                .technical_committee()
+                .proposal_of(proposal_hash),
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("proposal {proposal_hash:?} not found"))?
+        .encode()
+        .len() as u32;
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().technical_committee().close(
+                    proposal_hash,
+                    index,
+                    PROPOSAL_WEIGHT_BOUND,
+                    length_bound,
+                ),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// get the hashes of the open proposals
+pub async fn proposals(client: &FullClient) -> Result<Vec<H256>> {
+    Ok(client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch_or_default(&gdev::storage().technical_committee().proposals())
+        .await?
+        .0)
+}
+
+// get the hash and index of the last open proposal
+async fn last_proposal(client: &FullClient) -> Result<(H256, u32)> {
+    let proposal_hash = *proposals(client)
+        .await?
+        .last()
+        .ok_or_else(|| anyhow::anyhow!("no open proposal"))?;
+    let votes = client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&gdev::storage().technical_committee().voting(proposal_hash))
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("no votes for proposal {proposal_hash:?}"))?;
+
+    Ok((proposal_hash, votes.index))
+}
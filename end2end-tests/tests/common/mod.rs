@@ -16,11 +16,17 @@
 
 #![allow(clippy::enum_variant_names, dead_code, unused_imports)]
 
+pub mod authority;
 pub mod balances;
 pub mod cert;
+pub mod committee;
 pub mod distance;
 pub mod identity;
+pub mod multisig;
 pub mod oneshot;
+pub mod proxy;
+pub mod smith;
+pub mod treasury;
 
 #[subxt::subxt(
     runtime_metadata_path = "../resources/gdev_metadata.scale",
@@ -200,6 +206,24 @@ pub async fn create_empty_block(client: &RpcClient) -> Result<()> {
     Ok(())
 }
 
+/// Creates `count` blocks, each `step` milliseconds after the previous one.
+pub async fn make_blocks(client: &RpcClient, count: u32, step: u64) -> Result<()> {
+    let _: Value = client
+        .request("dev_makeBlocks", rpc_params![count, step])
+        .await?;
+
+    Ok(())
+}
+
+/// Creates a block at the given unix timestamp in milliseconds.
+pub async fn advance_to(client: &RpcClient, timestamp: u64) -> Result<()> {
+    let _: Value = client
+        .request("dev_advanceTo", rpc_params![timestamp])
+        .await?;
+
+    Ok(())
+}
+
 pub async fn create_block_with_extrinsic(
     client: &RpcClient,
     extrinsic: SubmittableExtrinsic,
@@ -0,0 +1,149 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    gdev,
+    gdev::runtime_types::{gdev_runtime::RuntimeCall, pallet_balances},
+    *,
+};
+use crate::common::pair_signer::PairSigner;
+use sp_core::hashing::blake2_256;
+use sp_keyring::sr25519::Keyring;
+use subxt::{
+    ext::codec::Encode,
+    utils::{AccountId32, MultiAddress},
+};
+
+type Weight = gdev::runtime_types::sp_weights::weight_v2::Weight;
+
+// upper bound of the weight of the calls executed on the last approval
+const CALL_WEIGHT_BOUND: Weight = Weight {
+    ref_time: 100_000_000_000,
+    proof_size: 100_000,
+};
+
+/// Account of the multisig of the given signatories, as derived by the multisig pallet.
+pub fn multisig_account(signatories: &[Keyring], threshold: u16) -> AccountId32 {
+    let mut who: Vec<AccountId32> = signatories
+        .iter()
+        .map(|signatory| signatory.to_raw_public().into())
+        .collect();
+    who.sort();
+
+    AccountId32((b"modlpy/utilisuba", who, threshold).using_encoded(blake2_256))
+}
+
+pub async fn transfer_to_multisig(
+    client: &FullClient,
+    from: Keyring,
+    amount: u64,
+    signatories: &[Keyring],
+    threshold: u16,
+) -> Result<()> {
+    let from = PairSigner::new(from.pair());
+    let to = MultiAddress::Id(multisig_account(signatories, threshold));
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().balances().transfer_keep_alive(to, amount),
+                &from,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Approve a call of the multisig, which is dispatched when the threshold is reached.
+pub async fn approve(
+    client: &FullClient,
+    from: Keyring,
+    signatories: &[Keyring],
+    threshold: u16,
+    call: RuntimeCall,
+) -> Result<()> {
+    let multisig = multisig_account(signatories, threshold);
+    // the first approval is the timepoint of the multisig operation
+    let maybe_timepoint = client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(
+            &gdev::storage()
+                .multisig()
+                .multisigs(multisig, call.using_encoded(blake2_256)),
+        )
+        .await?
+        .map(|multisig| multisig.when);
+    let mut other_signatories: Vec<AccountId32> = signatories
+        .iter()
+        .filter(|signatory| **signatory != from)
+        .map(|signatory| signatory.to_raw_public().into())
+        .collect();
+    other_signatories.sort();
+    let signer = PairSigner::new(from.pair());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().multisig().as_multi(
+                    threshold,
+                    other_signatories,
+                    maybe_timepoint,
+                    call,
+                    CALL_WEIGHT_BOUND,
+                ),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Approve a transfer from the multisig.
+pub async fn approve_transfer(
+    client: &FullClient,
+    from: Keyring,
+    signatories: &[Keyring],
+    threshold: u16,
+    amount: u64,
+    to: Keyring,
+) -> Result<()> {
+    approve(
+        client,
+        from,
+        signatories,
+        threshold,
+        RuntimeCall::Balances(pallet_balances::pallet::Call::transfer_keep_alive {
+            dest: MultiAddress::Id(to.to_raw_public().into()),
+            value: amount,
+        }),
+    )
+    .await
+}
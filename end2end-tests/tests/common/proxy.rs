@@ -0,0 +1,143 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+    gdev,
+    gdev::runtime_types::{
+        gdev_runtime::{ProxyType, RuntimeCall},
+        pallet_balances,
+    },
+    *,
+};
+use crate::common::pair_signer::PairSigner;
+use sp_keyring::sr25519::Keyring;
+use subxt::utils::MultiAddress;
+
+impl FromStr for ProxyType {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<ProxyType, String> {
+        match input {
+            "almost any" => Ok(ProxyType::AlmostAny),
+            "transfer only" => Ok(ProxyType::TransferOnly),
+            "cancel proxy" => Ok(ProxyType::CancelProxy),
+            "technical committee propose" => Ok(ProxyType::TechnicalCommitteePropose),
+            "certification renewal" => Ok(ProxyType::CertificationRenewal),
+            "distance request" => Ok(ProxyType::DistanceRequest),
+            "smith operations" => Ok(ProxyType::SmithOperations),
+            _ => Err(format!("'{input}' does not match a proxy type")),
+        }
+    }
+}
+
+pub async fn add_proxy(
+    client: &FullClient,
+    from: Keyring,
+    delegate: Keyring,
+    proxy_type: ProxyType,
+) -> Result<()> {
+    let from = PairSigner::new(from.pair());
+    let delegate = MultiAddress::Id(delegate.to_raw_public().into());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().proxy().add_proxy(delegate, proxy_type, 0),
+                &from,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_proxy(
+    client: &FullClient,
+    from: Keyring,
+    delegate: Keyring,
+    proxy_type: ProxyType,
+) -> Result<()> {
+    let from = PairSigner::new(from.pair());
+    let delegate = MultiAddress::Id(delegate.to_raw_public().into());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().proxy().remove_proxy(delegate, proxy_type, 0),
+                &from,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Dispatch a call from the account `real` through its proxy `delegate`.
+pub async fn proxy(
+    client: &FullClient,
+    delegate: Keyring,
+    real: Keyring,
+    call: RuntimeCall,
+) -> Result<()> {
+    let delegate = PairSigner::new(delegate.pair());
+    let real = MultiAddress::Id(real.to_raw_public().into());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().proxy().proxy(real, None, call),
+                &delegate,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Transfer from the account `real` through its proxy `delegate`.
+pub async fn proxy_transfer(
+    client: &FullClient,
+    delegate: Keyring,
+    real: Keyring,
+    amount: u64,
+    to: Keyring,
+) -> Result<()> {
+    proxy(
+        client,
+        delegate,
+        real,
+        RuntimeCall::Balances(pallet_balances::pallet::Call::transfer_keep_alive {
+            dest: MultiAddress::Id(to.to_raw_public().into()),
+            value: amount,
+        }),
+    )
+    .await
+}
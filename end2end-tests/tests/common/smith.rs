@@ -0,0 +1,113 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{gdev, *};
+use crate::common::pair_signer::PairSigner;
+use sp_keyring::sr25519::Keyring;
+
+type SmithMeta = gdev::runtime_types::pallet_smith_members::types::SmithMeta<u32, u32>;
+
+pub async fn invite_smith(client: &FullClient, from: Keyring, to: Keyring) -> Result<()> {
+    let signer = PairSigner::new(from.pair());
+    let receiver_index = identity_index(client, to).await?;
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().smith_members().invite_smith(receiver_index),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn accept_invitation(client: &FullClient, who: Keyring) -> Result<()> {
+    let signer = PairSigner::new(who.pair());
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().smith_members().accept_invitation(),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn certify_smith(client: &FullClient, from: Keyring, to: Keyring) -> Result<()> {
+    let signer = PairSigner::new(from.pair());
+    let receiver_index = identity_index(client, to).await?;
+
+    let _events = create_block_with_extrinsic(
+        &client.rpc,
+        client
+            .client
+            .tx()
+            .create_signed(
+                &gdev::tx().smith_members().certify_smith(receiver_index),
+                &signer,
+                SubstrateExtrinsicParamsBuilder::new().build(),
+            )
+            .await?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// get smith metadata from account keyring
+pub async fn get_smith_meta(client: &FullClient, who: Keyring) -> Result<Option<SmithMeta>> {
+    let idty_index = identity_index(client, who).await?;
+
+    Ok(client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(&gdev::storage().smith_members().smiths(idty_index))
+        .await?)
+}
+
+async fn identity_index(client: &FullClient, who: Keyring) -> Result<u32> {
+    let account: subxt::utils::AccountId32 = who.to_raw_public().into();
+
+    client
+        .client
+        .storage()
+        .at_latest()
+        .await?
+        .fetch(
+            &gdev::storage()
+                .identity()
+                .identity_index_of(account.clone()),
+        )
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("identity {account} has no associated index").into())
+}
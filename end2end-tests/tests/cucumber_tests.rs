@@ -198,6 +198,20 @@ async fn n_blocks_later(world: &mut DuniterWorld, n: usize) -> Result<()> {
     Ok(())
 }
 
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"(\d+) (minutes?|hours?|days?|months?) later")]
+async fn n_periods_later(world: &mut DuniterWorld, n: u32, unit: String) -> Result<()> {
+    // one block is created per period
+    let step = match unit.trim_end_matches('s') {
+        "minute" => 60_000,
+        "hour" => 3_600_000,
+        "day" => 86_400_000,
+        "month" => 2_629_800_000,
+        _ => unreachable!(),
+    };
+    common::make_blocks(world.rpc_client(), n, step).await
+}
+
 #[allow(clippy::needless_pass_by_ref_mut)]
 #[when(regex = r"([a-zA-Z]+) sends? (\d+) (ĞD|cĞD|UD|mUD) to ([a-zA-Z]+)$")]
 async fn transfer(
@@ -362,6 +376,205 @@ async fn run_distance_oracle(world: &mut DuniterWorld, who: String) -> Result<()
     .await
 }
 
+// parse a list of accounts like "alice, bob and charlie"
+fn parse_accounts(accounts: &str) -> Vec<Keyring> {
+    accounts
+        .split([',', ' '])
+        .filter(|name| !name.is_empty() && *name != "and")
+        .map(|name| Keyring::from_str(name).expect("unknown account"))
+        .collect()
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) invites ([a-zA-Z]+) to become smith")]
+async fn invite_smith(world: &mut DuniterWorld, from: String, to: String) -> Result<()> {
+    let from = Keyring::from_str(&from).expect("unknown from");
+    let to = Keyring::from_str(&to).expect("unknown to");
+
+    common::smith::invite_smith(world.full_client(), from, to).await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) accepts the smith invitation")]
+async fn accept_smith_invitation(world: &mut DuniterWorld, who: String) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown origin");
+
+    common::smith::accept_invitation(world.full_client(), who).await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) issues a smith certification to ([a-zA-Z]+)")]
+async fn certify_smith(world: &mut DuniterWorld, from: String, to: String) -> Result<()> {
+    let from = Keyring::from_str(&from).expect("unknown from");
+    let to = Keyring::from_str(&to).expect("unknown to");
+
+    common::smith::certify_smith(world.full_client(), from, to).await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) sets (?:his|her) session keys")]
+async fn set_session_keys(world: &mut DuniterWorld, who: String) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown origin");
+
+    common::authority::set_session_keys(world.full_client(), who).await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) goes (online|offline)")]
+async fn go_online_or_offline(world: &mut DuniterWorld, who: String, online: String) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown origin");
+
+    if online == "online" {
+        common::authority::go_online(world.full_client(), who).await
+    } else {
+        common::authority::go_offline(world.full_client(), who).await
+    }
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(
+    regex = r"([a-zA-Z]+) proposes to the technical committee to set the balance of ([a-zA-Z]+) to (\d+) (ĞD|cĞD)"
+)]
+async fn propose_set_balance(
+    world: &mut DuniterWorld,
+    from: String,
+    who: String,
+    amount: u64,
+    unit: String,
+) -> Result<()> {
+    let from = Keyring::from_str(&from).expect("unknown from");
+    let who = Keyring::from_str(&who).expect("unknown who");
+    let (amount, _is_ud) = parse_amount(amount, &unit);
+
+    common::committee::propose_set_balance(world.full_client(), from, who, amount).await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) votes (yes|no) on the last technical committee proposal")]
+async fn vote_last_proposal(world: &mut DuniterWorld, who: String, vote: String) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown origin");
+
+    common::committee::vote(world.full_client(), who, vote == "yes").await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) closes the last technical committee proposal")]
+async fn close_last_proposal(world: &mut DuniterWorld, who: String) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown origin");
+
+    common::committee::close(world.full_client(), who).await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(
+    regex = r"([a-zA-Z]+) sends? (\d+) (ĞD|cĞD) to the multisig of ([a-zA-Z, ]+) with threshold (\d+)"
+)]
+async fn transfer_to_multisig(
+    world: &mut DuniterWorld,
+    from: String,
+    amount: u64,
+    unit: String,
+    signatories: String,
+    threshold: u16,
+) -> Result<()> {
+    let from = Keyring::from_str(&from).expect("unknown from");
+    let signatories = parse_accounts(&signatories);
+    let (amount, _is_ud) = parse_amount(amount, &unit);
+
+    common::multisig::transfer_to_multisig(
+        world.full_client(),
+        from,
+        amount,
+        &signatories,
+        threshold,
+    )
+    .await
+}
+
+#[when(
+    regex = r"([a-zA-Z]+) approves a transfer of (\d+) (ĞD|cĞD) to ([a-zA-Z]+) from the multisig of ([a-zA-Z, ]+) with threshold (\d+)"
+)]
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::needless_pass_by_ref_mut)]
+async fn approve_multisig_transfer(
+    world: &mut DuniterWorld,
+    from: String,
+    amount: u64,
+    unit: String,
+    to: String,
+    signatories: String,
+    threshold: u16,
+) -> Result<()> {
+    let from = Keyring::from_str(&from).expect("unknown from");
+    let to = Keyring::from_str(&to).expect("unknown to");
+    let signatories = parse_accounts(&signatories);
+    let (amount, _is_ud) = parse_amount(amount, &unit);
+
+    common::multisig::approve_transfer(
+        world.full_client(),
+        from,
+        &signatories,
+        threshold,
+        amount,
+        to,
+    )
+    .await
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) (adds|removes) ([a-zA-Z]+) as ([a-z ]+) proxy")]
+async fn add_or_remove_proxy(
+    world: &mut DuniterWorld,
+    from: String,
+    action: String,
+    delegate: String,
+    proxy_type: String,
+) -> Result<()> {
+    let from = Keyring::from_str(&from).expect("unknown from");
+    let delegate = Keyring::from_str(&delegate).expect("unknown delegate");
+    let proxy_type = gdev::runtime_types::gdev_runtime::ProxyType::from_str(&proxy_type)?;
+
+    if action == "adds" {
+        common::proxy::add_proxy(world.full_client(), from, delegate, proxy_type).await
+    } else {
+        common::proxy::remove_proxy(world.full_client(), from, delegate, proxy_type).await
+    }
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) sends? (\d+) (ĞD|cĞD) to ([a-zA-Z]+) on behalf of ([a-zA-Z]+)")]
+async fn proxy_transfer(
+    world: &mut DuniterWorld,
+    delegate: String,
+    amount: u64,
+    unit: String,
+    to: String,
+    real: String,
+) -> Result<()> {
+    let delegate = Keyring::from_str(&delegate).expect("unknown delegate");
+    let to = Keyring::from_str(&to).expect("unknown to");
+    let real = Keyring::from_str(&real).expect("unknown real");
+    let (amount, _is_ud) = parse_amount(amount, &unit);
+
+    let res = common::proxy::proxy_transfer(world.full_client(), delegate, real, amount, to).await;
+
+    if world.ignore_errors() { Ok(()) } else { res }
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[when(regex = r"([a-zA-Z]+) funds the treasury with (\d+) (ĞD|cĞD)")]
+async fn fund_treasury(
+    world: &mut DuniterWorld,
+    from: String,
+    amount: u64,
+    unit: String,
+) -> Result<()> {
+    let from = Keyring::from_str(&from).expect("unknown from");
+    let (amount, _is_ud) = parse_amount(amount, &unit);
+
+    common::treasury::fund(world.full_client(), from, amount).await
+}
+
 // ===== then ====
 
 #[allow(clippy::needless_pass_by_ref_mut)]
@@ -371,9 +584,7 @@ async fn treasury_should_contain(
     amount: u64,
     unit: String,
 ) -> Result<()> {
-    let who =
-        subxt::utils::AccountId32::from_str("5EYCAe5ijiYfyeZ2JJCGq56LmPyNRAKzpG4QkoQkkQNB5e6Z")
-            .expect("invalid treasury account id");
+    let who = common::treasury::treasury_account();
     let (amount, _is_ud) = parse_amount(amount, &unit);
 
     let who_account = world
@@ -451,6 +662,21 @@ async fn current_ud_amount_should_be(
     Ok(())
 }
 
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[then(regex = r"(\d+) universal dividends? should have been created")]
+async fn universal_dividends_should_have_been_created(
+    world: &mut DuniterWorld,
+    count: u16,
+) -> Result<()> {
+    // the index of the next UD starts at 1
+    let next_ud_index = world
+        .read_or_default(&gdev::storage().universal_dividend().current_ud_index())
+        .await
+        .await?;
+    assert_eq!(next_ud_index - 1, count);
+    Ok(())
+}
+
 #[allow(clippy::needless_pass_by_ref_mut)]
 #[then(regex = r"Monetary mass should be (\d+).(\d+)")]
 async fn monetary_mass_should_be(world: &mut DuniterWorld, amount: u64, cents: u64) -> Result<()> {
@@ -569,6 +795,99 @@ async fn identity_status_should_be(
     Ok(())
 }
 
+use gdev::runtime_types::pallet_smith_members::SmithStatus;
+
+// smith status from string
+impl FromStr for SmithStatus {
+    type Err = String;
+
+    fn from_str(input: &str) -> std::result::Result<SmithStatus, String> {
+        match input {
+            "invited" => Ok(SmithStatus::Invited),
+            "pending" => Ok(SmithStatus::Pending),
+            "smith" => Ok(SmithStatus::Smith),
+            "excluded" => Ok(SmithStatus::Excluded),
+            _ => Err(format!("'{input}' does not match a smith status")),
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[then(regex = r"([a-zA-Z]+) smith status should be ([a-zA-Z]+)")]
+async fn smith_status_should_be(
+    world: &mut DuniterWorld,
+    who: String,
+    status: String,
+) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown who");
+    let expected_status = SmithStatus::from_str(&status)?;
+
+    let smith_meta = common::smith::get_smith_meta(world.full_client(), who)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("{who} has never been invited as smith"))?;
+    assert_eq!(smith_meta.status, expected_status);
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[then(regex = r"([a-zA-Z]+) authority status should be ([a-zA-Z]+)")]
+async fn authority_status_should_be(
+    world: &mut DuniterWorld,
+    who: String,
+    status: String,
+) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown who");
+    let expected_status = common::authority::AuthorityStatus::from_str(&status)?;
+
+    let actual_status = common::authority::get_authority_status(world.full_client(), who).await?;
+    assert_eq!(actual_status, expected_status);
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[then(regex = r"([a-zA-Z]+) should (not )?be a validator")]
+async fn should_be_validator(world: &mut DuniterWorld, who: String, not: String) -> Result<()> {
+    let who = Keyring::from_str(&who).expect("unknown who");
+
+    assert_eq!(
+        common::authority::is_validator(world.full_client(), who).await?,
+        not.is_empty()
+    );
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[then(regex = r"the technical committee should have (\d+) open proposals?")]
+async fn technical_committee_should_have_proposals(
+    world: &mut DuniterWorld,
+    count: usize,
+) -> Result<()> {
+    let proposals = common::committee::proposals(world.full_client()).await?;
+    assert_eq!(proposals.len(), count);
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_ref_mut)]
+#[then(regex = r"the multisig of ([a-zA-Z, ]+) with threshold (\d+) should have (\d+) (ĞD|cĞD)")]
+async fn multisig_should_have(
+    world: &mut DuniterWorld,
+    signatories: String,
+    threshold: u16,
+    amount: u64,
+    unit: String,
+) -> Result<()> {
+    let signatories = parse_accounts(&signatories);
+    let who = common::multisig::multisig_account(&signatories, threshold);
+    let (amount, _is_ud) = parse_amount(amount, &unit);
+
+    let who_account = world
+        .read_or_default(&gdev::storage().system().account(who))
+        .await
+        .await?;
+    assert_eq!(who_account.data.free, amount);
+    Ok(())
+}
+
 // ============================================================
 
 #[derive(clap::Args)]
@@ -15,6 +15,7 @@
 // along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
 
 // Common to all Duniter blockchains
+pub mod fork_off;
 pub mod gen_genesis_data;
 
 #[cfg(feature = "g1")]
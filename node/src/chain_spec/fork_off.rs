@@ -0,0 +1,887 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Rewrite the raw state of a live network so that it can be run locally.
+//!
+//! The first smiths of the state are taken over by development keys (`//Alice`, `//Bob`, …):
+//! the owner key, account and session keys of their identities are replaced by the development
+//! ones, they become the only online authorities and they form the technical committee.
+//! The other online authorities are moved offline, as if they had called `go_offline`.
+//!
+//! The account of a taken over identity is moved with the storage items of `ACCOUNT_MAPS`.
+//! Other references to the previous owner key, like proxies delegating to it or multisig
+//! signatories, are left as is.
+//!
+//! The consensus state (BABE epochs, GRANDPA set) and the block history are reset so that the
+//! rewritten state can be used as the genesis of a new chain. Block numbers restart from zero:
+//! block-based deadlines stored in the state (memberships, certifications, scheduled tasks)
+//! are reached later than on the live network.
+
+use crate::chain_spec::{
+    gen_genesis_data::{
+        AuthorityKeys, SessionKeysProvider, get_authority_keys_from_seed, local_chain_member_seed,
+    },
+    get_local_sudo_account_id_from_seed,
+};
+use common_runtime::{AccountId, IdtyValue, entities::IdtyData};
+use pallet_authority_members::MemberData;
+use pallet_committee_membership::MemberInfo;
+use pallet_smith_members::{SmithMeta, SmithStatus};
+use sp_core::{
+    Decode, Encode,
+    hashing::{blake2_128, twox_64, twox_128},
+};
+use sp_runtime::traits::OpaqueKeys;
+use std::collections::BTreeMap;
+
+/// Raw top storage of a chain spec.
+pub type RawStorage = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Storage maps keyed by account moved to the new owner key of a taken over identity,
+/// along with `System.Account`, with the hasher of their key.
+const ACCOUNT_MAPS: &[(&str, &str, fn(&[u8]) -> Vec<u8>)] = &[
+    ("Balances", "Locks", blake2_128_concat),
+    ("Balances", "Reserves", blake2_128_concat),
+    ("Balances", "Holds", blake2_128_concat),
+    ("Balances", "Freezes", blake2_128_concat),
+    ("Proxy", "Proxies", twox_64_concat),
+    ("Proxy", "Announcements", twox_64_concat),
+    ("Quota", "AccountTxCount", blake2_128_concat),
+];
+
+type BlockNumber = u32;
+type IdtyIndex = u32;
+type SessionIndex = u32;
+
+/// Parameters of the takeover of a forked state.
+pub struct ForkOffParams {
+    /// Number of smiths going online with development keys.
+    pub authorities: usize,
+    /// Number of smiths seating in the technical committee with development keys.
+    pub technical_committee: usize,
+    /// Number of sessions before the exclusion of an offline smith.
+    pub smith_inactivity_max_duration: SessionIndex,
+    /// Duration of the terms of the technical committee members.
+    pub committee_term_duration: BlockNumber,
+}
+
+/// A smith taken over by development keys.
+struct TakenOver {
+    idty_index: IdtyIndex,
+    keys: AuthorityKeys,
+}
+
+/// Replace the authorities, the technical committee and the sudo key of a raw state by
+/// development keys.
+pub fn fork_off<SessionKeys, SKP>(
+    storage: &mut RawStorage,
+    params: &ForkOffParams,
+) -> Result<(), String>
+where
+    SessionKeys: Encode + Decode + OpaqueKeys,
+    SKP: SessionKeysProvider<SessionKeys>,
+{
+    if params.authorities == 0 {
+        return Err("at least one authority is required".to_string());
+    }
+
+    let previously_online: Vec<IdtyIndex> = get(
+        storage,
+        &storage_key("AuthorityMembers", "OnlineAuthorities"),
+    )?
+    .unwrap_or_default();
+    let mut smiths = smiths(storage)?;
+
+    // Online authorities are taken over first, then the other smiths by index.
+    let required = params.authorities.max(params.technical_committee);
+    let candidates = previously_online.iter().copied().chain(
+        smiths
+            .keys()
+            .copied()
+            .filter(|idty_index| !previously_online.contains(idty_index)),
+    );
+    let taken_over = candidates
+        .filter(|idty_index| {
+            smiths
+                .get(idty_index)
+                .is_some_and(|smith| smith.status == SmithStatus::Smith)
+        })
+        .take(required)
+        .enumerate()
+        .map(|(i, idty_index)| TakenOver {
+            idty_index,
+            keys: get_authority_keys_from_seed(&local_chain_member_seed(i)),
+        })
+        .collect::<Vec<_>>();
+    if taken_over.len() < required {
+        return Err(format!(
+            "{required} smiths are required but the state only has {}",
+            taken_over.len()
+        ));
+    }
+
+    for smith in &taken_over {
+        take_over_identity::<SessionKeys>(storage, smith.idty_index, &smith.keys.0)?;
+    }
+
+    let authorities = &taken_over[..params.authorities];
+    let technical_committee = &taken_over[..params.technical_committee];
+
+    // smiths
+    let current_session: SessionIndex =
+        get(storage, &storage_key("SmithMembers", "CurrentSession"))?.unwrap_or_default();
+    for idty_index in &previously_online {
+        if authorities.iter().any(|a| a.idty_index == *idty_index) {
+            continue;
+        }
+        if let Some(smith) = smiths.get_mut(idty_index) {
+            let expires_on = current_session + params.smith_inactivity_max_duration;
+            smith.expires_on = Some(expires_on);
+            smith.last_online = Some(0);
+            let expires_key = map_key(
+                "SmithMembers",
+                "ExpiresOn",
+                &twox_64_concat(&expires_on.encode()),
+            );
+            let mut expiring: Vec<IdtyIndex> = get(storage, &expires_key)?.unwrap_or_default();
+            expiring.push(*idty_index);
+            put(storage, expires_key, &expiring);
+        }
+    }
+    for authority in authorities {
+        if let Some(smith) = smiths.get_mut(&authority.idty_index) {
+            smith.expires_on = None;
+            smith.last_online = None;
+        }
+    }
+    for (idty_index, smith) in &smiths {
+        put(
+            storage,
+            map_key(
+                "SmithMembers",
+                "Smiths",
+                &twox_64_concat(&idty_index.encode()),
+            ),
+            smith,
+        );
+    }
+
+    set_authorities::<SessionKeys, SKP>(storage, authorities);
+    set_technical_committee(storage, technical_committee, params.committee_term_duration);
+
+    // sudo, only on runtimes having it
+    let sudo_key = storage_key("Sudo", "Key");
+    if storage.contains_key(&sudo_key) {
+        put(
+            storage,
+            sudo_key,
+            &get_local_sudo_account_id_from_seed("Alice"),
+        );
+    }
+
+    reset_block_history(storage);
+
+    Ok(())
+}
+
+/// Read all the smiths of the state.
+fn smiths(
+    storage: &RawStorage,
+) -> Result<BTreeMap<IdtyIndex, SmithMeta<IdtyIndex, BlockNumber>>, String> {
+    let prefix = storage_key("SmithMembers", "Smiths");
+    storage
+        .range(prefix.clone()..)
+        .take_while(|(key, _)| key.starts_with(&prefix))
+        .map(|(key, value)| {
+            // the key is followed by the twox64 hash of the identity index
+            let idty_index = IdtyIndex::decode(&mut &key[prefix.len() + 8..])
+                .map_err(|e| format!("invalid smith key: {e}"))?;
+            let smith = SmithMeta::decode(&mut &value[..])
+                .map_err(|e| format!("invalid smith {idty_index}: {e}"))?;
+            Ok((idty_index, smith))
+        })
+        .collect()
+}
+
+/// Move an identity to a new owner key, along with its account and the storage items of
+/// `ACCOUNT_MAPS`.
+///
+/// The session keys of the previous owner key are removed.
+fn take_over_identity<SessionKeys>(
+    storage: &mut RawStorage,
+    idty_index: IdtyIndex,
+    new_owner_key: &AccountId,
+) -> Result<(), String>
+where
+    SessionKeys: Decode + OpaqueKeys,
+{
+    let idty_key = map_key(
+        "Identity",
+        "Identities",
+        &twox_64_concat(&idty_index.encode()),
+    );
+    let mut idty: IdtyValue<BlockNumber, AccountId, IdtyData> =
+        get(storage, &idty_key)?.ok_or_else(|| format!("identity {idty_index} does not exist"))?;
+    let old_owner_key = std::mem::replace(&mut idty.owner_key, new_owner_key.clone());
+    idty.old_owner_key = None;
+    put(storage, idty_key, &idty);
+
+    let old_account_key = map_key(
+        "System",
+        "Account",
+        &blake2_128_concat(&old_owner_key.encode()),
+    );
+    let new_account_key = map_key(
+        "System",
+        "Account",
+        &blake2_128_concat(&new_owner_key.encode()),
+    );
+    if storage.contains_key(&new_account_key) {
+        return Err(format!(
+            "account {new_owner_key} already exists in the state and cannot own identity {idty_index}"
+        ));
+    }
+    if let Some(account) = storage.remove(&old_account_key) {
+        storage.insert(new_account_key, account);
+    }
+    for (pallet, item, hasher) in ACCOUNT_MAPS {
+        if let Some(value) =
+            storage.remove(&map_key(pallet, item, &hasher(&old_owner_key.encode())))
+        {
+            storage.insert(
+                map_key(pallet, item, &hasher(&new_owner_key.encode())),
+                value,
+            );
+        }
+    }
+
+    storage.remove(&map_key(
+        "Identity",
+        "IdentityIndexOf",
+        &blake2_128_concat(&old_owner_key.encode()),
+    ));
+    put(
+        storage,
+        map_key(
+            "Identity",
+            "IdentityIndexOf",
+            &blake2_128_concat(&new_owner_key.encode()),
+        ),
+        &idty_index,
+    );
+
+    let member_key = map_key(
+        "AuthorityMembers",
+        "Members",
+        &twox_64_concat(&idty_index.encode()),
+    );
+    if storage.contains_key(&member_key) {
+        put(
+            storage,
+            member_key,
+            &MemberData {
+                owner_key: new_owner_key.clone(),
+            },
+        );
+    }
+
+    let next_keys_key = map_key(
+        "Session",
+        "NextKeys",
+        &twox_64_concat(&old_owner_key.encode()),
+    );
+    if let Some(session_keys) = get::<SessionKeys>(storage, &next_keys_key)? {
+        for key_type in SessionKeys::key_ids() {
+            storage.remove(&key_owner_key(
+                *key_type,
+                session_keys.get_raw(*key_type).to_vec(),
+            ));
+        }
+        storage.remove(&next_keys_key);
+    }
+
+    Ok(())
+}
+
+/// Make the given smiths the only online authorities, with their development session keys.
+fn set_authorities<SessionKeys, SKP>(storage: &mut RawStorage, authorities: &[TakenOver])
+where
+    SessionKeys: Encode + OpaqueKeys,
+    SKP: SessionKeysProvider<SessionKeys>,
+{
+    // authority members
+    for authority in authorities {
+        put(
+            storage,
+            map_key(
+                "AuthorityMembers",
+                "Members",
+                &twox_64_concat(&authority.idty_index.encode()),
+            ),
+            &MemberData {
+                owner_key: authority.keys.0.clone(),
+            },
+        );
+    }
+    put(
+        storage,
+        storage_key("AuthorityMembers", "OnlineAuthorities"),
+        &authorities
+            .iter()
+            .map(|authority| authority.idty_index)
+            .collect::<Vec<_>>(),
+    );
+    storage.remove(&storage_key("AuthorityMembers", "IncomingAuthorities"));
+    storage.remove(&storage_key("AuthorityMembers", "OutgoingAuthorities"));
+
+    // session
+    let mut queued_keys = Vec::with_capacity(authorities.len());
+    for authority in authorities {
+        let account_id = authority.keys.0.clone();
+        let session_keys = SKP::session_keys(&authority.keys);
+        for key_type in SessionKeys::key_ids() {
+            put(
+                storage,
+                key_owner_key(*key_type, session_keys.get_raw(*key_type).to_vec()),
+                &account_id,
+            );
+        }
+        put(
+            storage,
+            map_key("Session", "NextKeys", &twox_64_concat(&account_id.encode())),
+            &session_keys,
+        );
+        queued_keys.push((account_id, session_keys));
+    }
+    put(
+        storage,
+        storage_key("Session", "Validators"),
+        &authorities
+            .iter()
+            .map(|authority| authority.keys.0.clone())
+            .collect::<Vec<_>>(),
+    );
+    put(storage, storage_key("Session", "QueuedKeys"), &queued_keys);
+    storage.remove(&storage_key("Session", "QueuedChanged"));
+    storage.remove(&storage_key("Session", "DisabledValidators"));
+
+    // babe, the epochs restart with the first block
+    let babe_authorities = authorities
+        .iter()
+        .map(|authority| (authority.keys.2.clone(), 1u64))
+        .collect::<Vec<_>>();
+    put(
+        storage,
+        storage_key("Babe", "Authorities"),
+        &babe_authorities,
+    );
+    put(
+        storage,
+        storage_key("Babe", "NextAuthorities"),
+        &babe_authorities,
+    );
+    for item in [
+        "EpochIndex",
+        "GenesisSlot",
+        "CurrentSlot",
+        "EpochStart",
+        "SegmentIndex",
+        "Initialized",
+        "AuthorVrfRandomness",
+        "Lateness",
+        "PendingEpochConfigChange",
+    ] {
+        storage.remove(&storage_key("Babe", item));
+    }
+    clear_prefix(storage, &storage_key("Babe", "UnderConstruction"));
+    clear_prefix(storage, &storage_key("Babe", "SkippedEpochs"));
+
+    // grandpa, the authority set restarts at id 0
+    put(
+        storage,
+        storage_key("Grandpa", "Authorities"),
+        &authorities
+            .iter()
+            .map(|authority| (authority.keys.1.clone(), 1u64))
+            .collect::<Vec<_>>(),
+    );
+    for item in [
+        "State",
+        "PendingChange",
+        "NextForced",
+        "Stalled",
+        "CurrentSetId",
+    ] {
+        storage.remove(&storage_key("Grandpa", item));
+    }
+    clear_prefix(storage, &storage_key("Grandpa", "SetIdSession"));
+
+    // im online
+    put(
+        storage,
+        storage_key("ImOnline", "Keys"),
+        &authorities
+            .iter()
+            .map(|authority| authority.keys.3.clone())
+            .collect::<Vec<_>>(),
+    );
+    clear_prefix(storage, &storage_key("ImOnline", "ReceivedHeartbeats"));
+    clear_prefix(storage, &storage_key("ImOnline", "AuthoredBlocks"));
+
+    // authority discovery
+    let discovery_keys = authorities
+        .iter()
+        .map(|authority| authority.keys.4.clone())
+        .collect::<Vec<_>>();
+    put(
+        storage,
+        storage_key("AuthorityDiscovery", "Keys"),
+        &discovery_keys,
+    );
+    put(
+        storage,
+        storage_key("AuthorityDiscovery", "NextKeys"),
+        &discovery_keys,
+    );
+}
+
+/// Make the given smiths the only technical committee members, for a full term.
+fn set_technical_committee(storage: &mut RawStorage, members: &[TakenOver], term_end: BlockNumber) {
+    let mut accounts = members
+        .iter()
+        .map(|member| member.keys.0.clone())
+        .collect::<Vec<_>>();
+    // the collective expects sorted members
+    accounts.sort();
+    put(
+        storage,
+        storage_key("TechnicalCommittee", "Members"),
+        &accounts,
+    );
+    storage.remove(&storage_key("TechnicalCommittee", "Prime"));
+    storage.remove(&storage_key("TechnicalCommittee", "Proposals"));
+    clear_prefix(storage, &storage_key("TechnicalCommittee", "ProposalOf"));
+    clear_prefix(storage, &storage_key("TechnicalCommittee", "Voting"));
+
+    clear_prefix(storage, &storage_key("CommitteeMembership", "Members"));
+    clear_prefix(
+        storage,
+        &storage_key("CommitteeMembership", "TermsEndingOn"),
+    );
+    storage.remove(&storage_key("CommitteeMembership", "Prime"));
+    for member in members {
+        put(
+            storage,
+            map_key(
+                "CommitteeMembership",
+                "Members",
+                &twox_64_concat(&member.idty_index.encode()),
+            ),
+            &MemberInfo {
+                account: member.keys.0.clone(),
+                term_end,
+            },
+        );
+    }
+    put(
+        storage,
+        storage_key("CommitteeMembership", "CounterForMembers"),
+        &(members.len() as u32),
+    );
+    put(
+        storage,
+        map_key(
+            "CommitteeMembership",
+            "TermsEndingOn",
+            &twox_64_concat(&term_end.encode()),
+        ),
+        &members
+            .iter()
+            .map(|member| member.idty_index)
+            .collect::<Vec<_>>(),
+    );
+}
+
+/// Reset the block history as done by the `frame_system` genesis build.
+fn reset_block_history(storage: &mut RawStorage) {
+    let hash69 = [69u8; 32];
+    clear_prefix(storage, &storage_key("System", "BlockHash"));
+    put(
+        storage,
+        map_key("System", "BlockHash", &twox_64_concat(&0u32.encode())),
+        &hash69,
+    );
+    put(storage, storage_key("System", "ParentHash"), &hash69);
+    for item in ["Number", "Digest", "Events", "EventCount"] {
+        storage.remove(&storage_key("System", item));
+    }
+    clear_prefix(storage, &storage_key("System", "EventTopics"));
+}
+
+fn storage_key(pallet: &str, item: &str) -> Vec<u8> {
+    [twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+fn map_key(pallet: &str, item: &str, hashed_key: &[u8]) -> Vec<u8> {
+    [storage_key(pallet, item).as_slice(), hashed_key].concat()
+}
+
+fn key_owner_key(key_type: sp_runtime::KeyTypeId, key: Vec<u8>) -> Vec<u8> {
+    map_key(
+        "Session",
+        "KeyOwner",
+        &twox_64_concat(&(key_type, key).encode()),
+    )
+}
+
+fn twox_64_concat(data: &[u8]) -> Vec<u8> {
+    [twox_64(data).as_slice(), data].concat()
+}
+
+fn blake2_128_concat(data: &[u8]) -> Vec<u8> {
+    [blake2_128(data).as_slice(), data].concat()
+}
+
+fn get<T: Decode>(storage: &RawStorage, key: &[u8]) -> Result<Option<T>, String> {
+    storage
+        .get(key)
+        .map(|value| {
+            T::decode(&mut &value[..])
+                .map_err(|e| format!("cannot decode value at 0x{}: {e}", hex::encode(key)))
+        })
+        .transpose()
+}
+
+fn put<T: Encode>(storage: &mut RawStorage, key: Vec<u8>, value: &T) {
+    storage.insert(key, value.encode());
+}
+
+fn clear_prefix(storage: &mut RawStorage, prefix: &[u8]) {
+    storage.retain(|key, _| !key.starts_with(prefix));
+}
+
+#[cfg(all(test, feature = "gdev"))]
+mod tests {
+    use super::*;
+    use crate::chain_spec::gdev::GDevSKP;
+    use common_runtime::IdtyStatus;
+    use gdev_runtime::opaque::SessionKeys;
+
+    const CURRENT_SESSION: SessionIndex = 10;
+
+    fn params(authorities: usize, technical_committee: usize) -> ForkOffParams {
+        ForkOffParams {
+            authorities,
+            technical_committee,
+            smith_inactivity_max_duration: 48,
+            committee_term_duration: 1_000,
+        }
+    }
+
+    fn live_keys(idty_index: IdtyIndex) -> AuthorityKeys {
+        get_authority_keys_from_seed(&format!("LiveSmith{idty_index}"))
+    }
+
+    /// State with three smiths owned by live keys, the first two being online.
+    fn live_state() -> RawStorage {
+        let mut storage = RawStorage::new();
+        for idty_index in 1..=3 {
+            let keys = live_keys(idty_index);
+            put(
+                &mut storage,
+                map_key(
+                    "Identity",
+                    "Identities",
+                    &twox_64_concat(&idty_index.encode()),
+                ),
+                &IdtyValue {
+                    data: IdtyData::default(),
+                    next_creatable_identity_on: 0u32,
+                    old_owner_key: None,
+                    owner_key: keys.0.clone(),
+                    next_scheduled: 0,
+                    status: IdtyStatus::Member,
+                },
+            );
+            put(
+                &mut storage,
+                map_key(
+                    "Identity",
+                    "IdentityIndexOf",
+                    &blake2_128_concat(&keys.0.encode()),
+                ),
+                &idty_index,
+            );
+            put(
+                &mut storage,
+                map_key("System", "Account", &blake2_128_concat(&keys.0.encode())),
+                &[idty_index as u8; 4],
+            );
+            put(
+                &mut storage,
+                map_key(
+                    "AuthorityMembers",
+                    "Members",
+                    &twox_64_concat(&idty_index.encode()),
+                ),
+                &MemberData {
+                    owner_key: keys.0.clone(),
+                },
+            );
+            let session_keys = GDevSKP::session_keys(&keys);
+            for key_type in SessionKeys::key_ids() {
+                put(
+                    &mut storage,
+                    key_owner_key(*key_type, session_keys.get_raw(*key_type).to_vec()),
+                    &keys.0,
+                );
+            }
+            put(
+                &mut storage,
+                map_key("Session", "NextKeys", &twox_64_concat(&keys.0.encode())),
+                &session_keys,
+            );
+            put(
+                &mut storage,
+                map_key(
+                    "SmithMembers",
+                    "Smiths",
+                    &twox_64_concat(&idty_index.encode()),
+                ),
+                &SmithMeta::<IdtyIndex, BlockNumber> {
+                    status: SmithStatus::Smith,
+                    expires_on: (idty_index == 3).then_some(CURRENT_SESSION + 5),
+                    issued_certs: vec![],
+                    received_certs: vec![],
+                    last_online: None,
+                },
+            );
+        }
+        put(
+            &mut storage,
+            storage_key("AuthorityMembers", "OnlineAuthorities"),
+            &vec![1u32, 2],
+        );
+        put(
+            &mut storage,
+            storage_key("SmithMembers", "CurrentSession"),
+            &CURRENT_SESSION,
+        );
+        put(&mut storage, storage_key("Sudo", "Key"), &live_keys(1).0);
+        put(
+            &mut storage,
+            map_key("System", "BlockHash", &twox_64_concat(&42u32.encode())),
+            &[1u8; 32],
+        );
+        storage
+    }
+
+    fn dev_account(i: usize) -> AccountId {
+        get_authority_keys_from_seed(&local_chain_member_seed(i)).0
+    }
+
+    #[test]
+    fn test_fork_off_takes_over_online_authorities() {
+        let mut storage = live_state();
+        fork_off::<SessionKeys, GDevSKP>(&mut storage, &params(1, 2)).unwrap();
+
+        assert_eq!(
+            get::<Vec<IdtyIndex>>(
+                &storage,
+                &storage_key("AuthorityMembers", "OnlineAuthorities")
+            )
+            .unwrap(),
+            Some(vec![1])
+        );
+        assert_eq!(
+            get::<Vec<AccountId>>(&storage, &storage_key("Session", "Validators")).unwrap(),
+            Some(vec![dev_account(0)])
+        );
+
+        // the identity and its account moved to the development key
+        let idty: IdtyValue<BlockNumber, AccountId, IdtyData> = get(
+            &storage,
+            &map_key("Identity", "Identities", &twox_64_concat(&1u32.encode())),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(idty.owner_key, dev_account(0));
+        assert_eq!(
+            get::<IdtyIndex>(
+                &storage,
+                &map_key(
+                    "Identity",
+                    "IdentityIndexOf",
+                    &blake2_128_concat(&dev_account(0).encode())
+                )
+            )
+            .unwrap(),
+            Some(1)
+        );
+        assert!(!storage.contains_key(&map_key(
+            "System",
+            "Account",
+            &blake2_128_concat(&live_keys(1).0.encode())
+        )));
+        assert!(storage.contains_key(&map_key(
+            "System",
+            "Account",
+            &blake2_128_concat(&dev_account(0).encode())
+        )));
+
+        // the session keys of the live key are gone
+        let live_session_keys = GDevSKP::session_keys(&live_keys(1));
+        for key_type in SessionKeys::key_ids() {
+            assert!(!storage.contains_key(&key_owner_key(
+                *key_type,
+                live_session_keys.get_raw(*key_type).to_vec()
+            )));
+        }
+
+        // the other online authority went offline
+        let smiths = smiths(&storage).unwrap();
+        assert_eq!(smiths[&1].expires_on, None);
+        assert_eq!(smiths[&2].expires_on, Some(CURRENT_SESSION + 48));
+        assert_eq!(
+            get::<Vec<IdtyIndex>>(
+                &storage,
+                &map_key(
+                    "SmithMembers",
+                    "ExpiresOn",
+                    &twox_64_concat(&(CURRENT_SESSION + 48).encode())
+                )
+            )
+            .unwrap(),
+            Some(vec![2])
+        );
+
+        // the technical committee is made of the two first smiths
+        let mut members = vec![dev_account(0), dev_account(1)];
+        members.sort();
+        assert_eq!(
+            get::<Vec<AccountId>>(&storage, &storage_key("TechnicalCommittee", "Members")).unwrap(),
+            Some(members)
+        );
+        assert_eq!(
+            get::<u32>(
+                &storage,
+                &storage_key("CommitteeMembership", "CounterForMembers")
+            )
+            .unwrap(),
+            Some(2)
+        );
+
+        assert_eq!(
+            get::<AccountId>(&storage, &storage_key("Sudo", "Key")).unwrap(),
+            Some(get_local_sudo_account_id_from_seed("Alice"))
+        );
+        assert!(!storage.contains_key(&map_key(
+            "System",
+            "BlockHash",
+            &twox_64_concat(&42u32.encode())
+        )));
+    }
+
+    #[test]
+    fn test_fork_off_moves_account_storage() {
+        let mut storage = live_state();
+        let live_account = live_keys(1).0;
+        for item in ["Holds", "Locks"] {
+            put(
+                &mut storage,
+                map_key("Balances", item, &blake2_128_concat(&live_account.encode())),
+                &[1u8; 4],
+            );
+        }
+        // the identity was not an authority member yet
+        storage.remove(&map_key(
+            "AuthorityMembers",
+            "Members",
+            &twox_64_concat(&1u32.encode()),
+        ));
+        fork_off::<SessionKeys, GDevSKP>(&mut storage, &params(1, 1)).unwrap();
+
+        for item in ["Holds", "Locks"] {
+            assert!(!storage.contains_key(&map_key(
+                "Balances",
+                item,
+                &blake2_128_concat(&live_account.encode())
+            )));
+            assert_eq!(
+                storage.get(&map_key(
+                    "Balances",
+                    item,
+                    &blake2_128_concat(&dev_account(0).encode())
+                )),
+                Some(&[1u8; 4].encode())
+            );
+        }
+        assert_eq!(
+            get::<MemberData<AccountId>>(
+                &storage,
+                &map_key(
+                    "AuthorityMembers",
+                    "Members",
+                    &twox_64_concat(&1u32.encode())
+                )
+            )
+            .unwrap(),
+            Some(MemberData {
+                owner_key: dev_account(0)
+            })
+        );
+    }
+
+    #[test]
+    fn test_fork_off_takes_over_offline_smiths_when_needed() {
+        let mut storage = live_state();
+        fork_off::<SessionKeys, GDevSKP>(&mut storage, &params(3, 3)).unwrap();
+
+        assert_eq!(
+            get::<Vec<IdtyIndex>>(
+                &storage,
+                &storage_key("AuthorityMembers", "OnlineAuthorities")
+            )
+            .unwrap(),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(smiths(&storage).unwrap()[&3].expires_on, None);
+    }
+
+    #[test]
+    fn test_fork_off_requires_enough_smiths() {
+        let mut storage = live_state();
+        assert_eq!(
+            fork_off::<SessionKeys, GDevSKP>(&mut storage, &params(4, 1)),
+            Err("4 smiths are required but the state only has 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fork_off_rejects_existing_development_account() {
+        let mut storage = live_state();
+        put(
+            &mut storage,
+            map_key(
+                "System",
+                "Account",
+                &blake2_128_concat(&dev_account(0).encode()),
+            ),
+            &[0u8; 4],
+        );
+        assert!(fork_off::<SessionKeys, GDevSKP>(&mut storage, &params(1, 1)).is_err());
+    }
+}
@@ -40,7 +40,7 @@ const TOKEN_DECIMALS: usize = 2;
 const TOKEN_SYMBOL: &str = "Ğ";
 static EXISTENTIAL_DEPOSIT: u64 = parameters::ExistentialDeposit::get();
 
-struct G1SKP;
+pub(crate) struct G1SKP;
 impl SessionKeysProvider<SessionKeys> for G1SKP {
     fn session_keys(keys: &AuthorityKeys) -> SessionKeys {
         let cloned = keys.clone();
@@ -53,7 +53,9 @@ impl SessionKeysProvider<SessionKeys> for G1SKP {
     }
 }
 
-fn get_parameters(_parameters_from_file: &Option<GenesisParameters>) -> CommonParameters {
+pub(crate) fn get_parameters(
+    _parameters_from_file: &Option<GenesisParameters>,
+) -> CommonParameters {
     CommonParameters {
         currency_name: TOKEN_SYMBOL.to_string(),
         decimals: TOKEN_DECIMALS,
@@ -70,25 +72,25 @@ fn get_parameters(_parameters_from_file: &Option<GenesisParameters>) -> CommonPa
         universal_dividend_ud_creation_period: parameters::UdCreationPeriod::get() as u64,
         universal_dividend_ud_reeval_period: parameters::UdReevalPeriod::get() as u64,
         wot_first_issuable_on: parameters::WotFirstCertIssuableOn::get(),
-        wot_min_cert_for_membership: parameters::WotMinCertForMembership::get(),
-        wot_min_cert_for_create_idty_right: parameters::WotMinCertForCreateIdtyRight::get(),
+        wot_min_cert_for_membership: parameters::DefaultWotMinCertForMembership::get(),
+        wot_min_cert_for_create_idty_right: parameters::DefaultWotMinCertForCreateIdtyRight::get(),
         identity_confirm_period: parameters::ConfirmPeriod::get(),
         identity_change_owner_key_period: parameters::ChangeOwnerKeyPeriod::get(),
         identity_idty_creation_period: parameters::IdtyCreationPeriod::get(),
         identity_autorevocation_period: parameters::AutorevocationPeriod::get(),
         identity_deletion_period: parameters::DeletionPeriod::get(),
-        membership_membership_period: parameters::MembershipPeriod::get(),
-        membership_membership_renewal_period: parameters::MembershipRenewalPeriod::get(),
-        cert_max_by_issuer: parameters::MaxByIssuer::get(),
+        membership_membership_period: parameters::DefaultMembershipPeriod::get(),
+        membership_membership_renewal_period: parameters::DefaultMembershipRenewalPeriod::get(),
+        cert_max_by_issuer: parameters::DefaultMaxByIssuer::get(),
         cert_min_received_cert_to_be_able_to_issue_cert:
-            parameters::MinReceivedCertToBeAbleToIssueCert::get(),
-        cert_validity_period: parameters::ValidityPeriod::get(),
+            parameters::DefaultMinReceivedCertToBeAbleToIssueCert::get(),
+        cert_validity_period: parameters::DefaultValidityPeriod::get(),
         distance_min_accessible_referees: parameters::MinAccessibleReferees::get(),
         distance_max_depth: parameters::MaxRefereeDistance::get(),
         smith_sub_wot_min_cert_for_membership: parameters::SmithWotMinCertForMembership::get(),
         smith_inactivity_max_duration: parameters::SmithInactivityMaxDuration::get(),
         smith_cert_max_by_issuer: parameters::SmithMaxByIssuer::get(),
-        cert_cert_period: parameters::CertPeriod::get(),
+        cert_cert_period: parameters::DefaultCertPeriod::get(),
         distance_evaluation_period: parameters::EvaluationPeriod::get(),
         treasury_spend_period: <Runtime as pallet_treasury::Config>::SpendPeriod::get(),
     }
@@ -246,6 +248,7 @@ fn genesis_data_to_g1_genesis_conf(
         initial_smiths,
         sudo_key,
         technical_committee_members,
+        technical_committee_idties,
         ud,
     } = genesis_data;
 
@@ -273,6 +276,9 @@ fn genesis_data_to_g1_genesis_conf(
         "technicalCommittee": {
             "members": technical_committee_members,
         },
+        "committeeMembership": {
+            "members": technical_committee_idties,
+        },
         "quota": {
             "identities": identities.iter().map(|i| i.idty_index).collect::<Vec<_>>(),
         },
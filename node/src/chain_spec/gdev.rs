@@ -40,7 +40,7 @@ static EXISTENTIAL_DEPOSIT: u64 = parameters::ExistentialDeposit::get();
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
-struct GDevSKP;
+pub(crate) struct GDevSKP;
 impl SessionKeysProvider<SessionKeys> for GDevSKP {
     fn session_keys(keys: &AuthorityKeys) -> SessionKeys {
         let cloned = keys.clone();
@@ -53,7 +53,7 @@ impl SessionKeysProvider<SessionKeys> for GDevSKP {
     }
 }
 
-fn get_parameters(parameters_from_file: &Option<GenesisParameters>) -> CommonParameters {
+pub(crate) fn get_parameters(parameters_from_file: &Option<GenesisParameters>) -> CommonParameters {
     let parameters_from_file = parameters_from_file
         .clone()
         .expect("parameters must be defined in file for GDev");
@@ -244,6 +244,7 @@ fn genesis_data_to_gdev_genesis_conf(
         initial_smiths,
         sudo_key,
         technical_committee_members,
+        technical_committee_idties,
         ud,
     } = genesis_data;
 
@@ -274,6 +275,9 @@ fn genesis_data_to_gdev_genesis_conf(
         "technicalCommittee": {
             "members": technical_committee_members,
         },
+        "committeeMembership": {
+            "members": technical_committee_idties,
+        },
         "quota": {
             "identities": identities.iter().map(|i| i.idty_index).collect::<Vec<_>>(),
         },
@@ -352,6 +356,10 @@ fn get_local_chain_parameters() -> Option<GenesisParameters> {
         wot_min_cert_for_create_idty_right: 2,
         wot_min_cert_for_membership: 2,
         evaluation_period: 7,
+        rate_limit_window: 100,
+        rate_limit_max_per_account: 100,
+        rate_limit_max_per_member_account: 1_000,
+        rate_limit_max_per_identity: 1_000,
     })
 }
 
@@ -84,6 +84,7 @@ pub struct GenesisData<Parameters: DeserializeOwned, SessionKeys: Decode> {
     pub initial_smiths: BTreeMap<u32, (bool, Vec<u32>)>,
     pub sudo_key: Option<AccountId>,
     pub technical_committee_members: Vec<AccountId>,
+    pub technical_committee_idties: Vec<(u32, AccountId)>,
     pub ud: u64,
 }
 
@@ -220,6 +221,7 @@ struct IdentityV2 {
 struct RawSmith {
     name: String,
     /// optional pre-set session keys (at least for the smith bootstraping the blockchain)
+    #[serde(skip_serializing_if = "Option::is_none")]
     session_keys: Option<String>,
     #[serde(default)]
     certs_received: Vec<String>,
@@ -263,6 +265,78 @@ struct GenesisInfo<'a> {
     common_parameters: &'a CommonParameters,
 }
 
+/// summary of the prepared genesis, as logged by `dump_genesis_info`
+#[derive(Serialize)]
+struct GenesisSummary {
+    genesis_timestamp: u64,
+    accounts: usize,
+    simple_wallets: usize,
+    identities: usize,
+    members: usize,
+    not_members: usize,
+    revoked: usize,
+    smiths: usize,
+    online_authorities: u32,
+    certifications: u32,
+    smith_certifications: u32,
+    technical_committee_members: usize,
+    parameters: CommonParameters,
+}
+
+impl From<&GenesisInfo<'_>> for GenesisSummary {
+    fn from(info: &GenesisInfo<'_>) -> Self {
+        let count_inactive = |status: IdtyStatus| {
+            info.inactive_identities
+                .values()
+                .filter(|(_, s)| *s == status)
+                .count()
+        };
+        GenesisSummary {
+            genesis_timestamp: info.genesis_timestamp,
+            accounts: info.accounts.len(),
+            simple_wallets: *info.genesis_data_wallets_count,
+            identities: info.identity_index.len(),
+            members: info.identities.len() - info.inactive_identities.len(),
+            not_members: count_inactive(IdtyStatus::NotMember),
+            revoked: count_inactive(IdtyStatus::Revoked),
+            smiths: info.initial_smiths.len(),
+            online_authorities: *info.counter_online_authorities,
+            certifications: *info.counter_cert,
+            smith_certifications: *info.counter_smith_cert,
+            technical_committee_members: info.technical_committee_members.len(),
+            parameters: info.common_parameters.clone(),
+        }
+    }
+}
+
+/// diagnostics collected while building the genesis data
+#[derive(Default, Serialize)]
+pub struct GenesisReport {
+    /// summary of the prepared genesis, absent if the input could not be processed
+    pub summary: Option<serde_json::Value>,
+    /// issues worth a look which do not prevent from building the genesis
+    pub warnings: Vec<String>,
+    /// issues preventing from building a sane genesis
+    pub errors: Vec<String>,
+}
+
+impl GenesisReport {
+    /// whether a sane genesis can be built from the input
+    pub fn is_sane(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    fn warn(&mut self, message: String) {
+        warn!("{message}");
+        self.warnings.push(message);
+    }
+
+    fn error(&mut self, message: String) {
+        error!("{message}");
+        self.errors.push(message);
+    }
+}
+
 /// generate genesis data from a json file
 /// takes DUNITER_GENESIS_CONFIG env var if present or duniter-gen-conf.json by default
 // this function is targeting dev chainspecs, do not use in production network
@@ -277,7 +351,185 @@ where
     SKP: SessionKeysProvider<SessionKeys>,
 {
     let genesis_timestamp: u64 = get_genesis_timestamp()?;
+    let genesis_input = get_genesis_input::<P>(
+        std::env::var("DUNITER_GENESIS_CONFIG").unwrap_or_else(|_| config_file_path.to_owned()),
+    )?;
+    let genesis_data = get_genesis_migration_data(get_genesis_migration_data_path())?;
+
+    let mut report = GenesisReport::default();
+    let genesis_data = build_genesis_data::<P, SK, SessionKeys, SKP>(
+        genesis_input,
+        genesis_data,
+        genesis_timestamp,
+        get_common_parameters,
+        maybe_force_authority,
+        &mut report,
+    )?;
+
+    // check the logs to see all the fatal error preventing from starting gtest currency
+    if !report.is_sane() {
+        return Err(format!(
+            "some previously logged error prevent from building a sane genesis:\n- {}",
+            report.errors.join("\n- ")
+        ));
+    }
+    Ok(genesis_data)
+}
+
+/// run all the checks of the genesis building on a genesis input and its migration data,
+/// without building any chain spec
+pub fn validate_genesis_data<P, SK, SessionKeys: Encode, SKP>(
+    config_file_path: String,
+    migration_data_path: String,
+    genesis_timestamp: u64,
+    get_common_parameters: fn(&Option<P>) -> CommonParameters,
+    maybe_force_authority: Option<String>,
+) -> GenesisReport
+where
+    P: Default + DeserializeOwned,
+    SK: Decode,
+    SKP: SessionKeysProvider<SessionKeys>,
+{
+    let mut report = GenesisReport::default();
+    let inputs = get_genesis_input::<P>(config_file_path).and_then(|genesis_input| {
+        Ok((
+            genesis_input,
+            get_genesis_migration_data(migration_data_path)?,
+        ))
+    });
+    let result = inputs.and_then(|(genesis_input, genesis_data)| {
+        build_genesis_data::<P, SK, SessionKeys, SKP>(
+            genesis_input,
+            genesis_data,
+            genesis_timestamp,
+            get_common_parameters,
+            maybe_force_authority,
+            &mut report,
+        )
+    });
+    if let Err(e) = result {
+        report.error(e);
+    }
+    report
+}
 
+/// suggest the smallest changes making the smith WoT of a genesis input valid under
+/// `smith_sub_wot_min_cert_for_membership`: either the extra smith certifications to add, or the
+/// smiths to drop. The suggestions are returned in the input YAML format.
+pub fn suggest_smith_wot_repair<P>(
+    config_file_path: String,
+    get_common_parameters: fn(&Option<P>) -> CommonParameters,
+    maybe_force_authority: Option<String>,
+) -> Result<String, String>
+where
+    P: Default + DeserializeOwned,
+{
+    let GenesisInput {
+        parameters,
+        smith_identities,
+        clique_smiths,
+        ..
+    } = get_genesis_input::<P>(config_file_path)?;
+    if smith_identities.is_none() && clique_smiths.is_none() {
+        return Err("one of 'smiths' or 'clique_smiths' must be defined".to_owned());
+    }
+    let common_parameters = get_common_parameters(&parameters);
+    let min_cert = common_parameters.smith_sub_wot_min_cert_for_membership;
+
+    let mut smiths = build_smiths_wot(&clique_smiths, smith_identities)?;
+    if clique_smiths.is_some() {
+        // All initial smiths are considered to be certifying all each other
+        let names: Vec<String> = smiths.iter().map(|smith| smith.name.clone()).collect();
+        for smith in &mut smiths {
+            smith.certs_received = names
+                .iter()
+                .filter(|name| **name != smith.name)
+                .cloned()
+                .collect();
+        }
+    }
+    // the forced authority must exist in the smith WoT
+    if let Some(authority_name) = &maybe_force_authority
+        && !smiths.iter().any(|smith| &smith.name == authority_name)
+    {
+        smiths.push(RawSmith {
+            name: authority_name.clone(),
+            session_keys: None,
+            certs_received: vec![],
+        });
+    }
+    smiths.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut suggestions = format!(
+        "# {} smiths, {min_cert} smith certifications required for membership\n",
+        smiths.len()
+    );
+    let (smiths, ignored_certs) = filter_smith_certs(smiths);
+    for (issuer, receiver) in &ignored_certs {
+        suggestions.push_str(&format!(
+            "# ignored certification {issuer} -> {receiver}: issuer is not another smith\n"
+        ));
+    }
+    if ignored_certs.is_empty()
+        && smiths
+            .iter()
+            .all(|smith| smith.certs_received.len() >= min_cert as usize)
+    {
+        suggestions.push_str("# the smith WoT is valid\n");
+        return Ok(suggestions);
+    }
+
+    match add_missing_smith_certs(
+        smiths.clone(),
+        min_cert,
+        common_parameters.smith_cert_max_by_issuer,
+    ) {
+        Ok((repaired, added_certs)) => {
+            suggestions.push_str(&format!("# add {added_certs} smith certifications\n"));
+            suggestions.push_str(&smiths_to_yaml(repaired)?);
+        }
+        Err(e) => {
+            suggestions.push_str(&format!(
+                "# the missing smith certifications cannot be added: {e}\n"
+            ));
+        }
+    }
+
+    let (kept, dropped) = drop_smiths_below_min_cert(smiths, min_cert, &maybe_force_authority);
+    suggestions.push_str("---\n");
+    suggestions.push_str(&format!(
+        "# or drop {} smiths: {}\n",
+        dropped.len(),
+        dropped.join(", ")
+    ));
+    if let Some(authority) = kept
+        .iter()
+        .find(|smith| Some(&smith.name) == maybe_force_authority.as_ref())
+        && authority.certs_received.len() < min_cert as usize
+    {
+        suggestions.push_str(&format!(
+            "# forced authority {} is kept with only {}/{min_cert} smith certifications\n",
+            authority.name,
+            authority.certs_received.len()
+        ));
+    }
+    suggestions.push_str(&smiths_to_yaml(kept)?);
+    Ok(suggestions)
+}
+
+fn build_genesis_data<P, SK, SessionKeys: Encode, SKP>(
+    genesis_input: GenesisInput<P>,
+    mut genesis_data: GenesisMigrationData,
+    genesis_timestamp: u64,
+    get_common_parameters: fn(&Option<P>) -> CommonParameters,
+    maybe_force_authority: Option<String>,
+    report: &mut GenesisReport,
+) -> Result<GenesisData<P, SK>, String>
+where
+    P: Default + DeserializeOwned,
+    SK: Decode,
+    SKP: SessionKeysProvider<SessionKeys>,
+{
     // Per network input
     let GenesisInput {
         sudo_key,
@@ -290,9 +542,7 @@ where
         clique_smiths,
         technical_committee,
         ud,
-    } = get_genesis_input::<P>(
-        std::env::var("DUNITER_GENESIS_CONFIG").unwrap_or_else(|_| config_file_path.to_owned()),
-    )?;
+    } = genesis_input;
 
     // Per network parameters
     let common_parameters = get_common_parameters(&parameters);
@@ -301,22 +551,25 @@ where
     let mut smiths = build_smiths_wot(&clique_smiths, smith_identities)?;
 
     // G1 data migration (common to all networks)
-    let mut genesis_data = get_genesis_migration_data()?;
-    check_parameters_consistency(&genesis_data.wallets, &first_ud, &first_ud_reeval, &ud)?;
+    if let Err(e) =
+        check_parameters_consistency(&genesis_data.wallets, &first_ud, &first_ud_reeval, &ud)
+    {
+        report.error(e);
+    }
     check_genesis_data_and_filter_expired_certs_since_export(
         &mut genesis_data,
         genesis_timestamp,
         &common_parameters,
+        report,
     );
     let mut identities_v2: HashMap<String, IdentityV2> =
-        genesis_data_to_identities_v2(genesis_data.identities, genesis_timestamp);
-    check_identities_v2(&identities_v2, &common_parameters);
+        genesis_data_to_identities_v2(genesis_data.identities, genesis_timestamp)?;
+    check_identities_v2(&identities_v2, &common_parameters, report);
 
     // MONEY AND WOT //
     // declare variables for building genesis
     // -------------------------------------
-    // track if fatal error occured, but let processing continue
-    let mut fatal = false;
+    // fatal errors are reported, but let processing continue
     // initial Treasury balance
     let mut treasury_balance = 0;
     // track identity index
@@ -328,6 +581,8 @@ where
     // -------------------------------------
     // members of technical committee
     let mut technical_committee_members: Vec<AccountId> = Vec::new();
+    // identities of technical committee members
+    let mut technical_committee_idties: Vec<(u32, AccountId)> = Vec::new();
     // memberships
     let mut memberships = BTreeMap::new();
     // certifications
@@ -350,26 +605,22 @@ where
 
     // SIMPLE WALLETS //
     let genesis_data_wallets_count = genesis_data.wallets.len();
-    let (was_fatal, mut monetary_mass, mut accounts, invalid_wallets) =
-        v1_wallets_to_v2_accounts(genesis_data.wallets, &common_parameters);
-    if was_fatal {
-        eprintln!("DEBUG: fatal after v1_wallets_to_v2_accounts");
-        fatal = true;
-    }
+    let (mut monetary_mass, mut accounts, invalid_wallets) =
+        v1_wallets_to_v2_accounts(genesis_data.wallets, &common_parameters, report);
 
     // Technical Comittee //
     // NOTE : when changing owner key, the technical committee is not changed
     for name in &technical_committee {
         if let Some(identity) = &identities_v2.get(name) {
             technical_committee_members.push(identity.owner_key.clone());
+            technical_committee_idties.push((identity.index, identity.owner_key.clone()));
         } else {
-            eprintln!("Identity '{name}' does not exist");
-            fatal = true;
+            report.error(format!("Identity '{name}' does not exist"));
         }
     }
 
     // IDENTITIES //
-    let (was_fatal, identities) = feed_identities(
+    let identities = feed_identities(
         &mut accounts,
         &mut identity_index,
         &mut monetary_mass,
@@ -377,19 +628,12 @@ where
         &mut memberships,
         &identities_v2,
         &common_parameters,
+        report,
     )?;
-    if was_fatal {
-        eprintln!("DEBUG: fatal after feed_identities");
-        fatal = true;
-    }
 
     // CERTIFICATIONS //
     // counter for certifications
-    let (was_fatal, counter_cert) = feed_certs_by_receiver(&mut certs_by_receiver, &identities_v2);
-    if was_fatal {
-        eprintln!("DEBUG: fatal after feed_certs_by_receiver");
-        fatal = true;
-    }
+    let counter_cert = feed_certs_by_receiver(&mut certs_by_receiver, &identities_v2, report);
 
     // SMITHS SUB-WOT //
 
@@ -398,11 +642,10 @@ where
         check_authority_exists_in_both_wots(name, &identities_v2, &smiths);
     }
 
-    let smiths = decorate_smiths_with_identity(smiths, &identity_index, &identities_v2);
+    let smiths = decorate_smiths_with_identity(smiths, &identity_index, &identities_v2, report);
 
     // counter for online authorities at genesis
     let (
-        was_fatal,
         counter_online_authorities,
         counter_smith_cert,
         SmithMembers {
@@ -414,11 +657,8 @@ where
         &identities_v2,
         &smiths,
         &clique_smiths,
+        report,
     )?;
-    if was_fatal {
-        eprintln!("DEBUG: fatal after create_smith_wot");
-        fatal = true;
-    }
 
     // Verify certifications coherence (can be ignored for old users)
     for (idty_index, receiver_certs) in &certs_by_receiver {
@@ -426,13 +666,12 @@ where
             let name = identity_index.get(idty_index).unwrap();
             let identity = identities_v2.get(&(*name).clone()).unwrap();
             if identity.membership_expire_on != 0 {
-                eprintln!(
+                report.error(format!(
                     "[{}] has received only {}/{} certifications",
                     name,
                     receiver_certs.len(),
                     common_parameters.wot_min_cert_for_membership
-                );
-                fatal = true;
+                ));
             }
         }
     }
@@ -440,57 +679,66 @@ where
     // Verify smith certifications coherence
     for (idty_index, (_online, certs)) in &initial_smiths_wot {
         if certs.len() < common_parameters.smith_sub_wot_min_cert_for_membership as usize {
-            eprintln!(
+            report.error(format!(
                 "[{}] has received only {}/{} smith certifications",
                 identity_index.get(idty_index).unwrap(),
                 certs.len(),
                 common_parameters.smith_sub_wot_min_cert_for_membership
-            );
-            fatal = true;
+            ));
         }
     }
 
     // check number of online authorities
     if maybe_force_authority.is_none() && counter_online_authorities != 1 {
-        eprintln!("one and only one smith must be online, not {counter_online_authorities}");
+        report.warn(format!(
+            "one and only one smith must be online, not {counter_online_authorities}"
+        ));
     }
 
     // check monetary mass
     if monetary_mass != genesis_data.initial_monetary_mass {
-        log::warn!(
+        report.warn(format!(
             "actual monetary_mass ({}) and initial_monetary_mass ({}) do not match",
             monetary_mass.to_formatted_string(&Locale::en),
             genesis_data
                 .initial_monetary_mass
                 .to_formatted_string(&Locale::en)
-        );
+        ));
         if monetary_mass > genesis_data.initial_monetary_mass {
-            eprintln!("money has been created");
-            fatal = true;
+            report.error("money has been created".to_owned());
         }
     }
 
     // treasury balance must come from existing money
-    let treasury_funder: AccountId = match (treasury_funder_address, treasury_funder_pubkey) {
-        (Some(address), None) => address,
-        (None, Some(pubkey)) => {
-            v1_pubkey_to_account_id(pubkey).expect("treasury founder must have a valid public key")
+    let treasury_funder: Option<AccountId> = match (treasury_funder_address, treasury_funder_pubkey)
+    {
+        (Some(address), None) => Some(address),
+        (None, Some(pubkey)) => v1_pubkey_to_account_id(pubkey)
+            .map_err(|e| report.error(format!("treasury funder has an invalid public key: {e}")))
+            .ok(),
+        _ => {
+            report.error(
+                "One of treasury_funder_address or treasury_funder_pubkey must be set".to_owned(),
+            );
+            None
         }
-        _ => panic!("One of treasury_funder_address or treasury_funder_pubkey must be set"),
     };
-    if let Some(existing_account) = accounts.get_mut(&treasury_funder) {
-        existing_account.balance = existing_account
+    if let Some(existing_account) = treasury_funder.and_then(|funder| accounts.get_mut(&funder)) {
+        if let Some(balance) = existing_account
             .balance
             .checked_sub(common_parameters.balances_existential_deposit)
-            .expect("should have enough money to fund Treasury");
-        treasury_balance = common_parameters.balances_existential_deposit;
+        {
+            existing_account.balance = balance;
+            treasury_balance = common_parameters.balances_existential_deposit;
+        } else {
+            report.error("treasury funder does not have enough money to fund Treasury".to_owned());
+        }
     }
     if treasury_balance < common_parameters.balances_existential_deposit {
-        eprintln!(
+        report.error(format!(
             "Treasury balance {} is inferior to existential deposit {}",
             treasury_balance, common_parameters.balances_existential_deposit
-        );
-        fatal = true;
+        ));
     }
 
     smiths.iter().for_each(|smith| {
@@ -531,6 +779,10 @@ where
         common_parameters: &common_parameters,
     };
 
+    report.summary = Some(
+        serde_json::to_value(GenesisSummary::from(&genesis_info))
+            .map_err(|e| format!("cannot serialize genesis summary: {e}"))?,
+    );
     dump_genesis_info(genesis_info);
 
     if parameters.is_some() {
@@ -545,135 +797,129 @@ where
 
         // static parameters (GTest or G1)
         if common_parameters.decimals != G1_DUNITER_V1_DECIMALS {
-            warn!(
+            report.warn(format!(
                 "parameter `decimals` value ({}) is different from Ğ1 value ({})",
                 common_parameters.decimals, G1_DUNITER_V1_DECIMALS
-            )
+            ));
         }
         if common_parameters.balances_existential_deposit != G1_DUNITER_V1_EXISTENTIAL_DEPOSIT {
-            warn!(
+            report.warn(format!(
                 "parameter `existential_deposit` value ({}) is different from Ğ1 value ({})",
                 common_parameters.balances_existential_deposit, G1_DUNITER_V1_EXISTENTIAL_DEPOSIT
-            )
+            ));
         }
         if common_parameters.membership_membership_period / DAYS
             != G1_DUNITER_V1_MSVALIDITY / DUNITER_V1_DAYS
         {
-            warn!(
+            report.warn(format!(
                 "parameter `membership_period` ({} days) is different from Ğ1's ({} days)",
                 common_parameters.membership_membership_period as f32 / DAYS as f32,
                 G1_DUNITER_V1_MSVALIDITY as f32 / DUNITER_V1_DAYS as f32
-            )
+            ));
         }
         if common_parameters.membership_membership_renewal_period / DAYS
             != G1_DUNITER_V1_MSVALIDITY / DUNITER_V1_DAYS
         {
-            warn!(
+            report.warn(format!(
                 "parameter `membership_renewal_period` ({} days) is different from Ğ1's ({} days)",
                 common_parameters.membership_membership_renewal_period as f32 / DAYS as f32,
                 G1_DUNITER_V1_MSVALIDITY as f32 / DUNITER_V1_DAYS as f32
-            )
+            ));
         }
         if common_parameters.cert_cert_period / DAYS != G1_DUNITER_V1_SIGPERIOD / DUNITER_V1_DAYS {
-            warn!(
+            report.warn(format!(
                 "parameter `cert_period` ({} days) is different from Ğ1's ({} days)",
                 common_parameters.cert_cert_period as f32 / DAYS as f32,
                 G1_DUNITER_V1_SIGPERIOD as f32 / DUNITER_V1_DAYS as f32
-            )
+            ));
         }
         if common_parameters.cert_validity_period / DAYS
             != G1_DUNITER_V1_SIGVALIDITY / DUNITER_V1_DAYS
         {
-            warn!(
+            report.warn(format!(
                 "parameter `cert_validity_period` ({} days) is different from Ğ1's ({} days)",
                 common_parameters.cert_validity_period as f32 / DAYS as f32,
                 G1_DUNITER_V1_SIGVALIDITY as f32 / DUNITER_V1_DAYS as f32
-            )
+            ));
         }
         if common_parameters.wot_min_cert_for_membership != G1_DUNITER_V1_SIGQTY {
-            warn!(
+            report.warn(format!(
                 "parameter `min_cert` value ({}) is different from Ğ1 value ({})",
                 common_parameters.wot_min_cert_for_membership, G1_DUNITER_V1_SIGQTY
-            )
+            ));
         }
         if common_parameters.cert_max_by_issuer != G1_DUNITER_V1_SIGSTOCK {
-            warn!(
+            report.warn(format!(
                 "parameter `cert_max_by_issuer` value ({}) is different from Ğ1 value ({})",
                 common_parameters.cert_max_by_issuer, G1_DUNITER_V1_SIGSTOCK
-            )
+            ));
         }
         if c != g1_duniter_v1_c {
-            warn!("parameter `c` value ({c}) is different from Ğ1 value ({g1_duniter_v1_c})")
+            report.warn(format!(
+                "parameter `c` value ({c}) is different from Ğ1 value ({g1_duniter_v1_c})"
+            ));
         }
         if common_parameters.universal_dividend_ud_creation_period as f32 / DAYS as f32
             != G1_DUNITER_V1_DT as f32 / DUNITER_V1_DAYS as f32
         {
-            warn!(
+            report.warn(format!(
                 "parameter `ud_creation_period` value ({} days) is different from Ğ1 value ({} days)",
                 common_parameters.universal_dividend_ud_creation_period as f32 / DAYS as f32,
                 G1_DUNITER_V1_DT as f32 / DUNITER_V1_DAYS as f32
-            )
+            ));
         }
         if common_parameters.universal_dividend_ud_reeval_period as f32 / DAYS as f32
             != G1_DUNITER_V1_DTREEVAL as f32 / DUNITER_V1_DAYS as f32
         {
-            warn!(
+            report.warn(format!(
                 "parameter `ud_reeval_period` value ({} days) is different from Ğ1 value ({} days)",
                 common_parameters.universal_dividend_ud_reeval_period as f32 / DAYS as f32,
                 G1_DUNITER_V1_DTREEVAL as f32 / DUNITER_V1_DAYS as f32
-            )
+            ));
         }
         if common_parameters.distance_min_accessible_referees != g1_duniter_v1_xpercent {
-            warn!(
+            report.warn(format!(
                 "parameter `distance_min_accessible_referees` value ({:?}) is different from {:?}",
                 common_parameters.distance_min_accessible_referees, g1_duniter_v1_xpercent
-            )
+            ));
         }
         if common_parameters.distance_max_depth != G1_DUNITER_V1_STEPMAX {
-            warn!(
+            report.warn(format!(
                 "parameter `max_depth` value ({}) is different from Ğ1 value ({})",
                 common_parameters.distance_max_depth, G1_DUNITER_V1_STEPMAX
-            )
+            ));
         }
         let count_uds = common_parameters.universal_dividend_ud_reeval_period
             / common_parameters.universal_dividend_ud_creation_period;
         if count_uds == 0 {
-            error!(
+            report.error(format!(
                 "the `ud_reeval_period / ud_creation_period` is zero ({} days/{} days)",
                 common_parameters.universal_dividend_ud_reeval_period / DAYS as u64,
                 common_parameters.universal_dividend_ud_creation_period / DAYS as u64
-            );
-            fatal = true;
+            ));
         }
     }
 
-    // some more checks
-    assert_eq!(
-        identities.len() - inactive_identities.len(),
-        memberships.len()
-    );
-    assert_eq!(initial_smiths_wot.len(), initial_authorities.len());
-    assert_eq!(initial_smiths_wot.len(), session_keys_map.len());
-    assert_eq!(identity_index.len(), identities.len());
-    assert_eq!(
-        accounts.len(),
-        identity_index.len() + genesis_data_wallets_count.sub(invalid_wallets)
+    smiths_and_technical_committee_checks(
+        &inactive_identities,
+        &technical_committee,
+        &smiths,
+        report,
     );
-    smiths_and_technical_committee_checks(&inactive_identities, &technical_committee, &smiths);
 
-    // check the logs to see all the fatal error preventing from starting gtest currency
-    if fatal {
-        eprintln!("some previously logged error prevent from building a sane genesis");
-        eprintln!("FATAL: some previously logged error prevent from building a sane genesis");
-        eprintln!(
-            "DEBUG: identities={}, accounts={}, smiths={}, authorities={}, certs_by_receiver={}",
-            identities.len(),
+    // some more checks, only meaningful on a sane genesis
+    if report.is_sane() {
+        assert_eq!(
+            identities.len() - inactive_identities.len(),
+            memberships.len()
+        );
+        assert_eq!(initial_smiths_wot.len(), initial_authorities.len());
+        assert_eq!(initial_smiths_wot.len(), session_keys_map.len());
+        assert_eq!(identity_index.len(), identities.len());
+        assert_eq!(
             accounts.len(),
-            initial_smiths_wot.len(),
-            initial_authorities.len(),
-            certs_by_receiver.len()
+            identity_index.len() + genesis_data_wallets_count.sub(invalid_wallets)
         );
-        panic!();
     }
 
     // Indexer output
@@ -695,6 +941,7 @@ where
         initial_smiths: initial_smiths_wot,
         sudo_key,
         technical_committee_members,
+        technical_committee_idties,
         ud,
     };
 
@@ -986,6 +1233,7 @@ fn smiths_and_technical_committee_checks(
     inactive_identities: &HashMap<u32, (String, IdtyStatus)>,
     technical_committee: &Vec<String>,
     smiths: &Vec<SmithData>,
+    report: &mut GenesisReport,
 ) {
     // no inactive tech comm
     for tech_com_member in technical_committee {
@@ -993,19 +1241,17 @@ fn smiths_and_technical_committee_checks(
             .values()
             .any(|(name, _)| name == tech_com_member);
         if inactive_commitee_member {
-            panic!("{tech_com_member} is an inactive technical commitee member");
+            report.error(format!(
+                "{tech_com_member} is an inactive technical commitee member"
+            ));
         }
     }
     // no inactive smith
     for SmithData { name: smith, .. } in smiths {
-        let inactive_smiths: Vec<_> = inactive_identities
+        inactive_identities
             .values()
             .filter(|(name, _)| name == smith)
-            .collect();
-        inactive_smiths
-            .iter()
-            .for_each(|(name, _)| log::warn!("Smith {name} is inactive"));
-        assert_eq!(inactive_smiths.len(), 0);
+            .for_each(|(name, _)| report.error(format!("Smith {name} is inactive")));
     }
 }
 
@@ -1014,8 +1260,8 @@ fn create_smith_wot<SK: Decode>(
     identities_v2: &HashMap<String, IdentityV2>,
     smiths: &Vec<SmithData>,
     clique_smiths: &Option<Vec<CliqueSmith>>,
-) -> Result<(bool, u32, u32, SmithMembers<SK>), String> {
-    let mut fatal = false;
+    report: &mut GenesisReport,
+) -> Result<(u32, u32, SmithMembers<SK>), String> {
     let mut counter_online_authorities = 0;
     // counter for smith certifications
     let mut counter_smith_cert = 0;
@@ -1043,15 +1289,13 @@ fn create_smith_wot<SK: Decode>(
                 &identities_v2_clone,
             )?;
         } else {
-            eprintln!(
+            report.error(format!(
                 "Smith '{}' does not correspond to exising identity",
                 &smith.name
-            );
-            fatal = true;
+            ));
         }
     }
     Ok((
-        fatal,
         counter_online_authorities,
         counter_smith_cert,
         SmithMembers {
@@ -1074,8 +1318,8 @@ fn create_smith_wot<SK: Decode>(
 fn v1_wallets_to_v2_accounts(
     wallets: BTreeMap<PubkeyV1, u64>,
     common_parameters: &CommonParameters,
+    report: &mut GenesisReport,
 ) -> (
-    bool,
     u64,
     BTreeMap<AccountId32, GenesisAccountData<u64, u32>>,
     usize,
@@ -1085,15 +1329,13 @@ fn v1_wallets_to_v2_accounts(
     // account inserted in genesis
     let mut accounts: BTreeMap<AccountId, GenesisAccountData<u64, u32>> = BTreeMap::new();
     let mut invalid_wallets = 0;
-    let mut fatal = false;
     for (pubkey, balance) in wallets {
         // check existential deposit
         if balance < common_parameters.balances_existential_deposit {
-            eprintln!(
+            report.error(format!(
                 "wallet {pubkey} has {balance} cǦT which is below {}",
                 common_parameters.balances_existential_deposit
-            );
-            fatal = true;
+            ));
         }
 
         // double check the monetary mass
@@ -1109,16 +1351,17 @@ fn v1_wallets_to_v2_accounts(
                 },
             );
         } else {
-            log::warn!("wallet {pubkey} has wrong format");
+            report.warn(format!("wallet {pubkey} has wrong format"));
             invalid_wallets = invalid_wallets.add(1);
         }
     }
-    (fatal, monetary_mass, accounts, invalid_wallets)
+    (monetary_mass, accounts, invalid_wallets)
 }
 
 fn check_identities_v2(
     identities_v2: &HashMap<String, IdentityV2>,
     common_parameters: &CommonParameters,
+    report: &mut GenesisReport,
 ) {
     // // Identities whose membership was lost since export
     // identities_v2.iter_mut()
@@ -1143,7 +1386,7 @@ fn check_identities_v2(
         .for_each(|(name, i)| {
             let nb_certs = i.certs_received.len() as u32;
             if nb_certs < common_parameters.wot_min_cert_for_membership {
-                log::warn!("{name} has only {nb_certs} valid certifications");
+                report.warn(format!("{name} has only {nb_certs} valid certifications"));
             }
         });
 }
@@ -1152,6 +1395,7 @@ fn check_genesis_data_and_filter_expired_certs_since_export(
     genesis_data: &mut GenesisMigrationData,
     genesis_timestamp: u64,
     common_parameters: &CommonParameters,
+    report: &mut GenesisReport,
 ) {
     // Remove expired certs since export
     genesis_data
@@ -1161,7 +1405,7 @@ fn check_genesis_data_and_filter_expired_certs_since_export(
             i.certs_received.retain(|issuer, v| {
                 let retain = (v.0 as u64) >= genesis_timestamp;
                 if !retain {
-                    log::warn!("{issuer} -> {receiver} cert expired since export");
+                    report.warn(format!("{issuer} -> {receiver} cert expired since export"));
                 }
                 retain
             });
@@ -1170,7 +1414,7 @@ fn check_genesis_data_and_filter_expired_certs_since_export(
     genesis_data.identities.iter_mut().for_each(|(name, i)| {
         if (i.membership_expire_on.0 as u64) < genesis_timestamp {
             if (i.membership_expire_on.0 as u64) >= genesis_data.current_block.median_time {
-                log::warn!("{name} membership expired since export");
+                report.warn(format!("{name} membership expired since export"));
             }
             i.membership_expire_on = TimestampV1(0);
         }
@@ -1181,16 +1425,22 @@ fn check_genesis_data_and_filter_expired_certs_since_export(
             && i.certs_received.len() < common_parameters.wot_min_cert_for_membership as usize
         {
             i.membership_expire_on = TimestampV1(0);
-            log::warn!("{name} lost membership because of lost certifications since export");
+            report.warn(format!(
+                "{name} lost membership because of lost certifications since export"
+            ));
         }
     });
 
     genesis_data.identities.iter().for_each(|(name, i)| {
         if i.owner_pubkey.is_some() && i.owner_address.is_some() {
-            log::warn!("{name} both has a pubkey and an address defined - address will be used");
+            report.warn(format!(
+                "{name} both has a pubkey and an address defined - address will be used"
+            ));
         }
         if i.owner_pubkey.is_none() && i.owner_address.is_none() {
-            eprintln!("{name} neither has a pubkey and an address defined");
+            report.error(format!(
+                "{name} neither has a pubkey and an address defined"
+            ));
         }
     });
 }
@@ -1198,23 +1448,20 @@ fn check_genesis_data_and_filter_expired_certs_since_export(
 fn genesis_data_to_identities_v2(
     genesis_identities: BTreeMap<String, IdentityV1>,
     genesis_timestamp: u64,
-) -> HashMap<String, IdentityV2> {
+) -> Result<HashMap<String, IdentityV2>, String> {
     genesis_identities
         .into_iter()
         .map(|(name, i)| {
-            let legacy_account = i
-                .owner_pubkey
-                .map(|pubkey| {
-                    v1_pubkey_to_account_id(pubkey)
-                        .expect("a G1 identity necessarily has a valid pubkey")
-                })
-                .unwrap_or_else(|| {
-                    i.owner_address.unwrap_or_else(|| {
-                        panic!("neither pubkey nor address is defined for {name}")
-                    })
-                });
+            let legacy_account = match (i.owner_pubkey, i.owner_address) {
+                (Some(pubkey), _) => v1_pubkey_to_account_id(pubkey)
+                    .map_err(|e| format!("{name} has an invalid pubkey: {e}"))?,
+                (None, Some(address)) => address,
+                (None, None) => {
+                    return Err(format!("neither pubkey nor address is defined for {name}"));
+                }
+            };
             let owner_key = legacy_account.clone();
-            (
+            Ok((
                 name,
                 IdentityV2 {
                     index: i.index,
@@ -1240,7 +1487,7 @@ fn genesis_data_to_identities_v2(
                         })
                         .collect(),
                 },
-            )
+            ))
         })
         .collect()
 }
@@ -1316,8 +1563,8 @@ fn feed_identities(
     memberships: &mut BTreeMap<u32, MembershipData>,
     identities_v2: &HashMap<String, IdentityV2>,
     common_parameters: &CommonParameters,
-) -> Result<(bool, Vec<GenesisIdentity>), String> {
-    let mut fatal = false;
+    report: &mut GenesisReport,
+) -> Result<Vec<GenesisIdentity>, String> {
     let mut identities: Vec<GenesisIdentity> = Vec::new();
     for (name, identity) in identities_v2 {
         // identity name
@@ -1328,11 +1575,10 @@ fn feed_identities(
         // Money
         // check that wallet with same owner_key does not exist
         if accounts.get(&identity.owner_key).is_some() {
-            eprintln!(
+            report.error(format!(
                 "{name} owner_key {} already exists as a simple wallet",
                 identity.owner_key
-            );
-            fatal = true;
+            ));
         }
         // insert as an account
         accounts.insert(
@@ -1349,11 +1595,10 @@ fn feed_identities(
         // insert identity
         // check that index does not already exist
         if let Some(other_name) = identity_index.get(&identity.index) {
-            eprintln!(
+            report.error(format!(
                 "{other_name} already has identity index {} of {name}",
                 identity.index
-            );
-            fatal = true;
+            ));
         }
         identity_index.insert(identity.index, name.to_owned());
 
@@ -1417,7 +1662,7 @@ fn feed_identities(
     // sort the identities by index for reproducibility (should have been a vec in json)
     identities.sort_unstable_by(|a, b| a.idty_index.cmp(&b.idty_index));
 
-    Ok((fatal, identities))
+    Ok(identities)
 }
 
 fn set_smith_session_keys_and_authority_status<SK>(
@@ -1478,21 +1723,19 @@ fn feed_smith_certs_by_receiver(
     let mut certs = Vec::<u32>::new();
     if clique_smiths.is_some() {
         // All initial smiths are considered to be certifying all each other
-        clique_smiths
+        for other_smith in clique_smiths
             .as_ref()
             .unwrap()
             .iter()
             .filter(|other_smith| *other_smith.name.as_str() != *smith.name)
-            .for_each(|other_smith| {
-                let issuer_index = &identities_v2
-                    .get(other_smith.name.as_str())
-                    .unwrap_or_else(|| {
-                        panic!("Identity '{}' does not exist", other_smith.name.as_str())
-                    })
-                    .index;
-                certs.push(*issuer_index);
-                counter_smith_cert += 1;
-            });
+        {
+            let issuer_index = &identities_v2
+                .get(other_smith.name.as_str())
+                .ok_or(format!("Identity '{}' does not exist", other_smith.name))?
+                .index;
+            certs.push(*issuer_index);
+            counter_smith_cert += 1;
+        }
     } else {
         for issuer in &smith.certs_received {
             let issuer_index = &identities_v2
@@ -1510,8 +1753,8 @@ fn feed_smith_certs_by_receiver(
 fn feed_certs_by_receiver(
     certs_by_receiver: &mut BTreeMap<u32, BTreeMap<u32, Option<u32>>>,
     identities_v2: &HashMap<String, IdentityV2>,
-) -> (bool, u32) {
-    let mut fatal = false;
+    report: &mut GenesisReport,
+) -> u32 {
     let mut counter_cert = 0;
     for identity in identities_v2.values() {
         let mut certs = BTreeMap::new();
@@ -1520,13 +1763,12 @@ fn feed_certs_by_receiver(
                 certs.insert(issuer.index, Some(*expire_on));
                 counter_cert += 1;
             } else {
-                eprintln!("Identity '{issuer}' does not exist");
-                fatal = true;
+                report.error(format!("Identity '{issuer}' does not exist"));
             };
         }
         certs_by_receiver.insert(identity.index, certs);
     }
-    (fatal, counter_cert)
+    counter_cert
 }
 
 fn check_authority_exists_in_both_wots(
@@ -1574,26 +1816,135 @@ fn build_smiths_wot(
     Ok(smiths)
 }
 
+/// keep only the smith certifications issued by another smith, once, and return the others
+fn filter_smith_certs(mut smiths: Vec<RawSmith>) -> (Vec<RawSmith>, Vec<(String, String)>) {
+    let names: Vec<String> = smiths.iter().map(|smith| smith.name.clone()).collect();
+    let mut ignored_certs = Vec::new();
+    for smith in &mut smiths {
+        let mut certs_received: Vec<String> = Vec::new();
+        for issuer in &smith.certs_received {
+            if *issuer != smith.name && names.contains(issuer) {
+                if !certs_received.contains(issuer) {
+                    certs_received.push(issuer.clone());
+                }
+            } else {
+                ignored_certs.push((issuer.clone(), smith.name.clone()));
+            }
+        }
+        smith.certs_received = certs_received;
+    }
+    (smiths, ignored_certs)
+}
+
+/// add the missing smith certifications, issued by the smiths having issued the fewest ones
+fn add_missing_smith_certs(
+    mut smiths: Vec<RawSmith>,
+    min_cert: u32,
+    max_by_issuer: u32,
+) -> Result<(Vec<RawSmith>, u32), String> {
+    let mut issued: BTreeMap<String, u32> =
+        smiths.iter().map(|smith| (smith.name.clone(), 0)).collect();
+    for issuer in smiths.iter().flat_map(|smith| &smith.certs_received) {
+        *issued.get_mut(issuer).expect("certs have been filtered") += 1;
+    }
+    let mut added_certs = 0;
+    for smith in &mut smiths {
+        while smith.certs_received.len() < min_cert as usize {
+            let issuer = issued
+                .iter()
+                .filter(|(name, count)| {
+                    **name != smith.name
+                        && **count < max_by_issuer
+                        && !smith.certs_received.contains(*name)
+                })
+                .min_by_key(|(_, count)| **count)
+                .map(|(name, _)| name.clone())
+                .ok_or(format!(
+                    "{} can receive only {}/{min_cert} smith certifications",
+                    smith.name,
+                    smith.certs_received.len()
+                ))?;
+            *issued.get_mut(&issuer).expect("issuer is a smith") += 1;
+            smith.certs_received.push(issuer);
+            added_certs += 1;
+        }
+    }
+    Ok((smiths, added_certs))
+}
+
+/// drop the smiths below the minimum of smith certifications, until the remaining ones are valid
+fn drop_smiths_below_min_cert(
+    mut smiths: Vec<RawSmith>,
+    min_cert: u32,
+    maybe_force_authority: &Option<String>,
+) -> (Vec<RawSmith>, Vec<String>) {
+    let mut dropped = Vec::new();
+    loop {
+        let (invalid, valid): (Vec<RawSmith>, Vec<RawSmith>) =
+            smiths.into_iter().partition(|smith| {
+                smith.certs_received.len() < min_cert as usize
+                    && Some(&smith.name) != maybe_force_authority.as_ref()
+            });
+        smiths = valid;
+        if invalid.is_empty() {
+            return (smiths, dropped);
+        }
+        // the certifications issued by dropped smiths are lost
+        for smith in &mut smiths {
+            smith
+                .certs_received
+                .retain(|issuer| !invalid.iter().any(|dropped| &dropped.name == issuer));
+        }
+        dropped.extend(invalid.into_iter().map(|smith| smith.name));
+    }
+}
+
+fn smiths_to_yaml(smiths: Vec<RawSmith>) -> Result<String, String> {
+    #[derive(Serialize)]
+    struct SmithsInput {
+        smiths: BTreeMap<String, RawSmith>,
+    }
+    serde_yaml::to_string(&SmithsInput {
+        smiths: smiths
+            .into_iter()
+            .map(|smith| (smith.name.clone(), smith))
+            .collect(),
+    })
+    .map_err(|e| format!("cannot serialize smiths: {e}"))
+}
+
 fn decorate_smiths_with_identity(
     smiths: Vec<RawSmith>,
     identity_index: &HashMap<u32, String>,
     identities_v2: &HashMap<String, IdentityV2>,
+    report: &mut GenesisReport,
 ) -> Vec<SmithData> {
     smiths
         .into_iter()
-        .map(|smith| SmithData {
-            idty_index: identity_index
+        .filter_map(|smith| {
+            let idty_index = identity_index
                 .iter()
                 .find(|(_, v)| ***v == smith.name)
-                .map(|(k, _)| *k)
-                .expect("smith must have an identity"),
-            account: identities_v2
+                .map(|(k, _)| *k);
+            let account = identities_v2
                 .get(smith.name.as_str())
-                .map(|i| i.owner_key.clone())
-                .expect("identity must exist"),
-            name: smith.name,
-            session_keys: smith.session_keys,
-            certs_received: smith.certs_received,
+                .map(|i| i.owner_key.clone());
+            match (idty_index, account) {
+                (Some(idty_index), Some(account)) => Some(SmithData {
+                    idty_index,
+                    account,
+                    name: smith.name,
+                    session_keys: smith.session_keys,
+                    certs_received: smith.certs_received,
+                }),
+                _ => {
+                    report.error(format!(
+                        "Smith '{}' does not correspond to exising identity",
+                        smith.name
+                    ));
+                    None
+                }
+            }
         })
         .collect()
 }
@@ -1629,7 +1980,8 @@ fn local_chain_default_technical_committee_members() -> Vec<AccountId> {
     vec![get_account_id_from_seed::<ed25519::Public>("Alice")]
 }
 
-fn local_chain_technical_committee_member_seed(index: usize) -> String {
+/// Seed of the development account at the given position (`Alice`, `Bob`, ...).
+pub(crate) fn local_chain_member_seed(index: usize) -> String {
     let names: [&str; 6] = ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
     if index < names.len() {
         names[index].to_string()
@@ -1642,7 +1994,7 @@ fn local_chain_technical_committee_members(
     technical_committee_members_len: usize,
 ) -> Vec<AccountId> {
     (0..technical_committee_members_len)
-        .map(local_chain_technical_committee_member_seed)
+        .map(local_chain_member_seed)
         .map(|seed| get_account_id_from_seed::<ed25519::Public>(seed.as_str()))
         .collect()
 }
@@ -1779,6 +2131,12 @@ where
     } else {
         local_chain_default_technical_committee_members()
     };
+    // committee members without identity are not managed by the committee membership pallet
+    let technical_committee_idties = identities
+        .iter()
+        .filter(|identity| technical_committee_members.contains(&identity.owner_key))
+        .map(|identity| (identity.idty_index, identity.owner_key.clone()))
+        .collect();
 
     let genesis_timestamp: u64 = get_genesis_timestamp()?;
     let genesis_info = GenesisInfo {
@@ -1827,6 +2185,7 @@ where
         initial_smiths: initial_smiths_wot,
         sudo_key: Some(root_key),
         technical_committee_members,
+        technical_committee_idties,
         ud,
     };
 
@@ -1921,9 +2280,12 @@ fn get_genesis_input<P: Default + DeserializeOwned>(
     }
 }
 
-fn get_genesis_migration_data() -> Result<GenesisMigrationData, String> {
-    let json_file_path = std::env::var("DUNITER_GENESIS_DATA")
-        .unwrap_or_else(|_| "./resources/g1-data.json".to_owned());
+/// takes DUNITER_GENESIS_DATA env var if present or ./resources/g1-data.json by default
+pub fn get_genesis_migration_data_path() -> String {
+    std::env::var("DUNITER_GENESIS_DATA").unwrap_or_else(|_| "./resources/g1-data.json".to_owned())
+}
+
+fn get_genesis_migration_data(json_file_path: String) -> Result<GenesisMigrationData, String> {
     let file = std::fs::File::open(&json_file_path)
         .map_err(|e| format!("Error opening gen migration file `{json_file_path}`: {e}"))?;
     let bytes = unsafe {
@@ -1934,7 +2296,8 @@ fn get_genesis_migration_data() -> Result<GenesisMigrationData, String> {
         .map_err(|e| format!("Error parsing gen migration file: {e}"))
 }
 
-fn get_genesis_timestamp() -> Result<u64, String> {
+/// takes DUNITER_GENESIS_TIMESTAMP env var if present or the current time by default
+pub fn get_genesis_timestamp() -> Result<u64, String> {
     if let Ok(genesis_timestamp) = std::env::var("DUNITER_GENESIS_TIMESTAMP") {
         genesis_timestamp
             .parse()
@@ -2006,7 +2369,7 @@ pub struct CommonParameters {
 }
 
 /// Generate an authority keys.
-fn get_authority_keys_from_seed(s: &str) -> AuthorityKeys {
+pub(crate) fn get_authority_keys_from_seed(s: &str) -> AuthorityKeys {
     (
         get_account_id_from_seed::<ed25519::Public>(s),
         get_from_seed::<GrandpaId>(s),
@@ -2173,6 +2536,153 @@ technical_committee: ["alice"]
         fs::remove_file(file_path).expect("must remove temporary yaml config");
     }
 
+    #[test]
+    fn test_report_expired_certs_and_lost_memberships() {
+        let identity = |certs_received: Vec<(&str, u32)>| IdentityV1 {
+            index: 1,
+            owner_pubkey: None,
+            owner_address: Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
+            old_owner_key: None,
+            membership_expire_on: TimestampV1(2_000),
+            membership_revokes_on: TimestampV1(3_000),
+            revoked: false,
+            balance: 1_000,
+            certs_received: certs_received
+                .into_iter()
+                .map(|(issuer, expire_on)| (issuer.to_owned(), TimestampV1(expire_on)))
+                .collect(),
+        };
+        let mut genesis_data = GenesisMigrationData {
+            initial_monetary_mass: 1_000,
+            current_block: BlockV1 {
+                number: 1,
+                median_time: 500,
+            },
+            identities: BTreeMap::from([(
+                "alice".to_owned(),
+                identity(vec![("bob", 900), ("charlie", 2_000)]),
+            )]),
+            wallets: BTreeMap::new(),
+        };
+        let common_parameters = CommonParameters {
+            wot_min_cert_for_membership: 2,
+            ..Default::default()
+        };
+        let mut report = GenesisReport::default();
+
+        check_genesis_data_and_filter_expired_certs_since_export(
+            &mut genesis_data,
+            1_000,
+            &common_parameters,
+            &mut report,
+        );
+
+        assert_eq!(
+            report.warnings,
+            vec![
+                "bob -> alice cert expired since export".to_owned(),
+                "alice lost membership because of lost certifications since export".to_owned(),
+            ]
+        );
+        assert!(report.is_sane());
+    }
+
+    #[test]
+    fn test_report_inactive_technical_committee_member_and_smith() {
+        let inactive_identities = HashMap::from([(2, ("bob".to_owned(), IdtyStatus::NotMember))]);
+        let smiths = vec![SmithData {
+            idty_index: 2,
+            name: "bob".to_owned(),
+            account: get_account_id_from_seed::<sr25519::Public>("Bob"),
+            session_keys: None,
+            certs_received: vec![],
+        }];
+        let mut report = GenesisReport::default();
+
+        smiths_and_technical_committee_checks(
+            &inactive_identities,
+            &vec!["alice".to_owned(), "bob".to_owned()],
+            &smiths,
+            &mut report,
+        );
+
+        assert_eq!(
+            report.errors,
+            vec![
+                "bob is an inactive technical commitee member".to_owned(),
+                "Smith bob is inactive".to_owned(),
+            ]
+        );
+        assert!(!report.is_sane());
+    }
+
+    fn raw_smith(name: &str, certs_received: &[&str]) -> RawSmith {
+        RawSmith {
+            name: name.to_owned(),
+            session_keys: None,
+            certs_received: certs_received.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_add_missing_smith_certs() {
+        let (smiths, ignored_certs) = filter_smith_certs(vec![
+            raw_smith("alice", &["bob", "bob", "eve"]),
+            raw_smith("bob", &["alice", "charlie"]),
+            raw_smith("charlie", &[]),
+        ]);
+        assert_eq!(ignored_certs, vec![("eve".to_owned(), "alice".to_owned())]);
+
+        let (smiths, added_certs) =
+            add_missing_smith_certs(smiths, 2, 10).expect("smith WoT can be repaired");
+
+        assert_eq!(added_certs, 3);
+        let certs: Vec<_> = smiths
+            .iter()
+            .map(|smith| (smith.name.as_str(), smith.certs_received.clone()))
+            .collect();
+        assert_eq!(
+            certs,
+            vec![
+                ("alice", vec!["bob".to_owned(), "charlie".to_owned()]),
+                ("bob", vec!["alice".to_owned(), "charlie".to_owned()]),
+                ("charlie", vec!["alice".to_owned(), "bob".to_owned()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_missing_smith_certs_needs_enough_smiths() {
+        let smiths = vec![raw_smith("alice", &["bob"]), raw_smith("bob", &["alice"])];
+
+        let Err(err) = add_missing_smith_certs(smiths, 2, 10) else {
+            panic!("two smiths cannot receive two smith certifications each");
+        };
+
+        assert_eq!(err, "alice can receive only 1/2 smith certifications");
+    }
+
+    #[test]
+    fn test_drop_smiths_below_min_cert() {
+        let smiths = vec![
+            raw_smith("alice", &["bob"]),
+            raw_smith("bob", &["alice"]),
+            raw_smith("charlie", &["dave"]),
+            raw_smith("dave", &[]),
+        ];
+
+        // dropping dave makes charlie lose its only certification
+        let (kept, dropped) = drop_smiths_below_min_cert(smiths.clone(), 1, &None);
+        assert_eq!(dropped, vec!["dave".to_owned(), "charlie".to_owned()]);
+        let kept: Vec<_> = kept.into_iter().map(|smith| smith.name).collect();
+        assert_eq!(kept, vec!["alice".to_owned(), "bob".to_owned()]);
+
+        // the forced authority is never dropped
+        let (kept, dropped) = drop_smiths_below_min_cert(smiths, 1, &Some("dave".to_owned()));
+        assert!(dropped.is_empty());
+        assert_eq!(kept.len(), 4);
+    }
+
     #[test]
     fn test_parse_local_chain_technical_committee_members_len_default() {
         assert_eq!(
@@ -44,6 +44,7 @@ pub struct DuniterConfigExtension {
     /// Trusted warp-sync checkpoint header from a JSON file.
     ///
     /// When provided, warp sync skips GRANDPA proof download and starts from this trusted header.
+    /// The file is a header, or a checkpoint exported by `duniter checkpoint export`.
     #[arg(
         long,
         value_name = "JSON_FILE_PATH",
@@ -78,6 +79,13 @@ pub struct DuniterConfigExtension {
     /// ```
     #[arg(long, value_name = "JSON_FILE_PATH")]
     pub public_endpoints: Option<String>,
+
+    /// Run the distance oracle inside the node, on authority nodes only.
+    ///
+    /// The results are handed over to the block authoring in memory. The result files of a
+    /// standalone oracle are still used when the embedded oracle has no result.
+    #[arg(long, default_value_t = false)]
+    pub embedded_distance_oracle: bool,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -88,6 +96,13 @@ pub enum Subcommand {
     /// Validate blocks.
     CheckBlock(sc_cli::CheckBlockCmd),
 
+    /// Check the runtime invariants on the state of a block of the local database.
+    CheckState(crate::command::check_state::CheckStateCmd),
+
+    /// Export or verify warp sync checkpoints.
+    #[clap(subcommand)]
+    Checkpoint(crate::command::checkpoint::CheckpointSubcommand),
+
     /// Run distance oracle.
     #[cfg(feature = "distance-oracle")]
     DistanceOracle(DistanceOracle),
@@ -98,6 +113,13 @@ pub enum Subcommand {
     /// Export the state of a given block into a chain spec.
     ExportState(sc_cli::ExportStateCmd),
 
+    /// Fork the state of a network into a chain spec run by development keys.
+    ForkOff(crate::command::fork_off::ForkOffCmd),
+
+    /// Genesis building cli utilities
+    #[clap(subcommand)]
+    Genesis(crate::command::genesis::GenesisSubcommand),
+
     /// Key management cli utilities
     #[clap(subcommand)]
     Key(crate::command::key::KeySubcommand),
@@ -18,12 +18,17 @@
 #![allow(clippy::result_large_err)]
 #![allow(unused_imports)]
 
+pub mod check_state;
+pub mod checkpoint;
+pub mod fork_off;
+pub mod genesis;
 pub mod key;
 pub mod utils;
 
 use crate::{
     chain_spec,
     cli::{Cli, DuniterConfigExtension, Subcommand},
+    command::checkpoint::CheckpointSubcommand,
     service,
     service::{RuntimeType, runtime_executor::Executor},
 };
@@ -307,6 +312,22 @@ pub fn run() -> sc_cli::Result<()> {
                 Ok((cmd.run(client, import_queue), task_manager))
             })
         }
+        Some(Subcommand::CheckState(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.async_run(|config| {
+                let (client, _, _, task_manager) =
+                    service::new_chain_ops(&config, cli.sealing.is_manual_consensus())?;
+                Ok((cmd.run(client), task_manager))
+            })
+        }
+        Some(Subcommand::Checkpoint(CheckpointSubcommand::Export(cmd))) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| cmd.run(config))
+        }
+        Some(Subcommand::Checkpoint(CheckpointSubcommand::Verify(cmd))) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.sync_run(|config| cmd.run(config))
+        }
         Some(Subcommand::ExportBlocks(cmd)) => {
             let runner = cli.create_runner(cmd)?;
             runner.async_run(|config| {
@@ -323,6 +344,15 @@ pub fn run() -> sc_cli::Result<()> {
                 Ok((cmd.run(client, config.chain_spec), task_manager))
             })
         }
+        Some(Subcommand::ForkOff(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            runner.async_run(|config| {
+                let (client, _, _, task_manager) =
+                    service::new_chain_ops(&config, cli.sealing.is_manual_consensus())?;
+                Ok((cmd.run(client, config.chain_spec), task_manager))
+            })
+        }
+        Some(Subcommand::Genesis(cmd)) => cmd.run(),
         Some(Subcommand::Key(cmd)) => cmd.run(&cli),
         Some(Subcommand::ImportBlocks(cmd)) => {
             let runner = cli.create_runner(cmd)?;
@@ -489,9 +519,21 @@ fn force_cli_options(cli: &mut Cli) {
         Some(Subcommand::CheckBlock(ref mut cmd)) => {
             cmd.import_params.database_params.database = Some(sc_cli::Database::ParityDb);
         }
+        Some(Subcommand::CheckState(ref mut cmd)) => {
+            cmd.database_params.database = Some(sc_cli::Database::ParityDb);
+        }
+        Some(Subcommand::Checkpoint(CheckpointSubcommand::Export(ref mut cmd))) => {
+            cmd.database_params.database = Some(sc_cli::Database::ParityDb);
+        }
+        Some(Subcommand::Checkpoint(CheckpointSubcommand::Verify(ref mut cmd))) => {
+            cmd.database_params.database = Some(sc_cli::Database::ParityDb);
+        }
         Some(Subcommand::ExportBlocks(ref mut cmd)) => {
             cmd.database_params.database = Some(sc_cli::Database::ParityDb);
         }
+        Some(Subcommand::ForkOff(ref mut cmd)) => {
+            cmd.database_params.database = Some(sc_cli::Database::ParityDb);
+        }
         Some(Subcommand::ImportBlocks(ref mut cmd)) => {
             cmd.import_params.database_params.database = Some(sc_cli::Database::ParityDb);
         }
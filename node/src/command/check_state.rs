@@ -0,0 +1,141 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::service::client::{
+    AbstractClient, Client, ClientHandle, ExecuteWithClient, RuntimeApiCollection,
+};
+use common_runtime::{Block, invariants::InvariantsApi};
+use log::info;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::UsageProvider;
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::BlakeTwo256};
+use std::{io::Write, sync::Arc};
+
+/// Check the runtime invariants on the state of a given block of the local database.
+///
+/// A JSON report of the violated invariants is printed on the standard output, and the
+/// command fails if any invariant is violated.
+#[derive(Debug, clap::Parser)]
+pub struct CheckStateCmd {
+    /// Block hash or number of the checked state, the best block by default.
+    #[clap(value_name = "HASH or NUMBER")]
+    pub input: Option<BlockNumberOrHash>,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+impl CheckStateCmd {
+    /// Run the command
+    pub async fn run(&self, client: Arc<Client>) -> sc_cli::Result<()> {
+        let report = client.execute_with(CheckState {
+            input: self.input.clone(),
+        })?;
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("cannot serialize report: {e}"))?;
+        std::io::stdout().write_all(json.as_bytes())?;
+        std::io::stdout().write_all(b"\n")?;
+
+        let violations = report["violations"].as_array().map_or(0, Vec::len);
+        if violations == 0 {
+            Ok(())
+        } else {
+            Err(format!("{violations} runtime invariants are violated").into())
+        }
+    }
+}
+
+struct CheckState {
+    input: Option<BlockNumberOrHash>,
+}
+
+impl ExecuteWithClient for CheckState {
+    type Output = sc_cli::Result<serde_json::Value>;
+
+    fn execute_with_client<Client, Api, Backend>(self, client: Arc<Client>) -> Self::Output
+    where
+        Backend: sc_client_api::Backend<Block> + 'static,
+        Backend::State: sc_client_api::StateBackend<BlakeTwo256>,
+        Api: RuntimeApiCollection,
+        Client: AbstractClient<Block, Backend, Api = Api> + 'static,
+    {
+        let hash = match self
+            .input
+            .as_ref()
+            .map(|b| b.parse::<Block>())
+            .transpose()?
+        {
+            Some(id) => client.expect_block_hash_from_id(&id)?,
+            None => client.usage_info().chain.best_hash,
+        };
+        let number = client.expect_block_number_from_id(&BlockId::Hash(hash))?;
+        info!("Checking runtime invariants at #{number} ({hash})...");
+        let runtime_api = client.runtime_api();
+        // runtimes older than the invariants API cannot be checked
+        if !runtime_api
+            .has_api::<dyn InvariantsApi<Block>>(hash)
+            .map_err(|e| format!("cannot read the runtime version: {e}"))?
+        {
+            return Err(format!(
+                "the runtime of block #{number} does not provide the InvariantsApi runtime API, \
+                 its state cannot be checked"
+            )
+            .into());
+        }
+        let violations = runtime_api
+            .check_invariants(hash)
+            .map_err(|e| format!("cannot check runtime invariants: {e}"))?;
+
+        Ok(serde_json::json!({
+            "block": {
+                "number": number,
+                "hash": hash,
+            },
+            "violations": violations
+                .iter()
+                .map(|violation| serde_json::json!({
+                    "pallet": String::from_utf8_lossy(&violation.pallet),
+                    "message": String::from_utf8_lossy(&violation.message),
+                }))
+                .collect::<Vec<_>>(),
+        }))
+    }
+}
+
+impl CliConfiguration for CheckStateCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
@@ -0,0 +1,584 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::service::{self, GRANDPA_JUSTIFICATION_PERIOD, client::Client};
+use codec::{Decode, Encode};
+use common_runtime::{Block, BlockNumber, Hash, Header};
+use log::info;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::BlockBackend;
+use sc_consensus_grandpa::{
+    AuthoritySetHardFork, GrandpaJustification, find_scheduled_change, warp_proof::WarpSyncFragment,
+};
+use sc_network_sync::strategy::warp::WarpSyncProvider;
+use sc_service::{ChainSpec, Configuration};
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_consensus_grandpa::{AuthorityList, GRANDPA_ENGINE_ID, SetId};
+use sp_core::{Bytes, hashing::twox_128};
+use sp_runtime::{BuildStorage, traits::Header as _};
+use std::{collections::HashMap, io::Write, path::PathBuf};
+
+/// Trusted warp sync checkpoint: a finalized header, with the GRANDPA justification of its
+/// finality.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Header of the finalized block.
+    pub header: Header,
+    /// SCALE encoded GRANDPA justification of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justification: Option<Bytes>,
+}
+
+impl Checkpoint {
+    /// Parses a checkpoint file, or a bare header like the first embedded checkpoints.
+    pub fn from_json(json: &[u8]) -> serde_json::Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum CheckpointFile {
+            Checkpoint(Checkpoint),
+            Header(Header),
+        }
+
+        Ok(match serde_json::from_slice(json)? {
+            CheckpointFile::Checkpoint(checkpoint) => checkpoint,
+            CheckpointFile::Header(header) => Self {
+                header,
+                justification: None,
+            },
+        })
+    }
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum CheckpointSubcommand {
+    /// Export a finalized header with its GRANDPA justification as a warp sync checkpoint.
+    Export(ExportCheckpointCmd),
+    /// Verify a warp sync checkpoint against the local database or a justification chain.
+    Verify(VerifyCheckpointCmd),
+}
+
+/// Export a finalized header of the local database with its GRANDPA justification.
+///
+/// The justification is verified against the authority sets followed from the genesis, including
+/// the GRANDPA hard forks of the network, before the checkpoint is written.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCheckpointCmd {
+    /// Block hash or number of the checkpoint, the last finalized block with a justification by
+    /// default.
+    #[clap(value_name = "HASH or NUMBER")]
+    pub input: Option<BlockNumberOrHash>,
+
+    /// Output file of the checkpoint, the standard output by default.
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Also write the justifications of the authority set changes from the genesis to the
+    /// checkpoint to this file, to verify the checkpoint without the database.
+    #[clap(long, value_name = "FILE")]
+    pub justification_chain: Option<PathBuf>,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+/// Verify a warp sync checkpoint.
+///
+/// Without justification chain, the checkpoint must be finalized in the local database, and its
+/// justification, if any, is verified against the authority set changes of the database.
+#[derive(Debug, clap::Parser)]
+pub struct VerifyCheckpointCmd {
+    /// Checkpoint file, as exported by `checkpoint export` or embedded in the node.
+    #[clap(value_name = "JSON_FILE_PATH")]
+    pub checkpoint: PathBuf,
+
+    /// Justification chain written by `checkpoint export`, to verify the checkpoint without the
+    /// database.
+    #[clap(long, value_name = "FILE")]
+    pub justification_chain: Option<PathBuf>,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+/// GRANDPA authority set finalizing the blocks after `since`.
+#[derive(Clone, Debug, PartialEq)]
+struct AuthoritySet {
+    since: BlockNumber,
+    set_id: SetId,
+    authorities: AuthorityList,
+}
+
+impl ExportCheckpointCmd {
+    /// Run the command
+    pub fn run(&self, config: Configuration) -> sc_cli::Result<()> {
+        let genesis_authorities = genesis_authorities(&*config.chain_spec)?;
+        let hard_forks = service::grandpa_hard_forks(&config);
+        let (client, warp_sync, _task_manager) = service::new_checkpoint_ops(&config)?;
+
+        let hash = match self
+            .input
+            .as_ref()
+            .map(|b| b.parse::<Block>())
+            .transpose()?
+        {
+            Some(id) => client.expect_block_hash_from_id(&id)?,
+            None => last_justified_block(&client)?,
+        };
+        let header = client.expect_header(hash)?;
+        let number = *header.number();
+        check_finalized(&client, &header)?;
+        let justification = client
+            .justifications(hash)?
+            .and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID))
+            .ok_or_else(|| {
+                format!(
+                    "no GRANDPA justification stored for #{number}, only blocks changing the \
+                     authority set and every {GRANDPA_JUSTIFICATION_PERIOD} blocks have one"
+                )
+            })?;
+        let checkpoint = Checkpoint {
+            header,
+            justification: Some(justification.into()),
+        };
+
+        let fragments = justification_chain(&client, &*warp_sync, number)?;
+        let sets = authority_sets(&fragments, genesis_authorities, &hard_forks)?;
+        let set_id = verify_justification(&checkpoint, &sets)?;
+        info!("Exporting checkpoint #{number} ({hash}) finalized by authority set {set_id}...");
+
+        let json = serde_json::to_string_pretty(&checkpoint)
+            .map_err(|e| format!("cannot serialize checkpoint: {e}"))?;
+        match &self.output {
+            Some(path) => std::fs::write(path, json + "\n")?,
+            None => std::io::stdout().write_all((json + "\n").as_bytes())?,
+        }
+        if let Some(path) = &self.justification_chain {
+            std::fs::write(path, fragments.encode())?;
+        }
+        Ok(())
+    }
+}
+
+impl VerifyCheckpointCmd {
+    /// Run the command
+    pub fn run(&self, config: Configuration) -> sc_cli::Result<()> {
+        let checkpoint = Checkpoint::from_json(&std::fs::read(&self.checkpoint)?)
+            .map_err(|e| format!("invalid checkpoint: {e}"))?;
+        let number = *checkpoint.header.number();
+        let hash = checkpoint.header.hash();
+        let genesis_authorities = genesis_authorities(&*config.chain_spec)?;
+        let hard_forks = service::grandpa_hard_forks(&config);
+
+        let fragments = match &self.justification_chain {
+            Some(path) => Vec::<WarpSyncFragment<Block>>::decode(&mut &std::fs::read(path)?[..])
+                .map_err(|e| format!("invalid justification chain: {e}"))?,
+            None => {
+                let (client, warp_sync, _task_manager) = service::new_checkpoint_ops(&config)?;
+                check_finalized(&client, &checkpoint.header)?;
+                info!("Checkpoint #{number} ({hash}) is finalized in the local database");
+                if checkpoint.justification.is_none() {
+                    return Ok(());
+                }
+                justification_chain(&client, &*warp_sync, number)?
+            }
+        };
+        let sets = authority_sets(&fragments, genesis_authorities, &hard_forks)?;
+        let set_id = verify_justification(&checkpoint, &sets)?;
+        info!("Checkpoint #{number} ({hash}) is finalized by authority set {set_id}");
+        Ok(())
+    }
+}
+
+/// GRANDPA authorities of the genesis of a chain spec.
+fn genesis_authorities(chain_spec: &dyn ChainSpec) -> Result<AuthorityList, String> {
+    let storage = chain_spec.as_storage_builder().build_storage()?;
+    let key = [twox_128(b"Grandpa"), twox_128(b"Authorities")].concat();
+    let authorities = storage
+        .top
+        .get(&key)
+        .ok_or("no GRANDPA authorities in the genesis")?;
+    AuthorityList::decode(&mut &authorities[..])
+        .map_err(|e| format!("invalid GRANDPA authorities in the genesis: {e}"))
+}
+
+/// Last finalized block with a GRANDPA justification.
+fn last_justified_block(client: &Client) -> Result<Hash, String> {
+    let mut hash = client.info().finalized_hash;
+    for _ in 0..=GRANDPA_JUSTIFICATION_PERIOD {
+        if client
+            .justifications(hash)
+            .map_err(|e| format!("cannot read justifications: {e}"))?
+            .and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID))
+            .is_some()
+        {
+            return Ok(hash);
+        }
+        let header = client
+            .expect_header(hash)
+            .map_err(|e| format!("cannot read header: {e}"))?;
+        if *header.number() == 0 {
+            break;
+        }
+        hash = *header.parent_hash();
+    }
+    Err(format!(
+        "no GRANDPA justification in the last {GRANDPA_JUSTIFICATION_PERIOD} finalized blocks"
+    ))
+}
+
+/// Checks that a header is finalized in the local database.
+fn check_finalized(client: &Client, header: &Header) -> Result<(), String> {
+    let number = *header.number();
+    let hash = header.hash();
+    let info = client.info();
+    let canonical_hash = client
+        .hash(number)
+        .map_err(|e| format!("cannot read block hash: {e}"))?;
+    if canonical_hash != Some(hash) {
+        return Err(format!(
+            "#{number} ({hash}) is not in the canonical chain of the local database"
+        ));
+    }
+    if number > info.finalized_number {
+        return Err(format!(
+            "#{number} ({hash}) is not finalized yet, the last finalized block is #{}",
+            info.finalized_number
+        ));
+    }
+    Ok(())
+}
+
+/// Justifications of the authority set changes before a block, from the warp sync proofs of the
+/// local database.
+fn justification_chain(
+    client: &Client,
+    warp_sync: &dyn WarpSyncProvider<Block>,
+    before: BlockNumber,
+) -> Result<Vec<WarpSyncFragment<Block>>, String> {
+    let mut fragments = Vec::new();
+    let mut start = client.info().genesis_hash;
+    loop {
+        let proof = warp_sync.generate(start).map_err(|e| {
+            format!(
+                "cannot generate the justification chain, the database must be synced from the \
+                 genesis: {e}"
+            )
+        })?;
+        // the fields of `WarpSyncProof` are private, it is decoded with the same layout
+        let (proof_fragments, is_finished) =
+            <(Vec<WarpSyncFragment<Block>>, bool)>::decode(&mut &proof.0[..])
+                .map_err(|e| format!("invalid warp sync proof: {e}"))?;
+        let Some(last) = proof_fragments.last() else {
+            break;
+        };
+        start = last.header.hash();
+        let done = is_finished || *last.header.number() >= before;
+        fragments.extend(
+            proof_fragments
+                .into_iter()
+                .take_while(|fragment| *fragment.header.number() < before),
+        );
+        if done {
+            break;
+        }
+    }
+    Ok(fragments)
+}
+
+/// Follows the authority set changes of a justification chain from the genesis set, replacing
+/// the set at the GRANDPA hard forks like warp sync does.
+fn authority_sets(
+    fragments: &[WarpSyncFragment<Block>],
+    genesis_authorities: AuthorityList,
+    hard_forks: &[AuthoritySetHardFork<Block>],
+) -> Result<Vec<AuthoritySet>, String> {
+    let hard_forks = hard_forks
+        .iter()
+        .map(|fork| (fork.block, (fork.set_id, fork.authorities.clone())))
+        .collect::<HashMap<_, _>>();
+    let mut sets = vec![AuthoritySet {
+        since: 0,
+        set_id: 0,
+        authorities: genesis_authorities,
+    }];
+    for fragment in fragments {
+        let hash = fragment.header.hash();
+        let number = *fragment.header.number();
+        let current = sets.last().expect("starts with the genesis set");
+        if number <= current.since {
+            return Err(format!(
+                "#{number} ({hash}) is not after the authority set change at #{}",
+                current.since
+            ));
+        }
+        let (set_id, authorities) = match hard_forks.get(&(hash, number)) {
+            Some((set_id, authorities)) => {
+                info!("Applying GRANDPA hard fork to set {set_id} at #{number} ({hash})");
+                (*set_id, authorities.clone())
+            }
+            None => {
+                fragment
+                    .justification
+                    .verify(current.set_id, &current.authorities)
+                    .map_err(|e| format!("invalid justification of #{number} ({hash}): {e}"))?;
+                if fragment.justification.target() != (number, hash) {
+                    return Err(format!(
+                        "the justification of #{number} ({hash}) finalizes another block"
+                    ));
+                }
+                let change = find_scheduled_change::<Block>(&fragment.header).ok_or_else(|| {
+                    format!("#{number} ({hash}) does not change the authority set")
+                })?;
+                (current.set_id + 1, change.next_authorities)
+            }
+        };
+        sets.push(AuthoritySet {
+            since: number,
+            set_id,
+            authorities,
+        });
+    }
+    Ok(sets)
+}
+
+/// Verifies the justification of a checkpoint with the authority set of its block, and returns
+/// the id of this set.
+fn verify_justification(checkpoint: &Checkpoint, sets: &[AuthoritySet]) -> Result<SetId, String> {
+    let number = *checkpoint.header.number();
+    let hash = checkpoint.header.hash();
+    let justification = checkpoint
+        .justification
+        .as_ref()
+        .ok_or("the checkpoint has no justification")?;
+    let justification = GrandpaJustification::<Block>::decode(&mut &justification[..])
+        .map_err(|e| format!("invalid justification: {e}"))?;
+    // the block changing the set is finalized by the previous set
+    let set = sets
+        .iter()
+        .rev()
+        .find(|set| set.since < number)
+        .ok_or("the genesis can not be a checkpoint")?;
+    justification
+        .verify(set.set_id, &set.authorities)
+        .map_err(|e| {
+            format!(
+                "invalid justification for authority set {}: {e}",
+                set.set_id
+            )
+        })?;
+    if justification.target() != (number, hash) {
+        return Err("the justification finalizes another block".into());
+    }
+    Ok(set.set_id)
+}
+
+impl CliConfiguration for ExportCheckpointCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
+
+impl CliConfiguration for VerifyCheckpointCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_consensus_grandpa::{
+        AuthorityId, AuthoritySignature, Commit, ConsensusLog, Message, Precommit, ScheduledChange,
+        SignedPrecommit, localized_payload,
+    };
+    use sp_keyring::Ed25519Keyring;
+    use sp_runtime::{Digest, DigestItem, traits::Header as _};
+
+    fn authorities(keys: &[Ed25519Keyring]) -> AuthorityList {
+        keys.iter()
+            .map(|key| (AuthorityId::from(key.public()), 1))
+            .collect()
+    }
+
+    fn header(number: BlockNumber, next_authorities: Option<AuthorityList>) -> Header {
+        let mut digest = Digest::default();
+        if let Some(next_authorities) = next_authorities {
+            digest.push(DigestItem::Consensus(
+                GRANDPA_ENGINE_ID,
+                ConsensusLog::<BlockNumber>::ScheduledChange(ScheduledChange {
+                    next_authorities,
+                    delay: 0,
+                })
+                .encode(),
+            ));
+        }
+        Header::new(
+            number,
+            Default::default(),
+            Default::default(),
+            Hash::repeat_byte(number as u8),
+            digest,
+        )
+    }
+
+    /// Justification of a header signed by one authority.
+    fn justification(
+        header: &Header,
+        key: Ed25519Keyring,
+        set_id: SetId,
+    ) -> GrandpaJustification<Block> {
+        let round = 1;
+        let precommit = Precommit::<Header> {
+            target_hash: header.hash(),
+            target_number: *header.number(),
+        };
+        let message = Message::<Header>::Precommit(precommit.clone());
+        let signature = key.sign(&localized_payload(round, set_id, &message));
+        let justification = sp_consensus_grandpa::GrandpaJustification::<Header> {
+            round,
+            commit: Commit::<Header> {
+                target_hash: header.hash(),
+                target_number: *header.number(),
+                precommits: vec![SignedPrecommit::<Header> {
+                    precommit,
+                    signature: AuthoritySignature::from(signature),
+                    id: AuthorityId::from(key.public()),
+                }],
+            },
+            votes_ancestries: Vec::new(),
+        };
+        GrandpaJustification::decode(&mut &justification.encode()[..]).unwrap()
+    }
+
+    fn fragment(header: Header, key: Ed25519Keyring, set_id: SetId) -> WarpSyncFragment<Block> {
+        WarpSyncFragment {
+            justification: justification(&header, key, set_id),
+            header,
+        }
+    }
+
+    fn checkpoint(number: BlockNumber, key: Ed25519Keyring, set_id: SetId) -> Checkpoint {
+        let header = header(number, None);
+        Checkpoint {
+            justification: Some(justification(&header, key, set_id).encode().into()),
+            header,
+        }
+    }
+
+    #[test]
+    fn follow_authority_set_changes_and_hard_forks() {
+        use Ed25519Keyring::{Alice, Bob, Charlie};
+        let hard_fork_header = header(20, None);
+        let hard_forks = vec![AuthoritySetHardFork {
+            set_id: 5,
+            block: (hard_fork_header.hash(), 20),
+            authorities: authorities(&[Charlie]),
+            last_finalized: Some(15),
+        }];
+        let fragments = vec![
+            fragment(header(10, Some(authorities(&[Bob]))), Alice, 0),
+            // the stalled set can not justify the hard fork
+            fragment(hard_fork_header, Bob, 1),
+        ];
+
+        let sets = authority_sets(&fragments, authorities(&[Alice]), &hard_forks).unwrap();
+        assert_eq!(
+            sets.iter()
+                .map(|set| (set.since, set.set_id))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (10, 1), (20, 5)]
+        );
+        assert_eq!(
+            verify_justification(&checkpoint(30, Charlie, 5), &sets),
+            Ok(5)
+        );
+        assert_eq!(
+            verify_justification(&checkpoint(10, Alice, 0), &sets),
+            Ok(0)
+        );
+        assert!(verify_justification(&checkpoint(30, Bob, 1), &sets).is_err());
+        assert!(verify_justification(&checkpoint(30, Charlie, 1), &sets).is_err());
+
+        // without the hard fork, the chain is broken
+        assert_eq!(
+            authority_sets(&fragments, authorities(&[Alice]), &[]),
+            Err(format!(
+                "#20 ({}) does not change the authority set",
+                fragments[1].header.hash()
+            ))
+        );
+    }
+
+    #[test]
+    fn reject_unjustified_authority_set_change() {
+        use Ed25519Keyring::{Alice, Bob};
+        let fragments = vec![fragment(header(10, Some(authorities(&[Bob]))), Bob, 0)];
+
+        assert!(authority_sets(&fragments, authorities(&[Alice]), &[]).is_err());
+    }
+
+    #[test]
+    fn parse_checkpoint_files() {
+        let checkpoint = checkpoint(30, Ed25519Keyring::Alice, 0);
+        let json = serde_json::to_vec(&checkpoint).unwrap();
+        assert_eq!(Checkpoint::from_json(&json).unwrap(), checkpoint);
+
+        // first embedded checkpoints
+        let json = serde_json::to_vec(&checkpoint.header).unwrap();
+        assert_eq!(
+            Checkpoint::from_json(&json).unwrap(),
+            Checkpoint {
+                header: checkpoint.header,
+                justification: None,
+            }
+        );
+    }
+}
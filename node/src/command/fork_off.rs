@@ -0,0 +1,137 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    chain_spec::fork_off::{ForkOffParams, fork_off},
+    service::runtime_executor::runtime,
+};
+use log::info;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{StorageProvider, UsageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::Get;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::{fmt::Debug, io::Write, path::PathBuf, str::FromStr, sync::Arc};
+
+#[cfg(feature = "g1")]
+use crate::chain_spec::g1::G1SKP as RuntimeSKP;
+#[cfg(feature = "gdev")]
+use crate::chain_spec::gdev::GDevSKP as RuntimeSKP;
+#[cfg(feature = "gtest")]
+use crate::chain_spec::gtest::GTestSKP as RuntimeSKP;
+
+/// Fork the state of a network into a chain spec run by development keys.
+///
+/// The state is read from the database of the `--chain` network. To fork a state exported
+/// with `export-state`, pass it as `--chain` with an empty database (`--tmp`).
+#[derive(Debug, clap::Parser)]
+pub struct ForkOffCmd {
+    /// Block hash or number of the forked state, the best block by default.
+    #[clap(value_name = "HASH or NUMBER")]
+    pub input: Option<BlockNumberOrHash>,
+
+    /// Number of smiths taken over by development keys to author blocks.
+    #[clap(long, default_value_t = 1)]
+    pub authorities: usize,
+
+    /// Number of smiths taken over by development keys to seat in the technical committee.
+    /// Defaults to the number of authorities.
+    #[clap(long)]
+    pub technical_committee: Option<usize>,
+
+    /// Output file of the chain spec, the standard output by default.
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+impl ForkOffCmd {
+    /// Run the command
+    pub async fn run<B, BA, C>(
+        &self,
+        client: Arc<C>,
+        mut spec: Box<dyn sc_service::ChainSpec>,
+    ) -> sc_cli::Result<()>
+    where
+        B: BlockT,
+        <B::Hash as FromStr>::Err: Debug,
+        NumberFor<B>: FromStr,
+        <NumberFor<B> as FromStr>::Err: Debug,
+        BA: sc_client_api::backend::Backend<B>,
+        C: UsageProvider<B> + StorageProvider<B, BA> + HeaderBackend<B>,
+    {
+        let hash = match self.input.as_ref().map(|b| b.parse::<B>()).transpose()? {
+            Some(id) => client.expect_block_hash_from_id(&id)?,
+            None => client.usage_info().chain.best_hash,
+        };
+        info!("Exporting raw state at {hash}...");
+        let mut storage = sc_service::chain_ops::export_raw_state(client, hash)?;
+
+        let params =
+            sp_io::TestExternalities::new(storage.clone()).execute_with(|| ForkOffParams {
+                authorities: self.authorities,
+                technical_committee: self.technical_committee.unwrap_or(self.authorities),
+                smith_inactivity_max_duration: runtime::SmithInactivityMaxDuration::get(),
+                committee_term_duration: runtime::CommitteeTermDuration::get(),
+            });
+        info!("Taking over {} authorities...", params.authorities);
+        fork_off::<runtime::opaque::SessionKeys, RuntimeSKP>(&mut storage.top, &params)?;
+        spec.set_storage(storage);
+
+        let json = sc_service::chain_ops::build_spec(&*spec, true)?;
+        let mut json: serde_json::Value =
+            serde_json::from_str(&json).map_err(|e| format!("invalid chain spec: {e}"))?;
+        // A distinct id keeps the database of the fork apart from the forked network.
+        json["name"] = format!("{} fork", spec.name()).into();
+        json["id"] = format!("{}_fork", spec.id()).into();
+        json["chainType"] = "Local".into();
+        json["bootNodes"] = serde_json::Value::Array(Vec::new());
+        json["codeSubstitutes"] = serde_json::Value::Object(Default::default());
+        let json = serde_json::to_string_pretty(&json)
+            .map_err(|e| format!("cannot serialize chain spec: {e}"))?;
+
+        match &self.output {
+            Some(path) => std::fs::write(path, json)?,
+            None => std::io::stdout().write_all(json.as_bytes())?,
+        }
+        Ok(())
+    }
+}
+
+impl CliConfiguration for ForkOffCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
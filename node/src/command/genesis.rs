@@ -0,0 +1,140 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    chain_spec::gen_genesis_data::{
+        get_genesis_migration_data_path, get_genesis_timestamp, suggest_smith_wot_repair,
+        validate_genesis_data,
+    },
+    service::runtime_executor::runtime,
+};
+use std::io::Write;
+
+#[cfg(feature = "g1")]
+use crate::chain_spec::g1::{G1SKP as RuntimeSKP, get_parameters};
+#[cfg(feature = "gdev")]
+use crate::chain_spec::gdev::{GDevSKP as RuntimeSKP, get_parameters};
+#[cfg(feature = "gtest")]
+use crate::chain_spec::gtest::{GTestSKP as RuntimeSKP, get_parameters};
+
+#[derive(Debug, clap::Subcommand)]
+pub enum GenesisSubcommand {
+    /// Run the checks of the genesis building on a genesis input, without writing a chain spec
+    Validate(ValidateCmd),
+
+    /// Suggest the smith certifications to add or the smiths to drop to make the smith WoT valid
+    SuggestSmiths(SuggestSmithsCmd),
+}
+
+impl GenesisSubcommand {
+    /// Run the command
+    pub fn run(&self) -> sc_cli::Result<()> {
+        match self {
+            Self::Validate(cmd) => cmd.run(),
+            Self::SuggestSmiths(cmd) => cmd.run(),
+        }
+    }
+}
+
+/// Run the checks of the genesis building on a genesis input and the Ğ1 migration data.
+///
+/// A JSON report with the summary of the prepared genesis, the warnings and the errors is
+/// printed on the standard output, and the command fails if any error prevents from building
+/// a sane genesis.
+#[derive(Debug, clap::Parser)]
+pub struct ValidateCmd {
+    /// Genesis input, in YAML or in JSON with a `.json` extension.
+    #[clap(value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub input: String,
+
+    /// Ğ1 migration data, `DUNITER_GENESIS_DATA` or `./resources/g1-data.json` by default.
+    #[clap(long, value_name = "JSON_FILE", value_hint = clap::ValueHint::FilePath)]
+    pub data: Option<String>,
+
+    /// Genesis timestamp in seconds, `DUNITER_GENESIS_TIMESTAMP` or the current time by default.
+    #[clap(long)]
+    pub timestamp: Option<u64>,
+
+    /// Identity forced to be an online authority, like Alice in development chain specs.
+    #[clap(long, value_name = "NAME")]
+    pub authority: Option<String>,
+}
+
+impl ValidateCmd {
+    /// Run the command
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let genesis_timestamp = match self.timestamp {
+            Some(timestamp) => timestamp,
+            None => get_genesis_timestamp()?,
+        };
+        let report = validate_genesis_data::<
+            _,
+            runtime::opaque::SessionKeys,
+            runtime::opaque::SessionKeys,
+            RuntimeSKP,
+        >(
+            self.input.clone(),
+            self.data
+                .clone()
+                .unwrap_or_else(get_genesis_migration_data_path),
+            genesis_timestamp,
+            get_parameters,
+            self.authority.clone(),
+        );
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("cannot serialize report: {e}"))?;
+        std::io::stdout().write_all(json.as_bytes())?;
+        std::io::stdout().write_all(b"\n")?;
+
+        if report.is_sane() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} errors prevent from building a sane genesis",
+                report.errors.len()
+            )
+            .into())
+        }
+    }
+}
+
+/// Suggest the smallest changes making the smith WoT of a genesis input valid under the minimum
+/// of received smith certifications for membership.
+///
+/// Two alternatives are printed on the standard output in the input YAML format: the `smiths`
+/// section with the extra smith certifications, and the `smiths` section without the smiths
+/// which cannot reach the minimum. Clique smiths are expanded to explicit smiths.
+#[derive(Debug, clap::Parser)]
+pub struct SuggestSmithsCmd {
+    /// Genesis input, in YAML or in JSON with a `.json` extension.
+    #[clap(value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub input: String,
+
+    /// Identity forced to be an online authority, like Alice in development chain specs.
+    /// It is added to the smiths if missing and never dropped.
+    #[clap(long, value_name = "NAME")]
+    pub authority: Option<String>,
+}
+
+impl SuggestSmithsCmd {
+    /// Run the command
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let suggestions =
+            suggest_smith_wot_repair(self.input.clone(), get_parameters, self.authority.clone())?;
+        std::io::stdout().write_all(suggestions.as_bytes())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Distance precheck RPC API
+//!
+//! Exposes the `distance_precheck` RPC method.
+
+use crate::distance_precheck::{At, DistancePrecheck, WotCache, wot_at};
+use common_runtime::{AccountId, Block, BlockNumber, IdtyIndex};
+use jsonrpsee::{
+    proc_macros::rpc,
+    types::{ErrorObject, ErrorObjectOwned},
+};
+use pallet_distance::DistanceApi;
+use sc_client_api::StorageProvider;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use std::{marker::PhantomData, sync::Arc};
+
+/// Error code of the distance precheck.
+const PRECHECK_ERROR: i32 = 1;
+
+/// The exposed RPC methods
+#[rpc(client, server)]
+pub trait DistancePrecheckApi {
+    /// Computes the distance of an identity from the state of the best or finalized block
+    #[method(name = "distance_precheck", blocking)]
+    fn precheck(
+        &self,
+        idty_index: IdtyIndex,
+        at: Option<At>,
+    ) -> Result<DistancePrecheck, ErrorObjectOwned>;
+}
+
+/// API implementation
+pub struct DistancePrecheckRpc<C, B> {
+    client: Arc<C>,
+    wot_cache: WotCache,
+    _backend: PhantomData<B>,
+}
+
+impl<C, B> DistancePrecheckRpc<C, B> {
+    /// Creates a new instance of the distance precheck RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            wot_cache: WotCache::default(),
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl<C, B> DistancePrecheckApiServer for DistancePrecheckRpc<C, B>
+where
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, B>,
+    C: Send + Sync + 'static,
+    C::Api: DistanceApi<Block, AccountId, BlockNumber>,
+    B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+{
+    fn precheck(
+        &self,
+        idty_index: IdtyIndex,
+        at: Option<At>,
+    ) -> Result<DistancePrecheck, ErrorObjectOwned> {
+        let info = self.client.info();
+        let at = at.unwrap_or_default();
+        let hash = match at {
+            At::Best => info.best_hash,
+            At::Finalized => info.finalized_hash,
+        };
+        let parameters = self
+            .client
+            .runtime_api()
+            .distance_rule_parameters(hash)
+            .map_err(|err| precheck_error(format!("Cannot get distance rule parameters: {err}")))?;
+        let wot = self
+            .wot_cache
+            .get_or_read(at, hash, || {
+                wot_at(&*self.client, hash, parameters.max_referee_distance)
+            })
+            .map_err(precheck_error)?;
+        Ok(DistancePrecheck {
+            at: hash,
+            distance: wot.distance(idty_index),
+            min_accessible_referees: parameters.min_accessible_referees,
+        })
+    }
+}
+
+fn precheck_error(message: String) -> ErrorObjectOwned {
+    ErrorObject::owned(PRECHECK_ERROR, message, None::<()>)
+}
@@ -0,0 +1,157 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Distance precheck
+//!
+//! Lets users know whether an identity passes the distance rule before paying for a distance
+//! evaluation request.
+//!
+//! The node computes the distance rule like the distance oracle, with the `dc-distance-rule`
+//! crate, but on the web of trust of its own state, without any RPC round-trip. The result may
+//! differ from the evaluation, which uses the state at the beginning of the evaluation period.
+//!
+//! ## RPC methods
+//!
+//! ### `distance_precheck`
+//!
+//! Computes the distance of an identity at the best block, or at the finalized block with
+//! `"finalized"` as second parameter, and returns it with the `MinAccessibleReferees` threshold
+//! of the runtime, as parts per billion.
+//!
+//! ```json
+//! {"jsonrpc":"2.0","id":0,"method":"distance_precheck","params":[42, "finalized"]}
+//! ```
+//!
+//! Reading the web of trust from the state is the expensive part of the computation, so the
+//! last web of trust read is kept for each of the best and finalized blocks. It is read at most
+//! once per block, however many requests are made.
+
+pub(crate) mod api;
+#[cfg(test)]
+mod tests;
+
+use codec::Decode;
+use common_runtime::{Block, BlockNumber, Hash, IdtyIndex};
+use dc_distance_rule::{Wot, idty_index_from_storage_key};
+use parking_lot::Mutex;
+use sc_client_api::{StorageKey, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_runtime::Perbill;
+use std::sync::Arc;
+
+/// Block whose state is used to compute the distance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum At {
+    /// The best block.
+    #[default]
+    Best,
+    /// The last finalized block.
+    Finalized,
+}
+
+/// Distance of an identity and threshold of the distance rule.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DistancePrecheck {
+    /// Hash of the block whose state was used.
+    pub at: Hash,
+    /// Fraction of the referees accessible from the identity.
+    pub distance: Perbill,
+    /// Minimum fraction of accessible referees to pass the distance rule.
+    pub min_accessible_referees: Perbill,
+}
+
+/// Last web of trust read, for each of the best and finalized blocks.
+#[derive(Default)]
+pub(crate) struct WotCache {
+    best: Mutex<Option<(Hash, Arc<Wot>)>>,
+    finalized: Mutex<Option<(Hash, Arc<Wot>)>>,
+}
+
+impl WotCache {
+    /// Returns the web of trust of a block, reading it with `read` if it is not cached.
+    ///
+    /// The lock is held while reading, so that concurrent requests read it only once.
+    pub(crate) fn get_or_read(
+        &self,
+        at: At,
+        hash: Hash,
+        read: impl FnOnce() -> Result<Wot, String>,
+    ) -> Result<Arc<Wot>, String> {
+        let mut cached = match at {
+            At::Best => self.best.lock(),
+            At::Finalized => self.finalized.lock(),
+        };
+        if let Some((cached_hash, wot)) = &*cached
+            && *cached_hash == hash
+        {
+            return Ok(wot.clone());
+        }
+        let wot = Arc::new(read()?);
+        *cached = Some((hash, wot.clone()));
+        Ok(wot)
+    }
+}
+
+/// Reads the web of trust from the state of a block.
+pub fn wot_at<C, B>(client: &C, hash: Hash, max_depth: u32) -> Result<Wot, String>
+where
+    C: StorageProvider<Block, B>,
+    B: sc_client_api::Backend<Block>,
+{
+    let members = client
+        .storage_keys(
+            hash,
+            Some(&storage_prefix(b"Membership", b"Membership")),
+            None,
+        )
+        .map_err(|err| format!("Cannot read memberships: {err:?}"))?
+        .map(|key| idty_index_from_storage_key(&key.0));
+    // idty -> received certs
+    let received_certs = client
+        .storage_pairs(
+            hash,
+            Some(&storage_prefix(b"Certification", b"CertsByReceiver")),
+            None,
+        )
+        .map_err(|err| format!("Cannot read certifications: {err:?}"))?
+        .map(|(key, value)| {
+            Vec::<(IdtyIndex, BlockNumber)>::decode(&mut &value.0[..]).map(|issuers| {
+                (
+                    idty_index_from_storage_key(&key.0),
+                    issuers
+                        .into_iter()
+                        .map(|(issuer, _removable_on)| issuer)
+                        .collect(),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("Cannot decode certifications: {err:?}"))?;
+    Ok(Wot::new(members, received_certs, max_depth))
+}
+
+/// Storage key prefix of a storage item.
+pub(crate) fn storage_prefix(pallet: &[u8], storage: &[u8]) -> StorageKey {
+    StorageKey(
+        [
+            sp_core::hashing::twox_128(pallet),
+            sp_core::hashing::twox_128(storage),
+        ]
+        .concat(),
+    )
+}
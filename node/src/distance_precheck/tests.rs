@@ -0,0 +1,84 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::distance_precheck::{At, WotCache};
+use common_runtime::Hash;
+use dc_distance_rule::Wot;
+use std::cell::Cell;
+
+/// A web of trust of two members certifying each other.
+fn wot() -> Result<Wot, String> {
+    Ok(Wot::new([1, 2], [(1, vec![2]), (2, vec![1])], 5))
+}
+
+#[test]
+fn wot_is_read_once_per_block() {
+    let cache = WotCache::default();
+    let reads = Cell::new(0);
+    let read = || {
+        reads.set(reads.get() + 1);
+        wot()
+    };
+
+    assert!(
+        cache
+            .get_or_read(At::Best, Hash::repeat_byte(1), read)
+            .is_ok()
+    );
+    assert!(
+        cache
+            .get_or_read(At::Best, Hash::repeat_byte(1), read)
+            .is_ok()
+    );
+    assert_eq!(reads.get(), 1);
+
+    // the finalized block has its own entry
+    assert!(
+        cache
+            .get_or_read(At::Finalized, Hash::repeat_byte(1), read)
+            .is_ok()
+    );
+    assert!(
+        cache
+            .get_or_read(At::Best, Hash::repeat_byte(1), read)
+            .is_ok()
+    );
+    assert_eq!(reads.get(), 2);
+
+    // a new best block is read again
+    assert!(
+        cache
+            .get_or_read(At::Best, Hash::repeat_byte(2), read)
+            .is_ok()
+    );
+    assert_eq!(reads.get(), 3);
+}
+
+#[test]
+fn read_errors_are_not_cached() {
+    let cache = WotCache::default();
+    assert_eq!(
+        cache
+            .get_or_read(At::Best, Hash::repeat_byte(1), || Err("unavailable".into()))
+            .err(),
+        Some("unavailable".to_string())
+    );
+    assert!(
+        cache
+            .get_or_read(At::Best, Hash::repeat_byte(1), wot)
+            .is_ok()
+    );
+}
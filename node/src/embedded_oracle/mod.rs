@@ -0,0 +1,167 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Embedded distance oracle
+//!
+//! Runs the distance oracle inside an authority node, enabled with `--embedded-distance-oracle`.
+//!
+//! Like the standalone `distance-oracle`, the worker evaluates the pool of the current period on
+//! the state of the evaluation block, but it reads the state from the client backend instead of
+//! the RPC API. It evaluates once per period, at the first new best block of the period, and hands
+//! the result to the distance inherent data provider in memory, to be published during the next
+//! period. The other new best blocks of the period only cost the read of the period index, and the
+//! evaluation runs on a blocking thread so that it does not hold the async executor.
+//!
+//! The result files of a standalone oracle are still read when the embedded oracle has no result.
+
+use crate::distance_precheck::{storage_prefix, wot_at};
+use codec::Decode;
+use common_runtime::{AccountId, Block, BlockNumber, Hash, IdtyIndex};
+use dc_distance::EvaluationResults;
+use futures::StreamExt;
+use pallet_distance::DistanceApi;
+use sc_client_api::{BlockchainEvents, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use std::sync::Arc;
+
+#[cfg(test)]
+mod tests;
+
+/// Evaluates the distance pools of new periods until the import notifications end.
+pub async fn run<C, B>(client: Arc<C>, results: EvaluationResults)
+where
+    C: BlockchainEvents<Block> + ProvideRuntimeApi<Block> + StorageProvider<Block, B>,
+    C: Send + Sync + 'static,
+    C::Api: DistanceApi<Block, AccountId, BlockNumber>,
+    B: sc_client_api::Backend<Block> + 'static,
+{
+    // Last period whose pool was evaluated, or found empty
+    let mut evaluated_period = None;
+    let mut import_notifications = client.import_notification_stream();
+    while let Some(notification) = import_notifications.next().await {
+        if !notification.is_new_best {
+            continue;
+        }
+        let best_hash = notification.hash;
+        let current_period_index: u32 =
+            match read_storage(&*client, best_hash, b"CurrentPeriodIndex") {
+                Ok(index) => index.unwrap_or_default(),
+                Err(e) => {
+                    log::error!("🧙 [distance oracle] {e}");
+                    continue;
+                }
+            };
+        if !needs_evaluation(current_period_index, evaluated_period, &results) {
+            continue;
+        }
+
+        let (client, task_results) = (client.clone(), results.clone());
+        let evaluation = tokio::task::spawn_blocking(move || {
+            evaluate(&*client, best_hash, current_period_index, &task_results)
+        })
+        .await
+        .map_err(|e| format!("Evaluation task failed: {e}"))
+        .and_then(|result| result);
+        match evaluation {
+            Ok(()) => evaluated_period = Some(current_period_index),
+            // retried at the next new best block
+            Err(e) => log::error!("🧙 [distance oracle] {e}"),
+        }
+    }
+}
+
+/// Whether the pool of the current period remains to be evaluated.
+///
+/// The result is published during the next period, and may have been computed before a restart
+/// of the worker.
+fn needs_evaluation(
+    current_period_index: u32,
+    evaluated_period: Option<u32>,
+    results: &EvaluationResults,
+) -> bool {
+    evaluated_period != Some(current_period_index) && !results.contains(current_period_index + 1)
+}
+
+/// Evaluates the pool of the current period at the given block.
+fn evaluate<C, B>(
+    client: &C,
+    best_hash: Hash,
+    current_period_index: u32,
+    results: &EvaluationResults,
+) -> Result<(), String>
+where
+    C: ProvideRuntimeApi<Block> + StorageProvider<Block, B>,
+    C::Api: DistanceApi<Block, AccountId, BlockNumber>,
+    B: sc_client_api::Backend<Block>,
+{
+    // Same pool as the one read by the standalone oracle
+    let pool_name: &[u8] = match current_period_index % 3 {
+        0 => b"EvaluationPool1",
+        1 => b"EvaluationPool2",
+        2 => b"EvaluationPool0",
+        _ => unreachable!("n<3"),
+    };
+    let Some(evaluation_pool) =
+        read_storage::<_, _, pallet_distance::EvaluationPool<AccountId, IdtyIndex>>(
+            client, best_hash, pool_name,
+        )?
+    else {
+        return Ok(());
+    };
+    if evaluation_pool.evaluations.is_empty() {
+        return Ok(());
+    }
+
+    let evaluation_block: Hash = read_storage(client, best_hash, b"EvaluationBlock")?
+        .ok_or_else(|| "No evaluation block".to_string())?;
+    let parameters = client
+        .runtime_api()
+        .distance_rule_parameters(best_hash)
+        .map_err(|e| format!("Cannot get distance rule parameters: {e}"))?;
+
+    log::info!("🧙 [distance oracle] Evaluating distance for period {current_period_index}");
+    let wot = wot_at(client, evaluation_block, parameters.max_referee_distance)?;
+    let idties: Vec<IdtyIndex> = evaluation_pool
+        .evaluations
+        .iter()
+        .map(|(idty, _)| *idty)
+        .collect();
+    results.insert(
+        current_period_index + 1,
+        sp_distance::ComputationResult {
+            distances: wot.distances(&idties),
+        },
+    );
+    Ok(())
+}
+
+/// Reads a storage value of the distance pallet.
+fn read_storage<C, B, T: Decode>(
+    client: &C,
+    hash: Hash,
+    storage: &[u8],
+) -> Result<Option<T>, String>
+where
+    C: StorageProvider<Block, B>,
+    B: sc_client_api::Backend<Block>,
+{
+    client
+        .storage(hash, &storage_prefix(b"Distance", storage))
+        .map_err(|e| format!("Cannot read storage: {e:?}"))?
+        .map(|data| T::decode(&mut &data.0[..]))
+        .transpose()
+        .map_err(|e| format!("Cannot decode storage: {e:?}"))
+}
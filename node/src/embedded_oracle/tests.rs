@@ -0,0 +1,41 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::embedded_oracle::needs_evaluation;
+use dc_distance::EvaluationResults;
+
+#[test]
+fn evaluation_once_per_period() {
+    let results = EvaluationResults::default();
+    assert!(needs_evaluation(5, None, &results));
+    assert!(needs_evaluation(5, Some(4), &results));
+    // the pool of the period was evaluated, or found empty
+    assert!(!needs_evaluation(5, Some(5), &results));
+    assert!(needs_evaluation(6, Some(5), &results));
+}
+
+#[test]
+fn no_evaluation_with_a_result_to_publish() {
+    let results = EvaluationResults::default();
+    results.insert(
+        6,
+        sp_distance::ComputationResult {
+            distances: Vec::new(),
+        },
+    );
+    assert!(!needs_evaluation(5, None, &results));
+    assert!(needs_evaluation(6, None, &results));
+}
@@ -0,0 +1,83 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Identity proof RPC API
+//!
+//! Exposes the `identity_proof` RPC method.
+
+use crate::identity_proof::{IdentityProofBundle, identity_proof_at};
+use common_runtime::{Block, IdtyIndex};
+use jsonrpsee::{
+    proc_macros::rpc,
+    types::{ErrorObject, ErrorObjectOwned},
+};
+use sc_client_api::{ProofProvider, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use std::{marker::PhantomData, sync::Arc};
+
+/// Error code of the identity proof.
+const PROOF_ERROR: i32 = 1;
+
+/// The exposed RPC methods
+#[rpc(client, server)]
+pub trait IdentityProofApi {
+    /// Returns the storage proof of an identity at the last finalized block
+    #[method(name = "identity_proof", blocking)]
+    fn identity_proof(
+        &self,
+        idty_index: IdtyIndex,
+    ) -> Result<IdentityProofBundle, ErrorObjectOwned>;
+}
+
+/// API implementation
+pub struct IdentityProofRpc<C, B> {
+    client: Arc<C>,
+    _backend: PhantomData<B>,
+}
+
+impl<C, B> IdentityProofRpc<C, B> {
+    /// Creates a new instance of the identity proof RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl<C, B> IdentityProofApiServer for IdentityProofRpc<C, B>
+where
+    C: HeaderBackend<Block> + StorageProvider<Block, B> + ProofProvider<Block>,
+    C: Send + Sync + 'static,
+    B: sc_client_api::Backend<Block> + Send + Sync + 'static,
+{
+    fn identity_proof(
+        &self,
+        idty_index: IdtyIndex,
+    ) -> Result<IdentityProofBundle, ErrorObjectOwned> {
+        let hash = self.client.info().finalized_hash;
+        let header = self
+            .client
+            .header(hash)
+            .map_err(|err| proof_error(format!("Cannot read finalized header: {err}")))?
+            .ok_or_else(|| proof_error("Finalized header not found".to_owned()))?;
+        identity_proof_at(&*self.client, hash, &header, idty_index).map_err(proof_error)
+    }
+}
+
+fn proof_error(message: String) -> ErrorObjectOwned {
+    ErrorObject::owned(PROOF_ERROR, message, None::<()>)
+}
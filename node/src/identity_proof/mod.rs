@@ -0,0 +1,97 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Identity proof
+//!
+//! Serves storage proofs of the state of an identity, so that light clients like mobile wallets
+//! can check the status, the owner key and the membership of an identity without trusting the
+//! node.
+//!
+//! The proof covers the `Identities`, `IdentityIndexOf`, `Membership` and `CertsByReceiver`
+//! storage items of the identity and the account of its owner key, which holds `linked_idty`. It
+//! is checked and decoded with the `no_std` `sp-identity-proof` crate.
+//!
+//! ## RPC methods
+//!
+//! ### `identity_proof`
+//!
+//! Returns the SCALE encoded `IdentityProof` of an identity at the last finalized block, with the
+//! hash and number of this block. The client checks the proof against the state root of the
+//! header of this block.
+//!
+//! ```json
+//! {"jsonrpc":"2.0","id":0,"method":"identity_proof","params":[42]}
+//! ```
+//!
+//! A proof is returned for unknown identities too, proving their absence.
+
+pub(crate) mod api;
+
+use codec::{Decode, Encode};
+use common_runtime::{Block, BlockNumber, Hash, Header, IdtyIndex};
+use sc_client_api::{ProofProvider, StorageKey, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+use sp_identity_proof::{IdentityProof, IdtyValue};
+use sp_runtime::traits::{BlakeTwo256, Header as _};
+
+/// Storage proof of an identity, with the finalized block it was built at.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityProofBundle {
+    /// Hash of the block whose state is proven.
+    pub at: Hash,
+    /// Number of the block whose state is proven.
+    pub block_number: BlockNumber,
+    /// SCALE encoded `sp_identity_proof::IdentityProof`.
+    pub proof: Bytes,
+}
+
+/// Builds the storage proof of an identity at a block.
+pub fn identity_proof_at<C, B>(
+    client: &C,
+    hash: Hash,
+    header: &Header,
+    idty_index: IdtyIndex,
+) -> Result<IdentityProofBundle, String>
+where
+    C: StorageProvider<Block, B> + ProofProvider<Block>,
+    B: sc_client_api::Backend<Block>,
+{
+    // the keys of the owner key are only known from the identity value
+    let owner_key = client
+        .storage(
+            hash,
+            &StorageKey(sp_identity_proof::identities_key(idty_index)),
+        )
+        .map_err(|err| format!("Cannot read identity: {err:?}"))?
+        .map(|value| IdtyValue::decode(&mut &value.0[..]))
+        .transpose()
+        .map_err(|err| format!("Cannot decode identity: {err:?}"))?
+        .map(|identity| identity.owner_key);
+    let keys = sp_identity_proof::storage_keys(idty_index, owner_key.as_ref());
+
+    let proof = client
+        .read_proof(hash, &mut keys.iter().map(Vec::as_slice))
+        .map_err(|err| format!("Cannot build storage proof: {err:?}"))?
+        .into_compact_proof::<BlakeTwo256>(*header.state_root())
+        .map_err(|err| format!("Cannot compact storage proof: {err:?}"))?;
+    Ok(IdentityProofBundle {
+        at: hash,
+        block_number: *header.number(),
+        proof: IdentityProof { idty_index, proof }.encode().into(),
+    })
+}
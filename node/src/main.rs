@@ -30,8 +30,12 @@ mod chain_spec;
 mod service;
 pub(crate) mod cli;
 mod command;
+mod distance_precheck;
+mod embedded_oracle;
 mod endpoint_gossip;
+mod identity_proof;
 mod rpc;
+mod time_travel;
 
 fn main() -> sc_cli::Result<()> {
     command::run()
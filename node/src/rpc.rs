@@ -25,6 +25,7 @@
 use crate::endpoint_gossip::rpc::{api::DuniterPeeringRpcApiServer, state::DuniterPeeringsState};
 use common_runtime::{AccountId, Balance, Block, BlockNumber, Hash, Index};
 use jsonrpsee::RpcModule;
+use sc_client_api::{ProofProvider, StorageProvider};
 use sc_consensus_babe::{BabeApi, BabeWorkerHandle};
 use sc_consensus_grandpa::{
     self, FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
@@ -69,6 +70,15 @@ pub struct DuniterPeeringRpcModuleDeps {
     pub state: DuniterPeeringsState,
 }
 
+/// Dependencies for the time travel RPC of dev nodes
+#[derive(Clone)]
+pub struct TimeTravelDeps {
+    /// Sends the commands sealing blocks, with their time jump.
+    pub command_sink: futures::channel::mpsc::Sender<crate::time_travel::TimeTravelCommand>,
+    /// Duration of a BABE slot in milliseconds.
+    pub slot_duration: u64,
+}
+
 /// Full client dependencies.
 pub struct FullDeps<C, P, SC, B> {
     /// The client instance to use.
@@ -81,6 +91,8 @@ pub struct FullDeps<C, P, SC, B> {
     pub command_sink_opt: Option<
         futures::channel::mpsc::Sender<sc_consensus_manual_seal::EngineCommand<sp_core::H256>>,
     >,
+    /// Time travel dependencies, only for manual consensus.
+    pub time_travel: Option<TimeTravelDeps>,
     /// BABE specific dependencies.
     pub babe: Option<BabeDeps>,
     /// GRANDPA specific dependencies.
@@ -101,10 +113,15 @@ where
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
     C::Api: BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
+    C::Api: pallet_distance::DistanceApi<Block, AccountId, BlockNumber>,
+    C: StorageProvider<Block, B> + ProofProvider<Block>,
     P: TransactionPool + 'static,
     SC: SelectChain<Block> + 'static,
-    B: sc_client_api::Backend<Block> + 'static,
+    B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 {
+    use crate::distance_precheck::api::{DistancePrecheckApiServer, DistancePrecheckRpc};
+    use crate::identity_proof::api::{IdentityProofApiServer, IdentityProofRpc};
+    use crate::time_travel::api::{TimeTravel, TimeTravelApiServer};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
     use sc_consensus_babe_rpc::{Babe, BabeApiServer};
     use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
@@ -117,6 +134,7 @@ where
         pool,
         select_chain,
         command_sink_opt,
+        time_travel,
         babe,
         grandpa,
         duniter_peering: endpoint_gossip,
@@ -152,11 +170,20 @@ where
 
     module.merge(System::new(client.clone(), pool).into_rpc())?;
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module.merge(DistancePrecheckRpc::<C, B>::new(client.clone()).into_rpc())?;
+    module.merge(IdentityProofRpc::<C, B>::new(client.clone()).into_rpc())?;
     if let Some(command_sink) = command_sink_opt {
         // We provide the rpc handler with the sending end of the channel to allow the rpc
         // send EngineCommands to the background block authorship task.
         module.merge(ManualSeal::new(command_sink).into_rpc())?;
     };
+    if let Some(TimeTravelDeps {
+        command_sink,
+        slot_duration,
+    }) = time_travel
+    {
+        module.merge(TimeTravel::new(client.clone(), command_sink, slot_duration).into_rpc())?;
+    }
 
     // Extend this RPC with a custom API by using the following syntax.
     // `YourRpcStruct` should have a reference to a client, which is needed
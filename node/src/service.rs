@@ -22,6 +22,7 @@ pub mod client;
 
 use self::client::{Client, ClientHandle, RuntimeApiCollection};
 use crate::{
+    command::checkpoint::Checkpoint,
     endpoint_gossip::{
         DuniterEndpoint, DuniterEndpoints, Peering,
         rpc::state::DuniterPeeringsState,
@@ -117,7 +118,7 @@ pub mod runtime_executor {
 ///
 /// The minimum period of blocks on which justifications will be
 /// imported and generated.
-const GRANDPA_JUSTIFICATION_PERIOD: u32 = 512;
+pub(crate) const GRANDPA_JUSTIFICATION_PERIOD: u32 = 512;
 
 #[cfg(feature = "g1")]
 const DEFAULT_WARP_CHECKPOINT_JSON: &str = include_str!("../specs/g1-checkpoint.json");
@@ -189,7 +190,7 @@ fn gtest_grandpa_hard_forks() -> Vec<sc_consensus_grandpa::AuthoritySetHardFork<
     )]
 }
 
-fn grandpa_hard_forks(
+pub(crate) fn grandpa_hard_forks(
     _config: &Configuration,
 ) -> Vec<sc_consensus_grandpa::AuthoritySetHardFork<Block>> {
     #[cfg(feature = "gtest")]
@@ -202,11 +203,13 @@ fn grandpa_hard_forks(
 
 fn load_checkpoint_from_file(path: PathBuf) -> Result<Header, ServiceError> {
     let bytes = fs::read(path)?;
-    serde_json::from_slice(&bytes).map_err(|e| {
-        ServiceError::from(format!(
-            "failed to decode warp checkpoint header from file: {e}"
-        ))
-    })
+    Checkpoint::from_json(&bytes)
+        .map(|checkpoint| checkpoint.header)
+        .map_err(|e| {
+            ServiceError::from(format!(
+                "failed to decode warp checkpoint header from file: {e}"
+            ))
+        })
 }
 
 fn build_warp_sync_config(
@@ -223,7 +226,8 @@ fn build_warp_sync_config(
         )?));
     }
 
-    let checkpoint = serde_json::from_slice::<Header>(DEFAULT_WARP_CHECKPOINT_JSON.as_bytes())
+    let checkpoint = Checkpoint::from_json(DEFAULT_WARP_CHECKPOINT_JSON.as_bytes())
+        .map(|checkpoint| checkpoint.header)
         .map_err(|e| {
             ServiceError::from(format!(
                 "failed to decode embedded warp checkpoint for network {DEFAULT_WARP_CHECKPOINT_NAME}: {e}"
@@ -280,6 +284,41 @@ pub fn new_chain_ops(
     ))
 }
 
+/// Builds a new object suitable for checkpoint operations, with the warp sync proof provider
+/// of the GRANDPA authority set changes of the database.
+#[allow(clippy::type_complexity)]
+pub fn new_checkpoint_ops(
+    config: &Configuration,
+) -> Result<
+    (
+        Arc<Client>,
+        Arc<sc_consensus_grandpa::warp_proof::NetworkProvider<Block, FullBackend>>,
+        TaskManager,
+    ),
+    ServiceError,
+> {
+    let (
+        PartialComponents {
+            client,
+            backend,
+            task_manager,
+            other: (_, _, _, grandpa_link, _),
+            ..
+        },
+        _duniter_config,
+    ) = new_partial::<runtime_executor::runtime::RuntimeApi, runtime_executor::Executor>(
+        config,
+        false,
+        Default::default(),
+    )?;
+    let warp_sync = Arc::new(sc_consensus_grandpa::warp_proof::NetworkProvider::new(
+        backend,
+        grandpa_link.shared_authority_set().clone(),
+        grandpa_hard_forks(config),
+    ));
+    Ok((Arc::new(Client::Client(client)), warp_sync, task_manager))
+}
+
 type FullGrandpaBlockImport<RuntimeApi, Executor> = sc_consensus_grandpa::GrandpaBlockImport<
     FullBackend,
     Block,
@@ -583,11 +622,23 @@ where
     }
 
     let mut command_sink_opt = None;
+    let mut time_travel_opt = None;
     if role.is_authority() {
         let distance_dir = config
             .base_path
             .config_dir(config.chain_spec.id())
             .join("distance");
+        let embedded_results = dc_distance::EvaluationResults::default();
+        if duniter_options.embedded_distance_oracle {
+            task_manager.spawn_handle().spawn(
+                "distance-oracle",
+                Some("distance-oracle"),
+                crate::embedded_oracle::run::<_, FullBackend>(
+                    client.clone(),
+                    embedded_results.clone(),
+                ),
+            );
+        }
 
         let proposer_factory = sc_basic_authorship::ProposerFactory::new(
             task_manager.spawn_handle(),
@@ -601,7 +652,16 @@ where
         let client = client.clone();
 
         if sealing.is_manual_consensus() {
-            let commands_stream: Box<dyn Stream<Item = EngineCommand<H256>> + Send + Sync + Unpin> =
+            // The time travel RPC seals blocks whatever the manual sealing mode.
+            let slot_duration = babe_link.config().slot_duration();
+            let (time_travel_sink, time_travel_stream) = futures::channel::mpsc::channel(1000);
+            let time_travel_state = crate::time_travel::TimeTravelState::default();
+            time_travel_opt = Some(crate::rpc::TimeTravelDeps {
+                command_sink: time_travel_sink,
+                slot_duration: slot_duration.as_millis(),
+            });
+
+            let sealing_stream: Box<dyn Stream<Item = EngineCommand<H256>> + Send + Sync + Unpin> =
                 match sealing {
                     crate::cli::Sealing::Instant => {
                         Box::new(
@@ -633,6 +693,9 @@ where
                     )),
                     crate::cli::Sealing::Production => unreachable!(),
                 };
+            // The time jump only applies to the blocks sealed by the time travel RPC.
+            let commands_stream =
+                time_travel_state.merge_commands(time_travel_stream, sealing_stream);
 
             let babe_consensus_data_provider =
                 sc_consensus_manual_seal::consensus::babe::BabeConsensusDataProvider::new(
@@ -662,28 +725,34 @@ where
                     create_inherent_data_providers: move |parent, _| {
                         let client = client.clone();
                         let distance_dir = distance_dir.clone();
+                        let embedded_results = embedded_results.clone();
+                        let time_travel_state = time_travel_state.clone();
                         let babe_owner_keys =
                             std::sync::Arc::new(sp_keystore::Keystore::sr25519_public_keys(
                                 keystore_ptr.as_ref(),
                                 sp_runtime::KeyTypeId(*b"babe"),
                             ));
                         async move {
+                            // The slot follows the parent one, unless a time jump was requested.
+                            let (slot, timestamp) = time_travel_state.next_block_time(
+                                &*client,
+                                parent,
+                                slot_duration.as_millis(),
+                            )?;
                             let timestamp =
-                                sc_consensus_manual_seal::consensus::timestamp::SlotTimestampProvider::new_babe(
-                                    client.clone(),
-                                )
-                                .map_err(|err| format!("{err:?}"))?;
-                            let babe = InherentDataProvider::new(
-                                timestamp.slot(),
+                                sp_timestamp::InherentDataProvider::new(timestamp.into());
+                            let babe = InherentDataProvider::new(slot);
+                            let distance = dc_distance::create_distance_inherent_data_provider::<
+                                Block,
+                                FullClient<RuntimeApi, Executor>,
+                                FullBackend,
+                            >(
+                                &*client,
+                                parent,
+                                distance_dir,
+                                &babe_owner_keys.clone(),
+                                &embedded_results,
                             );
-                            let distance =
-                                dc_distance::create_distance_inherent_data_provider::<
-                                    Block,
-                                    FullClient<RuntimeApi, Executor>,
-                                    FullBackend,
-                                >(
-                                    &*client, parent, distance_dir, &babe_owner_keys.clone()
-                                );
                             Ok((timestamp, babe, distance))
                         }
                     },
@@ -704,6 +773,7 @@ where
 
                     let client = client.clone();
                     let distance_dir = distance_dir.clone();
+                    let embedded_results = embedded_results.clone();
                     let babe_owner_keys =
                         std::sync::Arc::new(sp_keystore::Keystore::sr25519_public_keys(
                             keystore_ptr.as_ref(),
@@ -728,7 +798,11 @@ where
                             FullClient<RuntimeApi, Executor>,
                             FullBackend,
                         >(
-                            &*client, parent, distance_dir, &babe_owner_keys.clone()
+                            &*client,
+                            parent,
+                            distance_dir,
+                            &babe_owner_keys.clone(),
+                            &embedded_results,
                         );
 
                         Ok((slot, timestamp, storage_proof, distance))
@@ -793,6 +867,7 @@ where
                     grandpa: grandpa_deps,
                     duniter_peering: endpoint_gossip_deps,
                     command_sink_opt: command_sink_opt.clone(),
+                    time_travel: time_travel_opt.clone(),
                 };
 
                 crate::rpc::create_full(deps).map_err(Into::into)
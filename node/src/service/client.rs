@@ -119,7 +119,9 @@ pub trait ExecuteWithClient {
 /// This trait has no methods or associated type. It is a concise marker for all the trait bounds
 /// that it contains.
 pub trait RuntimeApiCollection:
-    pallet_grandpa::fg_primitives::GrandpaApi<Block>
+    common_runtime::invariants::InvariantsApi<Block>
+    + pallet_distance::DistanceApi<Block, AccountId, BlockNumber>
+    + pallet_grandpa::fg_primitives::GrandpaApi<Block>
     + pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
     + sp_api::ApiExt<Block>
     + sp_authority_discovery::AuthorityDiscoveryApi<Block>
@@ -133,7 +135,9 @@ pub trait RuntimeApiCollection:
 {
 }
 impl<Api> RuntimeApiCollection for Api where
-    Api: pallet_grandpa::fg_primitives::GrandpaApi<Block>
+    Api: common_runtime::invariants::InvariantsApi<Block>
+        + pallet_distance::DistanceApi<Block, AccountId, BlockNumber>
+        + pallet_grandpa::fg_primitives::GrandpaApi<Block>
         + pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
         + sp_api::ApiExt<Block>
         + sp_authority_discovery::AuthorityDiscoveryApi<Block>
@@ -349,6 +353,12 @@ impl BenchmarkCallSigner<super::runtime_executor::runtime::RuntimeCall, sp_core:
             frame_metadata_hash_extension::CheckMetadataHash::<
                 super::runtime_executor::runtime::Runtime,
             >::new(false),
+            common_runtime::check_wot_priority::CheckWotPriority::<
+                super::runtime_executor::runtime::Runtime,
+            >::new(),
+            common_runtime::check_rate_limit::CheckRateLimit::<
+                super::runtime_executor::runtime::Runtime,
+            >::new(),
         );
 
         let payload = sp_runtime::generic::SignedPayload::from_raw(
@@ -364,6 +374,8 @@ impl BenchmarkCallSigner<super::runtime_executor::runtime::RuntimeCall, sp_core:
                 (),
                 (),
                 None,
+                (),
+                (),
             ),
         );
 
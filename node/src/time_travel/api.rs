@@ -0,0 +1,113 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Time travel RPC API
+//!
+//! Exposes the `dev_makeBlocks` and `dev_advanceTo` RPC methods.
+
+use crate::time_travel::{TimeJump, TimeTravelCommand, block_slot};
+use common_runtime::{Block, Hash};
+use futures::{SinkExt, channel::mpsc::Sender, lock::Mutex};
+use jsonrpsee::{core::async_trait, proc_macros::rpc};
+use sc_consensus_manual_seal::{EngineCommand, Error};
+use sp_blockchain::HeaderBackend;
+use std::sync::Arc;
+
+/// The exposed RPC methods
+#[rpc(client, server)]
+pub trait TimeTravelApi {
+    /// Seals `count` blocks, each `step` milliseconds after its parent, and returns their hashes
+    #[method(name = "dev_makeBlocks")]
+    async fn make_blocks(&self, count: u32, step: Option<u64>) -> Result<Vec<Hash>, Error>;
+
+    /// Seals a block at the given unix timestamp in milliseconds and returns its hash
+    #[method(name = "dev_advanceTo")]
+    async fn advance_to(&self, timestamp: u64) -> Result<Hash, Error>;
+}
+
+/// API implementation
+pub struct TimeTravel<C> {
+    client: Arc<C>,
+    command_sink: Sender<TimeTravelCommand>,
+    slot_duration: u64,
+    // prevents concurrent requests from interleaving their blocks
+    lock: Mutex<()>,
+}
+
+impl<C: HeaderBackend<Block>> TimeTravel<C> {
+    /// Creates a new instance of the time travel RPC handler.
+    pub fn new(
+        client: Arc<C>,
+        command_sink: Sender<TimeTravelCommand>,
+        slot_duration: u64,
+    ) -> Self {
+        Self {
+            client,
+            command_sink,
+            slot_duration,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Seals a block with the given time jump.
+    async fn seal_block(&self, jump: TimeJump) -> Result<Hash, Error> {
+        let (sender, receiver) = futures::channel::oneshot::channel();
+        self.command_sink
+            .clone()
+            .send((
+                jump,
+                EngineCommand::SealNewBlock {
+                    create_empty: true,
+                    finalize: true,
+                    parent_hash: None,
+                    sender: Some(sender),
+                },
+            ))
+            .await?;
+        let created_block = receiver.await??;
+        Ok(created_block.hash)
+    }
+}
+
+#[async_trait]
+impl<C> TimeTravelApiServer for TimeTravel<C>
+where
+    C: HeaderBackend<Block> + Send + Sync + 'static,
+{
+    async fn make_blocks(&self, count: u32, step: Option<u64>) -> Result<Vec<Hash>, Error> {
+        let _guard = self.lock.lock().await;
+        let step = step.unwrap_or(self.slot_duration);
+        let mut hashes = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            hashes.push(self.seal_block(TimeJump::Step(step)).await?);
+        }
+        Ok(hashes)
+    }
+
+    async fn advance_to(&self, timestamp: u64) -> Result<Hash, Error> {
+        let _guard = self.lock.lock().await;
+        let best_hash = self.client.info().best_hash;
+        if let Some(best_slot) = block_slot(&*self.client, best_hash).map_err(Error::StringError)? {
+            let best_timestamp = best_slot * self.slot_duration;
+            if timestamp < best_timestamp + self.slot_duration {
+                return Err(Error::StringError(format!(
+                    "target timestamp {timestamp} is not after the best block timestamp {best_timestamp}"
+                )));
+            }
+        }
+        self.seal_block(TimeJump::To(timestamp)).await
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Time travel
+//!
+//! Lets a node using manual consensus (`--sealing instant`, `manual` or an interval) jump forward
+//! in time, to test features driven by the block timestamp (universal dividend, membership expiry,
+//! certification validity…) without making thousands of blocks.
+//!
+//! The RPC methods are only available when the node does not use production sealing.
+//!
+//! ## RPC methods
+//!
+//! ### `dev_makeBlocks`
+//!
+//! Seals `count` blocks, each `step` milliseconds after its parent (one slot if not given), and
+//! returns their hashes.
+//!
+//! ```json
+//! {"jsonrpc":"2.0","id":0,"method":"dev_makeBlocks","params":[3, 86400000]}
+//! ```
+//!
+//! ### `dev_advanceTo`
+//!
+//! Seals one block at the given unix timestamp in milliseconds, and returns its hash.
+//!
+//! ```json
+//! {"jsonrpc":"2.0","id":0,"method":"dev_advanceTo","params":[1767225600000]}
+//! ```
+//!
+//! ## Seal commands
+//!
+//! The RPC sends its seal commands along with their time jump, on a channel merged with the other
+//! seal commands of the node (instant, interval or manual sealing). The block authoring task seals
+//! one block per command, in order: the time jump of a time travel command is set when the command
+//! is taken from the merged stream and the other commands clear it, so that the jump only applies
+//! to the block sealed by the time travel command.
+//!
+//! ## Consensus
+//!
+//! Timestamps are rounded down to a BABE slot, so that the runtime sees a timestamp consistent with
+//! the slot of the block. Skipped slots and epochs are handled by BABE like an offline network.
+//!
+//! The universal dividend pallet creates at most one UD per block: after a jump over several
+//! `UdCreationPeriod`s, the missed UDs are created in the following blocks. Use `dev_makeBlocks`
+//! with a step of one UD period to get one UD per block.
+
+pub(crate) mod api;
+#[cfg(test)]
+mod tests;
+
+use common_runtime::{Block, Hash};
+use futures::{Stream, StreamExt, channel::mpsc::Receiver};
+use parking_lot::Mutex;
+use sc_consensus_manual_seal::EngineCommand;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_babe::Slot;
+use sp_runtime::traits::{Header as _, Zero};
+use std::sync::Arc;
+
+/// Time jump applied to the next sealed block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeJump {
+    /// The block is `step` milliseconds after its parent.
+    Step(u64),
+    /// The block is at the given unix timestamp in milliseconds.
+    To(u64),
+}
+
+/// Seal command of the time travel RPC, with the time jump of the block it seals.
+pub type TimeTravelCommand = (TimeJump, EngineCommand<Hash>);
+
+/// Time jump of the block being sealed, shared between the seal commands stream and the block
+/// authoring task.
+#[derive(Clone, Default)]
+pub struct TimeTravelState {
+    next_jump: Arc<Mutex<Option<TimeJump>>>,
+}
+
+impl TimeTravelState {
+    /// Merges the seal commands of the time travel RPC with the other seal commands of the node.
+    ///
+    /// Each command sets the time jump of the block it seals, if any.
+    pub fn merge_commands<S>(
+        &self,
+        time_travel_commands: Receiver<TimeTravelCommand>,
+        other_commands: S,
+    ) -> impl Stream<Item = EngineCommand<Hash>> + Send + Sync + Unpin + use<S>
+    where
+        S: Stream<Item = EngineCommand<Hash>> + Send + Sync + Unpin,
+    {
+        let state = self.clone();
+        let time_travel_commands = time_travel_commands.map(move |(jump, command)| {
+            state.set_next_jump(Some(jump));
+            command
+        });
+        let state = self.clone();
+        let other_commands = other_commands.map(move |command| {
+            state.set_next_jump(None);
+            command
+        });
+        futures::stream::select(time_travel_commands, other_commands)
+    }
+
+    /// Sets the time jump of the next sealed block.
+    fn set_next_jump(&self, jump: Option<TimeJump>) {
+        *self.next_jump.lock() = jump;
+    }
+
+    /// Takes the time jump of the next sealed block, if any.
+    pub fn take_next_jump(&self) -> Option<TimeJump> {
+        self.next_jump.lock().take()
+    }
+
+    /// Returns the slot and the timestamp of the block authored on top of `parent`.
+    ///
+    /// The pending time jump is consumed.
+    pub fn next_block_time<C: HeaderBackend<Block>>(
+        &self,
+        client: &C,
+        parent: <Block as sp_runtime::traits::Block>::Hash,
+        slot_duration: u64,
+    ) -> Result<(Slot, u64), String> {
+        let parent_slot = match block_slot(client, parent)? {
+            Some(slot) => slot,
+            // the first block is authored at the current time
+            None => now_millis()? / slot_duration - 1,
+        };
+        let slot = next_slot(parent_slot, slot_duration, self.take_next_jump());
+        Ok((Slot::from(slot), slot * slot_duration))
+    }
+}
+
+/// Returns the slot of the block authored after `parent_slot` with the given jump.
+///
+/// The slot is always after the parent one.
+pub fn next_slot(parent_slot: u64, slot_duration: u64, jump: Option<TimeJump>) -> u64 {
+    let slot = match jump {
+        None => parent_slot + 1,
+        Some(TimeJump::Step(step)) => parent_slot + step / slot_duration,
+        Some(TimeJump::To(timestamp)) => timestamp / slot_duration,
+    };
+    slot.max(parent_slot + 1)
+}
+
+/// Returns the BABE slot of a block, or `None` for the genesis block.
+pub fn block_slot<C: HeaderBackend<Block>>(
+    client: &C,
+    hash: <Block as sp_runtime::traits::Block>::Hash,
+) -> Result<Option<u64>, String> {
+    let header = client
+        .header(hash)
+        .map_err(|err| format!("{err:?}"))?
+        .ok_or_else(|| format!("unknown block {hash}"))?;
+    if header.number().is_zero() {
+        return Ok(None);
+    }
+    sc_consensus_babe::find_pre_digest::<Block>(&header)
+        .map(|pre_digest| Some(*pre_digest.slot()))
+        .map_err(|err| format!("{err:?}"))
+}
+
+fn now_millis() -> Result<u64, String> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|now| now.as_millis() as u64)
+        .map_err(|err| format!("{err:?}"))
+}
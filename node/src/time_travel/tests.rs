@@ -0,0 +1,100 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::time_travel::{TimeJump, TimeTravelState, next_slot};
+use futures::{FutureExt, StreamExt, channel::mpsc::channel};
+use sc_consensus_manual_seal::EngineCommand;
+
+const SLOT_DURATION: u64 = 6_000;
+
+#[test]
+fn next_slot_without_jump() {
+    assert_eq!(next_slot(10, SLOT_DURATION, None), 11);
+}
+
+#[test]
+fn next_slot_with_step() {
+    // one day later
+    assert_eq!(
+        next_slot(10, SLOT_DURATION, Some(TimeJump::Step(86_400_000))),
+        10 + 14_400
+    );
+    // the step is rounded down to a slot
+    assert_eq!(
+        next_slot(
+            10,
+            SLOT_DURATION,
+            Some(TimeJump::Step(2 * SLOT_DURATION + 1))
+        ),
+        12
+    );
+    // the block is always after its parent
+    assert_eq!(next_slot(10, SLOT_DURATION, Some(TimeJump::Step(0))), 11);
+}
+
+#[test]
+fn next_slot_with_target_timestamp() {
+    assert_eq!(
+        next_slot(
+            10,
+            SLOT_DURATION,
+            Some(TimeJump::To(100 * SLOT_DURATION + 1))
+        ),
+        100
+    );
+    // a past target results in the next slot
+    assert_eq!(next_slot(10, SLOT_DURATION, Some(TimeJump::To(0))), 11);
+}
+
+#[test]
+fn time_jump_is_consumed() {
+    let state = TimeTravelState::default();
+    state.set_next_jump(Some(TimeJump::Step(SLOT_DURATION)));
+    assert_eq!(state.take_next_jump(), Some(TimeJump::Step(SLOT_DURATION)));
+    assert_eq!(state.take_next_jump(), None);
+}
+
+fn seal_command() -> EngineCommand<common_runtime::Hash> {
+    EngineCommand::SealNewBlock {
+        create_empty: true,
+        finalize: false,
+        parent_hash: None,
+        sender: None,
+    }
+}
+
+#[test]
+fn time_jump_only_applies_to_time_travel_commands() {
+    let state = TimeTravelState::default();
+    let (mut time_travel_sink, time_travel_stream) = channel(10);
+    let (mut other_sink, other_stream) = channel(10);
+    let mut commands = state.merge_commands(time_travel_stream, other_stream);
+
+    time_travel_sink
+        .try_send((TimeJump::Step(SLOT_DURATION), seal_command()))
+        .unwrap();
+    assert!(commands.next().now_or_never().flatten().is_some());
+    assert_eq!(state.take_next_jump(), Some(TimeJump::Step(SLOT_DURATION)));
+
+    // a block sealed by another command does not take a pending jump
+    time_travel_sink
+        .try_send((TimeJump::To(100 * SLOT_DURATION), seal_command()))
+        .unwrap();
+    assert!(commands.next().now_or_never().flatten().is_some());
+    other_sink.try_send(seal_command()).unwrap();
+    assert!(commands.next().now_or_never().flatten().is_some());
+    assert_eq!(state.take_next_jump(), None);
+}
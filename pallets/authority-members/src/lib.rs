@@ -236,6 +236,16 @@ pub mod pallet {
         TooManyAuthorities,
     }
 
+    // HOOKS //
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
     // CALLS //
 
     #[pallet::call]
@@ -411,6 +421,38 @@ pub mod pallet {
                 - OutgoingAuthorities::<T>::get().len();
             count as u32
         }
+
+        /// Check the storage invariants of the pallet.
+        ///
+        /// - incoming, online and outgoing authorities are strictly sorted;
+        /// - incoming and online authorities are members;
+        /// - incoming authorities are neither online nor outgoing.
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let incoming = IncomingAuthorities::<T>::get();
+            let online = OnlineAuthorities::<T>::get();
+            let outgoing = OutgoingAuthorities::<T>::get();
+            for members_ids in [&incoming, &online, &outgoing] {
+                ensure!(
+                    members_ids.windows(2).all(|w| w[0] < w[1]),
+                    "authorities are not strictly sorted"
+                );
+            }
+            ensure!(
+                incoming
+                    .iter()
+                    .chain(online.iter())
+                    .all(|member_id| Members::<T>::contains_key(member_id)),
+                "incoming or online authority is not a member"
+            );
+            ensure!(
+                incoming.iter().all(|member_id| {
+                    online.binary_search(member_id).is_err()
+                        && outgoing.binary_search(member_id).is_err()
+                }),
+                "incoming authority is already online or outgoing"
+            );
+            Ok(())
+        }
     }
 
     // INTERNAL FUNCTIONS //
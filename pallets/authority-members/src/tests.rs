@@ -126,6 +126,7 @@ fn test_go_online() {
             AuthorityMembers::member(12),
             Some(MemberData { owner_key: 12 })
         );
+        assert_ok!(AuthorityMembers::do_try_state());
 
         // Member 12 should be "programmed" at the next session
         run_to_block(5);
@@ -141,6 +142,7 @@ fn test_go_online() {
         run_to_block(10);
         assert_eq!(Session::current_index(), 2);
         assert_eq!(Session::validators(), vec![3, 6, 9, 12]);
+        assert_ok!(AuthorityMembers::do_try_state());
     });
 }
 
@@ -289,6 +289,11 @@ pub mod pallet {
         fn on_initialize(n: BlockNumberFor<T>) -> Weight {
             Self::prune_certifications(n).saturating_add(T::WeightInfo::on_initialize())
         }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
     }
 
     // CALLS //
@@ -569,6 +574,58 @@ pub mod pallet {
             total_weight
         }
 
+        /// Check the storage invariants of the pallet.
+        ///
+        /// - received certifications are sorted by issuer without duplicates;
+        /// - every certification expires in the future and is scheduled at its expiry block;
+        /// - issued and received counts in the metadata match the certifications.
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let block_number = frame_system::pallet::Pallet::<T>::block_number();
+            let mut issued_counts = BTreeMap::<T::IdtyIndex, u32>::new();
+            for (receiver, issuers) in CertsByReceiver::<T>::iter() {
+                ensure!(
+                    issuers.windows(2).all(|w| w[0].0 < w[1].0),
+                    "received certifications are not strictly sorted by issuer"
+                );
+                ensure!(
+                    StorageIdtyCertMeta::<T>::get(receiver).received_count == issuers.len() as u32,
+                    "received count does not match the received certifications"
+                );
+                for (issuer, removable_on) in issuers {
+                    ensure!(
+                        removable_on > block_number,
+                        "certification should already have expired"
+                    );
+                    ensure!(
+                        CertsRemovableOn::<T>::get(removable_on)
+                            .is_some_and(|certs| certs.contains(&(issuer, receiver))),
+                        "certification expiry is not scheduled"
+                    );
+                    *issued_counts.entry(issuer).or_default() += 1;
+                }
+            }
+            for (idty_index, cert_meta) in StorageIdtyCertMeta::<T>::iter() {
+                ensure!(
+                    cert_meta.issued_count
+                        == issued_counts.get(&idty_index).copied().unwrap_or_default(),
+                    "issued count does not match the issued certifications"
+                );
+                if cert_meta.received_count > 0 {
+                    ensure!(
+                        CertsByReceiver::<T>::contains_key(idty_index),
+                        "received count does not match the received certifications"
+                    );
+                }
+            }
+            ensure!(
+                issued_counts
+                    .keys()
+                    .all(|issuer| StorageIdtyCertMeta::<T>::contains_key(issuer)),
+                "certification issuer has no metadata"
+            );
+            Ok(())
+        }
+
         /// Check if adding a certification is allowed.
         // 1. no self cert
         // 2. issuer received cert count
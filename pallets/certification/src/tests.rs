@@ -347,3 +347,41 @@ fn test_cert_renewal_expiration() {
         }));
     });
 }
+
+// storage invariants should hold through certification lifecycle
+#[test]
+fn test_try_state() {
+    new_test_ext(DefaultCertificationConfig {
+        apply_cert_period_at_genesis: true,
+        certs_by_receiver: btreemap![
+            0 => btreemap![
+                1 => Some(5),
+                2 => Some(5),
+            ],
+            1 => btreemap![
+                0 => Some(6),
+                2 => Some(6),
+            ],
+            2 => btreemap![
+                0 => Some(7),
+                1 => Some(7),
+            ],
+        ],
+    })
+    .execute_with(|| {
+        assert_ok!(DefaultCertification::do_try_state());
+        run_to_block(2);
+        assert_ok!(DefaultCertification::add_cert(RuntimeOrigin::signed(0), 3));
+        assert_ok!(DefaultCertification::renew_cert(
+            RuntimeOrigin::signed(1),
+            0
+        ));
+        assert_ok!(DefaultCertification::do_try_state());
+        run_to_block(7);
+        assert_ok!(DefaultCertification::do_try_state());
+
+        // a wrong issued count is detected
+        crate::StorageIdtyCertMeta::<Test>::mutate(0, |cert_meta| cert_meta.issued_count += 1);
+        assert!(DefaultCertification::do_try_state().is_err());
+    });
+}
@@ -0,0 +1,162 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+
+fn assert_has_event<T: Config>(generic_event: <T as frame_system::Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_has_event(generic_event);
+}
+
+/// Makes sure the identity seats in the committee.
+fn ensure_member<T: Config>(idty_index: T::IdtyIndex) {
+    if !Members::<T>::contains_key(idty_index) {
+        let account = T::IdtyAttr::owner_key(idty_index).expect("identity must exist");
+        Pallet::<T>::do_add_member(idty_index, account);
+    }
+}
+
+#[benchmarks(
+        where
+            T::IdtyIndex: From<u32>
+)]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn add_member() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        // Should be a smith of the local_testnet_config
+        let idty_index: T::IdtyIndex = 1.into();
+        Pallet::<T>::do_remove_member(idty_index, RemovalReason::Removed);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, idty_index);
+
+        assert!(Members::<T>::contains_key(idty_index));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn renew_term() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let idty_index: T::IdtyIndex = 1.into();
+        ensure_member::<T>(idty_index);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, idty_index);
+
+        let term_end = frame_system::Pallet::<T>::block_number() + T::TermDuration::get();
+        assert_has_event::<T>(
+            Event::<T>::TermRenewed {
+                idty_index,
+                term_end,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn remove_member() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let idty_index: T::IdtyIndex = 1.into();
+        ensure_member::<T>(idty_index);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, idty_index);
+
+        assert!(!Members::<T>::contains_key(idty_index));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn resign() {
+        let idty_index: T::IdtyIndex = 1.into();
+        ensure_member::<T>(idty_index);
+        let caller = T::IdtyAttr::owner_key(idty_index).expect("identity must exist");
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller));
+
+        assert!(!Members::<T>::contains_key(idty_index));
+    }
+
+    #[benchmark]
+    fn expire_terms(i: Linear<0, 100>) {
+        let n = frame_system::Pallet::<T>::block_number() + T::TermDuration::get();
+        for j in 0..i {
+            let idty_index: T::IdtyIndex = (10_000 + j).into();
+            Members::<T>::insert(
+                idty_index,
+                MemberInfo {
+                    account: account("member", j, 0),
+                    term_end: n,
+                },
+            );
+            TermsEndingOn::<T>::append(n, idty_index);
+        }
+
+        #[block]
+        {
+            Pallet::<T>::expire_terms(n);
+        }
+
+        assert!(!TermsEndingOn::<T>::contains_key(n));
+    }
+
+    #[benchmark]
+    fn rotate_prime() {
+        ensure_member::<T>(1.into());
+
+        #[block]
+        {
+            Pallet::<T>::rotate_prime();
+        }
+    }
+
+    #[benchmark]
+    fn on_removed_wot_member() {
+        let idty_index: T::IdtyIndex = 1.into();
+        ensure_member::<T>(idty_index);
+
+        #[block]
+        {
+            Pallet::<T>::on_removed_wot_member(idty_index);
+        }
+
+        assert!(!Members::<T>::contains_key(idty_index));
+    }
+
+    #[benchmark]
+    fn on_removed_wot_member_empty() {
+        let idty_index: T::IdtyIndex = 1.into();
+        Pallet::<T>::do_remove_member(idty_index, RemovalReason::Removed);
+
+        #[block]
+        {
+            Pallet::<T>::on_removed_wot_member(idty_index);
+        }
+    }
+}
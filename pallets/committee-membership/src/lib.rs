@@ -0,0 +1,551 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Duniter Committee Membership Pallet
+//!
+//! The Committee Membership pallet manages the members of the technical committee collective.
+//!
+//! ## Overview
+//!
+//! Committee members are identities. Their owner keys are the accounts seating in the collective,
+//! which is kept in sync by this pallet through the `ChangeMembers` trait.
+//!
+//! ## Key Concepts
+//!
+//! ### Eligibility
+//!
+//! A committee member must remain a member of the Web of Trust, and a smith when `RequireSmith` is set.
+//! A member losing one of these statuses is automatically removed from the committee.
+//!
+//! ### Terms
+//!
+//! Each member seats for a term of `TermDuration` blocks. The committee can renew the term of a member
+//! through `ManagerOrigin`, otherwise the member is removed when its term ends.
+//!
+//! ### Prime
+//!
+//! The prime member of the collective rotates among the committee members every `PrimeRotationPeriod` blocks,
+//! following the order of their identity indexes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+mod benchmarking;
+pub mod migrations;
+mod types;
+pub mod weights;
+
+use codec::Codec;
+use duniter_primitives::Idty;
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    pallet_prelude::{Get, Weight},
+    traits::ChangeMembers,
+};
+use frame_system::{
+    ensure_signed,
+    pallet_prelude::{BlockNumberFor, OriginFor},
+};
+use scale_info::prelude::{fmt::Debug, vec::Vec};
+use sp_runtime::{
+    DispatchError,
+    traits::{AtLeast32BitUnsigned, IsMember, Zero},
+};
+
+pub use crate::weights::WeightInfo;
+pub use pallet::*;
+pub use types::*;
+
+#[allow(unreachable_patterns)]
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{pallet_prelude::*, traits::StorageVersion};
+
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    /// The pallet's config trait.
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// A short identity index type.
+        type IdtyIndex: Parameter
+            + Member
+            + AtLeast32BitUnsigned
+            + Codec
+            + Default
+            + Copy
+            + MaybeSerializeDeserialize
+            + Debug
+            + MaxEncodedLen;
+
+        /// Something that gives the IdtyIndex of an AccountId and reverse.
+        type IdtyAttr: duniter_primitives::Idty<Self::IdtyIndex, Self::AccountId>;
+
+        /// Trait to check if identity is a WoT member.
+        type IsWoTMember: IsMember<Self::IdtyIndex>;
+
+        /// Trait to check if identity is a smith.
+        type IsSmith: IsMember<Self::IdtyIndex>;
+
+        /// Whether committee members must be smiths.
+        #[pallet::constant]
+        type RequireSmith: Get<bool>;
+
+        /// Origin allowed to add, remove and renew committee members.
+        type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Maximum number of committee members.
+        #[pallet::constant]
+        type MaxMembers: Get<u32>;
+
+        /// Duration of a committee member term.
+        #[pallet::constant]
+        type TermDuration: Get<BlockNumberFor<Self>>;
+
+        /// Period after which the prime member is rotated.
+        #[pallet::constant]
+        type PrimeRotationPeriod: Get<BlockNumberFor<Self>>;
+
+        /// The collective whose membership is managed.
+        type MembershipChanged: ChangeMembers<Self::AccountId>;
+
+        /// Current members of the collective.
+        type CollectiveMembers: Get<Vec<Self::AccountId>>;
+
+        /// Type representing the weight of this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    /// Events type.
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// An identity joined the committee.
+        MemberAdded {
+            idty_index: T::IdtyIndex,
+            term_end: BlockNumberFor<T>,
+        },
+        /// The term of a committee member has been renewed.
+        TermRenewed {
+            idty_index: T::IdtyIndex,
+            term_end: BlockNumberFor<T>,
+        },
+        /// An identity left the committee.
+        MemberRemoved {
+            idty_index: T::IdtyIndex,
+            reason: RemovalReason,
+        },
+        /// The prime member changed.
+        PrimeChanged { idty_index: Option<T::IdtyIndex> },
+    }
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub members: Vec<(T::IdtyIndex, T::AccountId)>,
+    }
+
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            Self {
+                members: Default::default(),
+            }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            assert!(
+                self.members.len() <= T::MaxMembers::get() as usize,
+                "Too many committee members."
+            );
+            let term_end = T::TermDuration::get();
+            for (idty_index, account) in &self.members {
+                assert!(
+                    !Members::<T>::contains_key(idty_index),
+                    "Committee member declared twice."
+                );
+                Members::<T>::insert(
+                    idty_index,
+                    MemberInfo {
+                        account: account.clone(),
+                        term_end,
+                    },
+                );
+                TermsEndingOn::<T>::append(term_end, idty_index);
+            }
+            // the collective members are set by its own genesis config
+            let prime = Pallet::<T>::next_prime(None);
+            Prime::<T>::set(prime);
+            Pallet::<T>::sync_prime();
+        }
+    }
+
+    /// The committee members, indexed by identity.
+    #[pallet::storage]
+    pub type Members<T: Config> = CountedStorageMap<
+        _,
+        Twox64Concat,
+        T::IdtyIndex,
+        MemberInfo<T::AccountId, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// The identities whose term ends at a given block.
+    #[pallet::storage]
+    #[pallet::unbounded]
+    pub type TermsEndingOn<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<T::IdtyIndex>, ValueQuery>;
+
+    /// The current prime member.
+    #[pallet::storage]
+    pub type Prime<T: Config> = StorageValue<_, T::IdtyIndex, OptionQuery>;
+
+    // HOOKS //
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let mut weight = Self::expire_terms(n);
+            let period = T::PrimeRotationPeriod::get();
+            if !period.is_zero() && (n % period).is_zero() {
+                Self::rotate_prime();
+                weight = weight.saturating_add(T::WeightInfo::rotate_prime());
+            }
+            weight
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
+    // ERRORS //
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Identity not found.
+        IdtyNotFound,
+        /// Committee members must be members of the WoT.
+        NotWoTMember,
+        /// Committee members must be smiths.
+        NotSmith,
+        /// Identity is already a committee member.
+        AlreadyMember,
+        /// Identity is not a committee member.
+        NotMember,
+        /// The committee is full.
+        TooManyMembers,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Add an identity to the committee for a full term.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::add_member())]
+        pub fn add_member(origin: OriginFor<T>, idty_index: T::IdtyIndex) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin)?;
+            let account = Self::check_add_member(idty_index)?;
+            Self::do_add_member(idty_index, account);
+            Ok(())
+        }
+
+        /// Renew the term of a committee member, starting a new full term from now.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::renew_term())]
+        pub fn renew_term(origin: OriginFor<T>, idty_index: T::IdtyIndex) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin)?;
+            Self::check_eligible(idty_index)?;
+            Self::do_renew_term(idty_index)
+        }
+
+        /// Remove an identity from the committee.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::remove_member())]
+        pub fn remove_member(origin: OriginFor<T>, idty_index: T::IdtyIndex) -> DispatchResult {
+            T::ManagerOrigin::ensure_origin(origin)?;
+            ensure!(
+                Members::<T>::contains_key(idty_index),
+                Error::<T>::NotMember
+            );
+            Self::do_remove_member(idty_index, RemovalReason::Removed);
+            Ok(())
+        }
+
+        /// Leave the committee.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::resign())]
+        pub fn resign(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let idty_index = T::IdtyAttr::idty_index(who).ok_or(Error::<T>::IdtyNotFound)?;
+            ensure!(
+                Members::<T>::contains_key(idty_index),
+                Error::<T>::NotMember
+            );
+            Self::do_remove_member(idty_index, RemovalReason::Resigned);
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Check that an identity can seat in the committee.
+    fn check_eligible(idty_index: T::IdtyIndex) -> DispatchResult {
+        ensure!(
+            T::IsWoTMember::is_member(&idty_index),
+            Error::<T>::NotWoTMember
+        );
+        ensure!(
+            !T::RequireSmith::get() || T::IsSmith::is_member(&idty_index),
+            Error::<T>::NotSmith
+        );
+        Ok(())
+    }
+
+    /// Check conditions before adding a committee member and return its account.
+    fn check_add_member(idty_index: T::IdtyIndex) -> Result<T::AccountId, DispatchError> {
+        let account = T::IdtyAttr::owner_key(idty_index).ok_or(Error::<T>::IdtyNotFound)?;
+        ensure!(
+            !Members::<T>::contains_key(idty_index),
+            Error::<T>::AlreadyMember
+        );
+        ensure!(
+            Members::<T>::count() < T::MaxMembers::get(),
+            Error::<T>::TooManyMembers
+        );
+        Self::check_eligible(idty_index)?;
+        Ok(account)
+    }
+
+    /// Perform the addition of a committee member.
+    fn do_add_member(idty_index: T::IdtyIndex, account: T::AccountId) {
+        let term_end = frame_system::Pallet::<T>::block_number() + T::TermDuration::get();
+        Members::<T>::insert(
+            idty_index,
+            MemberInfo {
+                account: account.clone(),
+                term_end,
+            },
+        );
+        TermsEndingOn::<T>::append(term_end, idty_index);
+        if Prime::<T>::get().is_none() {
+            Prime::<T>::set(Some(idty_index));
+            Self::deposit_event(Event::<T>::PrimeChanged {
+                idty_index: Some(idty_index),
+            });
+        }
+        Self::change_collective(Some(account), None);
+        Self::deposit_event(Event::<T>::MemberAdded {
+            idty_index,
+            term_end,
+        });
+    }
+
+    /// Perform the renewal of a committee member term.
+    fn do_renew_term(idty_index: T::IdtyIndex) -> DispatchResult {
+        let term_end = frame_system::Pallet::<T>::block_number() + T::TermDuration::get();
+        Members::<T>::try_mutate(idty_index, |maybe_member| {
+            let member = maybe_member.as_mut().ok_or(Error::<T>::NotMember)?;
+            // the previous term end is left scheduled, it is ignored on expiry
+            member.term_end = term_end;
+            Ok::<(), Error<T>>(())
+        })?;
+        TermsEndingOn::<T>::append(term_end, idty_index);
+        Self::deposit_event(Event::<T>::TermRenewed {
+            idty_index,
+            term_end,
+        });
+        Ok(())
+    }
+
+    /// Perform the removal of a committee member.
+    fn do_remove_member(idty_index: T::IdtyIndex, reason: RemovalReason) {
+        if let Some(member) = Members::<T>::take(idty_index) {
+            if Prime::<T>::get() == Some(idty_index) {
+                let prime = Self::next_prime(Some(idty_index));
+                Prime::<T>::set(prime);
+                Self::deposit_event(Event::<T>::PrimeChanged { idty_index: prime });
+            }
+            Self::change_collective(None, Some(member.account));
+            Self::deposit_event(Event::<T>::MemberRemoved { idty_index, reason });
+        }
+    }
+
+    /// Remove the members whose term ends at the given block.
+    fn expire_terms(n: BlockNumberFor<T>) -> Weight {
+        let ending = TermsEndingOn::<T>::take(n);
+        let mut expired = 0u32;
+        for idty_index in ending {
+            if Members::<T>::get(idty_index).is_some_and(|member| member.term_end == n) {
+                Self::do_remove_member(idty_index, RemovalReason::TermEnded);
+                expired += 1;
+            }
+        }
+        T::WeightInfo::expire_terms(expired)
+    }
+
+    /// Give the prime role to the next committee member.
+    fn rotate_prime() {
+        let current = Prime::<T>::get();
+        let prime = Self::next_prime(current);
+        if prime != current {
+            Prime::<T>::set(prime);
+            Self::sync_prime();
+            Self::deposit_event(Event::<T>::PrimeChanged { idty_index: prime });
+        }
+    }
+
+    /// Return the member following the given identity index, wrapping around.
+    fn next_prime(after: Option<T::IdtyIndex>) -> Option<T::IdtyIndex> {
+        let mut members: Vec<T::IdtyIndex> = Members::<T>::iter_keys().collect();
+        members.sort();
+        match after {
+            Some(after) => members
+                .iter()
+                .find(|idty_index| **idty_index > after)
+                .or(members.first())
+                .copied(),
+            None => members.first().copied(),
+        }
+    }
+
+    /// Apply a membership change to the collective.
+    fn change_collective(incoming: Option<T::AccountId>, outgoing: Option<T::AccountId>) {
+        let mut members = T::CollectiveMembers::get();
+        if let Some(outgoing) = &outgoing {
+            members.retain(|account| account != outgoing);
+        }
+        if let Some(incoming) = &incoming
+            && !members.contains(incoming)
+        {
+            members.push(incoming.clone());
+        }
+        members.sort();
+        T::MembershipChanged::change_members_sorted(
+            incoming.as_slice(),
+            outgoing.as_slice(),
+            &members,
+        );
+        // the collective resets its prime on membership change
+        Self::sync_prime();
+    }
+
+    /// Set the prime of the collective to the account of the prime member.
+    fn sync_prime() {
+        let account = Prime::<T>::get()
+            .and_then(Members::<T>::get)
+            .map(|member| member.account);
+        T::MembershipChanged::set_prime(account);
+    }
+
+    /// Check the storage invariants of the pallet.
+    ///
+    /// - the committee is not over-full and every term end is scheduled in the future;
+    /// - the prime is a member, and there is one as long as the committee is not empty;
+    /// - the collective members are the accounts of the committee members.
+    pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+        let block_number = frame_system::Pallet::<T>::block_number();
+        let mut accounts = Vec::new();
+        for (idty_index, member) in Members::<T>::iter() {
+            ensure!(
+                member.term_end > block_number,
+                "committee member term should already have ended"
+            );
+            ensure!(
+                TermsEndingOn::<T>::get(member.term_end).contains(&idty_index),
+                "committee member term end is not scheduled"
+            );
+            accounts.push(member.account);
+        }
+        ensure!(
+            accounts.len() as u32 == Members::<T>::count(),
+            "committee members counter does not match the number of members"
+        );
+        ensure!(
+            Members::<T>::count() <= T::MaxMembers::get(),
+            "too many committee members"
+        );
+        match Prime::<T>::get() {
+            Some(prime) => ensure!(
+                Members::<T>::contains_key(prime),
+                "committee prime is not a member"
+            ),
+            None => ensure!(accounts.is_empty(), "committee has no prime"),
+        }
+        accounts.sort();
+        let mut collective_members = T::CollectiveMembers::get();
+        collective_members.sort();
+        ensure!(
+            accounts == collective_members,
+            "collective members do not match the committee members"
+        );
+        Ok(())
+    }
+
+    /// Handle actions upon the removal of a Web of Trust member.
+    pub fn on_removed_wot_member(idty_index: T::IdtyIndex) -> Weight {
+        let mut weight = T::WeightInfo::on_removed_wot_member_empty();
+        if Members::<T>::contains_key(idty_index) {
+            Self::do_remove_member(idty_index, RemovalReason::LostMembership);
+            weight = weight.saturating_add(T::WeightInfo::on_removed_wot_member());
+        }
+        weight
+    }
+
+    /// Handle actions upon the removal of a smith.
+    ///
+    /// The removal is the same as upon the loss of the WoT membership, and weighs the same.
+    pub fn on_smith_removed(idty_index: T::IdtyIndex) -> Weight {
+        if !T::RequireSmith::get() {
+            return Weight::zero();
+        }
+        let mut weight = T::WeightInfo::on_removed_wot_member_empty();
+        if Members::<T>::contains_key(idty_index) {
+            Self::do_remove_member(idty_index, RemovalReason::LostSmithStatus);
+            weight = weight.saturating_add(T::WeightInfo::on_removed_wot_member());
+        }
+        weight
+    }
+
+    /// Handle the change of the owner key of an identity.
+    pub fn on_owner_key_changed(idty_index: T::IdtyIndex, new_account: T::AccountId) {
+        let old_account = Members::<T>::mutate(idty_index, |maybe_member| {
+            maybe_member
+                .as_mut()
+                .map(|member| core::mem::replace(&mut member.account, new_account.clone()))
+        });
+        if let Some(old_account) = old_account {
+            Self::change_collective(Some(new_account), Some(old_account));
+        }
+    }
+}
+
+impl<T: Config> sp_runtime::traits::IsMember<T::IdtyIndex> for Pallet<T> {
+    fn is_member(idty_index: &T::IdtyIndex) -> bool {
+        Members::<T>::contains_key(idty_index)
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations of the committee membership pallet.
+
+use crate::*;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+
+/// Imports the current collective members as committee members for a full term.
+///
+/// The pallet storage version is set before the migrations run when the pallet is added,
+/// so the import is guarded by the committee being empty instead.
+/// Collective members which could not seat in the committee, because they have no identity,
+/// are not eligible or exceed `MaxMembers`, are removed from the collective.
+pub struct ImportCollectiveMembers<T>(core::marker::PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for ImportCollectiveMembers<T> {
+    fn on_runtime_upgrade() -> Weight {
+        if Members::<T>::count() != 0 {
+            return T::DbWeight::get().reads(1);
+        }
+        let accounts = T::CollectiveMembers::get();
+        if accounts.is_empty() {
+            return T::DbWeight::get().reads(2);
+        }
+        let term_end = frame_system::Pallet::<T>::block_number() + T::TermDuration::get();
+        let mut kept = Vec::new();
+        let mut outgoing = Vec::new();
+        for account in accounts.iter() {
+            match T::IdtyAttr::idty_index(account.clone()) {
+                Some(idty_index)
+                    if !Members::<T>::contains_key(idty_index)
+                        && Members::<T>::count() < T::MaxMembers::get()
+                        && Pallet::<T>::check_eligible(idty_index).is_ok() =>
+                {
+                    Members::<T>::insert(
+                        idty_index,
+                        MemberInfo {
+                            account: account.clone(),
+                            term_end,
+                        },
+                    );
+                    TermsEndingOn::<T>::append(term_end, idty_index);
+                    kept.push(account.clone());
+                }
+                _ => outgoing.push(account.clone()),
+            }
+        }
+        if !outgoing.is_empty() {
+            kept.sort();
+            outgoing.sort();
+            T::MembershipChanged::change_members_sorted(&[], &outgoing, &kept);
+        }
+        Prime::<T>::set(Pallet::<T>::next_prime(None));
+        Pallet::<T>::sync_prime();
+        T::DbWeight::get().reads_writes(
+            2 + accounts.len() as u64 * 5,
+            4 + kept.len() as u64 * 3 + outgoing.len() as u64,
+        )
+    }
+}
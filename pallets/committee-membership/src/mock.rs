@@ -0,0 +1,136 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{self as pallet_committee_membership};
+use frame_support::{
+    derive_impl,
+    pallet_prelude::Hooks,
+    parameter_types,
+    traits::{ChangeMembers, ConstBool, ConstU32, ConstU64},
+    weights::constants::RocksDbWeight,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{
+    BuildStorage,
+    traits::{BlakeTwo256, IdentityLookup, IsMember},
+};
+
+parameter_types! {
+    pub static WotMembers: Vec<u64> = vec![1, 2, 3, 4, 5];
+    pub static Smiths: Vec<u64> = vec![1, 2, 3, 4];
+    pub static CollectiveMembers: Vec<u64> = vec![];
+    pub static CollectivePrime: Option<u64> = None;
+}
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+frame_support::construct_runtime!(
+    pub struct Runtime {
+        System: frame_system,
+        CommitteeMembership: pallet_committee_membership,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Runtime {
+    type AccountId = u64;
+    type BaseCallFilter = frame_support::traits::Everything;
+    type Block = Block;
+    type BlockHashCount = ConstU64<250>;
+    type DbWeight = RocksDbWeight;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type MaxConsumers = ConstU32<16>;
+    type Nonce = u64;
+    type PalletInfo = PalletInfo;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+}
+
+pub struct IsWoTMemberMock;
+impl IsMember<u64> for IsWoTMemberMock {
+    fn is_member(idty_index: &u64) -> bool {
+        WotMembers::get().contains(idty_index)
+    }
+}
+
+pub struct IsSmithMock;
+impl IsMember<u64> for IsSmithMock {
+    fn is_member(idty_index: &u64) -> bool {
+        Smiths::get().contains(idty_index)
+    }
+}
+
+/// Collective mock, which kills the prime on membership change like `pallet_collective`.
+pub struct CollectiveMock;
+impl ChangeMembers<u64> for CollectiveMock {
+    fn change_members_sorted(_incoming: &[u64], _outgoing: &[u64], new: &[u64]) {
+        CollectiveMembers::set(new.to_vec());
+        CollectivePrime::set(None);
+    }
+
+    fn set_prime(prime: Option<u64>) {
+        CollectivePrime::set(prime);
+    }
+
+    fn get_prime() -> Option<u64> {
+        CollectivePrime::get()
+    }
+}
+
+impl pallet_committee_membership::Config for Runtime {
+    type CollectiveMembers = CollectiveMembers;
+    type IdtyAttr = ();
+    type IdtyIndex = u64;
+    type IsSmith = IsSmithMock;
+    type IsWoTMember = IsWoTMemberMock;
+    type ManagerOrigin = EnsureRoot<u64>;
+    type MaxMembers = ConstU32<3>;
+    type MembershipChanged = CollectiveMock;
+    type PrimeRotationPeriod = ConstU64<4>;
+    type RequireSmith = ConstBool<true>;
+    type TermDuration = ConstU64<10>;
+    type WeightInfo = ();
+}
+
+pub fn new_test_ext(members: Vec<u64>) -> sp_io::TestExternalities {
+    // the collective genesis is built before the committee membership one
+    CollectiveMembers::set(members.clone());
+    CollectivePrime::set(None);
+    RuntimeGenesisConfig {
+        system: SystemConfig::default(),
+        committee_membership: pallet_committee_membership::GenesisConfig {
+            members: members.into_iter().map(|idty| (idty, idty)).collect(),
+        },
+    }
+    .build_storage()
+    .unwrap()
+    .into()
+}
+
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        CommitteeMembership::on_finalize(System::block_number());
+        System::on_finalize(System::block_number());
+        System::reset_events();
+        System::set_block_number(System::block_number() + 1);
+        System::on_initialize(System::block_number());
+        CommitteeMembership::on_initialize(System::block_number());
+    }
+}
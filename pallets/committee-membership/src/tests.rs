@@ -0,0 +1,222 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
+use sp_runtime::DispatchError;
+
+#[test]
+fn genesis_members_and_prime() {
+    new_test_ext(vec![1, 2]).execute_with(|| {
+        assert_eq!(Members::<Runtime>::count(), 2);
+        assert_eq!(
+            Members::<Runtime>::get(1),
+            Some(MemberInfo {
+                account: 1,
+                term_end: 10
+            })
+        );
+        assert_eq!(TermsEndingOn::<Runtime>::get(10), vec![1, 2]);
+        assert_eq!(Prime::<Runtime>::get(), Some(1));
+        assert_eq!(CollectivePrime::get(), Some(1));
+    });
+}
+
+#[test]
+fn add_member_checks_eligibility() {
+    new_test_ext(vec![1, 2]).execute_with(|| {
+        run_to_block(1);
+        assert_noop!(
+            CommitteeMembership::add_member(RuntimeOrigin::signed(1), 3),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            CommitteeMembership::add_member(RuntimeOrigin::root(), 1),
+            Error::<Runtime>::AlreadyMember
+        );
+        // 5 is a WoT member but not a smith
+        assert_noop!(
+            CommitteeMembership::add_member(RuntimeOrigin::root(), 5),
+            Error::<Runtime>::NotSmith
+        );
+        assert_noop!(
+            CommitteeMembership::add_member(RuntimeOrigin::root(), 6),
+            Error::<Runtime>::NotWoTMember
+        );
+
+        assert_ok!(CommitteeMembership::add_member(RuntimeOrigin::root(), 3));
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::MemberAdded {
+            idty_index: 3,
+            term_end: 11,
+        }));
+        assert_eq!(CollectiveMembers::get(), vec![1, 2, 3]);
+        // the prime is restored after the collective change
+        assert_eq!(CollectivePrime::get(), Some(1));
+
+        assert_noop!(
+            CommitteeMembership::add_member(RuntimeOrigin::root(), 4),
+            Error::<Runtime>::TooManyMembers
+        );
+    });
+}
+
+#[test]
+fn terms_end_unless_renewed() {
+    new_test_ext(vec![1, 2]).execute_with(|| {
+        run_to_block(5);
+        assert_ok!(CommitteeMembership::renew_term(RuntimeOrigin::root(), 1));
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::TermRenewed {
+            idty_index: 1,
+            term_end: 15,
+        }));
+        assert_noop!(
+            CommitteeMembership::renew_term(RuntimeOrigin::root(), 3),
+            Error::<Runtime>::NotMember
+        );
+
+        run_to_block(10);
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::MemberRemoved {
+            idty_index: 2,
+            reason: RemovalReason::TermEnded,
+        }));
+        assert!(Members::<Runtime>::contains_key(1));
+        assert_eq!(CollectiveMembers::get(), vec![1]);
+
+        run_to_block(15);
+        assert_eq!(Members::<Runtime>::count(), 0);
+        assert_eq!(CollectiveMembers::get(), Vec::<u64>::new());
+        assert_eq!(Prime::<Runtime>::get(), None);
+        assert_eq!(CollectivePrime::get(), None);
+    });
+}
+
+#[test]
+fn renewal_requires_eligibility() {
+    new_test_ext(vec![1, 2]).execute_with(|| {
+        run_to_block(1);
+        Smiths::set(vec![2, 3, 4]);
+        assert_noop!(
+            CommitteeMembership::renew_term(RuntimeOrigin::root(), 1),
+            Error::<Runtime>::NotSmith
+        );
+    });
+}
+
+#[test]
+fn members_are_removed_on_status_loss() {
+    new_test_ext(vec![1, 2, 3]).execute_with(|| {
+        run_to_block(1);
+        CommitteeMembership::on_removed_wot_member(2);
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::MemberRemoved {
+            idty_index: 2,
+            reason: RemovalReason::LostMembership,
+        }));
+        // not a committee member, nothing happens
+        CommitteeMembership::on_removed_wot_member(5);
+
+        // the prime is handed to the next member
+        CommitteeMembership::on_smith_removed(1);
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::MemberRemoved {
+            idty_index: 1,
+            reason: RemovalReason::LostSmithStatus,
+        }));
+        assert_eq!(Prime::<Runtime>::get(), Some(3));
+        assert_eq!(CollectiveMembers::get(), vec![3]);
+        assert_eq!(CollectivePrime::get(), Some(3));
+
+        assert_noop!(
+            CommitteeMembership::resign(RuntimeOrigin::signed(1)),
+            Error::<Runtime>::NotMember
+        );
+        assert_ok!(CommitteeMembership::resign(RuntimeOrigin::signed(3)));
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::MemberRemoved {
+            idty_index: 3,
+            reason: RemovalReason::Resigned,
+        }));
+        assert_eq!(Prime::<Runtime>::get(), None);
+    });
+}
+
+#[test]
+fn prime_rotates() {
+    new_test_ext(vec![1, 2, 3]).execute_with(|| {
+        run_to_block(4);
+        assert_eq!(Prime::<Runtime>::get(), Some(2));
+        assert_eq!(CollectivePrime::get(), Some(2));
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::PrimeChanged {
+            idty_index: Some(2),
+        }));
+        run_to_block(8);
+        assert_eq!(Prime::<Runtime>::get(), Some(3));
+
+        // the rotation wraps around
+        assert_ok!(CommitteeMembership::remove_member(RuntimeOrigin::root(), 3));
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(Event::MemberRemoved {
+            idty_index: 3,
+            reason: RemovalReason::Removed,
+        }));
+        assert_eq!(Prime::<Runtime>::get(), Some(1));
+        assert_eq!(CollectivePrime::get(), Some(1));
+        assert_ok!(CommitteeMembership::do_try_state());
+    });
+}
+
+#[test]
+fn owner_key_change_is_applied_to_the_collective() {
+    new_test_ext(vec![1, 2]).execute_with(|| {
+        run_to_block(1);
+        CommitteeMembership::on_owner_key_changed(1, 11);
+        assert_eq!(Members::<Runtime>::get(1).unwrap().account, 11);
+        assert_eq!(CollectiveMembers::get(), vec![2, 11]);
+        assert_eq!(CollectivePrime::get(), Some(11));
+
+        // not a committee member, nothing happens
+        CommitteeMembership::on_owner_key_changed(3, 13);
+        assert_eq!(CollectiveMembers::get(), vec![2, 11]);
+        assert_ok!(CommitteeMembership::do_try_state());
+    });
+}
+
+#[test]
+fn import_collective_members_migration() {
+    new_test_ext(vec![]).execute_with(|| {
+        run_to_block(3);
+        // the collective was managed before the pallet was added,
+        // 5 is not a smith and 6 is not a member of the WoT
+        CollectiveMembers::set(vec![3, 2, 5, 6]);
+
+        crate::migrations::ImportCollectiveMembers::<Runtime>::on_runtime_upgrade();
+        assert_eq!(Members::<Runtime>::count(), 2);
+        assert_eq!(
+            Members::<Runtime>::get(3),
+            Some(MemberInfo {
+                account: 3,
+                term_end: 13
+            })
+        );
+        assert_eq!(TermsEndingOn::<Runtime>::get(13), vec![3, 2]);
+        assert_eq!(Prime::<Runtime>::get(), Some(2));
+        // ineligible accounts are removed from the collective
+        assert_eq!(CollectiveMembers::get(), vec![2, 3]);
+        assert_eq!(CollectivePrime::get(), Some(2));
+
+        // the migration only imports into an empty committee
+        CollectiveMembers::set(vec![1, 2, 3]);
+        crate::migrations::ImportCollectiveMembers::<Runtime>::on_runtime_upgrade();
+        assert_eq!(Members::<Runtime>::count(), 2);
+    });
+}
@@ -0,0 +1,45 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Various basic types for use in the committee membership pallet.
+
+use codec::{Decode, Encode};
+use frame_support::pallet_prelude::*;
+use scale_info::TypeInfo;
+
+/// Committee member metadata attached to an identity.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MemberInfo<AccountId, BlockNumber> {
+    /// Account seating in the collective on behalf of the identity.
+    pub account: AccountId,
+    /// Block at which the term of the member ends.
+    pub term_end: BlockNumber,
+}
+
+/// Reasons for the removal of a committee member.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum RemovalReason {
+    /// The member was removed by the committee.
+    Removed,
+    /// The member resigned.
+    Resigned,
+    /// The term of the member ended without being renewed.
+    TermEnded,
+    /// The member lost its WoT membership.
+    LostMembership,
+    /// The member lost its smith status.
+    LostSmithStatus,
+}
@@ -0,0 +1,65 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::weights::Weight;
+
+/// Weight functions needed for pallet.
+pub trait WeightInfo {
+    fn add_member() -> Weight;
+    fn renew_term() -> Weight;
+    fn remove_member() -> Weight;
+    fn resign() -> Weight;
+    fn expire_terms(i: u32) -> Weight;
+    fn rotate_prime() -> Weight;
+    fn on_removed_wot_member() -> Weight;
+    fn on_removed_wot_member_empty() -> Weight;
+}
+
+impl WeightInfo for () {
+    fn add_member() -> Weight {
+        Weight::zero()
+    }
+
+    fn renew_term() -> Weight {
+        Weight::zero()
+    }
+
+    fn remove_member() -> Weight {
+        Weight::zero()
+    }
+
+    fn resign() -> Weight {
+        Weight::zero()
+    }
+
+    fn expire_terms(_i: u32) -> Weight {
+        Weight::zero()
+    }
+
+    fn rotate_prime() -> Weight {
+        Weight::zero()
+    }
+
+    fn on_removed_wot_member() -> Weight {
+        Weight::zero()
+    }
+
+    fn on_removed_wot_member_empty() -> Weight {
+        Weight::zero()
+    }
+}
@@ -21,7 +21,10 @@ use super::*;
 
 use codec::Encode;
 use frame_benchmarking::v2::*;
-use frame_support::traits::{Get, OnFinalize, OnInitialize, fungible::Mutate};
+use frame_support::{
+    BoundedVec,
+    traits::{Get, OnFinalize, OnInitialize, fungible::Mutate},
+};
 use frame_system::{RawOrigin, pallet_prelude::BlockNumberFor};
 use scale_info::prelude::vec;
 use sp_runtime::Perbill;
@@ -30,7 +33,7 @@ use crate::Pallet;
 
 #[benchmarks(
         where
-        T: pallet_balances::Config,
+        T: pallet_balances::Config + pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
 		BalanceOf<T>: From<u32>,
         BlockNumberFor<T>: From<u32>,
 )]
@@ -110,6 +113,39 @@ mod benchmarks {
         );
     }
 
+    #[benchmark]
+    fn cancel_distance_evaluation() -> Result<(), BenchmarkError> {
+        // Worst case: the last request of a full queue is cancelled.
+        // More than membership renewal to avoid antispam
+        frame_system::pallet::Pallet::<T>::set_block_number(500_000_000u32.into());
+        let idty = T::IdtyIndex::one();
+        let caller: T::AccountId = pallet_identity::Identities::<T>::get(idty)
+            .unwrap()
+            .owner_key;
+        let _ = T::Currency::set_balance(&caller, u32::MAX.into());
+        let queued = (1..T::MaxQueuedEvaluations::get())
+            .map(|j| (j + MAX_EVALUATIONS_PER_SESSION, caller.clone()))
+            .collect::<vec::Vec<_>>();
+        EvaluationQueue::<T>::put(BoundedVec::truncate_from(queued));
+        Pallet::<T>::request_distance_evaluation(RawOrigin::Signed(caller.clone()).into())?;
+        assert!(
+            Pallet::<T>::queue_position(idty) == Some(T::MaxQueuedEvaluations::get() - 1),
+            "Request not queued"
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller.clone()), idty);
+
+        assert_has_event::<T>(
+            Event::<T>::EvaluationCancelled {
+                idty_index: idty,
+                who: caller,
+            }
+            .into(),
+        );
+        Ok(())
+    }
+
     #[benchmark]
     fn update_evaluation(i: Linear<1, MAX_EVALUATIONS_PER_SESSION>) -> Result<(), BenchmarkError> {
         let digest_data = sp_consensus_babe::digests::PreDigest::SecondaryPlain(
@@ -281,6 +317,52 @@ mod benchmarks {
         Ok(())
     }
 
+    #[benchmark]
+    fn record_evaluator_deviation(i: Linear<1, MAX_EVALUATIONS_PER_SESSION>) {
+        // Worst case: a validator exceeding the tolerance with a full history of outliers is reported.
+        let evaluator: T::AccountId = pallet_session::Validators::<T>::get()
+            .first()
+            .cloned()
+            .unwrap_or_else(|| {
+                pallet_identity::Identities::<T>::get(T::IdtyIndex::one())
+                    .unwrap()
+                    .owner_key
+            });
+        let outlier = EvaluatorRecord {
+            period: 0,
+            deviation: Perbill::one(),
+        };
+        EvaluatorHistory::<T>::insert(
+            &evaluator,
+            BoundedVec::truncate_from(vec![outlier; T::MaxEvaluatorHistory::get() as usize]),
+        );
+        let distances = vec![Perbill::one(); i as usize];
+        let medians = vec![Some(Perbill::zero()); i as usize];
+
+        #[block]
+        {
+            Pallet::<T>::record_evaluator_deviation(evaluator, 1, &distances, &medians);
+        }
+    }
+
+    #[benchmark]
+    fn schedule_queued_evaluations(i: Linear<0, MAX_EVALUATIONS_PER_SESSION>) {
+        let caller: T::AccountId = pallet_identity::Identities::<T>::get(T::IdtyIndex::one())
+            .unwrap()
+            .owner_key;
+        let queued = (0..i.min(T::MaxQueuedEvaluations::get()))
+            .map(|j| (j, caller.clone()))
+            .collect::<vec::Vec<_>>();
+        EvaluationQueue::<T>::put(BoundedVec::truncate_from(queued));
+
+        #[block]
+        {
+            Pallet::<T>::schedule_queued_evaluations(0);
+        }
+
+        assert!(EvaluationQueue::<T>::get().is_empty());
+    }
+
     #[benchmark]
     fn on_finalize() {
         DidUpdate::<T>::set(true);
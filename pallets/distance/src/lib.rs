@@ -31,6 +31,10 @@
 //!
 //! Any account can request a distance evaluation for a given identity provided it has enough currency to reserve. In this case, the distance status is marked as pending, and in the next evaluation period, inherents can start to publish results.
 //!
+//! When the evaluation pool of the current period is full, the request waits in a bounded queue, with the currency already reserved. At the start of each evaluation period, the queued requests enter the new evaluation pool in order of arrival.
+//!
+//! A request can be cancelled by its requester while it is queued or in the pool collecting the requests of the current period, and the reserved currency is released. When an identity is revoked or removed, its pending request is cancelled if it is still queued or in the pool collecting the requests. A request already under evaluation keeps its reserved currency until the pool is evaluated, where it is released or slashed as usual.
+//!
 //! This is the process for publishing a result:
 //!
 //! 1. A local worker creates a file containing the computation result.
@@ -41,7 +45,7 @@
 //!
 //! At the start of each new evaluation period:
 //!
-//! 1. Old results set to expire at this period are removed.
+//! 1. Old results set to expire at this period are removed, and queued requests enter the emptied pool.
 //! 2. Results from the current pool (results from the previous period's pool) are processed, and for each identity:
 //!     - The median of the distance results for this identity is chosen.
 //!     - If the distance is acceptable, it is marked as valid.
@@ -61,10 +65,24 @@
 //! - Pool number N - 1 % 3: Results from the previous evaluation period used in the current one (emptied for the next evaluation period).
 //! - Pool number N + 0 % 3: Inherent results are added here.
 //! - Pool number N + 1 % 3: Identities are added here for evaluation.
+//!
+//! ## Evaluator Accountability
+//!
+//! The distances published by each evaluator are kept until their pool is evaluated. Then, the
+//! largest gap between the distances of an evaluator and the medians is recorded in a bounded
+//! history of deviations per evaluator. An evaluator with a history which authors a block without
+//! publishing the results of the period is recorded with a full deviation when the pool is
+//! evaluated, unless it publishes them in another block. Authorities which run no oracle have no
+//! history, and authorities which author no block are not recorded. An evaluator whose deviation
+//! exceeds `OutlierTolerance` in more than `MaxOutlierPeriods` pools of its history is reported as
+//! an offence through the `offences` pallet, which usually reveals a misconfigured or stopped
+//! distance oracle. The history is exposed in the `DistanceApi` runtime API, and dropped when the
+//! evaluator leaves the authorities.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod median;
+mod runtime_api;
 pub mod traits;
 mod types;
 mod weights;
@@ -77,27 +95,45 @@ mod mock;
 mod tests;
 
 pub use pallet::*;
+pub use runtime_api::*;
 pub use traits::*;
 pub use types::*;
 pub use weights::WeightInfo;
 
 use frame_support::{
     DefaultNoBound,
+    pallet_prelude::Weight,
     traits::{
-        OnUnbalanced, StorageVersion,
+        FindAuthor, OnUnbalanced, StorageVersion, ValidatorSet, ValidatorSetWithIdentification,
         fungible::{self, Credit, Mutate, MutateHold, hold},
         tokens::Precision,
     },
 };
+use scale_info::prelude::vec;
 use sp_distance::{INHERENT_IDENTIFIER, InherentError};
 use sp_inherents::{InherentData, InherentIdentifier};
 use sp_runtime::{
     Saturating,
-    traits::{One, Zero},
+    traits::{Convert, One, Zero},
 };
+use sp_staking::offence::ReportOffence;
 
 type IdtyIndex = u32;
 
+/// A type for representing the validator id in a session.
+pub type ValidatorId<T> = <<T as Config>::ValidatorSet as ValidatorSet<
+    <T as frame_system::Config>::AccountId,
+>>::ValidatorId;
+
+/// A tuple of (ValidatorId, Identification) where `Identification` is the full identification of
+/// `ValidatorId`.
+pub type IdentificationTuple<T> = (
+    ValidatorId<T>,
+    <<T as Config>::ValidatorSet as ValidatorSetWithIdentification<
+        <T as frame_system::Config>::AccountId,
+    >>::Identification,
+);
+
 /// Maximum number of identities to be evaluated in an evaluation period.
 pub const MAX_EVALUATIONS_PER_SESSION: u32 = 1_300; // See https://git.duniter.org/nodes/rust/duniter-v2s/-/merge_requests/252
 /// Maximum number of evaluators in an evaluation period.
@@ -169,6 +205,33 @@ pub mod pallet {
 
         /// A trait that provides a method to check if a distance evaluation request is allowed.
         type CheckRequestDistanceEvaluation: CheckRequestDistanceEvaluation<Self>;
+
+        /// The maximum number of distance evaluation requests waiting for room in an evaluation pool.
+        #[pallet::constant]
+        type MaxQueuedEvaluations: Get<u32>;
+
+        /// The maximum number of deviations kept in the history of an evaluator.
+        #[pallet::constant]
+        type MaxEvaluatorHistory: Get<u32>;
+
+        /// The maximum number of pools in the history of an evaluator where its deviation from
+        /// the median exceeds the tolerance before it is reported as an outlier.
+        #[pallet::constant]
+        type MaxOutlierPeriods: Get<u32>;
+
+        /// The maximum deviation from the median tolerated for the results of an evaluator.
+        #[pallet::constant]
+        type OutlierTolerance: Get<Perbill>;
+
+        /// A type that gives us the ability to identify the evaluators in the validator set.
+        type ValidatorSet: ValidatorSetWithIdentification<Self::AccountId, ValidatorId = Self::AccountId>;
+
+        /// A type that gives us the ability to report outlier evaluators.
+        type ReportOutlier: ReportOffence<
+                Self::AccountId,
+                IdentificationTuple<Self>,
+                OutlierOffence<IdentificationTuple<Self>>,
+            >;
     }
 
     // STORAGE //
@@ -228,6 +291,20 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// The distance evaluation requests waiting for room in an evaluation pool, oldest first.
+    #[pallet::storage]
+    pub type EvaluationQueue<T: Config> = StorageValue<
+        _,
+        BoundedVec<
+            (
+                <T as pallet_identity::Config>::IdtyIndex,
+                <T as frame_system::Config>::AccountId,
+            ),
+            T::MaxQueuedEvaluations,
+        >,
+        ValueQuery,
+    >;
+
     /// Store if the evaluation was updated in this block.
     #[pallet::storage]
     pub(super) type DidUpdate<T: Config> = StorageValue<_, bool, ValueQuery>;
@@ -237,6 +314,47 @@ pub mod pallet {
     #[pallet::getter(fn current_period_index)]
     pub(super) type CurrentPeriodIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+    /// The distances published by each evaluator, by pool number, until the pool is evaluated.
+    #[pallet::storage]
+    pub(super) type EvaluatorResults<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,
+        Twox64Concat,
+        <T as frame_system::Config>::AccountId,
+        BoundedVec<Perbill, ConstU32<MAX_EVALUATIONS_PER_SESSION>>,
+        OptionQuery,
+    >;
+
+    /// The evaluators which authored a block without publishing a result, by pool number, until
+    /// the pool is evaluated.
+    #[pallet::storage]
+    pub(super) type SilentEvaluators<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u32,
+        BoundedVec<<T as frame_system::Config>::AccountId, ConstU32<MAX_EVALUATORS_PER_SESSION>>,
+        ValueQuery,
+    >;
+
+    /// The authorities at the start of the current evaluation period.
+    #[pallet::storage]
+    pub(super) type PeriodAuthorities<T: Config> = StorageValue<
+        _,
+        BoundedVec<<T as frame_system::Config>::AccountId, ConstU32<MAX_EVALUATORS_PER_SESSION>>,
+        ValueQuery,
+    >;
+
+    /// The deviations of the last evaluated pools of each evaluator, oldest first.
+    #[pallet::storage]
+    pub type EvaluatorHistory<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        <T as frame_system::Config>::AccountId,
+        BoundedVec<EvaluatorRecord, T::MaxEvaluatorHistory>,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -260,6 +378,20 @@ pub mod pallet {
             idty_index: T::IdtyIndex,
             who: T::AccountId,
         },
+        /// A distance evaluation was requested while the evaluation pool was full, and queued at
+        /// the given position (starting at 0).
+        EvaluationQueued {
+            idty_index: T::IdtyIndex,
+            who: T::AccountId,
+            position: u32,
+        },
+        /// A pending distance evaluation request was cancelled.
+        EvaluationCancelled {
+            idty_index: T::IdtyIndex,
+            who: T::AccountId,
+        },
+        /// An evaluator deviated too often from the median and was reported.
+        OutlierReported { who: T::AccountId, outliers: u32 },
     }
 
     // ERRORS //
@@ -284,7 +416,7 @@ pub mod pallet {
         CallerStatusInvalid,
         /// Target identity not found.
         TargetIdentityNotFound,
-        /// Evaluation queue is full.
+        /// Evaluation pool and queue are full.
         QueueFull,
         /// Too many evaluators in the current evaluation pool.
         TooManyEvaluators,
@@ -292,6 +424,10 @@ pub mod pallet {
         WrongResultLength,
         /// Targeted distance evaluation request is only possible for an unvalidated identity.
         TargetMustBeUnvalidated,
+        /// The distance evaluation request is neither queued nor in the current request pool.
+        NotCancellable,
+        /// Only the requester can cancel a distance evaluation request.
+        NotRequester,
     }
 
     #[pallet::genesis_config]
@@ -321,13 +457,20 @@ pub mod pallet {
                 CurrentPeriodIndex::<T>::put(index);
                 weight = weight
                     .saturating_add(Self::do_evaluation(index % 3))
+                    .saturating_add(Self::schedule_queued_evaluations(index % 3))
                     .saturating_add(T::DbWeight::get().reads_writes(1, 1));
+                weight = weight.saturating_add(Self::rotate_period_authorities());
             }
-            weight.saturating_add(<T as pallet::Config>::WeightInfo::on_finalize())
+            weight
+                .saturating_add(<T as pallet::Config>::WeightInfo::on_finalize())
+                // silent author in `on_finalize`
+                .saturating_add(T::DbWeight::get().reads_writes(4, 1))
         }
 
         fn on_finalize(_n: BlockNumberFor<T>) {
-            DidUpdate::<T>::take();
+            if !DidUpdate::<T>::take() {
+                Self::note_silent_author();
+            }
         }
     }
 
@@ -393,6 +536,26 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Cancel a distance evaluation request.
+        ///
+        /// Only the requester can cancel the request, while it is queued or in the pool
+        /// collecting the requests of the current period. The reserved currency is released.
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_distance_evaluation())]
+        pub fn cancel_distance_evaluation(
+            origin: OriginFor<T>,
+            target: T::IdtyIndex,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let requester =
+                PendingEvaluationRequest::<T>::get(target).ok_or(Error::<T>::NotCancellable)?;
+            ensure!(requester == who, Error::<T>::NotRequester);
+            ensure!(Self::withdraw_request(target), Error::<T>::NotCancellable);
+            Self::do_cancel_request(target);
+            Ok(())
+        }
+
         /// Force push an evaluation result to the pool.
         ///
         /// It is primarily used for testing purposes.
@@ -555,37 +718,197 @@ pub mod pallet {
             T::CheckRequestDistanceEvaluation::check_request_distance_evaluation(target)
         }
 
-        /// Request distance evaluation in the current pool.
+        /// Request distance evaluation in the current pool, or in the queue when the pool is full.
         fn do_request_distance_evaluation(
             who: &T::AccountId,
             idty_index: <T as pallet_identity::Config>::IdtyIndex,
         ) -> Result<(), DispatchError> {
-            Pallet::<T>::mutate_current_pool(CurrentPeriodIndex::<T>::get() % 3, |current_pool| {
-                // extrinsics are transactional by default, this check might not be needed
-                ensure!(
-                    current_pool.evaluations.len() < (MAX_EVALUATIONS_PER_SESSION as usize),
-                    Error::<T>::QueueFull
+            T::Currency::hold(
+                &HoldReason::DistanceHold.into(),
+                who,
+                <T as Config>::EvaluationPrice::get(),
+            )?;
+
+            // requests do not overtake the queued ones
+            let in_pool = EvaluationQueue::<T>::decode_len().unwrap_or_default() == 0
+                && Pallet::<T>::mutate_current_pool(
+                    CurrentPeriodIndex::<T>::get() % 3,
+                    |current_pool| {
+                        current_pool
+                            .evaluations
+                            .try_push((idty_index, median::MedianAcc::new()))
+                            .is_ok()
+                    },
                 );
 
-                T::Currency::hold(
-                    &HoldReason::DistanceHold.into(),
-                    who,
-                    <T as Config>::EvaluationPrice::get(),
-                )?;
-
-                current_pool
-                    .evaluations
-                    .try_push((idty_index, median::MedianAcc::new()))
-                    .map_err(|_| Error::<T>::QueueFull)?;
-
-                PendingEvaluationRequest::<T>::insert(idty_index, who);
-
+            if in_pool {
                 Self::deposit_event(Event::EvaluationRequested {
                     idty_index,
                     who: who.clone(),
                 });
-                Ok(())
-            })
+            } else {
+                let position = EvaluationQueue::<T>::try_mutate(|queue| {
+                    queue
+                        .try_push((idty_index, who.clone()))
+                        .map_err(|_| Error::<T>::QueueFull)?;
+                    Ok::<u32, Error<T>>(queue.len() as u32 - 1)
+                })?;
+                Self::deposit_event(Event::EvaluationQueued {
+                    idty_index,
+                    who: who.clone(),
+                    position,
+                });
+            }
+
+            PendingEvaluationRequest::<T>::insert(idty_index, who);
+            Ok(())
+        }
+
+        /// Remove the distance evaluation request of an identity from the queue or from the
+        /// current pool.
+        ///
+        /// Returns `false` if the request is not found, in which case it may already wait for
+        /// results.
+        fn withdraw_request(idty_index: <T as pallet_identity::Config>::IdtyIndex) -> bool {
+            let queued = EvaluationQueue::<T>::mutate(|queue| {
+                queue
+                    .iter()
+                    .position(|(queued, _)| *queued == idty_index)
+                    .map(|position| queue.remove(position))
+                    .is_some()
+            });
+            queued
+                || Pallet::<T>::mutate_current_pool(
+                    CurrentPeriodIndex::<T>::get() % 3,
+                    |current_pool| {
+                        current_pool
+                            .evaluations
+                            .iter()
+                            .position(|(idty, _)| *idty == idty_index)
+                            .map(|position| current_pool.evaluations.remove(position))
+                            .is_some()
+                    },
+                )
+        }
+
+        /// Forget the pending distance evaluation request of an identity and release the
+        /// reserved currency.
+        fn do_cancel_request(idty_index: <T as pallet_identity::Config>::IdtyIndex) {
+            let Some(requester) = PendingEvaluationRequest::<T>::take(idty_index) else {
+                return;
+            };
+            let _ = T::Currency::release(
+                &HoldReason::DistanceHold.into(),
+                &requester,
+                <T as Config>::EvaluationPrice::get(),
+                Precision::Exact,
+            );
+            Self::deposit_event(Event::EvaluationCancelled {
+                idty_index,
+                who: requester,
+            });
+        }
+
+        /// Cancel the pending distance evaluation request of an identity if it is queued or in
+        /// the current request pool.
+        ///
+        /// A request under evaluation cannot be removed from its pool without shifting the results
+        /// of the evaluators: it keeps its reserved currency until the pool is evaluated.
+        pub fn cancel_pending_evaluation(
+            idty_index: <T as pallet_identity::Config>::IdtyIndex,
+        ) -> Weight {
+            if !PendingEvaluationRequest::<T>::contains_key(idty_index) {
+                return T::DbWeight::get().reads(1);
+            }
+            if Self::withdraw_request(idty_index) {
+                Self::do_cancel_request(idty_index);
+            }
+            <T as pallet::Config>::WeightInfo::cancel_distance_evaluation()
+        }
+
+        /// Move the queued requests into the current pool of a new period, oldest first.
+        pub(crate) fn schedule_queued_evaluations(index: u32) -> Weight {
+            if EvaluationQueue::<T>::decode_len().unwrap_or_default() == 0 {
+                return <T as pallet::Config>::WeightInfo::schedule_queued_evaluations(0);
+            }
+
+            let mut queue = EvaluationQueue::<T>::take().into_inner();
+            let scheduled = Pallet::<T>::mutate_current_pool(index, |current_pool| {
+                let room = (MAX_EVALUATIONS_PER_SESSION as usize)
+                    .saturating_sub(current_pool.evaluations.len())
+                    .min(queue.len());
+                for (idty_index, who) in queue.drain(..room) {
+                    // room was checked above
+                    let _ = current_pool
+                        .evaluations
+                        .try_push((idty_index, median::MedianAcc::new()));
+                    Self::deposit_event(Event::EvaluationRequested { idty_index, who });
+                }
+                room as u32
+            });
+            EvaluationQueue::<T>::put(BoundedVec::truncate_from(queue));
+
+            <T as pallet::Config>::WeightInfo::schedule_queued_evaluations(scheduled)
+        }
+
+        /// Get the position of the queued distance evaluation request of an identity.
+        pub fn queue_position(
+            idty_index: <T as pallet_identity::Config>::IdtyIndex,
+        ) -> Option<u32> {
+            EvaluationQueue::<T>::get()
+                .iter()
+                .position(|(queued, _)| *queued == idty_index)
+                .map(|position| position as u32)
+        }
+
+        /// Get the fill level of the evaluation pools and the estimated evaluation block of a
+        /// new distance evaluation request.
+        pub fn evaluation_pools() -> EvaluationPoolsInfo<BlockNumberFor<T>> {
+            let index = CurrentPeriodIndex::<T>::get() % 3;
+            let fill = |pool_number: u32| {
+                let pool = match pool_number {
+                    0 => EvaluationPool0::<T>::get(),
+                    1 => EvaluationPool1::<T>::get(),
+                    2 => EvaluationPool2::<T>::get(),
+                    _ => unreachable!("pool_number < 3"),
+                };
+                PoolFill {
+                    evaluations: pool.evaluations.len() as u32,
+                    evaluators: pool.evaluators.len() as u32,
+                }
+            };
+            // see mutate_current_pool and mutate_next_pool
+            let requests = fill((index + 2) % 3);
+            let pending = fill((index + 1) % 3);
+            let results = fill(index);
+            let queued = EvaluationQueue::<T>::decode_len().unwrap_or_default() as u32;
+            let queue_capacity = T::MaxQueuedEvaluations::get();
+
+            // number of period starts before a new request enters a pool
+            let delay = if queued == 0 && requests.evaluations < MAX_EVALUATIONS_PER_SESSION {
+                Some(0)
+            } else if queued < queue_capacity {
+                Some(1 + queued / MAX_EVALUATIONS_PER_SESSION)
+            } else {
+                None
+            };
+            let period: BlockNumberFor<T> = T::EvaluationPeriod::get().into();
+            let now = frame_system::Pallet::<T>::block_number();
+            let next_period_start = now - now % period + period;
+            // a request is evaluated at the start of the third period after entering a pool
+            let next_request_evaluation = delay.map(|delay| {
+                next_period_start.saturating_add(period.saturating_mul((delay + 2).into()))
+            });
+
+            EvaluationPoolsInfo {
+                requests,
+                pending,
+                results,
+                capacity: MAX_EVALUATIONS_PER_SESSION,
+                queued,
+                queue_capacity,
+                next_request_evaluation,
+            }
         }
 
         /// Update distance evaluation in the next pool.
@@ -593,7 +916,8 @@ pub mod pallet {
             evaluator: <T as frame_system::Config>::AccountId,
             computation_result: ComputationResult,
         ) -> DispatchResult {
-            Pallet::<T>::mutate_next_pool(CurrentPeriodIndex::<T>::get() % 3, |result_pool| {
+            let index = CurrentPeriodIndex::<T>::get() % 3;
+            Pallet::<T>::mutate_next_pool(index, |result_pool| {
                 // evaluation must be provided for all identities (no more, no less)
                 ensure!(
                     computation_result.distances.len() == result_pool.evaluations.len(),
@@ -609,11 +933,17 @@ pub mod pallet {
                     // update the median accumulator with the new result
                     for (distance_value, (_identity, median_acc)) in computation_result
                         .distances
-                        .into_iter()
+                        .iter()
                         .zip(result_pool.evaluations.iter_mut())
                     {
-                        median_acc.push(distance_value);
+                        median_acc.push(*distance_value);
                     }
+                    // keep the result to compare it with the medians
+                    EvaluatorResults::<T>::insert(
+                        index,
+                        evaluator,
+                        BoundedVec::truncate_from(computation_result.distances),
+                    );
                     Ok(())
                 } else {
                     // one author can only submit one evaluation
@@ -655,6 +985,7 @@ pub mod pallet {
                 <T as frame_system::Config>::AccountId,
                 <T as pallet_identity::Config>::IdtyIndex,
             > = Pallet::<T>::take_current_pool(index);
+            let mut medians = vec::Vec::with_capacity(current_pool.evaluations.len());
 
             for (idty, median_acc) in current_pool.evaluations.into_iter() {
                 let mut distance_result: Option<Perbill> = None;
@@ -667,6 +998,7 @@ pub mod pallet {
                     // Update distance result
                     distance_result = Some(distance);
                 }
+                medians.push(distance_result);
 
                 // If there's a pending evaluation request with the provided identity
                 if let Some(requester) = PendingEvaluationRequest::<T>::take(idty) {
@@ -728,8 +1060,181 @@ pub mod pallet {
                 }
                 // If evaluation happened without request, it's ok to do nothing
             }
+
+            // The current pool was the result pool of the previous period
+            let period = CurrentPeriodIndex::<T>::get();
+            let mut silent_evaluators = SilentEvaluators::<T>::take((index + 2) % 3).into_inner();
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+            for (evaluator, distances) in EvaluatorResults::<T>::drain_prefix((index + 2) % 3) {
+                silent_evaluators.retain(|silent| silent != &evaluator);
+                Self::record_evaluator_deviation(evaluator, period, &distances, &medians);
+                weight = weight.saturating_add(
+                    <T as pallet::Config>::WeightInfo::record_evaluator_deviation(
+                        distances.len() as u32
+                    ),
+                );
+            }
+            // an evaluator which authored blocks without publishing its results fully deviates
+            if !medians.is_empty() {
+                for evaluator in silent_evaluators {
+                    Self::record_deviation(evaluator, period, Perbill::one());
+                    weight = weight.saturating_add(
+                        <T as pallet::Config>::WeightInfo::record_evaluator_deviation(0),
+                    );
+                }
+            }
             weight
         }
+
+        /// Note the author of a block without distance result if it is a known evaluator.
+        ///
+        /// Only the authorities which published results before have a history: the authorities
+        /// running no oracle are never noted.
+        fn note_silent_author() {
+            // `pallet_authorship` forgets the author when the block is finalized
+            let digest = frame_system::Pallet::<T>::digest();
+            let Some(author) = <T as pallet_authorship::Config>::FindAuthor::find_author(
+                digest.logs.iter().filter_map(|log| log.as_pre_runtime()),
+            ) else {
+                return;
+            };
+            if !EvaluatorHistory::<T>::contains_key(&author) {
+                return;
+            }
+            SilentEvaluators::<T>::mutate(CurrentPeriodIndex::<T>::get() % 3, |silent| {
+                if !silent.contains(&author) {
+                    // bounded by the number of evaluators of a pool
+                    let _ = silent.try_push(author);
+                }
+            });
+        }
+
+        /// Keep the authorities of the new evaluation period, and drop the history of the
+        /// evaluators which left the authorities.
+        fn rotate_period_authorities() -> Weight {
+            let authorities = BoundedVec::<_, ConstU32<MAX_EVALUATORS_PER_SESSION>>::truncate_from(
+                T::ValidatorSet::validators(),
+            );
+            let mut removed = 0;
+            for previous in PeriodAuthorities::<T>::get() {
+                if !authorities.contains(&previous) {
+                    EvaluatorHistory::<T>::remove(&previous);
+                    removed += 1;
+                }
+            }
+            PeriodAuthorities::<T>::put(authorities);
+            T::DbWeight::get().reads_writes(2, 1 + removed)
+        }
+
+        /// Record the deviation of the distances published by an evaluator from the medians.
+        pub(crate) fn record_evaluator_deviation(
+            evaluator: <T as frame_system::Config>::AccountId,
+            period: u32,
+            distances: &[Perbill],
+            medians: &[Option<Perbill>],
+        ) {
+            let deviation = distances
+                .iter()
+                .zip(medians)
+                .filter_map(|(distance, median)| {
+                    median.map(|median| {
+                        if *distance > median {
+                            distance.saturating_sub(median)
+                        } else {
+                            median.saturating_sub(*distance)
+                        }
+                    })
+                })
+                .max()
+                .unwrap_or_else(Perbill::zero);
+            Self::record_deviation(evaluator, period, deviation);
+        }
+
+        /// Record the deviation of an evaluator in a pool, and report it as an outlier when its
+        /// deviation exceeded the tolerance in more than `MaxOutlierPeriods` pools of its history.
+        fn record_deviation(
+            evaluator: <T as frame_system::Config>::AccountId,
+            period: u32,
+            deviation: Perbill,
+        ) {
+            let outliers = EvaluatorHistory::<T>::mutate(&evaluator, |history| {
+                // drop the oldest record when the history is full
+                let len = history.len();
+                let _ = history.force_insert_keep_right(len, EvaluatorRecord { period, deviation });
+                Self::count_outliers(history)
+            });
+
+            if outliers > T::MaxOutlierPeriods::get() {
+                Self::report_outlier(evaluator, period, outliers);
+            }
+        }
+
+        /// Count the records of a history where the deviation exceeds the tolerance.
+        fn count_outliers(history: &[EvaluatorRecord]) -> u32 {
+            let tolerance = T::OutlierTolerance::get();
+            history
+                .iter()
+                .filter(|record| record.deviation > tolerance)
+                .count() as u32
+        }
+
+        /// Report an outlier evaluator as an offence.
+        fn report_outlier(
+            evaluator: <T as frame_system::Config>::AccountId,
+            period: u32,
+            outliers: u32,
+        ) {
+            // only validators can be reported, other evaluators are forced by root
+            let Some(offender) = <T::ValidatorSet as ValidatorSet<_>>::ValidatorIdOf::convert(
+                evaluator.clone(),
+            )
+            .and_then(|id| {
+                <T::ValidatorSet as ValidatorSetWithIdentification<_>>::IdentificationOf::convert(
+                    id.clone(),
+                )
+                .map(|full_id| (id, full_id))
+            }) else {
+                return;
+            };
+
+            let offence = OutlierOffence {
+                period,
+                session_index: T::ValidatorSet::session_index(),
+                validator_set_count: T::ValidatorSet::validators().len() as u32,
+                offenders: vec![offender],
+            };
+            if let Err(e) = T::ReportOutlier::report_offence(vec![], offence) {
+                sp_runtime::print(e);
+            }
+
+            // start a new history, the evaluator has to fix its oracle
+            EvaluatorHistory::<T>::remove(&evaluator);
+            Self::deposit_event(Event::OutlierReported {
+                who: evaluator,
+                outliers,
+            });
+        }
+
+        /// Get the statistics of the distance results published by an evaluator.
+        pub fn evaluator_stats(
+            evaluator: &<T as frame_system::Config>::AccountId,
+        ) -> EvaluatorStats {
+            let history = EvaluatorHistory::<T>::get(evaluator);
+            EvaluatorStats {
+                outliers: Self::count_outliers(&history),
+                history: history.into_inner(),
+                tolerance: T::OutlierTolerance::get(),
+                max_outlier_periods: T::MaxOutlierPeriods::get(),
+            }
+        }
+
+        /// Get the parameters of the distance rule.
+        pub fn distance_rule_parameters() -> DistanceRuleParameters {
+            DistanceRuleParameters {
+                max_referee_distance: T::MaxRefereeDistance::get(),
+                min_accessible_referees: T::MinAccessibleReferees::get(),
+            }
+        }
     }
 
     #[pallet::inherent]
@@ -17,7 +17,9 @@
 use crate::{self as pallet_distance};
 use core::marker::PhantomData;
 use frame_support::{
-    derive_impl, parameter_types,
+    derive_impl,
+    pallet_prelude::Weight,
+    parameter_types,
     traits::{Everything, OnFinalize, OnInitialize},
 };
 use frame_system as system;
@@ -30,6 +32,7 @@ use sp_runtime::{
     testing::{TestSignature as SubtrateTestSignature, UintAuthorityId},
     traits::{BlakeTwo256, ConvertInto, IdentityLookup, IsMember, OpaqueKeys},
 };
+use sp_staking::offence::{OffenceError, ReportOffence};
 
 type Balance = u64;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -221,6 +224,17 @@ impl pallet_authority_members::Config for Test {
     type WeightInfo = ();
 }
 
+pub struct OnRemoveIdtyHandler;
+impl pallet_identity::traits::OnRemoveIdty<Test> for OnRemoveIdtyHandler {
+    fn on_removed(idty_index: &u32) -> Weight {
+        Distance::cancel_pending_evaluation(*idty_index)
+    }
+
+    fn on_revoked(idty_index: &u32) -> Weight {
+        Distance::cancel_pending_evaluation(*idty_index)
+    }
+}
+
 parameter_types! {
     pub const ExistentialDeposit: Balance = 10;
     pub const MaxLocks: u32 = 50;
@@ -274,26 +288,60 @@ impl pallet_identity::Config for Test {
     type IdtyNameValidator = IdtyNameValidatorTestImpl;
     type OnKeyChange = ();
     type OnNewIdty = ();
-    type OnRemoveIdty = ();
+    type OnRemoveIdty = OnRemoveIdtyHandler;
     type Signature = TestSignature;
     type Signer = UintAuthorityId;
     type ValidationPeriod = ValidationPeriod;
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub static ReportedOffences: Vec<(Vec<AccountId>, OutlierOffence)> = vec![];
+}
+
+type OutlierOffence = pallet_distance::OutlierOffence<pallet_distance::IdentificationTuple<Test>>;
+
+/// A mock offence report handler.
+pub struct OffenceHandler;
+impl ReportOffence<AccountId, pallet_distance::IdentificationTuple<Test>, OutlierOffence>
+    for OffenceHandler
+{
+    fn report_offence(
+        reporters: Vec<AccountId>,
+        offence: OutlierOffence,
+    ) -> Result<(), OffenceError> {
+        ReportedOffences::mutate(|l| l.push((reporters, offence)));
+        Ok(())
+    }
+
+    fn is_known_offence(
+        _offenders: &[pallet_distance::IdentificationTuple<Test>],
+        _time_slot: &u32,
+    ) -> bool {
+        false
+    }
+}
+
 parameter_types! {
     pub const MinAccessibleReferees: Perbill = Perbill::from_percent(80);
+    pub const OutlierTolerance: Perbill = Perbill::from_percent(5);
 }
 impl pallet_distance::Config for Test {
     type CheckRequestDistanceEvaluation = ();
     type Currency = Balances;
     type EvaluationPeriod = frame_support::traits::ConstU32<4>;
     type EvaluationPrice = frame_support::traits::ConstU64<1000>;
+    type MaxEvaluatorHistory = frame_support::traits::ConstU32<4>;
+    type MaxOutlierPeriods = frame_support::traits::ConstU32<2>;
+    type MaxQueuedEvaluations = frame_support::traits::ConstU32<2>;
     type MaxRefereeDistance = frame_support::traits::ConstU32<5>;
     type MinAccessibleReferees = MinAccessibleReferees;
     type OnUnbalanced = ();
     type OnValidDistanceStatus = ();
+    type OutlierTolerance = OutlierTolerance;
+    type ReportOutlier = OffenceHandler;
     type RuntimeHoldReason = RuntimeHoldReason;
+    type ValidatorSet = Historical;
     type WeightInfo = ();
 }
 
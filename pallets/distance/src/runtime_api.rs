@@ -0,0 +1,97 @@
+// Copyright 2022 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::EvaluatorRecord;
+use codec::{Codec, Decode, Encode};
+use scale_info::{TypeInfo, prelude::vec::Vec};
+use sp_runtime::{Perbill, RuntimeDebug};
+
+sp_api::decl_runtime_apis! {
+    /// Runtime API for Distance pallet
+    pub trait DistanceApi<AccountId, BlockNumber>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// Get the statistics of the distance results published by an evaluator
+        ///
+        /// Smiths can check whether the results of their distance oracle agree with the
+        /// results of the other evaluators.
+        fn evaluator_stats(evaluator: AccountId) -> EvaluatorStats;
+
+        /// Get the fill level of the evaluation pools and the estimated evaluation block of a
+        /// new distance evaluation request
+        fn evaluation_pools() -> EvaluationPoolsInfo<BlockNumber>;
+
+        /// Get the position of the queued distance evaluation request of an identity, starting at 0
+        fn queue_position(idty_index: u32) -> Option<u32>;
+
+        /// Get the parameters of the distance rule
+        ///
+        /// Nodes need them to compute the distance of an identity from their own state.
+        fn distance_rule_parameters() -> DistanceRuleParameters;
+    }
+}
+
+/// Statistics of the distance results published by an evaluator
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, RuntimeDebug)]
+pub struct EvaluatorStats {
+    /// Deviations from the medians of the last evaluated pools, oldest first.
+    pub history: Vec<EvaluatorRecord>,
+    /// Number of pools in the history where the deviation exceeds the tolerance.
+    pub outliers: u32,
+    /// Deviation from the median tolerated.
+    pub tolerance: Perbill,
+    /// Number of outlier pools in the history above which the evaluator is reported.
+    pub max_outlier_periods: u32,
+}
+
+/// Fill level of an evaluation pool
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, RuntimeDebug)]
+pub struct PoolFill {
+    /// Number of identities to evaluate.
+    pub evaluations: u32,
+    /// Number of evaluators who published a result.
+    pub evaluators: u32,
+}
+
+/// Fill level of the evaluation pools and of the request queue
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, RuntimeDebug)]
+pub struct EvaluationPoolsInfo<BlockNumber> {
+    /// Pool receiving the distance evaluation requests of the current period.
+    pub requests: PoolFill,
+    /// Pool holding the requests of the previous period, evaluated in the next period.
+    pub pending: PoolFill,
+    /// Pool receiving the results of the evaluators in the current period.
+    pub results: PoolFill,
+    /// Maximum number of identities in an evaluation pool.
+    pub capacity: u32,
+    /// Number of requests waiting for room in an evaluation pool.
+    pub queued: u32,
+    /// Maximum number of requests in the queue.
+    pub queue_capacity: u32,
+    /// Estimated block at which a new request would be evaluated, `None` if the queue is full.
+    pub next_request_evaluation: Option<BlockNumber>,
+}
+
+/// Parameters of the distance rule
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, RuntimeDebug)]
+pub struct DistanceRuleParameters {
+    /// Maximum number of certification steps from a referee.
+    pub max_referee_distance: u32,
+    /// Minimum fraction of accessible referees to pass the distance rule.
+    pub min_accessible_referees: Perbill,
+}
@@ -115,3 +115,424 @@ fn test_request_distance_evaluation_twice() {
         );
     });
 }
+
+// publish results for identity 1, evaluated in the third period after the request
+fn evaluate_with(results: &[(AccountId, Perbill)]) {
+    let block = System::block_number();
+    assert_ok!(Distance::request_distance_evaluation(
+        RuntimeOrigin::signed(1)
+    ));
+    run_to_block(block + 7);
+    for (evaluator, distance) in results {
+        assert_ok!(Distance::force_update_evaluation(
+            RuntimeOrigin::root(),
+            *evaluator,
+            ComputationResult {
+                distances: vec![*distance],
+            },
+        ));
+    }
+    run_to_block(block + 12);
+}
+
+// the deviation of each evaluator from the median is recorded
+#[test]
+fn test_evaluator_deviation_recorded() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Balances::set_balance(&1, 10_000);
+
+        evaluate_with(&[
+            (1, Perbill::one()),
+            (2, Perbill::one()),
+            (3, Perbill::from_percent(50)),
+        ]);
+        System::assert_has_event(RuntimeEvent::Distance(Event::EvaluatedValid {
+            idty_index: 1,
+            distance: Perbill::one(),
+        }));
+
+        assert_eq!(
+            EvaluatorHistory::<Test>::get(1).into_inner(),
+            vec![EvaluatorRecord {
+                period: 3,
+                deviation: Perbill::zero(),
+            }]
+        );
+        assert_eq!(
+            Distance::evaluator_stats(&3),
+            EvaluatorStats {
+                history: vec![EvaluatorRecord {
+                    period: 3,
+                    deviation: Perbill::from_percent(50),
+                }],
+                outliers: 1,
+                tolerance: Perbill::from_percent(5),
+                max_outlier_periods: 2,
+            }
+        );
+        // the results are dropped once evaluated
+        assert_eq!(EvaluatorResults::<Test>::iter().count(), 0);
+    });
+}
+
+// an evaluator deviating from the median in too many pools is reported
+#[test]
+fn test_outlier_evaluator_reported() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Balances::set_balance(&1, 10_000);
+
+        let results = [
+            (1, Perbill::one()),
+            (2, Perbill::one()),
+            (3, Perbill::zero()),
+        ];
+        evaluate_with(&results);
+        evaluate_with(&results);
+        assert_eq!(Distance::evaluator_stats(&3).outliers, 2);
+        assert!(ReportedOffences::get().is_empty());
+
+        evaluate_with(&results);
+        System::assert_has_event(RuntimeEvent::Distance(Event::OutlierReported {
+            who: 3,
+            outliers: 3,
+        }));
+        let offences = ReportedOffences::get();
+        assert_eq!(offences.len(), 1);
+        assert_eq!(offences[0].1.offenders, vec![(3, ())]);
+        assert_eq!(offences[0].1.period, 9);
+        // the reported evaluator starts a new history
+        assert!(EvaluatorHistory::<Test>::get(3).is_empty());
+        assert_eq!(EvaluatorHistory::<Test>::get(1).len(), 3);
+    });
+}
+
+// an evaluator authoring blocks without publishing its results is reported
+#[test]
+fn test_silent_evaluator_reported() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Balances::set_balance(&1, 10_000);
+
+        // 1 authors all the blocks
+        evaluate_with(&[
+            (1, Perbill::one()),
+            (2, Perbill::one()),
+            (3, Perbill::one()),
+        ]);
+        let results = [(2, Perbill::one()), (3, Perbill::one())];
+        evaluate_with(&results);
+        assert_eq!(
+            EvaluatorHistory::<Test>::get(1)[1],
+            EvaluatorRecord {
+                period: 6,
+                deviation: Perbill::one(),
+            }
+        );
+        evaluate_with(&results);
+        assert_eq!(Distance::evaluator_stats(&1).outliers, 2);
+        assert!(ReportedOffences::get().is_empty());
+
+        evaluate_with(&results);
+        System::assert_has_event(RuntimeEvent::Distance(Event::OutlierReported {
+            who: 1,
+            outliers: 3,
+        }));
+        let offences = ReportedOffences::get();
+        assert_eq!(offences.len(), 1);
+        assert_eq!(offences[0].1.offenders, vec![(1, ())]);
+        assert_eq!(Distance::evaluator_stats(&2).outliers, 0);
+    });
+}
+
+// an evaluator authoring no block is not penalised for the results it did not publish
+#[test]
+fn test_non_authoring_evaluator_not_penalised() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Balances::set_balance(&1, 10_000);
+
+        evaluate_with(&[
+            (1, Perbill::one()),
+            (2, Perbill::one()),
+            (3, Perbill::one()),
+        ]);
+        // 2 and 3 author no block
+        evaluate_with(&[(1, Perbill::one())]);
+        assert_eq!(EvaluatorHistory::<Test>::get(1).len(), 2);
+        assert_eq!(EvaluatorHistory::<Test>::get(2).len(), 1);
+        assert_eq!(Distance::evaluator_stats(&3).outliers, 0);
+        // the authorities running no oracle have no history
+        assert!(!EvaluatorHistory::<Test>::contains_key(4));
+    });
+}
+
+// the history of an evaluator leaving the authorities is dropped
+#[test]
+fn test_evaluator_history_pruned() {
+    new_test_ext().execute_with(|| {
+        // the session rotates every 5 blocks, the evaluation period every 4 blocks
+        run_to_block(7);
+        pallet_session::Validators::<Test>::put(vec![1, 2]);
+        run_to_block(8);
+        assert_eq!(PeriodAuthorities::<Test>::get().into_inner(), vec![1, 2]);
+        for evaluator in [1, 2] {
+            Distance::record_evaluator_deviation(
+                evaluator,
+                2,
+                &[Perbill::one()],
+                &[Some(Perbill::one())],
+            );
+        }
+
+        run_to_block(11);
+        pallet_session::Validators::<Test>::put(vec![1]);
+        run_to_block(12);
+        assert_eq!(EvaluatorHistory::<Test>::get(1).len(), 1);
+        assert!(!EvaluatorHistory::<Test>::contains_key(2));
+    });
+}
+
+// the history of an evaluator is bounded
+#[test]
+fn test_evaluator_history_bounded() {
+    new_test_ext().execute_with(|| {
+        for period in 1..=6 {
+            Distance::record_evaluator_deviation(
+                1,
+                period,
+                &[Perbill::one()],
+                &[Some(Perbill::one())],
+            );
+        }
+
+        let history = EvaluatorHistory::<Test>::get(1);
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[0].period, 3);
+        assert_eq!(history[3].period, 6);
+    });
+}
+
+// fill the pool receiving the requests of the first period
+fn fill_request_pool() {
+    EvaluationPool2::<Test>::mutate(|pool| {
+        let mut idty_index = 100;
+        while pool
+            .evaluations
+            .try_push((idty_index, MedianAcc::new()))
+            .is_ok()
+        {
+            idty_index += 1;
+        }
+    });
+}
+
+// requests beyond the pool capacity are queued and scheduled in the next period
+#[test]
+fn test_request_distance_evaluation_queued() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        fill_request_pool();
+        for who in 1..=3 {
+            Balances::set_balance(&who, 10_000);
+        }
+
+        assert_ok!(Distance::request_distance_evaluation(
+            RuntimeOrigin::signed(1)
+        ));
+        System::assert_has_event(RuntimeEvent::Distance(Event::EvaluationQueued {
+            idty_index: 1,
+            who: 1,
+            position: 0,
+        }));
+        assert_ok!(Distance::request_distance_evaluation(
+            RuntimeOrigin::signed(2)
+        ));
+        assert_eq!(Distance::queue_position(2), Some(1));
+        // the currency is reserved at request time
+        assert_eq!(Balances::reserved_balance(1), 1000);
+        assert_noop!(
+            Distance::request_distance_evaluation(RuntimeOrigin::signed(3)),
+            Error::<Test>::QueueFull
+        );
+
+        // the queued requests enter the pool of the next period
+        run_to_block(4);
+        System::assert_has_event(RuntimeEvent::Distance(Event::EvaluationRequested {
+            idty_index: 1,
+            who: 1,
+        }));
+        assert!(EvaluationQueue::<Test>::get().is_empty());
+        assert_eq!(Distance::queue_position(1), None);
+        assert_eq!(EvaluationPool0::<Test>::get().evaluations.len(), 2);
+
+        // and are evaluated three periods later
+        run_to_block(16);
+        System::assert_has_event(RuntimeEvent::Distance(Event::NotEvaluated {
+            idty_index: 1,
+            who: 1,
+        }));
+        assert_eq!(Balances::reserved_balance(1), 0);
+    });
+}
+
+// a queued request can be cancelled by its requester
+#[test]
+fn test_cancel_queued_distance_evaluation() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        Balances::set_balance(&1, 10_000);
+        fill_request_pool();
+        assert_ok!(Distance::request_distance_evaluation(
+            RuntimeOrigin::signed(1)
+        ));
+        assert_eq!(Distance::queue_position(1), Some(0));
+
+        assert_noop!(
+            Distance::cancel_distance_evaluation(RuntimeOrigin::signed(2), 1),
+            Error::<Test>::NotRequester
+        );
+        assert_ok!(Distance::cancel_distance_evaluation(
+            RuntimeOrigin::signed(1),
+            1
+        ));
+        System::assert_has_event(RuntimeEvent::Distance(Event::EvaluationCancelled {
+            idty_index: 1,
+            who: 1,
+        }));
+        assert_eq!(Distance::queue_position(1), None);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(PendingEvaluationRequest::<Test>::get(1), None);
+    });
+}
+
+// a request can be cancelled by its requester only in the request pool
+#[test]
+fn test_cancel_distance_evaluation() {
+    // request pool, pending pool, result pool
+    for (block, cancellable) in [(1, true), (4, false), (8, false)] {
+        new_test_ext().execute_with(|| {
+            run_to_block(1);
+            Balances::set_balance(&1, 10_000);
+            assert_ok!(Distance::request_distance_evaluation(
+                RuntimeOrigin::signed(1)
+            ));
+            run_to_block(block);
+
+            if cancellable {
+                assert_ok!(Distance::cancel_distance_evaluation(
+                    RuntimeOrigin::signed(1),
+                    1
+                ));
+                assert_eq!(Balances::reserved_balance(1), 0);
+                assert!(EvaluationPool2::<Test>::get().evaluations.is_empty());
+            } else {
+                assert_noop!(
+                    Distance::cancel_distance_evaluation(RuntimeOrigin::signed(1), 1),
+                    Error::<Test>::NotCancellable
+                );
+                assert_eq!(Balances::reserved_balance(1), 1000);
+            }
+        });
+    }
+}
+
+// the pending request of a revoked identity is cancelled before its evaluation
+#[test]
+fn test_revoked_identity_cancels_request() {
+    // queue, request pool
+    for queued in [true, false] {
+        new_test_ext().execute_with(|| {
+            run_to_block(1);
+            Balances::set_balance(&1, 10_000);
+            if queued {
+                fill_request_pool();
+            }
+            assert_ok!(Distance::request_distance_evaluation(
+                RuntimeOrigin::signed(1)
+            ));
+
+            Identity::do_revoke_identity(1, pallet_identity::RevocationReason::Root);
+            System::assert_has_event(RuntimeEvent::Distance(Event::EvaluationCancelled {
+                idty_index: 1,
+                who: 1,
+            }));
+            assert_eq!(Balances::reserved_balance(1), 0);
+            assert_eq!(PendingEvaluationRequest::<Test>::get(1), None);
+            assert!(EvaluationQueue::<Test>::get().is_empty());
+            assert!(
+                !EvaluationPool2::<Test>::get()
+                    .evaluations
+                    .iter()
+                    .any(|(idty, _)| *idty == 1)
+            );
+        });
+    }
+}
+
+// a request under evaluation keeps its hold until the end of the evaluation
+#[test]
+fn test_removed_identity_under_evaluation() {
+    // pending pool, result pool
+    for block in [4, 8] {
+        new_test_ext().execute_with(|| {
+            run_to_block(1);
+            Balances::set_balance(&1, 10_000);
+            assert_ok!(Distance::request_distance_evaluation(
+                RuntimeOrigin::signed(1)
+            ));
+            run_to_block(block);
+
+            Identity::do_remove_identity(1, pallet_identity::RemovalReason::Root);
+            assert_eq!(PendingEvaluationRequest::<Test>::get(1), Some(1));
+            assert_eq!(Balances::reserved_balance(1), 1000);
+
+            // the results of the other identities stay in line with the pool
+            run_to_block(8);
+            assert_ok!(Distance::force_update_evaluation(
+                RuntimeOrigin::root(),
+                2,
+                ComputationResult {
+                    distances: vec![Perbill::one()],
+                },
+            ));
+            run_to_block(12);
+            System::assert_has_event(RuntimeEvent::Distance(Event::EvaluatedValid {
+                idty_index: 1,
+                distance: Perbill::one(),
+            }));
+            assert_eq!(PendingEvaluationRequest::<Test>::get(1), None);
+            assert_eq!(Balances::reserved_balance(1), 0);
+        });
+    }
+}
+
+// the estimated evaluation block accounts for the queue
+#[test]
+fn test_evaluation_pools_info() {
+    new_test_ext().execute_with(|| {
+        run_to_block(1);
+        let info = Distance::evaluation_pools();
+        assert_eq!(info.requests.evaluations, 0);
+        assert_eq!(info.next_request_evaluation, Some(12));
+
+        fill_request_pool();
+        let info = Distance::evaluation_pools();
+        assert_eq!(info.requests.evaluations, MAX_EVALUATIONS_PER_SESSION);
+        assert_eq!(info.queued, 0);
+        assert_eq!(info.next_request_evaluation, Some(16));
+
+        Balances::set_balance(&1, 10_000);
+        Balances::set_balance(&2, 10_000);
+        assert_ok!(Distance::request_distance_evaluation(
+            RuntimeOrigin::signed(1)
+        ));
+        assert_ok!(Distance::request_distance_evaluation(
+            RuntimeOrigin::signed(2)
+        ));
+        let info = Distance::evaluation_pools();
+        assert_eq!(info.queued, 2);
+        assert_eq!(info.next_request_evaluation, None);
+    });
+}
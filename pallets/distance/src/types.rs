@@ -19,7 +19,12 @@ pub use sp_distance::ComputationResult;
 
 use codec::{Decode, Encode};
 use frame_support::pallet_prelude::*;
+use scale_info::prelude::vec::Vec;
 use sp_runtime::Perbill;
+use sp_staking::{
+    SessionIndex,
+    offence::{Kind, Offence},
+};
 
 /// Status of the distance evaluation of an identity.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
@@ -59,3 +64,54 @@ impl<AccountId: Ord, IdtyIndex> Default for EvaluationPool<AccountId, IdtyIndex>
         }
     }
 }
+
+/// Deviation of the results published by an evaluator from the medians of an evaluation pool.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct EvaluatorRecord {
+    /// Index of the evaluation period in which the pool was evaluated.
+    pub period: u32,
+    /// Largest gap between a distance published by the evaluator and the median.
+    pub deviation: Perbill,
+}
+
+/// An offence reported against evaluators whose results deviate too often from the median.
+///
+/// It usually means that their distance oracle is misconfigured.
+#[derive(RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Clone, PartialEq, Eq))]
+pub struct OutlierOffence<Offender> {
+    /// The evaluation period in which the offence was detected.
+    pub period: u32,
+    /// The current session index in which the offence was reported.
+    pub session_index: SessionIndex,
+    /// The size of the validator set in the current session.
+    pub validator_set_count: u32,
+    /// The outlier evaluators.
+    pub offenders: Vec<Offender>,
+}
+
+impl<Offender: Clone> Offence<Offender> for OutlierOffence<Offender> {
+    type TimeSlot = u32;
+
+    const ID: Kind = *b"distance:outlier";
+
+    fn offenders(&self) -> Vec<Offender> {
+        self.offenders.clone()
+    }
+
+    fn session_index(&self) -> SessionIndex {
+        self.session_index
+    }
+
+    fn validator_set_count(&self) -> u32 {
+        self.validator_set_count
+    }
+
+    fn time_slot(&self) -> Self::TimeSlot {
+        self.period
+    }
+
+    fn slash_fraction(&self, _offenders: u32) -> Perbill {
+        Perbill::zero()
+    }
+}
@@ -21,6 +21,7 @@ use frame_support::weights::{Weight, constants::RocksDbWeight};
 pub trait WeightInfo {
     fn request_distance_evaluation() -> Weight;
     fn request_distance_evaluation_for() -> Weight;
+    fn cancel_distance_evaluation() -> Weight;
     fn update_evaluation(i: u32) -> Weight;
     fn force_update_evaluation(i: u32) -> Weight;
     fn force_valid_distance_status() -> Weight;
@@ -28,6 +29,8 @@ pub trait WeightInfo {
     fn do_evaluation_overhead() -> Weight;
     fn do_evaluation_success() -> Weight;
     fn do_evaluation_failure() -> Weight;
+    fn record_evaluator_deviation(i: u32) -> Weight;
+    fn schedule_queued_evaluations(i: u32) -> Weight;
     fn on_finalize() -> Weight;
 }
 
@@ -40,7 +43,7 @@ impl WeightInfo for () {
         // Minimum execution time: 876_053_000 picoseconds.
         Weight::from_parts(898_445_000, 0)
             .saturating_add(Weight::from_parts(0, 4745))
-            .saturating_add(RocksDbWeight::get().reads(8))
+            .saturating_add(RocksDbWeight::get().reads(9))
             .saturating_add(RocksDbWeight::get().writes(3))
     }
 
@@ -51,10 +54,18 @@ impl WeightInfo for () {
         // Minimum execution time: 1_118_982_000 picoseconds.
         Weight::from_parts(1_292_782_000, 0)
             .saturating_add(Weight::from_parts(0, 7425))
-            .saturating_add(RocksDbWeight::get().reads(10))
+            .saturating_add(RocksDbWeight::get().reads(11))
             .saturating_add(RocksDbWeight::get().writes(3))
     }
 
+    fn cancel_distance_evaluation() -> Weight {
+        // Estimated, not measured
+        Weight::from_parts(436_291_000, 0)
+            .saturating_add(Weight::from_parts(0, 4807))
+            .saturating_add(RocksDbWeight::get().reads(6))
+            .saturating_add(RocksDbWeight::get().writes(5))
+    }
+
     fn update_evaluation(i: u32) -> Weight {
         // Proof Size summary in bytes:
         //  Measured:  `773 + i * (10 ±0)`
@@ -65,7 +76,7 @@ impl WeightInfo for () {
             // Standard Error: 292_144
             .saturating_add(Weight::from_parts(1_326_639, 0).saturating_mul(i.into()))
             .saturating_add(RocksDbWeight::get().reads(6))
-            .saturating_add(RocksDbWeight::get().writes(3))
+            .saturating_add(RocksDbWeight::get().writes(4))
             .saturating_add(Weight::from_parts(0, 10).saturating_mul(i.into()))
     }
 
@@ -79,7 +90,7 @@ impl WeightInfo for () {
             // Standard Error: 53_366
             .saturating_add(Weight::from_parts(1_841_329, 0).saturating_mul(i.into()))
             .saturating_add(RocksDbWeight::get().reads(2))
-            .saturating_add(RocksDbWeight::get().writes(1))
+            .saturating_add(RocksDbWeight::get().writes(2))
             .saturating_add(Weight::from_parts(0, 10).saturating_mul(i.into()))
     }
 
@@ -130,6 +141,25 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(1))
     }
 
+    fn record_evaluator_deviation(i: u32) -> Weight {
+        // Estimated, not measured
+        Weight::from_parts(10_212_427, 0)
+            .saturating_add(Weight::from_parts(0, 3720))
+            .saturating_add(Weight::from_parts(4_182, 0).saturating_mul(i.into()))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+
+    fn schedule_queued_evaluations(i: u32) -> Weight {
+        // Estimated, not measured
+        Weight::from_parts(112_836_000, 0)
+            .saturating_add(Weight::from_parts(0, 1655))
+            .saturating_add(Weight::from_parts(2_417_392, 0).saturating_mul(i.into()))
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(2))
+            .saturating_add(Weight::from_parts(0, 42).saturating_mul(i.into()))
+    }
+
     fn on_initialize_overhead() -> Weight {
         // Proof Size summary in bytes:
         //  Measured:  `170`
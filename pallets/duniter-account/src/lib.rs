@@ -176,6 +176,15 @@ pub mod pallet {
         AccountUnlinked(T::AccountId),
     }
 
+    // HOOKS //
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
     // CALLS //
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -244,6 +253,47 @@ pub mod pallet {
             })
         }
 
+        /// Check the storage invariants of the accounts.
+        ///
+        /// - an account that is not sufficient has a provider and respects the existential deposit;
+        /// - an account with consumers has a provider;
+        /// - a linked identity exists;
+        /// - the total issuance is the sum of all balances.
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let mut total_balance = T::Balance::zero();
+            for (_account_id, account) in frame_system::Account::<T>::iter() {
+                let balance = account.data.free.saturating_add(account.data.reserved);
+                if account.sufficients == 0 {
+                    ensure!(
+                        account.providers > 0,
+                        "account has no provider nor sufficient"
+                    );
+                    ensure!(
+                        balance >= T::ExistentialDeposit::get(),
+                        "account does not respect the existential deposit"
+                    );
+                }
+                if account.consumers > 0 {
+                    ensure!(
+                        account.providers > 0,
+                        "account has consumers but no provider"
+                    );
+                }
+                if let Some(idty_index) = account.data.linked_idty {
+                    ensure!(
+                        pallet_identity::Identities::<T>::contains_key(idty_index),
+                        "account is linked to a missing identity"
+                    );
+                }
+                total_balance = total_balance.saturating_add(balance);
+            }
+            ensure!(
+                total_balance == pallet_balances::TotalIssuance::<T>::get(),
+                "total issuance does not match the sum of balances"
+            );
+            Ok(())
+        }
+
         /// Link an account to an identity.
         pub fn do_link_identity(account_id: &T::AccountId, idty_id: IdtyIdOf<T>) {
             // no-op if identity does not change
@@ -0,0 +1,85 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::One;
+
+fn assert_has_event<T: Config>(generic_event: <T as frame_system::Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_has_event(generic_event);
+}
+
+/// Returns the highest value allowed for `key`.
+fn new_value<T: Config>(key: ParameterKey) -> u32 {
+    T::Bounds::bounds(key).max
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn schedule_change() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ChangeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let key = ParameterKey::CertValidityPeriod;
+        let value = new_value::<T>(key);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, key, value);
+
+        assert!(PendingChanges::<T>::contains_key(key));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn cancel_change() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ChangeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let key = ParameterKey::CertValidityPeriod;
+        Pallet::<T>::schedule_change(origin.clone(), key, new_value::<T>(key))
+            .map_err(|_| BenchmarkError::Stop("cannot schedule change"))?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, key);
+
+        assert_has_event::<T>(Event::<T>::ChangeCancelled { key }.into());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn enact_change() {
+        let key = ParameterKey::CertValidityPeriod;
+        let value = new_value::<T>(key);
+        // worst case: the history is full and its oldest entry is dropped
+        for _ in 0..T::MaxHistoryLen::get() {
+            Pallet::<T>::do_enact_change(key, value);
+        }
+        let enactment = frame_system::Pallet::<T>::block_number() + One::one();
+        frame_system::Pallet::<T>::set_block_number(enactment);
+        PendingChanges::<T>::insert(key, PendingChange { value, enactment });
+
+        #[extrinsic_call]
+        _(frame_system::RawOrigin::Root, key, value);
+
+        assert_eq!(History::<T>::get(key).len() as u32, T::MaxHistoryLen::get());
+    }
+}
@@ -0,0 +1,373 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Duniter Parameters Pallet
+//!
+//! This pallet allows the Ğ1 and ĞTest runtimes to change some Web of Trust parameters without a runtime upgrade.
+//!
+//! ## Overview
+//!
+//! Each parameter is identified by a [`ParameterKey`]. Its value is read by the other pallets through the getters
+//! defined in this crate (for example [`CertValidityPeriod`]), which implement `Get<u32>`. As long as a parameter has
+//! never been changed, the runtime default is returned.
+//!
+//! ## Governance
+//!
+//! A change is proposed with `schedule_change` by the `ChangeOrigin`, which is the Technical Committee in the runtimes.
+//! The new value must lie in the range allowed for the parameter by the runtime (see [`ParameterBounds`]).
+//! The change is then scheduled through the scheduler and enacted with root origin after `EnactmentDelay` blocks,
+//! leaving time to the community to review it. Until then, the same origin can cancel it with `cancel_change`.
+//! `enact_change` only enacts a scheduled change once its enactment block is reached, so that the delay cannot be
+//! bypassed by dispatching it directly with root origin.
+//!
+//! ## History
+//!
+//! The last `MaxHistoryLen` changes of each parameter are kept in `History` and can be queried through the
+//! storage or the `DuniterParametersApi` runtime API.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+mod runtime_api;
+mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use pallet::*;
+pub use runtime_api::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use codec::Encode;
+use frame_support::traits::{
+    OriginTrait, QueryPreimage, StorePreimage,
+    schedule::{DispatchTime, v3::Named as ScheduleNamed},
+};
+use scale_info::prelude::vec::Vec;
+
+/// Prefix of the scheduler task names used by this pallet.
+pub const TASK_NAME_PREFIX: &[u8] = b"duniter/parameters";
+
+#[allow(unreachable_patterns)]
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        dispatch::{GetDispatchInfo, PostDispatchInfo},
+        pallet_prelude::*,
+    };
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::Dispatchable;
+
+    pub type CallOf<T> = <T as Config>::RuntimeCall;
+    pub type PalletsOriginOf<T> =
+        <<T as frame_system::Config>::RuntimeOrigin as OriginTrait>::PalletsOrigin;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    // CONFIG //
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Default value and allowed range of each parameter.
+        type Bounds: ParameterBounds;
+
+        /// Origin allowed to schedule and cancel parameter changes.
+        type ChangeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of blocks between the scheduling of a change and its enactment.
+        #[pallet::constant]
+        type EnactmentDelay: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of changes kept in the history of each parameter.
+        #[pallet::constant]
+        type MaxHistoryLen: Get<u32>;
+
+        /// The preimage provider used to store the scheduled calls.
+        type Preimages: QueryPreimage<H = Self::Hashing> + StorePreimage;
+
+        /// The overarching call type.
+        type RuntimeCall: Parameter
+            + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+            + GetDispatchInfo
+            + From<Call<Self>>
+            + IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+        /// The scheduler used to enact the changes.
+        type Scheduler: ScheduleNamed<
+                BlockNumberFor<Self>,
+                CallOf<Self>,
+                PalletsOriginOf<Self>,
+                Hasher = Self::Hashing,
+            >;
+
+        /// Type representing the weight of this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    // STORAGE //
+
+    /// The values of the parameters changed since genesis.
+    #[pallet::storage]
+    pub type Values<T: Config> = StorageMap<_, Twox64Concat, ParameterKey, u32, OptionQuery>;
+
+    /// The changes scheduled but not enacted yet.
+    #[pallet::storage]
+    pub type PendingChanges<T: Config> =
+        StorageMap<_, Twox64Concat, ParameterKey, PendingChange<BlockNumberFor<T>>, OptionQuery>;
+
+    /// The last enacted changes of each parameter, oldest first.
+    #[pallet::storage]
+    pub type History<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        ParameterKey,
+        BoundedVec<ParameterChange<BlockNumberFor<T>>, T::MaxHistoryLen>,
+        ValueQuery,
+    >;
+
+    // EVENTS //
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A parameter change was scheduled.
+        ChangeScheduled {
+            key: ParameterKey,
+            value: u32,
+            enactment: BlockNumberFor<T>,
+        },
+        /// A scheduled parameter change was cancelled.
+        ChangeCancelled { key: ParameterKey },
+        /// A scheduled parameter change was not enacted because its value is no longer allowed.
+        ChangeRejected { key: ParameterKey, value: u32 },
+        /// A parameter change was enacted.
+        ParameterChanged {
+            key: ParameterKey,
+            old_value: u32,
+            new_value: u32,
+        },
+    }
+
+    // ERRORS //
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The value is outside the range allowed for this parameter.
+        ValueOutOfRange,
+        /// A change is already scheduled for this parameter.
+        ChangeAlreadyScheduled,
+        /// No change is scheduled for this parameter.
+        NoChangeScheduled,
+        /// The enactment block of the scheduled change is not reached yet.
+        EnactmentDelayNotElapsed,
+    }
+
+    // CALLS //
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Schedule the change of a parameter.
+        ///
+        /// The change is enacted after `EnactmentDelay` blocks.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::schedule_change())]
+        pub fn schedule_change(
+            origin: OriginFor<T>,
+            key: ParameterKey,
+            value: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::ChangeOrigin::ensure_origin(origin)?;
+            Self::check_bounds(key, value)?;
+            ensure!(
+                !PendingChanges::<T>::contains_key(key),
+                Error::<T>::ChangeAlreadyScheduled
+            );
+
+            let enactment =
+                frame_system::Pallet::<T>::block_number().saturating_add(T::EnactmentDelay::get());
+            let call: CallOf<T> = Call::<T>::enact_change { key, value }.into();
+            T::Scheduler::schedule_named(
+                Self::task_name(key),
+                DispatchTime::At(enactment),
+                None,
+                frame_support::traits::schedule::LOWEST_PRIORITY,
+                frame_system::RawOrigin::Root.into(),
+                T::Preimages::bound(call)?,
+            )?;
+            PendingChanges::<T>::insert(key, PendingChange { value, enactment });
+
+            Self::deposit_event(Event::ChangeScheduled {
+                key,
+                value,
+                enactment,
+            });
+            Ok(().into())
+        }
+
+        /// Cancel the scheduled change of a parameter.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::cancel_change())]
+        pub fn cancel_change(
+            origin: OriginFor<T>,
+            key: ParameterKey,
+        ) -> DispatchResultWithPostInfo {
+            T::ChangeOrigin::ensure_origin(origin)?;
+            ensure!(
+                PendingChanges::<T>::contains_key(key),
+                Error::<T>::NoChangeScheduled
+            );
+
+            T::Scheduler::cancel_named(Self::task_name(key))?;
+            PendingChanges::<T>::remove(key);
+
+            Self::deposit_event(Event::ChangeCancelled { key });
+            Ok(().into())
+        }
+
+        /// Enact the change of a parameter.
+        ///
+        /// This call is dispatched by the scheduler once the enactment delay has passed.
+        /// Only the scheduled change can be enacted, once its enactment block is reached.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::enact_change())]
+        pub fn enact_change(
+            origin: OriginFor<T>,
+            key: ParameterKey,
+            value: u32,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let pending = PendingChanges::<T>::get(key)
+                .filter(|pending| pending.value == value)
+                .ok_or(Error::<T>::NoChangeScheduled)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= pending.enactment,
+                Error::<T>::EnactmentDelayNotElapsed
+            );
+            // bounds may have changed with a runtime upgrade since the change was scheduled
+            if !T::Bounds::bounds(key).contains(value) {
+                PendingChanges::<T>::remove(key);
+                Self::deposit_event(Event::ChangeRejected { key, value });
+                return Ok(().into());
+            }
+            Self::do_enact_change(key, value);
+            Ok(().into())
+        }
+    }
+
+    // PUBLIC FUNCTIONS //
+
+    impl<T: Config> Pallet<T> {
+        /// Returns the current value of a parameter.
+        pub fn value(key: ParameterKey) -> u32 {
+            Values::<T>::get(key).unwrap_or_else(|| T::Bounds::bounds(key).default)
+        }
+
+        /// Returns the change history of a parameter, oldest first.
+        pub fn history(key: ParameterKey) -> Vec<ParameterChange<BlockNumberFor<T>>> {
+            History::<T>::get(key).into_inner()
+        }
+
+        /// Returns the change scheduled for a parameter, if any.
+        pub fn pending_change(key: ParameterKey) -> Option<PendingChange<BlockNumberFor<T>>> {
+            PendingChanges::<T>::get(key)
+        }
+    }
+
+    // INTERNAL FUNCTIONS //
+
+    impl<T: Config> Pallet<T> {
+        /// Checks that a value is allowed for a parameter.
+        fn check_bounds(key: ParameterKey, value: u32) -> DispatchResult {
+            ensure!(
+                T::Bounds::bounds(key).contains(value),
+                Error::<T>::ValueOutOfRange
+            );
+            Ok(())
+        }
+
+        /// Name of the scheduler task enacting a change of a parameter.
+        fn task_name(key: ParameterKey) -> [u8; 32] {
+            (TASK_NAME_PREFIX, key).using_encoded(sp_io::hashing::blake2_256)
+        }
+
+        /// Sets the new value of a parameter and records the change.
+        pub(super) fn do_enact_change(key: ParameterKey, value: u32) {
+            let old_value = Self::value(key);
+            Values::<T>::insert(key, value);
+            PendingChanges::<T>::remove(key);
+            History::<T>::mutate(key, |history| {
+                // drop the oldest change when the history is full
+                if history.is_full() {
+                    history.remove(0);
+                }
+                let _ = history.try_push(ParameterChange {
+                    block: frame_system::Pallet::<T>::block_number(),
+                    old_value,
+                    new_value: value,
+                });
+            });
+
+            Self::deposit_event(Event::ParameterChanged {
+                key,
+                old_value,
+                new_value: value,
+            });
+        }
+    }
+}
+
+macro_rules! parameter_getters {
+    ($($(#[$attr:meta])* $name:ident,)*) => {
+        $(
+            $(#[$attr])*
+            pub struct $name<T>(core::marker::PhantomData<T>);
+            impl<T: Config> frame_support::traits::Get<u32> for $name<T> {
+                fn get() -> u32 {
+                    Pallet::<T>::value(ParameterKey::$name)
+                }
+            }
+        )*
+    };
+}
+
+parameter_getters! {
+    /// Current value of [`ParameterKey::CertPeriod`].
+    CertPeriod,
+    /// Current value of [`ParameterKey::CertMaxByIssuer`].
+    CertMaxByIssuer,
+    /// Current value of [`ParameterKey::CertMinReceivedCertToIssueCert`].
+    CertMinReceivedCertToIssueCert,
+    /// Current value of [`ParameterKey::CertValidityPeriod`].
+    CertValidityPeriod,
+    /// Current value of [`ParameterKey::MembershipPeriod`].
+    MembershipPeriod,
+    /// Current value of [`ParameterKey::MembershipRenewalPeriod`].
+    MembershipRenewalPeriod,
+    /// Current value of [`ParameterKey::WotMinCertForMembership`].
+    WotMinCertForMembership,
+    /// Current value of [`ParameterKey::WotMinCertForCreateIdtyRight`].
+    WotMinCertForCreateIdtyRight,
+}
@@ -0,0 +1,167 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::{self as pallet_duniter_parameters};
+use frame_support::{
+    derive_impl, ord_parameter_types, parameter_types,
+    traits::{ConstU32, EqualPrivilegeOnly, Everything, OnFinalize, OnInitialize},
+    weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+    BuildStorage,
+    traits::{BlakeTwo256, IdentityLookup},
+};
+
+type Balance = u64;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
+        Parameters: pallet_duniter_parameters,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type AccountId = u64;
+    type BaseCallFilter = Everything;
+    type Block = Block;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type MaxConsumers = ConstU32<16>;
+    type Nonce = u64;
+    type PalletInfo = PalletInfo;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = Balance;
+    type DoneSlashHandler = ();
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type FreezeIdentifier = ();
+    type MaxFreezes = ConstU32<0>;
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeFreezeReason = ();
+    type RuntimeHoldReason = ();
+    type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
+}
+
+impl pallet_preimage::Config for Test {
+    type Consideration = ();
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<u64>;
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub MaximumSchedulerWeight: Weight = Weight::from_parts(1_000_000_000, u64::MAX);
+}
+
+impl pallet_scheduler::Config for Test {
+    type BlockNumberProvider = System;
+    type MaxScheduledPerBlock = ConstU32<10>;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type PalletsOrigin = OriginCaller;
+    type Preimages = Preimage;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type ScheduleOrigin = EnsureRoot<u64>;
+    type WeightInfo = ();
+}
+
+ord_parameter_types! {
+    pub const Committee: u64 = 1;
+}
+
+parameter_types! {
+    pub const EnactmentDelay: u64 = 5;
+    pub const MaxHistoryLen: u32 = 3;
+}
+
+pub struct TestBounds;
+impl ParameterBounds for TestBounds {
+    fn bounds(key: ParameterKey) -> Bounds {
+        match key {
+            ParameterKey::CertValidityPeriod => Bounds {
+                default: 100,
+                min: 10,
+                max: 1_000,
+            },
+            _ => Bounds {
+                default: 10,
+                min: 1,
+                max: 100,
+            },
+        }
+    }
+}
+
+impl pallet_duniter_parameters::Config for Test {
+    type Bounds = TestBounds;
+    type ChangeOrigin = EnsureSignedBy<Committee, u64>;
+    type EnactmentDelay = EnactmentDelay;
+    type MaxHistoryLen = MaxHistoryLen;
+    type Preimages = Preimage;
+    type RuntimeCall = RuntimeCall;
+    type Scheduler = Scheduler;
+    type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        Scheduler::on_finalize(System::block_number());
+        System::on_finalize(System::block_number());
+        System::reset_events();
+        System::set_block_number(System::block_number() + 1);
+        System::on_initialize(System::block_number());
+        Scheduler::on_initialize(System::block_number());
+    }
+}
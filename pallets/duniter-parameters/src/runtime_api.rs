@@ -0,0 +1,36 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{ParameterChange, ParameterKey, PendingChange};
+use codec::Codec;
+use scale_info::prelude::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// Runtime API for Duniter Parameters pallet
+    pub trait DuniterParametersApi<BlockNumber>
+    where
+        BlockNumber: Codec,
+    {
+        /// Get the current value of a parameter
+        fn parameter_value(key: ParameterKey) -> u32;
+
+        /// Get the change scheduled for a parameter, if any
+        fn pending_change(key: ParameterKey) -> Option<PendingChange<BlockNumber>>;
+
+        /// Get the last changes of a parameter, oldest first
+        fn parameter_history(key: ParameterKey) -> Vec<ParameterChange<BlockNumber>>;
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+use frame_support::{assert_noop, assert_ok, traits::Get};
+use sp_runtime::DispatchError;
+
+const KEY: ParameterKey = ParameterKey::CertValidityPeriod;
+
+/// test that default values are returned until a parameter is changed
+#[test]
+fn test_default_values() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(CertValidityPeriod::<Test>::get(), 100);
+        assert_eq!(CertMaxByIssuer::<Test>::get(), 10);
+        assert_eq!(Parameters::history(KEY), vec![]);
+    })
+}
+
+/// test that only the change origin can schedule a change, within bounds
+#[test]
+fn test_schedule_change_checks() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Parameters::schedule_change(RuntimeOrigin::signed(2), KEY, 200),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Parameters::schedule_change(RuntimeOrigin::signed(1), KEY, 9),
+            Error::<Test>::ValueOutOfRange
+        );
+        assert_noop!(
+            Parameters::schedule_change(RuntimeOrigin::signed(1), KEY, 1_001),
+            Error::<Test>::ValueOutOfRange
+        );
+        assert_ok!(Parameters::schedule_change(
+            RuntimeOrigin::signed(1),
+            KEY,
+            200
+        ));
+        assert_noop!(
+            Parameters::schedule_change(RuntimeOrigin::signed(1), KEY, 300),
+            Error::<Test>::ChangeAlreadyScheduled
+        );
+        // enactment can only be triggered by root
+        assert_noop!(
+            Parameters::enact_change(RuntimeOrigin::signed(1), KEY, 200),
+            DispatchError::BadOrigin
+        );
+    })
+}
+
+/// test that a change is enacted by the scheduler after the delay
+#[test]
+fn test_change_enacted_after_delay() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Parameters::schedule_change(
+            RuntimeOrigin::signed(1),
+            KEY,
+            200
+        ));
+        System::assert_has_event(RuntimeEvent::Parameters(Event::ChangeScheduled {
+            key: KEY,
+            value: 200,
+            enactment: 6,
+        }));
+        assert_eq!(
+            Parameters::pending_change(KEY),
+            Some(PendingChange {
+                value: 200,
+                enactment: 6
+            })
+        );
+
+        run_to_block(5);
+        assert_eq!(CertValidityPeriod::<Test>::get(), 100);
+
+        run_to_block(6);
+        assert_eq!(CertValidityPeriod::<Test>::get(), 200);
+        assert_eq!(Parameters::pending_change(KEY), None);
+        System::assert_has_event(RuntimeEvent::Parameters(Event::ParameterChanged {
+            key: KEY,
+            old_value: 100,
+            new_value: 200,
+        }));
+        assert_eq!(
+            Parameters::history(KEY),
+            vec![ParameterChange {
+                block: 6,
+                old_value: 100,
+                new_value: 200
+            }]
+        );
+        // other parameters are not impacted
+        assert_eq!(CertMaxByIssuer::<Test>::get(), 10);
+    })
+}
+
+/// test that a cancelled change is not enacted
+#[test]
+fn test_cancel_change() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Parameters::cancel_change(RuntimeOrigin::signed(1), KEY),
+            Error::<Test>::NoChangeScheduled
+        );
+        assert_ok!(Parameters::schedule_change(
+            RuntimeOrigin::signed(1),
+            KEY,
+            200
+        ));
+        assert_noop!(
+            Parameters::cancel_change(RuntimeOrigin::signed(2), KEY),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Parameters::cancel_change(RuntimeOrigin::signed(1), KEY));
+        assert_eq!(Parameters::pending_change(KEY), None);
+
+        run_to_block(10);
+        assert_eq!(CertValidityPeriod::<Test>::get(), 100);
+        assert_eq!(Parameters::history(KEY), vec![]);
+
+        // a new change can be scheduled
+        assert_ok!(Parameters::schedule_change(
+            RuntimeOrigin::signed(1),
+            KEY,
+            300
+        ));
+    })
+}
+
+/// test that the history keeps the last changes only
+#[test]
+fn test_history_is_bounded() {
+    new_test_ext().execute_with(|| {
+        for value in [200, 300, 400, 500] {
+            assert_ok!(Parameters::schedule_change(
+                RuntimeOrigin::signed(1),
+                KEY,
+                value
+            ));
+            run_to_block(Parameters::pending_change(KEY).unwrap().enactment);
+        }
+        let history = Parameters::history(KEY);
+        assert_eq!(history.len() as u32, <MaxHistoryLen as Get<u32>>::get());
+        assert_eq!(history[0].old_value, 200);
+        assert_eq!(history[2].new_value, 500);
+        assert_eq!(CertValidityPeriod::<Test>::get(), 500);
+    })
+}
+
+/// test that root cannot enact a change which is not scheduled or before its enactment block
+#[test]
+fn test_enactment_delay_cannot_be_bypassed() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Parameters::enact_change(RuntimeOrigin::root(), KEY, 200),
+            Error::<Test>::NoChangeScheduled
+        );
+        assert_ok!(Parameters::schedule_change(
+            RuntimeOrigin::signed(1),
+            KEY,
+            200
+        ));
+        assert_noop!(
+            Parameters::enact_change(RuntimeOrigin::root(), KEY, 300),
+            Error::<Test>::NoChangeScheduled
+        );
+        assert_noop!(
+            Parameters::enact_change(RuntimeOrigin::root(), KEY, 200),
+            Error::<Test>::EnactmentDelayNotElapsed
+        );
+
+        run_to_block(5);
+        assert_noop!(
+            Parameters::enact_change(RuntimeOrigin::root(), KEY, 200),
+            Error::<Test>::EnactmentDelayNotElapsed
+        );
+        assert_eq!(CertValidityPeriod::<Test>::get(), 100);
+    })
+}
@@ -0,0 +1,98 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Various basic types for use in the parameters pallet.
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Parameters that can be changed by governance.
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    RuntimeDebug,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum ParameterKey {
+    /// Minimum duration between two certifications issued by the same issuer, in blocks.
+    CertPeriod,
+    /// Maximum number of active certifications issued by the same issuer.
+    CertMaxByIssuer,
+    /// Minimum number of certifications received to be allowed to issue a certification.
+    CertMinReceivedCertToIssueCert,
+    /// Validity duration of a certification, in blocks.
+    CertValidityPeriod,
+    /// Validity duration of a membership, in blocks.
+    MembershipPeriod,
+    /// Minimum duration between two membership renewals, in blocks.
+    MembershipRenewalPeriod,
+    /// Minimum number of certifications received to become a member.
+    WotMinCertForMembership,
+    /// Minimum number of certifications received to be allowed to create an identity.
+    WotMinCertForCreateIdtyRight,
+}
+
+/// Default value and allowed range of a parameter.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct Bounds {
+    /// Value used until the parameter is changed.
+    pub default: u32,
+    /// Minimum allowed value.
+    pub min: u32,
+    /// Maximum allowed value.
+    pub max: u32,
+}
+
+impl Bounds {
+    /// Returns `true` if the value is in the allowed range.
+    pub fn contains(&self, value: u32) -> bool {
+        self.min <= value && value <= self.max
+    }
+}
+
+/// Provides the default value and allowed range of each parameter.
+pub trait ParameterBounds {
+    fn bounds(key: ParameterKey) -> Bounds;
+}
+
+/// A change scheduled but not enacted yet.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingChange<BlockNumber> {
+    /// The new value of the parameter.
+    pub value: u32,
+    /// The block at which the change is enacted.
+    pub enactment: BlockNumber,
+}
+
+/// An enacted change of a parameter.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ParameterChange<BlockNumber> {
+    /// The block at which the change was enacted.
+    pub block: BlockNumber,
+    /// The value before the change.
+    pub old_value: u32,
+    /// The value after the change.
+    pub new_value: u32,
+}
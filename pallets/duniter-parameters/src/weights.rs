@@ -0,0 +1,41 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::weights::Weight;
+
+/// Weight functions needed for pallet_duniter_parameters.
+pub trait WeightInfo {
+    fn schedule_change() -> Weight;
+    fn cancel_change() -> Weight;
+    fn enact_change() -> Weight;
+}
+
+// Insecure weights implementation, use it for tests only!
+impl WeightInfo for () {
+    fn schedule_change() -> Weight {
+        Weight::from_parts(50_000, 0)
+    }
+
+    fn cancel_change() -> Weight {
+        Weight::from_parts(50_000, 0)
+    }
+
+    fn enact_change() -> Weight {
+        Weight::from_parts(30_000, 0)
+    }
+}
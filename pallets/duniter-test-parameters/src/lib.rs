@@ -20,6 +20,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod migrations;
+
 pub use pallet::*;
 pub use types::*;
 
@@ -64,6 +66,10 @@ pub mod types {
         pub wot_min_cert_for_create_idty_right: CertCount,
         pub wot_min_cert_for_membership: CertCount,
         pub evaluation_period: BlockNumber,
+        pub rate_limit_window: BlockNumber,
+        pub rate_limit_max_per_account: CertCount,
+        pub rate_limit_max_per_member_account: CertCount,
+        pub rate_limit_max_per_identity: CertCount,
     }
 }
 
@@ -74,7 +80,7 @@ pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::StorageVersion};
 
     /// The current storage version.
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
     #[pallet::pallet]
     #[pallet::storage_version(STORAGE_VERSION)]
@@ -0,0 +1,113 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage migrations of the test parameters pallet.
+
+pub mod v2 {
+    use crate::*;
+    use codec::Decode;
+    use frame_support::{
+        migrations::VersionedMigration,
+        pallet_prelude::*,
+        traits::{Get, UncheckedOnRuntimeUpgrade},
+    };
+
+    /// Parameters as stored before the rate limit parameters were added.
+    #[derive(Decode)]
+    struct ParametersV1<BlockNumber, CertCount, PeriodCount, SessionCount> {
+        babe_epoch_duration: PeriodCount,
+        cert_period: BlockNumber,
+        cert_max_by_issuer: CertCount,
+        cert_min_received_cert_to_issue_cert: CertCount,
+        cert_validity_period: BlockNumber,
+        idty_confirm_period: BlockNumber,
+        idty_creation_period: BlockNumber,
+        membership_period: BlockNumber,
+        membership_renewal_period: BlockNumber,
+        ud_creation_period: PeriodCount,
+        ud_reeval_period: PeriodCount,
+        smith_cert_max_by_issuer: CertCount,
+        smith_wot_min_cert_for_membership: CertCount,
+        smith_inactivity_max_duration: SessionCount,
+        wot_first_cert_issuable_on: BlockNumber,
+        wot_min_cert_for_create_idty_right: CertCount,
+        wot_min_cert_for_membership: CertCount,
+        evaluation_period: BlockNumber,
+    }
+
+    /// Rate limit parameters used to fill the storage during the migration:
+    /// `(rate_limit_window, rate_limit_max_per_account, rate_limit_max_per_member_account, rate_limit_max_per_identity)`.
+    pub type RateLimitParameters<T> = (
+        <T as Config>::BlockNumber,
+        <T as Config>::CertCount,
+        <T as Config>::CertCount,
+        <T as Config>::CertCount,
+    );
+
+    /// Adds the rate limit parameters to the stored parameters.
+    pub struct InnerMigrateV1ToV2<T, RateLimits>(core::marker::PhantomData<(T, RateLimits)>);
+    impl<T: Config, RateLimits: Get<RateLimitParameters<T>>> UncheckedOnRuntimeUpgrade
+        for InnerMigrateV1ToV2<T, RateLimits>
+    {
+        fn on_runtime_upgrade() -> Weight {
+            let (
+                rate_limit_window,
+                rate_limit_max_per_account,
+                rate_limit_max_per_member_account,
+                rate_limit_max_per_identity,
+            ) = RateLimits::get();
+            let _ = ParametersStorage::<T>::translate::<
+                ParametersV1<T::BlockNumber, T::CertCount, T::PeriodCount, T::SessionCount>,
+                _,
+            >(|maybe_old| {
+                maybe_old.map(|old| Parameters {
+                    babe_epoch_duration: old.babe_epoch_duration,
+                    cert_period: old.cert_period,
+                    cert_max_by_issuer: old.cert_max_by_issuer,
+                    cert_min_received_cert_to_issue_cert: old.cert_min_received_cert_to_issue_cert,
+                    cert_validity_period: old.cert_validity_period,
+                    idty_confirm_period: old.idty_confirm_period,
+                    idty_creation_period: old.idty_creation_period,
+                    membership_period: old.membership_period,
+                    membership_renewal_period: old.membership_renewal_period,
+                    ud_creation_period: old.ud_creation_period,
+                    ud_reeval_period: old.ud_reeval_period,
+                    smith_cert_max_by_issuer: old.smith_cert_max_by_issuer,
+                    smith_wot_min_cert_for_membership: old.smith_wot_min_cert_for_membership,
+                    smith_inactivity_max_duration: old.smith_inactivity_max_duration,
+                    wot_first_cert_issuable_on: old.wot_first_cert_issuable_on,
+                    wot_min_cert_for_create_idty_right: old.wot_min_cert_for_create_idty_right,
+                    wot_min_cert_for_membership: old.wot_min_cert_for_membership,
+                    evaluation_period: old.evaluation_period,
+                    rate_limit_window,
+                    rate_limit_max_per_account,
+                    rate_limit_max_per_member_account,
+                    rate_limit_max_per_identity,
+                })
+            });
+            T::DbWeight::get().reads_writes(1, 1)
+        }
+    }
+
+    /// Migrates the pallet storage from version 1 to version 2.
+    pub type MigrateV1ToV2<T, RateLimits> = VersionedMigration<
+        1,
+        2,
+        InnerMigrateV1ToV2<T, RateLimits>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -97,6 +97,18 @@ pub mod pallet {
         type MinCertForCreateIdtyRight: Get<u32>;
     }
 
+    // HOOKS //
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<frame_system::pallet_prelude::BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn try_state(
+            _n: frame_system::pallet_prelude::BlockNumberFor<T>,
+        ) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
     // INTERNAL FUNCTIONS //
 
     impl<T: Config> Pallet<T> {
@@ -107,6 +119,35 @@ pub mod pallet {
                 block_number + T::FirstIssuableOn::get(),
             );
         }
+
+        /// Check the invariants between identities and memberships.
+        ///
+        /// Every identity with `Member` status has a membership and every membership
+        /// belongs to an identity with `Member` status.
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let mut members_count = 0u32;
+            for (idty_index, idty_value) in pallet_identity::Identities::<T>::iter() {
+                if idty_value.status == IdtyStatus::Member {
+                    members_count += 1;
+                    ensure!(
+                        pallet_membership::Membership::<T>::contains_key(idty_index),
+                        "member identity has no membership"
+                    );
+                }
+            }
+            for (idty_index, _) in pallet_membership::Membership::<T>::iter() {
+                ensure!(
+                    pallet_identity::Identities::<T>::get(idty_index)
+                        .is_some_and(|idty_value| idty_value.status == IdtyStatus::Member),
+                    "membership does not belong to a member identity"
+                );
+            }
+            ensure!(
+                members_count == pallet_membership::Membership::<T>::count(),
+                "memberships count does not match the number of member identities"
+            );
+            Ok(())
+        }
     }
 
     // ERRORS //
@@ -310,6 +310,12 @@ fn test_idty_membership_expire() {
             Cert::add_cert(RuntimeOrigin::signed(1), 3),
             pallet_duniter_wot::Error::<Test>::IdtyNotFound
         );
+
+        // storage invariants still hold after the identity removal
+        assert_ok!(DuniterWot::do_try_state());
+        assert_ok!(Identity::do_try_state());
+        assert_ok!(Membership::do_try_state());
+        assert_ok!(Cert::do_try_state());
     });
 }
 
@@ -289,6 +289,11 @@ pub mod pallet {
                 T::WeightInfo::on_initialize()
             }
         }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
     }
 
     // EVENTS //
@@ -915,6 +920,60 @@ pub mod pallet {
             T::WeightInfo::do_revoke_identity_noop()
         }
 
+        /// Check the storage invariants of the pallet.
+        ///
+        /// - every identity is indexed by its owner key and the index is counted;
+        /// - every identity owner key is sufficient;
+        /// - only confirmed identities have a name;
+        /// - members have no scheduled action and other identities are scheduled once.
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let named_identities = IdentitiesNames::<T>::iter_values().collect::<BTreeSet<_>>();
+            let mut count = 0u32;
+            for (idty_index, idty_value) in Identities::<T>::iter() {
+                count += 1;
+                ensure!(
+                    IdentityIndexOf::<T>::get(&idty_value.owner_key) == Some(idty_index),
+                    "identity owner key is not indexed"
+                );
+                ensure!(
+                    frame_system::Pallet::<T>::sufficients(&idty_value.owner_key) > 0,
+                    "identity owner key is not sufficient"
+                );
+                ensure!(
+                    named_identities.contains(&idty_index)
+                        == (idty_value.status != IdtyStatus::Unconfirmed),
+                    "identity name does not match its confirmation"
+                );
+                if idty_value.status == IdtyStatus::Member {
+                    ensure!(
+                        idty_value.next_scheduled.is_zero(),
+                        "member identity has a scheduled action"
+                    );
+                } else {
+                    ensure!(
+                        IdentityChangeSchedule::<T>::get(idty_value.next_scheduled)
+                            .iter()
+                            .filter(|i| **i == idty_index)
+                            .count()
+                            == 1,
+                        "non-member identity is not scheduled exactly once"
+                    );
+                }
+            }
+            ensure!(
+                count == Identities::<T>::count(),
+                "identities counter does not match the number of identities"
+            );
+            ensure!(
+                IdentityIndexOf::<T>::iter().all(|(account_id, idty_index)| {
+                    Identities::<T>::get(idty_index)
+                        .is_some_and(|idty_value| idty_value.owner_key == account_id)
+                }),
+                "account is indexed to an identity it does not own"
+            );
+            Ok(())
+        }
+
         /// incremental counter for identity index
         fn get_next_idty_index() -> T::IdtyIndex {
             if let Ok(next_index) = <NextIdtyIndex<T>>::try_get() {
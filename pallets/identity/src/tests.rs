@@ -1022,6 +1022,33 @@ fn test_revocation_of_genesis_member() {
     });
 }
 
+#[test]
+fn test_try_state() {
+    new_test_ext(IdentityConfig {
+        identities: vec![alice()],
+    })
+    .execute_with(|| {
+        assert_ok!(Identity::do_try_state());
+
+        run_to_block(1);
+        assert_ok!(Identity::create_identity(
+            RuntimeOrigin::signed(account(1).id),
+            account(2).id
+        ));
+        assert_ok!(Identity::do_try_state());
+
+        // Unconfirmed identity 2 is removed
+        run_to_block(3);
+        assert_ok!(Identity::do_try_state());
+
+        // A member with a scheduled action is detected
+        pallet::Identities::<Test>::mutate(1, |idty| {
+            idty.as_mut().unwrap().next_scheduled = 5;
+        });
+        assert!(Identity::do_try_state().is_err());
+    });
+}
+
 fn revoke_self_identity(idty: GenesisIdty<Test>) -> DispatchResult {
     Identity::revoke_identity(
         RuntimeOrigin::signed(account(idty.index as u8).id),
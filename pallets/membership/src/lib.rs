@@ -206,6 +206,11 @@ pub mod pallet {
                 T::WeightInfo::on_initialize()
             }
         }
+
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
     }
 
     // // CALLS //
@@ -325,6 +330,43 @@ pub mod pallet {
             T::WeightInfo::expire_memberships(expired_idty_count)
         }
 
+        /// Check the storage invariants of the pallet.
+        ///
+        /// - every membership expires in the future and is scheduled once at its expiry block;
+        /// - every scheduled expiry refers to an existing membership.
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let block_number = frame_system::pallet::Pallet::<T>::block_number();
+            let mut count = 0u32;
+            for (idty_id, membership_data) in Membership::<T>::iter() {
+                count += 1;
+                ensure!(
+                    membership_data.expire_on > block_number,
+                    "membership should already have expired"
+                );
+                ensure!(
+                    MembershipsExpireOn::<T>::get(membership_data.expire_on)
+                        .iter()
+                        .filter(|id| **id == idty_id)
+                        .count()
+                        == 1,
+                    "membership expiry is not scheduled exactly once"
+                );
+            }
+            ensure!(
+                count == Membership::<T>::count(),
+                "membership counter does not match the number of memberships"
+            );
+            for (expire_on, idty_ids) in MembershipsExpireOn::<T>::iter() {
+                for idty_id in idty_ids {
+                    ensure!(
+                        Membership::<T>::get(idty_id).map(|m| m.expire_on) == Some(expire_on),
+                        "scheduled expiry does not match a membership"
+                    );
+                }
+            }
+            Ok(())
+        }
+
         /// Check if an identity is a member.
         pub fn is_member(idty_id: &T::IdtyId) -> bool {
             Membership::<T>::contains_key(idty_id)
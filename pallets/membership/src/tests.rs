@@ -88,6 +88,22 @@ fn test_membership_renewal() {
     });
 }
 
+/// test storage invariants through membership lifecycle
+#[test]
+fn test_try_state() {
+    new_test_ext(default_gen_conf()).execute_with(|| {
+        assert_ok!(Membership::do_try_state());
+        run_to_block(2);
+        assert_ok!(Membership::try_renew_membership(0));
+        assert_ok!(Membership::do_try_state());
+        run_to_block(7);
+        assert_ok!(Membership::do_try_state());
+        // a membership without scheduled expiry is detected
+        crate::Membership::<Test>::insert(1, MembershipData { expire_on: 10 });
+        assert!(Membership::do_try_state().is_err());
+    });
+}
+
 /// test membership renewal for non member identity
 #[test]
 fn test_membership_renewal_nope() {
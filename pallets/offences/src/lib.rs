@@ -24,6 +24,8 @@
 //!
 //! - **`im-online` Pallet Offences**: Offences from the `im-online` pallet necessitate disconnection of the offender.
 //!
+//! - **`distance` Pallet Offences**: Evaluators whose distance results deviate too often from the median necessitate disconnection of the offender, until its oracle is fixed.
+//!
 //! - **Other Offences**: For all other offences, the pallet enforces:
 //!   - Disconnection of the offender.
 //!   - Addition of the offender to a blacklist.
@@ -141,7 +143,7 @@ where
         };
 
         // Define the slash strategy.
-        let slash_strategy = if O::ID == *b"im-online:offlin" {
+        let slash_strategy = if O::ID == *b"im-online:offlin" || O::ID == *b"distance:outlier" {
             SlashStrategy::Disconnect
         } else {
             SlashStrategy::Blacklist
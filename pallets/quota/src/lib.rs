@@ -34,6 +34,24 @@
 //! Refunds are executed under the following conditions:
 //! 1. The paying account is linked to an identity.
 //! 2. Quotas are allocated to the identity and have a non-zero value after updates.
+//!
+//! ## Transaction Priority
+//!
+//! Quotas also gate the transaction pool priority boost (`PriorityBoost`) granted to Web of Trust calls (see `CheckWotPriority` in the runtime).
+//! To prevent abuse, each identity can only use the boost `MaxPriorityPerWindow` times per `PriorityWindow` blocks.
+//! Usage is tracked in `IdtyPriorityUsage` and recorded with `note_priority_use` when a boosted transaction is dispatched.
+//! The transaction pool holds at most one pending boosted transaction per identity and call, since they provide a tag derived from `IdtyPriorityUsage` and the call.
+//!
+//! ## Rate Limit
+//!
+//! Since transactions are free while the chain is under its load target, the number of free transactions is limited
+//! over a rolling window of `RateLimitWindow` blocks (see `CheckRateLimit` in the runtime):
+//! - per account, with a higher limit for accounts linked to a member (`RateLimitMaxPerAccount` and `RateLimitMaxPerMemberAccount`);
+//! - per identity, for all the accounts linked to it (`RateLimitMaxPerIdentity`).
+//!
+//! The window is split into `RATE_LIMIT_BUCKETS` buckets so that counts are stored in a bounded way.
+//! The counts of the accounts which have not issued any free transaction in the window are removed during the `on_idle` phase,
+//! by `prune_account_tx_counts`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -53,7 +71,10 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::*;
 use scale_info::prelude::vec::Vec;
-use sp_runtime::traits::Zero;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, One, Zero},
+    transaction_validity::TransactionPriority,
+};
 
 pub use pallet::*;
 pub use traits::*;
@@ -65,6 +86,7 @@ pub mod pallet {
     use super::*;
 
     pub const MAX_QUEUED_REFUNDS: u32 = 256;
+    pub const RATE_LIMIT_BUCKETS: u32 = 4;
 
     // Currency used for quota is the one of pallet balances
     pub type CurrencyOf<T> = pallet_balances::Pallet<T>;
@@ -92,6 +114,34 @@ pub mod pallet {
         #[pallet::constant]
         type RefundAccount: Get<Self::AccountId>;
 
+        /// Number of blocks of the window in which priority usage is counted.
+        #[pallet::constant]
+        type PriorityWindow: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of priority transactions an identity can issue per window.
+        #[pallet::constant]
+        type MaxPriorityPerWindow: Get<u32>;
+
+        /// Priority added to eligible transactions in the transaction pool.
+        #[pallet::constant]
+        type PriorityBoost: Get<TransactionPriority>;
+
+        /// Number of blocks of the rolling window in which free transactions are counted.
+        #[pallet::constant]
+        type RateLimitWindow: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of free transactions per window for an account not linked to a member.
+        #[pallet::constant]
+        type RateLimitMaxPerAccount: Get<u32>;
+
+        /// Maximum number of free transactions per window for an account linked to a member.
+        #[pallet::constant]
+        type RateLimitMaxPerMemberAccount: Get<u32>;
+
+        /// Maximum number of free transactions per window for all the accounts linked to an identity.
+        #[pallet::constant]
+        type RateLimitMaxPerIdentity: Get<u32>;
+
         /// Type representing the weight of this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -117,6 +167,53 @@ pub mod pallet {
         pub amount: Balance,
     }
 
+    /// Represents the priority usage of an identity in the current window.
+    #[derive(Encode, Decode, Clone, TypeInfo, Debug, PartialEq, MaxEncodedLen)]
+    pub struct PriorityUsage<BlockNumber> {
+        /// Block number at which the current window started.
+        pub window_start: BlockNumber,
+        /// Number of priority transactions issued in the current window.
+        pub count: u32,
+    }
+
+    /// Represents the transaction counts of a rolling window, split into buckets.
+    #[derive(Encode, Decode, Clone, TypeInfo, Debug, PartialEq, MaxEncodedLen)]
+    pub struct RollingCount<BlockNumber> {
+        /// Starting block and transaction count of each bucket, oldest first.
+        pub buckets: BoundedVec<(BlockNumber, u32), ConstU32<RATE_LIMIT_BUCKETS>>,
+    }
+
+    impl<BlockNumber: AtLeast32BitUnsigned + Copy> RollingCount<BlockNumber> {
+        /// Removes the buckets that are out of the window ending at `current_bucket`.
+        fn prune(&mut self, current_bucket: BlockNumber, window: BlockNumber) {
+            self.buckets
+                .retain(|(start, _)| start.saturating_add(window) > current_bucket);
+        }
+
+        /// Returns the number of transactions in the window.
+        fn total(&self) -> u32 {
+            self.buckets
+                .iter()
+                .fold(0u32, |acc, (_, count)| acc.saturating_add(*count))
+        }
+
+        /// Counts one more transaction in the current bucket.
+        fn increment(&mut self, current_bucket: BlockNumber) {
+            let len = self.buckets.len();
+            match len.checked_sub(1).and_then(|i| self.buckets.get_mut(i)) {
+                Some((start, count)) if *start == current_bucket => {
+                    *count = count.saturating_add(1);
+                }
+                _ => {
+                    if self.buckets.is_full() {
+                        self.buckets.remove(0);
+                    }
+                    let _ = self.buckets.try_push((current_bucket, 1));
+                }
+            }
+        }
+    }
+
     // STORAGE //
     /// The quota for each identity.
     #[pallet::storage]
@@ -124,6 +221,29 @@ pub mod pallet {
     pub type IdtyQuota<T: Config> =
         StorageMap<_, Twox64Concat, IdtyId<T>, Quota<BlockNumberFor<T>, BalanceOf<T>>, OptionQuery>;
 
+    /// The priority usage for each identity.
+    #[pallet::storage]
+    pub type IdtyPriorityUsage<T: Config> =
+        StorageMap<_, Twox64Concat, IdtyId<T>, PriorityUsage<BlockNumberFor<T>>, OptionQuery>;
+
+    /// The free transactions issued by each account in the rolling window.
+    #[pallet::storage]
+    pub type AccountTxCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, RollingCount<BlockNumberFor<T>>, OptionQuery>;
+
+    /// The last account whose free transaction count was checked by `prune_account_tx_counts`.
+    #[pallet::storage]
+    pub type AccountTxCountCursor<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
+    /// The free transactions issued by the accounts linked to each identity in the rolling window.
+    #[pallet::storage]
+    pub type IdtyTxCount<T: Config> =
+        StorageMap<_, Twox64Concat, IdtyId<T>, RollingCount<BlockNumberFor<T>>, OptionQuery>;
+
+    /// The last identity whose free transaction count was checked by `prune_idty_tx_counts`.
+    #[pallet::storage]
+    pub type IdtyTxCountCursor<T: Config> = StorageValue<_, IdtyId<T>, OptionQuery>;
+
     /// The fees waiting to be refunded.
     #[pallet::storage]
     pub type RefundQueue<T: Config> = StorageValue<
@@ -178,6 +298,98 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> Pallet<T> {
+        /// Checks whether an identity has priority usage left in the current window.
+        ///
+        /// This function does not write to storage and is meant to be called during transaction validation.
+        pub fn can_use_priority(idty_index: IdtyId<T>) -> bool {
+            Self::priority_usage_in_window(idty_index) < T::MaxPriorityPerWindow::get()
+        }
+
+        /// Records that an identity used the priority boost at the current block.
+        pub fn note_priority_use(idty_index: IdtyId<T>) {
+            let current_block = frame_system::pallet::Pallet::<T>::block_number();
+            IdtyPriorityUsage::<T>::mutate(idty_index, |maybe_usage| match maybe_usage {
+                Some(usage) if current_block < usage.window_start + T::PriorityWindow::get() => {
+                    usage.count = usage.count.saturating_add(1);
+                }
+                _ => {
+                    *maybe_usage = Some(PriorityUsage {
+                        window_start: current_block,
+                        count: 1,
+                    });
+                }
+            });
+        }
+
+        /// Checks whether an account, and the identity it is linked to, can issue one more free transaction.
+        ///
+        /// This function does not write to storage and is meant to be called during transaction validation.
+        pub fn check_rate_limit(account: &T::AccountId, linked_idty: Option<IdtyId<T>>) -> bool {
+            let (current_bucket, window) = Self::rate_limit_window();
+            let count_in_window = |maybe_count: Option<RollingCount<BlockNumberFor<T>>>| {
+                maybe_count.map_or(0, |mut count| {
+                    count.prune(current_bucket, window);
+                    count.total()
+                })
+            };
+
+            let max_per_account = if linked_idty.is_some_and(is_eligible_for_refund::<T>) {
+                T::RateLimitMaxPerMemberAccount::get()
+            } else {
+                T::RateLimitMaxPerAccount::get()
+            };
+            if count_in_window(AccountTxCount::<T>::get(account)) >= max_per_account {
+                return false;
+            }
+            match linked_idty {
+                Some(idty_index) => {
+                    count_in_window(IdtyTxCount::<T>::get(idty_index))
+                        < T::RateLimitMaxPerIdentity::get()
+                }
+                None => true,
+            }
+        }
+
+        /// Records a free transaction issued by an account and the identity it is linked to.
+        pub fn note_transaction(account: &T::AccountId, linked_idty: Option<IdtyId<T>>) {
+            let (current_bucket, window) = Self::rate_limit_window();
+            let record = |maybe_count: &mut Option<RollingCount<BlockNumberFor<T>>>| {
+                let count = maybe_count.get_or_insert_with(|| RollingCount {
+                    buckets: BoundedVec::new(),
+                });
+                count.prune(current_bucket, window);
+                count.increment(current_bucket);
+            };
+            AccountTxCount::<T>::mutate(account, record);
+            if let Some(idty_index) = linked_idty {
+                IdtyTxCount::<T>::mutate(idty_index, record);
+            }
+        }
+
+        /// Returns the starting block of the current bucket and the duration of the rolling window.
+        fn rate_limit_window() -> (BlockNumberFor<T>, BlockNumberFor<T>) {
+            let bucket_length = (T::RateLimitWindow::get() / RATE_LIMIT_BUCKETS.into())
+                .max(BlockNumberFor::<T>::one());
+            let current_block = frame_system::pallet::Pallet::<T>::block_number();
+            (
+                current_block - current_block % bucket_length,
+                bucket_length * RATE_LIMIT_BUCKETS.into(),
+            )
+        }
+
+        /// Returns the number of priority transactions issued by an identity in the current window.
+        fn priority_usage_in_window(idty_index: IdtyId<T>) -> u32 {
+            let current_block = frame_system::pallet::Pallet::<T>::block_number();
+            match IdtyPriorityUsage::<T>::get(idty_index) {
+                Some(usage) if current_block < usage.window_start + T::PriorityWindow::get() => {
+                    usage.count
+                }
+                _ => 0,
+            }
+        }
+    }
+
     // INTERNAL FUNCTIONS //
     impl<T: Config> Pallet<T> {
         /// Adds a new refund request to the refund queue.
@@ -275,6 +487,88 @@ pub mod pallet {
             })
         }
 
+        /// Removes the free transaction counts of the accounts that have no transaction left in the window,
+        /// within the supplied weight limit.
+        ///
+        /// Accounts are checked in storage order, resuming after `AccountTxCountCursor`.
+        pub fn prune_account_tx_counts(weight_limit: Weight) -> Weight {
+            // cursor read and write
+            let mut total_weight = T::DbWeight::get().reads_writes(1, 1);
+            // count read and removal
+            let per_account = T::DbWeight::get().reads_writes(1, 1);
+            if total_weight
+                .saturating_add(per_account)
+                .any_gt(weight_limit)
+            {
+                return Weight::zero();
+            }
+
+            let (current_bucket, window) = Self::rate_limit_window();
+            let mut counts = match AccountTxCountCursor::<T>::get() {
+                Some(account) => {
+                    AccountTxCount::<T>::iter_from(AccountTxCount::<T>::hashed_key_for(account))
+                }
+                None => AccountTxCount::<T>::iter(),
+            };
+            let mut cursor = None;
+            while total_weight
+                .saturating_add(per_account)
+                .all_lte(weight_limit)
+            {
+                // the end of the map is reached, the next call starts over
+                let Some((account, mut count)) = counts.next() else {
+                    cursor = None;
+                    break;
+                };
+                count.prune(current_bucket, window);
+                if count.buckets.is_empty() {
+                    AccountTxCount::<T>::remove(&account);
+                }
+                total_weight = total_weight.saturating_add(per_account);
+                cursor = Some(account);
+            }
+            AccountTxCountCursor::<T>::set(cursor);
+            total_weight
+        }
+
+        /// Removes the free transaction counts of the identities that have no transaction left in the window,
+        /// within the supplied weight limit.
+        ///
+        /// Identities are checked in storage order, resuming after `IdtyTxCountCursor`.
+        pub fn prune_idty_tx_counts(weight_limit: Weight) -> Weight {
+            // cursor read and write
+            let mut total_weight = T::DbWeight::get().reads_writes(1, 1);
+            // count read and removal
+            let per_idty = T::DbWeight::get().reads_writes(1, 1);
+            if total_weight.saturating_add(per_idty).any_gt(weight_limit) {
+                return Weight::zero();
+            }
+
+            let (current_bucket, window) = Self::rate_limit_window();
+            let mut counts = match IdtyTxCountCursor::<T>::get() {
+                Some(idty_index) => {
+                    IdtyTxCount::<T>::iter_from(IdtyTxCount::<T>::hashed_key_for(idty_index))
+                }
+                None => IdtyTxCount::<T>::iter(),
+            };
+            let mut cursor = None;
+            while total_weight.saturating_add(per_idty).all_lte(weight_limit) {
+                // the end of the map is reached, the next call starts over
+                let Some((idty_index, mut count)) = counts.next() else {
+                    cursor = None;
+                    break;
+                };
+                count.prune(current_bucket, window);
+                if count.buckets.is_empty() {
+                    IdtyTxCount::<T>::remove(idty_index);
+                }
+                total_weight = total_weight.saturating_add(per_idty);
+                cursor = Some(idty_index);
+            }
+            IdtyTxCountCursor::<T>::set(cursor);
+            total_weight
+        }
+
         /// Spends the quota of an identity by deducting the specified `amount` from its quota balance.
         pub fn spend_quota(idty_id: IdtyId<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
             IdtyQuota::<T>::mutate_exists(idty_id, |quota| {
@@ -353,9 +647,15 @@ pub mod pallet {
     // HOOKS //
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        // process refund queue if space left on block
+        // process refund queue, then remove expired transaction counts, if space left on block
         fn on_idle(_block: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
-            Self::process_refund_queue(remaining_weight)
+            let mut weight = Self::process_refund_queue(remaining_weight);
+            weight = weight.saturating_add(Self::prune_account_tx_counts(
+                remaining_weight.saturating_sub(weight),
+            ));
+            weight.saturating_add(Self::prune_idty_tx_counts(
+                remaining_weight.saturating_sub(weight),
+            ))
         }
     }
 }
@@ -412,7 +712,8 @@ impl<T: Config> sp_membership::traits::OnRemoveMembership<IdtyId<T>> for Pallet<
         };
 
         IdtyQuota::<T>::remove(idty_id);
-        add_db_reads_writes(1, 1);
+        IdtyPriorityUsage::<T>::remove(idty_id);
+        add_db_reads_writes(2, 2);
         weight
     }
 }
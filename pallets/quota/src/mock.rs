@@ -61,9 +61,23 @@ impl frame_support::pallet_prelude::Get<AccountId> for TreasuryAccountId {
 parameter_types! {
     pub const ReloadRate: u64 = 10;
     pub const MaxQuota: u64 = 1000;
+    pub const PriorityWindow: u64 = 10;
+    pub const MaxPriorityPerWindow: u32 = 2;
+    pub const PriorityBoost: u64 = 1_000;
+    pub const RateLimitWindow: u64 = 8;
+    pub const RateLimitMaxPerAccount: u32 = 2;
+    pub const RateLimitMaxPerMemberAccount: u32 = 4;
+    pub const RateLimitMaxPerIdentity: u32 = 5;
 }
 impl Config for Test {
+    type MaxPriorityPerWindow = MaxPriorityPerWindow;
     type MaxQuota = MaxQuota;
+    type PriorityBoost = PriorityBoost;
+    type PriorityWindow = PriorityWindow;
+    type RateLimitMaxPerAccount = RateLimitMaxPerAccount;
+    type RateLimitMaxPerIdentity = RateLimitMaxPerIdentity;
+    type RateLimitMaxPerMemberAccount = RateLimitMaxPerMemberAccount;
+    type RateLimitWindow = RateLimitWindow;
     type RefundAccount = TreasuryAccountId;
     type ReloadRate = ReloadRate;
     type WeightInfo = ();
@@ -82,6 +96,7 @@ impl system::Config for Test {
     type BaseCallFilter = Everything;
     type Block = Block;
     type BlockHashCount = BlockHashCount;
+    type DbWeight = frame_support::weights::constants::RocksDbWeight;
     type Hash = H256;
     type Hashing = BlakeTwo256;
     type Lookup = IdentityLookup<Self::AccountId>;
@@ -367,3 +367,186 @@ fn test_process_refund_queue_weight_no_quotas() {
         }));
     })
 }
+
+/// test that priority usage is limited per window
+#[test]
+fn test_priority_usage_window() {
+    new_test_ext(QuotaConfig {
+        identities: vec![1, 2],
+    })
+    .execute_with(|| {
+        run_to_block(1);
+        // no usage yet
+        assert!(Quota::can_use_priority(1));
+        assert_eq!(pallet_quota::IdtyPriorityUsage::<Test>::get(1), None);
+
+        // use the boost up to the limit
+        Quota::note_priority_use(1);
+        assert!(Quota::can_use_priority(1));
+        Quota::note_priority_use(1);
+        assert!(!Quota::can_use_priority(1));
+        assert_eq!(
+            pallet_quota::IdtyPriorityUsage::<Test>::get(1),
+            Some(pallet_quota::PriorityUsage {
+                window_start: 1,
+                count: 2
+            })
+        );
+        // other identities are not impacted
+        assert!(Quota::can_use_priority(2));
+
+        // still in the same window
+        run_to_block(10);
+        assert!(!Quota::can_use_priority(1));
+
+        // a new window starts after PriorityWindow blocks
+        run_to_block(11);
+        assert!(Quota::can_use_priority(1));
+        Quota::note_priority_use(1);
+        assert_eq!(
+            pallet_quota::IdtyPriorityUsage::<Test>::get(1),
+            Some(pallet_quota::PriorityUsage {
+                window_start: 11,
+                count: 1
+            })
+        );
+    })
+}
+
+/// test that free transactions of accounts without identity are limited over a rolling window
+#[test]
+fn test_rate_limit_account() {
+    new_test_ext(QuotaConfig { identities: vec![] }).execute_with(|| {
+        run_to_block(1);
+        assert!(Quota::check_rate_limit(&account(1), None));
+        Quota::note_transaction(&account(1), None);
+        assert!(Quota::check_rate_limit(&account(1), None));
+        Quota::note_transaction(&account(1), None);
+        // limit reached
+        assert!(!Quota::check_rate_limit(&account(1), None));
+        // other accounts are not impacted
+        assert!(Quota::check_rate_limit(&account(2), None));
+
+        // transactions are counted in buckets of 2 blocks over a window of 8 blocks
+        run_to_block(4);
+        Quota::note_transaction(&account(2), None);
+        run_to_block(7);
+        assert!(!Quota::check_rate_limit(&account(1), None));
+        // first bucket is out of the window
+        run_to_block(8);
+        assert!(Quota::check_rate_limit(&account(1), None));
+        Quota::note_transaction(&account(1), None);
+        Quota::note_transaction(&account(2), None);
+        // the second transaction of account 2 is still in the window
+        assert!(!Quota::check_rate_limit(&account(2), None));
+        assert_eq!(
+            pallet_quota::AccountTxCount::<Test>::get(account(2))
+                .unwrap()
+                .buckets
+                .into_inner(),
+            vec![(4, 1), (8, 1)]
+        );
+    })
+}
+
+/// test that accounts linked to a member get a higher limit, bounded by the identity limit
+#[test]
+fn test_rate_limit_identity() {
+    new_test_ext(QuotaConfig {
+        identities: vec![1],
+    })
+    .execute_with(|| {
+        run_to_block(1);
+        pallet_identity::Identities::<Test>::insert(
+            1,
+            pallet_identity::IdtyValue {
+                data: (),
+                next_creatable_identity_on: 0,
+                old_owner_key: None,
+                owner_key: account(1),
+                next_scheduled: 0,
+                status: pallet_identity::IdtyStatus::Member,
+            },
+        );
+
+        // member account limit is higher than the default account limit
+        for _ in 0..4 {
+            assert!(Quota::check_rate_limit(&account(1), Some(1)));
+            Quota::note_transaction(&account(1), Some(1));
+        }
+        assert!(!Quota::check_rate_limit(&account(1), Some(1)));
+
+        // another account linked to the same identity is limited by the identity limit
+        assert!(Quota::check_rate_limit(&account(2), Some(1)));
+        Quota::note_transaction(&account(2), Some(1));
+        assert!(!Quota::check_rate_limit(&account(2), Some(1)));
+
+        // once the identity is no longer a member, the default account limit applies
+        pallet_identity::Identities::<Test>::mutate(1, |idty| {
+            idty.as_mut().unwrap().status = pallet_identity::IdtyStatus::NotMember
+        });
+        run_to_block(10);
+        Quota::note_transaction(&account(1), Some(1));
+        Quota::note_transaction(&account(1), Some(1));
+        assert!(!Quota::check_rate_limit(&account(1), Some(1)));
+    })
+}
+
+/// test that the counts of accounts without transaction in the window are removed
+#[test]
+fn test_prune_account_tx_counts() {
+    new_test_ext(QuotaConfig { identities: vec![] }).execute_with(|| {
+        run_to_block(1);
+        Quota::note_transaction(&account(1), None);
+        Quota::note_transaction(&account(2), None);
+        Quota::note_transaction(&account(3), None);
+        run_to_block(6);
+        Quota::note_transaction(&account(2), None);
+
+        // counts in the window are kept
+        Quota::prune_account_tx_counts(Weight::MAX);
+        assert_eq!(pallet_quota::AccountTxCount::<Test>::iter().count(), 3);
+
+        // once the first bucket is out of the window, only account 2 has a count left,
+        // accounts are checked as long as the weight allows it
+        run_to_block(8);
+        let one_account = <Test as frame_system::Config>::DbWeight::get().reads_writes(2, 2);
+        Quota::prune_account_tx_counts(one_account);
+        let checked = pallet_quota::AccountTxCountCursor::<Test>::get().unwrap();
+        assert_eq!(
+            pallet_quota::AccountTxCount::<Test>::contains_key(&checked),
+            checked == account(2)
+        );
+        Quota::prune_account_tx_counts(Weight::MAX);
+        assert_eq!(
+            pallet_quota::AccountTxCount::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![account(2)]
+        );
+        assert_eq!(pallet_quota::AccountTxCountCursor::<Test>::get(), None);
+    })
+}
+
+/// test that the counts of identities without transaction in the window are removed
+#[test]
+fn test_prune_idty_tx_counts() {
+    new_test_ext(QuotaConfig { identities: vec![] }).execute_with(|| {
+        run_to_block(1);
+        Quota::note_transaction(&account(1), Some(1));
+        Quota::note_transaction(&account(2), Some(2));
+        run_to_block(6);
+        Quota::note_transaction(&account(2), Some(2));
+
+        // counts in the window are kept
+        Quota::prune_idty_tx_counts(Weight::MAX);
+        assert_eq!(pallet_quota::IdtyTxCount::<Test>::iter().count(), 2);
+
+        // once the first bucket is out of the window, only identity 2 has a count left
+        run_to_block(8);
+        Quota::prune_idty_tx_counts(Weight::MAX);
+        assert_eq!(
+            pallet_quota::IdtyTxCount::<Test>::iter_keys().collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(pallet_quota::IdtyTxCountCursor::<Test>::get(), None);
+    })
+}
@@ -320,6 +320,14 @@ pub mod pallet {
         CertificationStockFullyConsumed,
     }
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// Invite a member of the Web of Trust to attempt becoming a Smith.
@@ -533,14 +541,18 @@ impl<T: Config> Pallet<T> {
     pub fn on_removed_wot_member(idty_index: T::IdtyIndex) -> Weight {
         let mut weight = T::WeightInfo::on_removed_wot_member_empty();
         if Smiths::<T>::get(idty_index).is_some() {
-            Self::_do_exclude_smith(idty_index, SmithRemovalReason::LostMembership);
-            weight = weight.saturating_add(T::WeightInfo::on_removed_wot_member());
+            weight = weight
+                .saturating_add(T::WeightInfo::on_removed_wot_member())
+                .saturating_add(Self::_do_exclude_smith(
+                    idty_index,
+                    SmithRemovalReason::LostMembership,
+                ));
         }
         weight
     }
 
-    /// Perform the exclusion of a Smith.
-    fn _do_exclude_smith(receiver: T::IdtyIndex, reason: SmithRemovalReason) {
+    /// Perform the exclusion of a Smith and return the weight of the cascading actions.
+    fn _do_exclude_smith(receiver: T::IdtyIndex, reason: SmithRemovalReason) -> Weight {
         let mut lost_certs = vec![];
         Smiths::<T>::mutate(receiver, |maybe_smith_meta| {
             if let Some(smith_meta) = maybe_smith_meta {
@@ -568,10 +580,11 @@ impl<T: Config> Pallet<T> {
             });
         }
         // Deletion done: notify (authority-members) for cascading
-        T::OnSmithDelete::on_smith_delete(receiver, reason);
+        let weight = T::OnSmithDelete::on_smith_delete(receiver, reason);
         Self::deposit_event(Event::<T>::SmithMembershipRemoved {
             idty_index: receiver,
         });
+        weight
     }
 
     /// Handle the event when a Smith goes online.
@@ -606,6 +619,53 @@ impl<T: Config> Pallet<T> {
         });
     }
 
+    /// Check the storage invariants of the pallet.
+    ///
+    /// - certifications are sorted and recorded on both the issuer and the receiver side;
+    /// - excluded smiths have no received certification and no expiry;
+    /// - every expiry is scheduled at its session.
+    pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+        for (idty_index, smith_meta) in Smiths::<T>::iter() {
+            ensure!(
+                smith_meta.received_certs.windows(2).all(|w| w[0] < w[1]),
+                "smith received certifications are not strictly sorted"
+            );
+            ensure!(
+                smith_meta.issued_certs.windows(2).all(|w| w[0] < w[1]),
+                "smith issued certifications are not strictly sorted"
+            );
+            for issuer in &smith_meta.received_certs {
+                ensure!(
+                    Smiths::<T>::get(issuer)
+                        .is_some_and(|issuer_meta| issuer_meta.issued_certs.contains(&idty_index)),
+                    "smith received certification is not recorded by its issuer"
+                );
+            }
+            for receiver in &smith_meta.issued_certs {
+                ensure!(
+                    Smiths::<T>::get(receiver).is_some_and(|receiver_meta| receiver_meta
+                        .received_certs
+                        .contains(&idty_index)),
+                    "smith issued certification is not recorded by its receiver"
+                );
+            }
+            if smith_meta.status == SmithStatus::Excluded {
+                ensure!(
+                    smith_meta.received_certs.is_empty() && smith_meta.expires_on.is_none(),
+                    "excluded smith keeps received certifications or an expiry"
+                );
+            }
+            if let Some(expires_on) = smith_meta.expires_on {
+                ensure!(
+                    ExpiresOn::<T>::get(expires_on)
+                        .is_some_and(|smiths| smiths.contains(&idty_index)),
+                    "smith expiry is not scheduled"
+                );
+            }
+        }
+        Ok(())
+    }
+
     /// Provide whether the given identity index is a Smith.
     fn provide_is_member(idty_id: &T::IdtyIndex) -> bool {
         Smiths::<T>::get(idty_id).is_some_and(|smith| smith.status == SmithStatus::Smith)
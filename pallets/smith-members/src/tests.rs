@@ -188,6 +188,7 @@ fn process_to_become_a_smith_and_lose_it() {
                 last_online: None,
             })
         );
+        assert_ok!(Pallet::<Runtime>::do_try_state());
     });
 }
 
@@ -504,6 +505,7 @@ fn smith_coming_back_recovers_its_issued_certs() {
             Pallet::<Runtime>::certify_smith(RuntimeOrigin::signed(2), 5),
             Error::<Runtime>::CertificationStockFullyConsumed
         );
+        assert_ok!(Pallet::<Runtime>::do_try_state());
     });
 }
 
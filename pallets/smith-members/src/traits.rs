@@ -1,11 +1,14 @@
 use crate::SmithRemovalReason;
+use frame_support::pallet_prelude::Weight;
 
 /// Trait for handling actions when a Smith is deleted.
 pub trait OnSmithDelete<IdtyIndex> {
-    /// Handle the deletion of a smith.
-    fn on_smith_delete(idty_index: IdtyIndex, reason: SmithRemovalReason);
+    /// Handle the deletion of a smith and return the weight consumed.
+    fn on_smith_delete(idty_index: IdtyIndex, reason: SmithRemovalReason) -> Weight;
 }
 
 impl<IdtyIndex> OnSmithDelete<IdtyIndex> for () {
-    fn on_smith_delete(_: IdtyIndex, _: SmithRemovalReason) {}
+    fn on_smith_delete(_: IdtyIndex, _: SmithRemovalReason) -> Weight {
+        Weight::zero()
+    }
 }
@@ -286,6 +286,16 @@ pub mod pallet {
         AccountNotAllowedToClaimUds,
     }
 
+    // HOOKS //
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        #[cfg(feature = "try-runtime")]
+        fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+            Self::do_try_state()
+        }
+    }
+
     // INTERNAL FUNCTIONS //
     impl<T: Config> Pallet<T> {
         /// create universal dividend
@@ -458,6 +468,31 @@ pub mod pallet {
     // PUBLIC FUNCTIONS
 
     impl<T: Config> Pallet<T> {
+        /// Check the storage invariants of the pallet.
+        ///
+        /// Past reevaluations are ordered by UD index, none is in the future and the last one
+        /// is the current UD amount.
+        pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+            let past_reevals = PastReevals::<T>::get();
+            ensure!(
+                past_reevals.windows(2).all(|w| w[0].0 <= w[1].0),
+                "past reevaluations are not sorted by UD index"
+            );
+            let (last_reeval_index, last_reeval_amount) = past_reevals
+                .last()
+                .copied()
+                .ok_or("past reevaluations are empty")?;
+            ensure!(
+                last_reeval_index <= CurrentUdIndex::<T>::get(),
+                "past reevaluation is in the future"
+            );
+            ensure!(
+                last_reeval_amount == CurrentUd::<T>::get(),
+                "last reevaluation does not match the current UD"
+            );
+            Ok(())
+        }
+
         /// Initialize the first eligible Universal Dividend index.
         pub fn init_first_eligible_ud() -> FirstEligibleUd {
             CurrentUdIndex::<T>::get().into()
@@ -353,6 +353,7 @@ fn test_ud_creation() {
             },
         ));
         assert_eq!(UniversalDividend::total_money_created(), 25_671);
+        assert_ok!(UniversalDividend::do_try_state());
     });
 }
 
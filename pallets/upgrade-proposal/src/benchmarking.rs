@@ -0,0 +1,148 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::prelude::vec;
+use sp_runtime::traits::Hash;
+
+/// Size of the code used in benchmarks, close to the size of a real runtime.
+const CODE_SIZE: usize = 2 * 1024 * 1024;
+
+fn assert_has_event<T: Config>(generic_event: <T as frame_system::Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_has_event(generic_event);
+}
+
+/// Notes a junk code in the preimage pallet and returns its hash.
+fn note_code<T: Config>() -> Result<T::Hash, BenchmarkError> {
+    T::Preimages::note(vec![0u8; CODE_SIZE].into())
+        .map_err(|_| BenchmarkError::Stop("cannot note code"))
+}
+
+/// Opens a proposal for a junk code and returns its hash.
+fn propose<T: Config>() -> Result<T::Hash, BenchmarkError> {
+    let origin =
+        T::ProposeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+    let code_hash = note_code::<T>()?;
+    Pallet::<T>::propose_upgrade(
+        origin,
+        code_hash,
+        T::Version::get().spec_version + 1,
+        T::Hashing::hash(b"release notes"),
+    )
+    .map_err(|_| BenchmarkError::Stop("cannot propose upgrade"))?;
+    Ok(code_hash)
+}
+
+/// Returns the earliest allowed enactment block.
+fn enactment<T: Config>() -> BlockNumberFor<T> {
+    frame_system::Pallet::<T>::block_number() + T::MinEnactmentDelay::get()
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn propose_upgrade() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ProposeOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let code_hash = note_code::<T>()?;
+        let spec_version = T::Version::get().spec_version + 1;
+
+        #[extrinsic_call]
+        _(
+            origin as T::RuntimeOrigin,
+            code_hash,
+            spec_version,
+            T::Hashing::hash(b"release notes"),
+        );
+
+        assert!(Proposal::<T>::exists());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn approve_upgrade() -> Result<(), BenchmarkError> {
+        let origin =
+            T::ApproveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let code_hash = propose::<T>()?;
+        let enactment = enactment::<T>();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, code_hash, enactment);
+
+        assert_eq!(
+            Pallet::<T>::pending_upgrade().and_then(|proposal| proposal.enactment()),
+            Some(enactment)
+        );
+        Ok(())
+    }
+
+    #[benchmark]
+    fn cancel_upgrade() -> Result<(), BenchmarkError> {
+        let origin =
+            T::CancelOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        // worst case: the proposal is approved and its enactment must be unscheduled
+        let code_hash = propose::<T>()?;
+        Pallet::<T>::approve_upgrade(
+            T::ApproveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?,
+            code_hash,
+            enactment::<T>(),
+        )
+        .map_err(|_| BenchmarkError::Stop("cannot approve upgrade"))?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin);
+
+        assert!(!Proposal::<T>::exists());
+        Ok(())
+    }
+
+    #[benchmark]
+    fn enact_upgrade() -> Result<(), BenchmarkError> {
+        // the junk code cannot be set, so this measures everything but `set_code`
+        // whose weight is added separately
+        let code_hash = propose::<T>()?;
+        let enactment = enactment::<T>();
+        Pallet::<T>::approve_upgrade(
+            T::ApproveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?,
+            code_hash,
+            enactment,
+        )
+        .map_err(|_| BenchmarkError::Stop("cannot approve upgrade"))?;
+        // worst case: the enactment task is still scheduled and must be cancelled
+        frame_system::Pallet::<T>::set_block_number(enactment);
+        let spec_version = T::Version::get().spec_version + 1;
+
+        #[extrinsic_call]
+        _(frame_system::RawOrigin::Root);
+
+        assert_has_event::<T>(
+            Event::<T>::UpgradeFailed {
+                spec_version,
+                error: Error::<T>::InvalidCode.into(),
+            }
+            .into(),
+        );
+        Ok(())
+    }
+}
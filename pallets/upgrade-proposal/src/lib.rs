@@ -0,0 +1,397 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Duniter Upgrade Proposal Pallet
+//!
+//! This pallet records the lifecycle of runtime upgrades, from their proposal to their enactment.
+//!
+//! ## Overview
+//!
+//! A proposal declares the hash of the new runtime code, which must already be noted in the preimage pallet,
+//! the `spec_version` of the new runtime and the hash of its release notes. Only one proposal can be open at a time.
+//!
+//! ## Lifecycle
+//!
+//! 1. A committee member proposes the upgrade with `propose_upgrade`.
+//! 1. The committee approves it with `approve_upgrade`, choosing an enactment block at least `MinEnactmentDelay`
+//!    blocks ahead. This delay leaves time to smiths to update their nodes.
+//! 1. The scheduler dispatches `enact_upgrade` at the enactment block, which checks that the code matches the
+//!    declared `spec_version` and sets it.
+//!
+//! Until its enactment, the proposal can be cancelled with `cancel_upgrade`.
+//! The pending upgrade and its enactment block are exposed by the `UpgradeProposalApi` runtime API,
+//! and enacted upgrades are recorded in `EnactedUpgrades`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+mod runtime_api;
+mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use pallet::*;
+pub use runtime_api::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode};
+use frame_support::traits::{
+    Get, OriginTrait, QueryPreimage, StorePreimage,
+    schedule::{DispatchTime, v3::Named as ScheduleNamed},
+};
+use sp_version::RuntimeVersion;
+
+/// Prefix of the scheduler task name used by this pallet.
+pub const TASK_NAME_PREFIX: &[u8] = b"duniter/upgrade-proposal";
+
+#[allow(unreachable_patterns)]
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use frame_support::{
+        dispatch::{GetDispatchInfo, PostDispatchInfo},
+        pallet_prelude::*,
+    };
+    use frame_system::pallet_prelude::*;
+    use sp_runtime::traits::Dispatchable;
+
+    pub type CallOf<T> = <T as Config>::RuntimeCall;
+    pub type PalletsOriginOf<T> =
+        <<T as frame_system::Config>::RuntimeOrigin as OriginTrait>::PalletsOrigin;
+    pub type UpgradeProposalOf<T> = UpgradeProposal<
+        <T as frame_system::Config>::AccountId,
+        BlockNumberFor<T>,
+        <T as frame_system::Config>::Hash,
+    >;
+
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    pub struct Pallet<T>(_);
+
+    // CONFIG //
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Origin allowed to approve an upgrade.
+        type ApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Origin allowed to cancel an upgrade.
+        type CancelOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Minimum number of blocks between the approval of an upgrade and its enactment.
+        #[pallet::constant]
+        type MinEnactmentDelay: Get<BlockNumberFor<Self>>;
+
+        /// The preimage provider storing the runtime code and the scheduled calls.
+        type Preimages: QueryPreimage<H = Self::Hashing> + StorePreimage;
+
+        /// Origin allowed to propose an upgrade.
+        type ProposeOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+        /// The overarching call type.
+        type RuntimeCall: Parameter
+            + Dispatchable<RuntimeOrigin = Self::RuntimeOrigin, PostInfo = PostDispatchInfo>
+            + GetDispatchInfo
+            + From<Call<Self>>
+            + IsType<<Self as frame_system::Config>::RuntimeCall>;
+
+        /// The scheduler used to enact the upgrades.
+        type Scheduler: ScheduleNamed<
+                BlockNumberFor<Self>,
+                CallOf<Self>,
+                PalletsOriginOf<Self>,
+                Hasher = Self::Hashing,
+            >;
+
+        /// Type representing the weight of this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    // STORAGE //
+
+    /// The current upgrade proposal.
+    #[pallet::storage]
+    pub type Proposal<T: Config> = StorageValue<_, UpgradeProposalOf<T>, OptionQuery>;
+
+    /// The enacted upgrades, by `spec_version`.
+    #[pallet::storage]
+    pub type EnactedUpgrades<T: Config> =
+        StorageMap<_, Twox64Concat, u32, EnactedUpgrade<BlockNumberFor<T>, T::Hash>, OptionQuery>;
+
+    // EVENTS //
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A runtime upgrade was proposed.
+        UpgradeProposed {
+            proposer: T::AccountId,
+            code_hash: T::Hash,
+            spec_version: u32,
+            release_notes_hash: T::Hash,
+        },
+        /// A runtime upgrade was approved and scheduled.
+        UpgradeApproved {
+            spec_version: u32,
+            enactment: BlockNumberFor<T>,
+        },
+        /// A runtime upgrade was cancelled.
+        UpgradeCancelled { spec_version: u32 },
+        /// A runtime upgrade was enacted.
+        UpgradeEnacted {
+            spec_version: u32,
+            code_hash: T::Hash,
+        },
+        /// A runtime upgrade could not be enacted.
+        UpgradeFailed {
+            spec_version: u32,
+            error: DispatchError,
+        },
+    }
+
+    // ERRORS //
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// An upgrade proposal is already open.
+        ProposalAlreadyExists,
+        /// There is no upgrade proposal.
+        NoProposal,
+        /// The code hash does not match the current proposal.
+        WrongProposal,
+        /// The upgrade proposal is already approved.
+        AlreadyApproved,
+        /// The runtime code is not noted in the preimage pallet.
+        CodeNotNoted,
+        /// The `spec_version` must be higher than the current one.
+        SpecVersionNotIncreasing,
+        /// The enactment block is too close.
+        EnactmentTooEarly,
+        /// The runtime version cannot be read from the code.
+        InvalidCode,
+        /// The `spec_version` of the code does not match the proposal.
+        SpecVersionMismatch,
+        /// The upgrade proposal is not approved.
+        NotApproved,
+        /// The enactment block of the upgrade is not reached.
+        EnactmentNotReached,
+    }
+
+    // CALLS //
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Propose a runtime upgrade.
+        ///
+        /// The runtime code must be noted in the preimage pallet beforehand.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::propose_upgrade())]
+        pub fn propose_upgrade(
+            origin: OriginFor<T>,
+            code_hash: T::Hash,
+            spec_version: u32,
+            release_notes_hash: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let proposer = T::ProposeOrigin::ensure_origin(origin)?;
+            ensure!(!Proposal::<T>::exists(), Error::<T>::ProposalAlreadyExists);
+            ensure!(
+                spec_version > T::Version::get().spec_version,
+                Error::<T>::SpecVersionNotIncreasing
+            );
+            let code_len = T::Preimages::len(&code_hash).ok_or(Error::<T>::CodeNotNoted)?;
+
+            // keep the code until the proposal is closed
+            T::Preimages::request(&code_hash);
+            Proposal::<T>::put(UpgradeProposal {
+                proposer: proposer.clone(),
+                code_hash,
+                code_len,
+                spec_version,
+                release_notes_hash,
+                proposed_at: frame_system::Pallet::<T>::block_number(),
+                status: ProposalStatus::Proposed,
+            });
+
+            Self::deposit_event(Event::UpgradeProposed {
+                proposer,
+                code_hash,
+                spec_version,
+                release_notes_hash,
+            });
+            Ok(().into())
+        }
+
+        /// Approve the current upgrade proposal and schedule its enactment.
+        ///
+        /// The enactment must be at least `MinEnactmentDelay` blocks ahead.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::approve_upgrade())]
+        pub fn approve_upgrade(
+            origin: OriginFor<T>,
+            code_hash: T::Hash,
+            enactment: BlockNumberFor<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::ApproveOrigin::ensure_origin(origin)?;
+            let mut proposal = Proposal::<T>::get().ok_or(Error::<T>::NoProposal)?;
+            ensure!(proposal.code_hash == code_hash, Error::<T>::WrongProposal);
+            ensure!(
+                proposal.status == ProposalStatus::Proposed,
+                Error::<T>::AlreadyApproved
+            );
+            ensure!(
+                enactment
+                    >= frame_system::Pallet::<T>::block_number()
+                        .saturating_add(T::MinEnactmentDelay::get()),
+                Error::<T>::EnactmentTooEarly
+            );
+
+            let call: CallOf<T> = Call::<T>::enact_upgrade {}.into();
+            // a task left by a previous proposal would prevent scheduling this one
+            Self::cancel_enactment();
+            T::Scheduler::schedule_named(
+                Self::task_name(),
+                DispatchTime::At(enactment),
+                None,
+                frame_support::traits::schedule::HIGHEST_PRIORITY,
+                frame_system::RawOrigin::Root.into(),
+                T::Preimages::bound(call)?,
+            )?;
+            proposal.status = ProposalStatus::Approved { enactment };
+            let spec_version = proposal.spec_version;
+            Proposal::<T>::put(proposal);
+
+            Self::deposit_event(Event::UpgradeApproved {
+                spec_version,
+                enactment,
+            });
+            Ok(().into())
+        }
+
+        /// Cancel the current upgrade proposal, approved or not.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::cancel_upgrade())]
+        pub fn cancel_upgrade(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            T::CancelOrigin::ensure_origin(origin)?;
+            let proposal = Proposal::<T>::take().ok_or(Error::<T>::NoProposal)?;
+
+            Self::cancel_enactment();
+            T::Preimages::unrequest(&proposal.code_hash);
+
+            Self::deposit_event(Event::UpgradeCancelled {
+                spec_version: proposal.spec_version,
+            });
+            Ok(().into())
+        }
+
+        /// Enact the approved upgrade.
+        ///
+        /// This call is dispatched by the scheduler at the enactment block, and cannot be dispatched earlier.
+        #[pallet::call_index(3)]
+        #[pallet::weight((
+            T::WeightInfo::enact_upgrade()
+                .saturating_add(<<T as frame_system::Config>::SystemWeightInfo as frame_system::WeightInfo>::set_code()),
+            DispatchClass::Operational,
+        ))]
+        pub fn enact_upgrade(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let proposal = Proposal::<T>::get().ok_or(Error::<T>::NoProposal)?;
+            let enactment = proposal.enactment().ok_or(Error::<T>::NotApproved)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= enactment,
+                Error::<T>::EnactmentNotReached
+            );
+            Proposal::<T>::kill();
+            // the task is still scheduled if the upgrade is enacted by another way
+            Self::cancel_enactment();
+
+            let result = Self::do_enact_upgrade(&proposal);
+            T::Preimages::unrequest(&proposal.code_hash);
+            match result {
+                Ok(post_info) => {
+                    EnactedUpgrades::<T>::insert(
+                        proposal.spec_version,
+                        EnactedUpgrade {
+                            code_hash: proposal.code_hash,
+                            release_notes_hash: proposal.release_notes_hash,
+                            enacted_at: frame_system::Pallet::<T>::block_number(),
+                        },
+                    );
+                    Self::deposit_event(Event::UpgradeEnacted {
+                        spec_version: proposal.spec_version,
+                        code_hash: proposal.code_hash,
+                    });
+                    Ok(post_info)
+                }
+                Err(error) => {
+                    Self::deposit_event(Event::UpgradeFailed {
+                        spec_version: proposal.spec_version,
+                        error: error.error,
+                    });
+                    Ok(().into())
+                }
+            }
+        }
+    }
+
+    // PUBLIC FUNCTIONS //
+
+    impl<T: Config> Pallet<T> {
+        /// Returns the current upgrade proposal, if any.
+        pub fn pending_upgrade() -> Option<UpgradeProposalOf<T>> {
+            Proposal::<T>::get()
+        }
+    }
+
+    // INTERNAL FUNCTIONS //
+
+    impl<T: Config> Pallet<T> {
+        /// Name of the scheduler task enacting the upgrade.
+        fn task_name() -> [u8; 32] {
+            TASK_NAME_PREFIX.using_encoded(sp_io::hashing::blake2_256)
+        }
+
+        /// Cancels the scheduled enactment task, if any.
+        ///
+        /// The task is already gone when it is the one being dispatched, so the error is ignored.
+        fn cancel_enactment() {
+            let _ = T::Scheduler::cancel_named(Self::task_name());
+        }
+
+        /// Checks the code of the proposal and sets it.
+        fn do_enact_upgrade(proposal: &UpgradeProposalOf<T>) -> DispatchResultWithPostInfo {
+            let code = T::Preimages::fetch(&proposal.code_hash, Some(proposal.code_len))?;
+            let version = sp_io::misc::runtime_version(&code)
+                .and_then(|version| RuntimeVersion::decode(&mut &version[..]).ok())
+                .ok_or(Error::<T>::InvalidCode)?;
+            ensure!(
+                version.spec_version == proposal.spec_version,
+                Error::<T>::SpecVersionMismatch
+            );
+            frame_system::Pallet::<T>::set_code(
+                frame_system::RawOrigin::Root.into(),
+                code.into_owned(),
+            )
+        }
+    }
+}
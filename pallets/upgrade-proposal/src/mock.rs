@@ -0,0 +1,189 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::{self as pallet_upgrade_proposal};
+use codec::Encode;
+use frame_support::{
+    derive_impl, ord_parameter_types, parameter_types,
+    traits::{ConstU32, EqualPrivilegeOnly, Everything, OnFinalize, OnInitialize},
+    weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
+use sp_core::{
+    H256,
+    traits::{Externalities, ReadRuntimeVersion, ReadRuntimeVersionExt},
+};
+use sp_runtime::{
+    BuildStorage,
+    traits::{BlakeTwo256, IdentityLookup},
+};
+
+type Balance = u64;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+    pub enum Test
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Preimage: pallet_preimage,
+        Scheduler: pallet_scheduler,
+        UpgradeProposal: pallet_upgrade_proposal,
+    }
+);
+
+/// Spec version of the mock runtime.
+pub const SPEC_VERSION: u32 = 100;
+
+/// Returns the runtime version of the mock runtime with the given `spec_version`.
+pub fn runtime_version(spec_version: u32) -> RuntimeVersion {
+    RuntimeVersion {
+        spec_name: "test".into(),
+        spec_version,
+        ..Default::default()
+    }
+}
+
+parameter_types! {
+    pub Version: RuntimeVersion = runtime_version(SPEC_VERSION);
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type AccountId = u64;
+    type BaseCallFilter = Everything;
+    type Block = Block;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type MaxConsumers = ConstU32<16>;
+    type Nonce = u64;
+    type PalletInfo = PalletInfo;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type Version = Version;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = Balance;
+    type DoneSlashHandler = ();
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type FreezeIdentifier = ();
+    type MaxFreezes = ConstU32<0>;
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeFreezeReason = ();
+    type RuntimeHoldReason = ();
+    type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
+}
+
+impl pallet_preimage::Config for Test {
+    type Consideration = ();
+    type Currency = Balances;
+    type ManagerOrigin = EnsureRoot<u64>;
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub MaximumSchedulerWeight: Weight = Weight::MAX;
+}
+
+impl pallet_scheduler::Config for Test {
+    type BlockNumberProvider = System;
+    type MaxScheduledPerBlock = ConstU32<10>;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type PalletsOrigin = OriginCaller;
+    type Preimages = Preimage;
+    type RuntimeCall = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeOrigin = RuntimeOrigin;
+    type ScheduleOrigin = EnsureRoot<u64>;
+    type WeightInfo = ();
+}
+
+ord_parameter_types! {
+    pub const Committee: u64 = 1;
+}
+
+parameter_types! {
+    pub const MinEnactmentDelay: u64 = 5;
+}
+
+impl pallet_upgrade_proposal::Config for Test {
+    type ApproveOrigin = EnsureSignedBy<Committee, u64>;
+    type CancelOrigin = EnsureSignedBy<Committee, u64>;
+    type MinEnactmentDelay = MinEnactmentDelay;
+    type Preimages = Preimage;
+    type ProposeOrigin = EnsureSigned<u64>;
+    type RuntimeCall = RuntimeCall;
+    type Scheduler = Scheduler;
+    type WeightInfo = ();
+}
+
+/// Reads the runtime version from a mock code made of the encoded `spec_version`.
+struct MockReadRuntimeVersion;
+impl ReadRuntimeVersion for MockReadRuntimeVersion {
+    fn read_runtime_version(
+        &self,
+        wasm_code: &[u8],
+        _ext: &mut dyn Externalities,
+    ) -> Result<Vec<u8>, String> {
+        u32::decode(&mut &wasm_code[..])
+            .map(|spec_version| runtime_version(spec_version).encode())
+            .map_err(|_| "invalid code".into())
+    }
+}
+
+/// Returns a mock runtime code with the given `spec_version`.
+pub fn mock_code(spec_version: u32) -> Vec<u8> {
+    spec_version.encode()
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.register_extension(ReadRuntimeVersionExt::new(MockReadRuntimeVersion));
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        Scheduler::on_finalize(System::block_number());
+        System::on_finalize(System::block_number());
+        System::reset_events();
+        System::set_block_number(System::block_number() + 1);
+        System::on_initialize(System::block_number());
+        Scheduler::on_initialize(System::block_number());
+    }
+}
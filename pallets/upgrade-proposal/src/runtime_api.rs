@@ -0,0 +1,31 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::UpgradeProposal;
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    /// Runtime API for Upgrade Proposal pallet
+    pub trait UpgradeProposalApi<AccountId, BlockNumber, Hash>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        /// Get the current runtime upgrade proposal, with its enactment block once approved
+        fn pending_upgrade() -> Option<UpgradeProposal<AccountId, BlockNumber, Hash>>;
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+use frame_support::{assert_noop, assert_ok, traits::QueryPreimage};
+use sp_core::H256;
+use sp_runtime::{
+    DispatchError,
+    traits::{BlakeTwo256, Hash},
+};
+
+const RELEASE_NOTES: &[u8] = b"release notes";
+
+/// Notes the mock code of the given `spec_version` and returns its hash.
+fn note_code(spec_version: u32) -> H256 {
+    let code = mock_code(spec_version);
+    assert_ok!(Preimage::note_preimage(
+        RuntimeOrigin::signed(2),
+        code.clone()
+    ));
+    BlakeTwo256::hash(&code)
+}
+
+fn release_notes_hash() -> H256 {
+    BlakeTwo256::hash(RELEASE_NOTES)
+}
+
+/// test the checks done when proposing an upgrade
+#[test]
+fn test_propose_upgrade_checks() {
+    new_test_ext().execute_with(|| {
+        let code_hash = BlakeTwo256::hash(&mock_code(101));
+        assert_noop!(
+            UpgradeProposal::propose_upgrade(RuntimeOrigin::none(), code_hash, 101, code_hash),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            UpgradeProposal::propose_upgrade(
+                RuntimeOrigin::signed(2),
+                code_hash,
+                101,
+                release_notes_hash()
+            ),
+            Error::<Test>::CodeNotNoted
+        );
+        note_code(101);
+        assert_noop!(
+            UpgradeProposal::propose_upgrade(
+                RuntimeOrigin::signed(2),
+                code_hash,
+                SPEC_VERSION,
+                release_notes_hash()
+            ),
+            Error::<Test>::SpecVersionNotIncreasing
+        );
+        assert_ok!(UpgradeProposal::propose_upgrade(
+            RuntimeOrigin::signed(2),
+            code_hash,
+            101,
+            release_notes_hash()
+        ));
+        System::assert_has_event(RuntimeEvent::UpgradeProposal(Event::UpgradeProposed {
+            proposer: 2,
+            code_hash,
+            spec_version: 101,
+            release_notes_hash: release_notes_hash(),
+        }));
+        // the code is kept until the proposal is closed
+        assert!(Preimage::is_requested(&code_hash));
+        assert_noop!(
+            UpgradeProposal::propose_upgrade(
+                RuntimeOrigin::signed(2),
+                code_hash,
+                101,
+                release_notes_hash()
+            ),
+            Error::<Test>::ProposalAlreadyExists
+        );
+    })
+}
+
+/// test that an approved upgrade is enacted at the enactment block
+#[test]
+fn test_upgrade_enacted_after_approval() {
+    new_test_ext().execute_with(|| {
+        let code_hash = note_code(101);
+        assert_ok!(UpgradeProposal::propose_upgrade(
+            RuntimeOrigin::signed(2),
+            code_hash,
+            101,
+            release_notes_hash()
+        ));
+        assert_noop!(
+            UpgradeProposal::approve_upgrade(RuntimeOrigin::signed(2), code_hash, 10),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            UpgradeProposal::approve_upgrade(RuntimeOrigin::signed(1), release_notes_hash(), 10),
+            Error::<Test>::WrongProposal
+        );
+        assert_noop!(
+            UpgradeProposal::approve_upgrade(RuntimeOrigin::signed(1), code_hash, 5),
+            Error::<Test>::EnactmentTooEarly
+        );
+        assert_ok!(UpgradeProposal::approve_upgrade(
+            RuntimeOrigin::signed(1),
+            code_hash,
+            10
+        ));
+        System::assert_has_event(RuntimeEvent::UpgradeProposal(Event::UpgradeApproved {
+            spec_version: 101,
+            enactment: 10,
+        }));
+        assert_noop!(
+            UpgradeProposal::approve_upgrade(RuntimeOrigin::signed(1), code_hash, 12),
+            Error::<Test>::AlreadyApproved
+        );
+        assert_eq!(
+            UpgradeProposal::pending_upgrade().and_then(|proposal| proposal.enactment()),
+            Some(10)
+        );
+
+        run_to_block(9);
+        assert!(UpgradeProposal::pending_upgrade().is_some());
+        run_to_block(10);
+        System::assert_has_event(RuntimeEvent::UpgradeProposal(Event::UpgradeEnacted {
+            spec_version: 101,
+            code_hash,
+        }));
+        System::assert_has_event(RuntimeEvent::System(frame_system::Event::CodeUpdated));
+        assert_eq!(
+            sp_io::storage::get(sp_core::storage::well_known_keys::CODE).map(|code| code.to_vec()),
+            Some(mock_code(101))
+        );
+        assert_eq!(UpgradeProposal::pending_upgrade(), None);
+        assert_eq!(
+            EnactedUpgrades::<Test>::get(101),
+            Some(EnactedUpgrade {
+                code_hash,
+                release_notes_hash: release_notes_hash(),
+                enacted_at: 10,
+            })
+        );
+        assert!(!Preimage::is_requested(&code_hash));
+    })
+}
+
+/// test that a cancelled upgrade is not enacted
+#[test]
+fn test_cancel_upgrade() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            UpgradeProposal::cancel_upgrade(RuntimeOrigin::signed(1)),
+            Error::<Test>::NoProposal
+        );
+        let code_hash = note_code(101);
+        assert_ok!(UpgradeProposal::propose_upgrade(
+            RuntimeOrigin::signed(2),
+            code_hash,
+            101,
+            release_notes_hash()
+        ));
+        assert_ok!(UpgradeProposal::approve_upgrade(
+            RuntimeOrigin::signed(1),
+            code_hash,
+            10
+        ));
+        assert_noop!(
+            UpgradeProposal::cancel_upgrade(RuntimeOrigin::signed(2)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(UpgradeProposal::cancel_upgrade(RuntimeOrigin::signed(1)));
+        System::assert_has_event(RuntimeEvent::UpgradeProposal(Event::UpgradeCancelled {
+            spec_version: 101,
+        }));
+        assert!(!Preimage::is_requested(&code_hash));
+
+        run_to_block(10);
+        assert!(sp_io::storage::get(sp_core::storage::well_known_keys::CODE).is_none());
+        assert_eq!(EnactedUpgrades::<Test>::get(101), None);
+    })
+}
+
+/// test that an upgrade whose code does not match the declared version is not enacted
+#[test]
+fn test_upgrade_with_wrong_spec_version_fails() {
+    new_test_ext().execute_with(|| {
+        let code_hash = note_code(101);
+        assert_ok!(UpgradeProposal::propose_upgrade(
+            RuntimeOrigin::signed(2),
+            code_hash,
+            102,
+            release_notes_hash()
+        ));
+        assert_ok!(UpgradeProposal::approve_upgrade(
+            RuntimeOrigin::signed(1),
+            code_hash,
+            10
+        ));
+
+        run_to_block(10);
+        System::assert_has_event(RuntimeEvent::UpgradeProposal(Event::UpgradeFailed {
+            spec_version: 102,
+            error: Error::<Test>::SpecVersionMismatch.into(),
+        }));
+        assert!(sp_io::storage::get(sp_core::storage::well_known_keys::CODE).is_none());
+        // a new proposal can be made
+        assert_eq!(UpgradeProposal::pending_upgrade(), None);
+    })
+}
+
+/// test that an upgrade cannot be enacted before its approved enactment block,
+/// and that enacting it another way does not prevent the next proposal from being scheduled
+#[test]
+fn test_enact_upgrade_checks() {
+    new_test_ext().execute_with(|| {
+        let code_hash = note_code(101);
+        assert_ok!(UpgradeProposal::propose_upgrade(
+            RuntimeOrigin::signed(2),
+            code_hash,
+            101,
+            release_notes_hash()
+        ));
+        assert_noop!(
+            UpgradeProposal::enact_upgrade(RuntimeOrigin::root()),
+            Error::<Test>::NotApproved
+        );
+        assert_ok!(UpgradeProposal::approve_upgrade(
+            RuntimeOrigin::signed(1),
+            code_hash,
+            10
+        ));
+        assert_noop!(
+            UpgradeProposal::enact_upgrade(RuntimeOrigin::root()),
+            Error::<Test>::EnactmentNotReached
+        );
+
+        // the upgrade is enacted by root before the scheduler dispatches its task
+        run_to_block(9);
+        System::set_block_number(10);
+        assert_ok!(UpgradeProposal::enact_upgrade(RuntimeOrigin::root()));
+        assert_eq!(
+            EnactedUpgrades::<Test>::get(101).map(|upgrade| upgrade.enacted_at),
+            Some(10)
+        );
+
+        // the next proposal can be approved and is enacted at its own enactment block
+        let code_hash = note_code(102);
+        assert_ok!(UpgradeProposal::propose_upgrade(
+            RuntimeOrigin::signed(2),
+            code_hash,
+            102,
+            release_notes_hash()
+        ));
+        assert_ok!(UpgradeProposal::approve_upgrade(
+            RuntimeOrigin::signed(1),
+            code_hash,
+            16
+        ));
+        run_to_block(15);
+        assert!(UpgradeProposal::pending_upgrade().is_some());
+        run_to_block(16);
+        System::assert_has_event(RuntimeEvent::UpgradeProposal(Event::UpgradeEnacted {
+            spec_version: 102,
+            code_hash,
+        }));
+        assert_eq!(UpgradeProposal::pending_upgrade(), None);
+    })
+}
@@ -0,0 +1,73 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Various basic types for use in the upgrade proposal pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Status of a runtime upgrade proposal.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ProposalStatus<BlockNumber> {
+    /// The proposal waits for the approval of the committee.
+    Proposed,
+    /// The proposal has been approved and the upgrade is scheduled.
+    Approved {
+        /// The block at which the upgrade is enacted.
+        enactment: BlockNumber,
+    },
+}
+
+/// A runtime upgrade proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct UpgradeProposal<AccountId, BlockNumber, Hash> {
+    /// The account which proposed the upgrade.
+    pub proposer: AccountId,
+    /// Hash of the runtime code, noted in the preimage pallet.
+    pub code_hash: Hash,
+    /// Length of the runtime code, in bytes.
+    pub code_len: u32,
+    /// The `spec_version` of the new runtime.
+    pub spec_version: u32,
+    /// Hash of the release notes of the new runtime.
+    pub release_notes_hash: Hash,
+    /// The block at which the upgrade was proposed.
+    pub proposed_at: BlockNumber,
+    /// Status of the proposal.
+    pub status: ProposalStatus<BlockNumber>,
+}
+
+impl<AccountId, BlockNumber: Copy, Hash> UpgradeProposal<AccountId, BlockNumber, Hash> {
+    /// Returns the enactment block of the upgrade, if approved.
+    pub fn enactment(&self) -> Option<BlockNumber> {
+        match self.status {
+            ProposalStatus::Proposed => None,
+            ProposalStatus::Approved { enactment } => Some(enactment),
+        }
+    }
+}
+
+/// Record of an enacted runtime upgrade.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EnactedUpgrade<BlockNumber, Hash> {
+    /// Hash of the runtime code.
+    pub code_hash: Hash,
+    /// Hash of the release notes of the runtime.
+    pub release_notes_hash: Hash,
+    /// The block at which the upgrade was enacted.
+    pub enacted_at: BlockNumber,
+}
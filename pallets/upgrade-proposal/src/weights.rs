@@ -0,0 +1,45 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::weights::Weight;
+
+/// Weight functions needed for pallet.
+pub trait WeightInfo {
+    fn propose_upgrade() -> Weight;
+    fn approve_upgrade() -> Weight;
+    fn cancel_upgrade() -> Weight;
+    fn enact_upgrade() -> Weight;
+}
+
+impl WeightInfo for () {
+    fn propose_upgrade() -> Weight {
+        Weight::zero()
+    }
+
+    fn approve_upgrade() -> Weight {
+        Weight::zero()
+    }
+
+    fn cancel_upgrade() -> Weight {
+        Weight::zero()
+    }
+
+    fn enact_upgrade() -> Weight {
+        Weight::zero()
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Identity proof
+//!
+//! Storage proofs of the state of an identity, letting light clients like mobile wallets check
+//! the status, the owner key and the membership of an identity without trusting an RPC node.
+//!
+//! The node serves an [`IdentityProof`] for a finalized block with the `identity_proof` RPC
+//! method. The client verifies it against the state root of the header of this block, obtained
+//! from its own light client, and gets the typed [`IdentityState`]:
+//!
+//! - `Identity.Identities` of the identity,
+//! - `Identity.IdentityIndexOf` of its owner key,
+//! - `Membership.Membership` of the identity,
+//! - `Certification.CertsByReceiver` of the identity,
+//! - `linked_idty` of the account data of its owner key (`System.Account`).
+//!
+//! The types of this crate mirror the SCALE encoding of the runtime types without depending on
+//! the pallets, which keeps the crate `no_std` and small.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use sp_core::{
+    H256,
+    hashing::{blake2_128, twox_64, twox_128},
+};
+use sp_runtime::{AccountId32, RuntimeDebug, traits::BlakeTwo256};
+use sp_trie::{CompactProof, LayoutV1};
+
+pub type AccountId = AccountId32;
+pub type Balance = u64;
+pub type BlockNumber = u32;
+pub type IdtyIndex = u32;
+
+/// Storage proof of the state of an identity, for the state root of a block.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+pub struct IdentityProof {
+    /// Index of the proven identity.
+    pub idty_index: IdtyIndex,
+    /// Trie nodes of the storage items of the identity, compact encoded.
+    pub proof: CompactProof,
+}
+
+/// Error of the verification of an [`IdentityProof`].
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum Error {
+    /// The proof does not match the state root.
+    InvalidProof,
+    /// A storage item of the identity is missing from the proof.
+    IncompleteProof,
+    /// A storage value cannot be decoded.
+    InvalidValue,
+}
+
+/// State of an identity, as proven by an [`IdentityProof`].
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct IdentityState {
+    /// Index of the identity.
+    pub idty_index: IdtyIndex,
+    /// Identity value, `None` if the identity does not exist.
+    pub identity: Option<IdtyValue>,
+    /// Identity index of the owner key of the identity.
+    pub idty_index_of_owner: Option<IdtyIndex>,
+    /// Membership of the identity.
+    pub membership: Option<MembershipData>,
+    /// Issuers of the certifications received by the identity, with their expiration block.
+    pub received_certs: Vec<(IdtyIndex, BlockNumber)>,
+    /// Identity linked to the account of the owner key.
+    pub linked_idty: Option<IdtyIndex>,
+}
+
+impl IdentityState {
+    /// Whether the identity exists and its owner key is consistently indexed.
+    pub fn is_owned_by(&self, account: &AccountId) -> bool {
+        self.identity
+            .as_ref()
+            .is_some_and(|identity| &identity.owner_key == account)
+            && self.idty_index_of_owner == Some(self.idty_index)
+    }
+}
+
+/// Mirror of `pallet_identity::IdtyValue` with the `IdtyData` of the runtime.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+pub struct IdtyValue {
+    /// Index of the first claimable universal dividend, `0` if not eligible.
+    pub first_eligible_ud: u16,
+    /// Block before which creating a new identity is not allowed.
+    pub next_creatable_identity_on: BlockNumber,
+    /// Previous owner key of this identity and the block of its change.
+    pub old_owner_key: Option<(AccountId, BlockNumber)>,
+    /// Current owner key of this identity.
+    pub owner_key: AccountId,
+    /// Next action scheduled on identity, `0` if no action is scheduled.
+    pub next_scheduled: BlockNumber,
+    /// Current status of the identity.
+    pub status: IdtyStatus,
+}
+
+/// Mirror of `pallet_identity::IdtyStatus`.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+pub enum IdtyStatus {
+    /// Created through a first certification but unconfirmed.
+    Unconfirmed,
+    /// Confirmed by key owner with a name published but unvalidated.
+    Unvalidated,
+    /// Member of the main web of trust.
+    Member,
+    /// Not a member of the main web of trust, auto-revocation planned.
+    NotMember,
+    /// Revoked manually or automatically, deletion possible.
+    Revoked,
+}
+
+/// Mirror of `sp_membership::MembershipData`.
+#[derive(Clone, Copy, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+pub struct MembershipData {
+    /// Block at which the membership expires.
+    pub expire_on: BlockNumber,
+}
+
+/// Mirror of `frame_system::AccountInfo` with the `AccountData` of the runtime.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+struct AccountInfo {
+    nonce: u32,
+    consumers: u32,
+    providers: u32,
+    sufficients: u32,
+    data: AccountData,
+}
+
+/// Mirror of `pallet_duniter_account::AccountData`.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug)]
+struct AccountData {
+    free: Balance,
+    reserved: Balance,
+    fee_frozen: Balance,
+    linked_idty: Option<IdtyIndex>,
+}
+
+impl IdentityProof {
+    /// Checks the proof against the state root of a block header and decodes the state of the
+    /// identity.
+    pub fn verify(&self, state_root: &H256) -> Result<IdentityState, Error> {
+        let (db, _) = self
+            .proof
+            .to_memory_db::<BlakeTwo256>(Some(state_root))
+            .map_err(|_| Error::InvalidProof)?;
+        let read = |key: Vec<u8>| {
+            sp_trie::read_trie_value::<LayoutV1<BlakeTwo256>, _>(&db, state_root, &key, None, None)
+                .map_err(|_| Error::IncompleteProof)
+        };
+
+        let identity: Option<IdtyValue> = decode(read(identities_key(self.idty_index))?)?;
+        let membership = decode(read(membership_key(self.idty_index))?)?;
+        let received_certs =
+            decode(read(certs_by_receiver_key(self.idty_index))?)?.unwrap_or_default();
+        let (idty_index_of_owner, linked_idty) = match &identity {
+            Some(identity) => (
+                decode(read(identity_index_of_key(&identity.owner_key))?)?,
+                decode::<AccountInfo>(read(account_key(&identity.owner_key))?)?
+                    .and_then(|account| account.data.linked_idty),
+            ),
+            None => (None, None),
+        };
+
+        Ok(IdentityState {
+            idty_index: self.idty_index,
+            identity,
+            idty_index_of_owner,
+            membership,
+            received_certs,
+            linked_idty,
+        })
+    }
+}
+
+fn decode<T: Decode>(value: Option<Vec<u8>>) -> Result<Option<T>, Error> {
+    value
+        .map(|value| T::decode(&mut &value[..]))
+        .transpose()
+        .map_err(|_| Error::InvalidValue)
+}
+
+/// Storage keys to prove for an identity, the keys of its owner key are known once its
+/// `Identities` value is read.
+pub fn storage_keys(idty_index: IdtyIndex, owner_key: Option<&AccountId>) -> Vec<Vec<u8>> {
+    let mut keys = alloc::vec![
+        identities_key(idty_index),
+        membership_key(idty_index),
+        certs_by_receiver_key(idty_index),
+    ];
+    if let Some(owner_key) = owner_key {
+        keys.push(identity_index_of_key(owner_key));
+        keys.push(account_key(owner_key));
+    }
+    keys
+}
+
+/// Storage key of `Identity.Identities`.
+pub fn identities_key(idty_index: IdtyIndex) -> Vec<u8> {
+    twox_64_concat_key(b"Identity", b"Identities", &idty_index.encode())
+}
+
+/// Storage key of `Identity.IdentityIndexOf`.
+pub fn identity_index_of_key(account: &AccountId) -> Vec<u8> {
+    blake2_128_concat_key(b"Identity", b"IdentityIndexOf", &account.encode())
+}
+
+/// Storage key of `Membership.Membership`.
+pub fn membership_key(idty_index: IdtyIndex) -> Vec<u8> {
+    twox_64_concat_key(b"Membership", b"Membership", &idty_index.encode())
+}
+
+/// Storage key of `Certification.CertsByReceiver`.
+pub fn certs_by_receiver_key(idty_index: IdtyIndex) -> Vec<u8> {
+    twox_64_concat_key(b"Certification", b"CertsByReceiver", &idty_index.encode())
+}
+
+/// Storage key of `System.Account`.
+pub fn account_key(account: &AccountId) -> Vec<u8> {
+    blake2_128_concat_key(b"System", b"Account", &account.encode())
+}
+
+fn twox_64_concat_key(pallet: &[u8], storage: &[u8], key: &[u8]) -> Vec<u8> {
+    [
+        &twox_128(pallet)[..],
+        &twox_128(storage)[..],
+        &twox_64(key)[..],
+        key,
+    ]
+    .concat()
+}
+
+fn blake2_128_concat_key(pallet: &[u8], storage: &[u8], key: &[u8]) -> Vec<u8> {
+    [
+        &twox_128(pallet)[..],
+        &twox_128(storage)[..],
+        &blake2_128(key)[..],
+        key,
+    ]
+    .concat()
+}
@@ -0,0 +1,174 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use core::num::NonZeroU16;
+use pallet_universal_dividend::FirstEligibleUd;
+use sp_state_machine::{InMemoryBackend, new_in_mem, prove_read};
+use sp_trie::StateVersion;
+
+const ALICE: AccountId = AccountId32::new([1; 32]);
+const BOB: AccountId = AccountId32::new([2; 32]);
+
+/// Same layout as the `IdtyData` of the runtime.
+#[derive(Encode)]
+struct IdtyData {
+    first_eligible_ud: FirstEligibleUd,
+}
+
+/// State of a network where Alice owns identity 1 and certified Bob, who owns identity 2.
+fn backend() -> (InMemoryBackend<BlakeTwo256>, H256) {
+    let identity = |owner_key: AccountId, status| {
+        pallet_identity::IdtyValue {
+            data: IdtyData {
+                first_eligible_ud: FirstEligibleUd(NonZeroU16::new(3)),
+            },
+            next_creatable_identity_on: 100,
+            old_owner_key: None,
+            owner_key,
+            next_scheduled: 0,
+            status,
+        }
+        .encode()
+    };
+    let account = |linked_idty| {
+        let mut data = pallet_duniter_account::AccountData::<Balance, IdtyIndex>::default();
+        data.linked_idty = linked_idty;
+        frame_system::AccountInfo::<u32, _> {
+            nonce: 0,
+            consumers: 0,
+            providers: 1,
+            sufficients: 1,
+            data,
+        }
+        .encode()
+    };
+    let storage = vec![
+        (
+            identities_key(1),
+            Some(identity(ALICE, pallet_identity::IdtyStatus::Member)),
+        ),
+        (identity_index_of_key(&ALICE), Some(1u32.encode())),
+        (
+            membership_key(1),
+            Some(sp_membership::MembershipData { expire_on: 500u32 }.encode()),
+        ),
+        (account_key(&ALICE), Some(account(Some(1)))),
+        (
+            identities_key(2),
+            Some(identity(BOB, pallet_identity::IdtyStatus::Unvalidated)),
+        ),
+        (identity_index_of_key(&BOB), Some(2u32.encode())),
+        (
+            certs_by_receiver_key(2),
+            Some(vec![(1u32, 300u32)].encode()),
+        ),
+        (account_key(&BOB), Some(account(None))),
+    ];
+    let mut backend = new_in_mem::<BlakeTwo256>();
+    backend.insert(vec![(None, storage)], StateVersion::V1);
+    let root = *backend.root();
+    (backend, root)
+}
+
+fn prove(
+    backend: &InMemoryBackend<BlakeTwo256>,
+    root: H256,
+    idty_index: IdtyIndex,
+    keys: &[Vec<u8>],
+) -> IdentityProof {
+    let proof = prove_read(backend.clone(), keys).expect("keys can be proven");
+    IdentityProof {
+        idty_index,
+        proof: proof
+            .into_compact_proof::<BlakeTwo256>(root)
+            .expect("proof matches the root"),
+    }
+}
+
+#[test]
+fn verify_identity_proof() {
+    let (backend, root) = backend();
+
+    let proof = prove(&backend, root, 1, &storage_keys(1, Some(&ALICE)));
+    let proof = IdentityProof::decode(&mut &proof.encode()[..]).unwrap();
+    let state = proof.verify(&root).unwrap();
+    assert_eq!(
+        state,
+        IdentityState {
+            idty_index: 1,
+            identity: Some(IdtyValue {
+                first_eligible_ud: 3,
+                next_creatable_identity_on: 100,
+                old_owner_key: None,
+                owner_key: ALICE,
+                next_scheduled: 0,
+                status: IdtyStatus::Member,
+            }),
+            idty_index_of_owner: Some(1),
+            membership: Some(MembershipData { expire_on: 500 }),
+            received_certs: vec![],
+            linked_idty: Some(1),
+        }
+    );
+    assert!(state.is_owned_by(&ALICE));
+    assert!(!state.is_owned_by(&BOB));
+
+    let state = prove(&backend, root, 2, &storage_keys(2, Some(&BOB)))
+        .verify(&root)
+        .unwrap();
+    assert_eq!(
+        state.identity.map(|identity| identity.status),
+        Some(IdtyStatus::Unvalidated)
+    );
+    assert_eq!(state.membership, None);
+    assert_eq!(state.received_certs, vec![(1, 300)]);
+    assert_eq!(state.linked_idty, None);
+}
+
+#[test]
+fn verify_absence_of_identity() {
+    let (backend, root) = backend();
+
+    let state = prove(&backend, root, 3, &storage_keys(3, None))
+        .verify(&root)
+        .unwrap();
+    assert_eq!(state.identity, None);
+    assert_eq!(state.idty_index_of_owner, None);
+    assert_eq!(state.membership, None);
+    assert!(state.received_certs.is_empty());
+}
+
+#[test]
+fn reject_invalid_proofs() {
+    let (backend, root) = backend();
+
+    // proof of another state
+    let proof = prove(&backend, root, 1, &storage_keys(1, Some(&ALICE)));
+    assert_eq!(
+        proof.verify(&H256::repeat_byte(1)),
+        Err(Error::InvalidProof)
+    );
+
+    // proof missing the keys of the owner
+    let proof = prove(&backend, root, 1, &storage_keys(1, None));
+    assert_eq!(proof.verify(&root), Err(Error::IncompleteProof));
+
+    // proof missing a trie node
+    let mut proof = prove(&backend, root, 1, &storage_keys(1, Some(&ALICE)));
+    proof.proof.encoded_nodes.pop();
+    assert!(proof.verify(&root).is_err());
+}
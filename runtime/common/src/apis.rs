@@ -236,6 +236,37 @@ impl pallet_universal_dividend::UniversalDividendApi<Block, AccountId, Balance>
     }
 }
 
+impl pallet_distance::DistanceApi<Block, AccountId, BlockNumber> for Runtime {
+    fn evaluator_stats(evaluator: AccountId) -> pallet_distance::EvaluatorStats {
+        Distance::evaluator_stats(&evaluator)
+    }
+
+    fn evaluation_pools() -> pallet_distance::EvaluationPoolsInfo<BlockNumber> {
+        Distance::evaluation_pools()
+    }
+
+    fn queue_position(idty_index: IdtyIndex) -> Option<u32> {
+        Distance::queue_position(idty_index)
+    }
+
+    fn distance_rule_parameters() -> pallet_distance::DistanceRuleParameters {
+        Distance::distance_rule_parameters()
+    }
+}
+
+impl pallet_upgrade_proposal::UpgradeProposalApi<Block, AccountId, BlockNumber, Hash> for Runtime {
+    fn pending_upgrade(
+    ) -> Option<pallet_upgrade_proposal::UpgradeProposal<AccountId, BlockNumber, Hash>> {
+        UpgradeProposal::pending_upgrade()
+    }
+}
+
+impl common_runtime::invariants::InvariantsApi<Block> for Runtime {
+    fn check_invariants() -> Vec<common_runtime::invariants::InvariantViolation> {
+        common_runtime::invariants::check_invariants::<Runtime>()
+    }
+}
+
 impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
     fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
         frame_support::genesis_builder_helper::build_state::<RuntimeGenesisConfig>(config)
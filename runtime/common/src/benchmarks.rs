@@ -18,7 +18,7 @@
 
 #[macro_export]
 macro_rules! benchmarks_config {
-    () => {
+    ($($custom:tt)*) => {
         #[macro_use]
         extern crate frame_benchmarking;
         pub use pallet_collective::RawOrigin;
@@ -33,12 +33,16 @@ macro_rules! benchmarks_config {
                 [pallet_universal_dividend, UniversalDividend]
                 [pallet_provide_randomness, ProvideRandomness]
                 [pallet_upgrade_origin, UpgradeOrigin]
+                [pallet_upgrade_proposal, UpgradeProposal]
                 [pallet_duniter_account, Account]
                 [pallet_quota, Quota]
                 [pallet_identity, Identity]
                 [pallet_membership, Membership]
                 [pallet_smith_members, SmithMembers]
+                [pallet_committee_membership, CommitteeMembership]
                 [pallet_authority_members, AuthorityMembers]
+                // Runtime specific
+                $($custom)*
                 // Substrate
                 [frame_system_extensions, SystemExtensionsBench::<Runtime>]
                 [pallet_balances, Balances]
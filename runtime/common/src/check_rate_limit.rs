@@ -0,0 +1,154 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Balance, IdtyIndex};
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+    dispatch::{DispatchInfo, PostDispatchInfo},
+    pallet_prelude::Weight,
+    traits::Get,
+};
+use scale_info::{
+    TypeInfo,
+    prelude::fmt::{Debug, Formatter},
+};
+use sp_runtime::{
+    traits::{
+        AsSystemOriginSigner, DispatchInfoOf, Dispatchable, Implication, TransactionExtension,
+        ValidateResult, Zero,
+    },
+    transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidityError, ValidTransaction,
+    },
+};
+
+/// Custom `InvalidTransaction` code returned when the rate limit is exceeded.
+pub const RATE_LIMIT_EXCEEDED: u8 = 1;
+
+/// Transaction extension limiting the number of free transactions.
+///
+/// While the chain is under its load target, transactions are free. To prevent an account from
+/// flooding the chain just under the target, free transactions are counted over a rolling window by
+/// `pallet_quota`, per account and per linked identity. Once a limit is reached, free transactions
+/// are rejected from the pool with `InvalidTransaction::Custom(RATE_LIMIT_EXCEEDED)`.
+///
+/// Transactions paying fees are not limited.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckRateLimit<T>(PhantomData<T>);
+
+impl<T> CheckRateLimit<T> {
+    /// Creates a new `CheckRateLimit` extension.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for CheckRateLimit<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for CheckRateLimit<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut Formatter) -> scale_info::prelude::fmt::Result {
+        write!(f, "CheckRateLimit")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut Formatter) -> scale_info::prelude::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T> CheckRateLimit<T>
+where
+    T: pallet_duniter_account::Config + pallet_identity::Config<IdtyIndex = IdtyIndex>,
+{
+    /// Returns the identity an account is linked to, either as owner key or as linked account.
+    pub fn linked_identity(who: &T::AccountId) -> Option<IdtyIndex> {
+        pallet_identity::IdentityIndexOf::<T>::get(who)
+            .or_else(|| frame_system::Account::<T>::get(who).data.linked_idty)
+    }
+}
+
+impl<T> TransactionExtension<T::RuntimeCall> for CheckRateLimit<T>
+where
+    T: pallet_duniter_account::Config
+        + pallet_identity::Config<IdtyIndex = IdtyIndex>
+        + pallet_transaction_payment::Config
+        + TypeInfo,
+    T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+    <T::RuntimeCall as Dispatchable>::RuntimeOrigin: AsSystemOriginSigner<T::AccountId> + Clone,
+    pallet_transaction_payment::BalanceOf<T>: Into<Balance>,
+{
+    type Implicit = ();
+    type Pre = ();
+    type Val = Option<(T::AccountId, Option<IdtyIndex>)>;
+
+    const IDENTIFIER: &'static str = "CheckRateLimit";
+
+    fn weight(&self, _call: &T::RuntimeCall) -> Weight {
+        // IdentityIndexOf, Account, Identities, AccountTxCount and IdtyTxCount reads,
+        // AccountTxCount and IdtyTxCount writes
+        T::DbWeight::get().reads_writes(5, 2)
+    }
+
+    fn validate(
+        &self,
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        _call: &T::RuntimeCall,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        len: usize,
+        _self_implicit: Self::Implicit,
+        _inherited_implication: &impl Implication,
+        _source: TransactionSource,
+    ) -> ValidateResult<Self::Val, T::RuntimeCall> {
+        let Some(who) = origin.as_system_origin_signer() else {
+            return Ok((ValidTransaction::default(), None, origin));
+        };
+        // Only free transactions are limited, the tip is not taken into account.
+        let fee: Balance =
+            pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, Zero::zero())
+                .into();
+        if !fee.is_zero() {
+            return Ok((ValidTransaction::default(), None, origin));
+        }
+
+        let linked_idty = Self::linked_identity(who);
+        if !pallet_quota::Pallet::<T>::check_rate_limit(who, linked_idty) {
+            return Err(InvalidTransaction::Custom(RATE_LIMIT_EXCEEDED).into());
+        }
+        let val = Some((who.clone(), linked_idty));
+        Ok((ValidTransaction::default(), val, origin))
+    }
+
+    fn prepare(
+        self,
+        val: Self::Val,
+        _origin: &T::RuntimeOrigin,
+        _call: &T::RuntimeCall,
+        _info: &DispatchInfoOf<T::RuntimeCall>,
+        _len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        if let Some((who, linked_idty)) = val {
+            pallet_quota::Pallet::<T>::note_transaction(&who, linked_idty);
+        }
+        Ok(())
+    }
+}
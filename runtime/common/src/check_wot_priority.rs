@@ -0,0 +1,217 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::IdtyIndex;
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+    dispatch::DispatchInfo,
+    pallet_prelude::Weight,
+    traits::{Get, IsSubType},
+};
+use pallet_identity::IdtyStatus;
+use scale_info::{
+    TypeInfo,
+    prelude::{
+        fmt::{Debug, Formatter},
+        vec,
+        vec::Vec,
+    },
+};
+use sp_runtime::{
+    traits::{
+        AsSystemOriginSigner, DispatchInfoOf, Dispatchable, Implication, TransactionExtension,
+        ValidateResult, Zero,
+    },
+    transaction_validity::{TransactionSource, TransactionValidityError, ValidTransaction},
+};
+
+/// Transaction extension boosting the pool priority of Web of Trust calls.
+///
+/// When fees apply, calls keeping the Web of Trust alive (certifications, identity confirmation
+/// and distance evaluation requests) would otherwise compete with plain transfers.
+/// These calls get a priority boost of `PriorityBoost` when:
+/// - they are signed by a member with remaining quota;
+/// - or, for `confirm_identity`, by the owner of the unconfirmed identity.
+///
+/// The boost is rate limited per identity by `pallet_quota`: an identity that has used all of its
+/// priority transactions for the current window gets the default priority until the next window.
+///
+/// Usage is only recorded when a boosted transaction is included, so boosted transactions provide
+/// a tag derived from the identity, its usage and the call, which only changes on inclusion:
+/// the transaction pool keeps at most one pending boosted transaction per identity and call.
+/// Boosted transactions of an identity with different calls are all accepted, and those exceeding
+/// the limit lose the boost when the pool revalidates them after an inclusion.
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckWotPriority<T>(PhantomData<T>);
+
+impl<T> CheckWotPriority<T> {
+    /// Creates a new `CheckWotPriority` extension.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for CheckWotPriority<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for CheckWotPriority<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut Formatter) -> scale_info::prelude::fmt::Result {
+        write!(f, "CheckWotPriority")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut Formatter) -> scale_info::prelude::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T> CheckWotPriority<T>
+where
+    T: pallet_identity::Config<IdtyIndex = IdtyIndex>
+        + pallet_quota::Config
+        + pallet_certification::Config
+        + pallet_distance::Config,
+    T::RuntimeCall: IsSubType<pallet_certification::Call<T>>
+        + IsSubType<pallet_distance::Call<T>>
+        + IsSubType<pallet_identity::Call<T>>,
+{
+    /// Returns `Some(true)` for an identity confirmation, `Some(false)` for other Web of Trust calls
+    /// and `None` for calls which are not eligible to the priority boost.
+    fn classify(call: &T::RuntimeCall) -> Option<bool> {
+        if let Some(pallet_identity::Call::confirm_identity { .. }) = call.is_sub_type() {
+            return Some(true);
+        }
+        if let Some(
+            pallet_certification::Call::add_cert { .. }
+            | pallet_certification::Call::renew_cert { .. },
+        ) = call.is_sub_type()
+        {
+            return Some(false);
+        }
+        if let Some(
+            pallet_distance::Call::request_distance_evaluation { .. }
+            | pallet_distance::Call::request_distance_evaluation_for { .. },
+        ) = call.is_sub_type()
+        {
+            return Some(false);
+        }
+        None
+    }
+
+    /// Returns the identity of the signer if the call is eligible to the priority boost.
+    pub fn boosted_identity(who: &T::AccountId, call: &T::RuntimeCall) -> Option<IdtyIndex> {
+        let is_confirmation = Self::classify(call)?;
+        let idty_index = pallet_identity::IdentityIndexOf::<T>::get(who)?;
+        let idty_value = pallet_identity::Identities::<T>::get(idty_index)?;
+        let eligible = match idty_value.status {
+            IdtyStatus::Unconfirmed => is_confirmation,
+            IdtyStatus::Member => {
+                !is_confirmation
+                    && !pallet_quota::Pallet::<T>::estimate_quota_refund(idty_index).is_zero()
+            }
+            _ => false,
+        };
+        (eligible && pallet_quota::Pallet::<T>::can_use_priority(idty_index)).then_some(idty_index)
+    }
+
+    /// Returns the tag provided by a boosted transaction of an identity.
+    ///
+    /// It is derived from the priority usage of the identity, which only changes when a boosted
+    /// transaction is included, and from the hash of the call.
+    pub fn priority_tag(idty_index: IdtyIndex, call: &T::RuntimeCall) -> Vec<u8> {
+        let usage = pallet_quota::IdtyPriorityUsage::<T>::get(idty_index)
+            .map(|usage| (usage.window_start, usage.count));
+        let call_hash = call.using_encoded(sp_core::hashing::blake2_256);
+        (b"CheckWotPriority", idty_index, usage, call_hash).encode()
+    }
+}
+
+impl<T> TransactionExtension<T::RuntimeCall> for CheckWotPriority<T>
+where
+    T: pallet_identity::Config<IdtyIndex = IdtyIndex>
+        + pallet_quota::Config
+        + pallet_certification::Config
+        + pallet_distance::Config
+        + TypeInfo,
+    T::RuntimeCall: Dispatchable<Info = DispatchInfo>
+        + IsSubType<pallet_certification::Call<T>>
+        + IsSubType<pallet_distance::Call<T>>
+        + IsSubType<pallet_identity::Call<T>>,
+    <T::RuntimeCall as Dispatchable>::RuntimeOrigin: AsSystemOriginSigner<T::AccountId> + Clone,
+{
+    type Implicit = ();
+    type Pre = ();
+    type Val = Option<IdtyIndex>;
+
+    const IDENTIFIER: &'static str = "CheckWotPriority";
+
+    fn weight(&self, call: &T::RuntimeCall) -> Weight {
+        if Self::classify(call).is_some() {
+            // IdentityIndexOf, Identities, IdtyQuota and IdtyPriorityUsage reads,
+            // IdtyPriorityUsage write
+            T::DbWeight::get().reads_writes(4, 1)
+        } else {
+            Weight::zero()
+        }
+    }
+
+    fn validate(
+        &self,
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        call: &T::RuntimeCall,
+        _info: &DispatchInfoOf<T::RuntimeCall>,
+        _len: usize,
+        _self_implicit: Self::Implicit,
+        _inherited_implication: &impl Implication,
+        _source: TransactionSource,
+    ) -> ValidateResult<Self::Val, T::RuntimeCall> {
+        let Some(who) = origin.as_system_origin_signer() else {
+            return Ok((ValidTransaction::default(), None, origin));
+        };
+        match Self::boosted_identity(who, call) {
+            Some(idty_index) => Ok((
+                ValidTransaction {
+                    priority: <T as pallet_quota::Config>::PriorityBoost::get(),
+                    provides: vec![Self::priority_tag(idty_index, call)],
+                    ..Default::default()
+                },
+                Some(idty_index),
+                origin,
+            )),
+            None => Ok((ValidTransaction::default(), None, origin)),
+        }
+    }
+
+    fn prepare(
+        self,
+        val: Self::Val,
+        _origin: &T::RuntimeOrigin,
+        _call: &T::RuntimeCall,
+        _info: &DispatchInfoOf<T::RuntimeCall>,
+        _len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        if let Some(idty_index) = val {
+            pallet_quota::Pallet::<T>::note_priority_use(idty_index);
+        }
+        Ok(())
+    }
+}
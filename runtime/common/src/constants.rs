@@ -55,5 +55,11 @@ pub const fn deposit(items: u32, bytes: u32) -> Balance {
     items as Balance * DEPOSIT_PER_ITEM + (bytes as Balance * DEPOSIT_PER_BYTE)
 }
 
+// Default limits of free transactions per rolling window
+pub const RATE_LIMIT_WINDOW: BlockNumber = HOURS;
+pub const RATE_LIMIT_MAX_PER_ACCOUNT: u32 = 60;
+pub const RATE_LIMIT_MAX_PER_MEMBER_ACCOUNT: u32 = 600;
+pub const RATE_LIMIT_MAX_PER_IDENTITY: u32 = 1_000;
+
 // Maximal weight proportion of normal extrinsics per block
 pub const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
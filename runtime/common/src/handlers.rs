@@ -50,17 +50,23 @@ impl<Runtime: pallet_duniter_wot::Config> pallet_identity::traits::OnNewIdty<Run
 /// Runtime handler for OnRemoveIdty, calling all implementations of
 /// OnRemoveIdty and implementing logic at the runtime level.
 pub struct OnRemoveIdtyHandler<Runtime>(core::marker::PhantomData<Runtime>);
-impl<Runtime: pallet_duniter_wot::Config + pallet_duniter_account::Config>
+impl<Runtime: pallet_duniter_wot::Config + pallet_duniter_account::Config + pallet_distance::Config>
     pallet_identity::traits::OnRemoveIdty<Runtime> for OnRemoveIdtyHandler<Runtime>
 {
     fn on_removed(idty_index: &IdtyIndex) -> Weight {
-        pallet_duniter_wot::Pallet::<Runtime>::on_removed(idty_index)
+        pallet_duniter_wot::Pallet::<Runtime>::on_removed(idty_index).saturating_add(
+            pallet_distance::Pallet::<Runtime>::cancel_pending_evaluation(*idty_index),
+        )
     }
 
     fn on_revoked(idty_index: &IdtyIndex) -> Weight {
-        pallet_duniter_wot::Pallet::<Runtime>::on_revoked(idty_index).saturating_add(
-            pallet_duniter_account::Pallet::<Runtime>::on_revoked(idty_index),
-        )
+        pallet_duniter_wot::Pallet::<Runtime>::on_revoked(idty_index)
+            .saturating_add(pallet_duniter_account::Pallet::<Runtime>::on_revoked(
+                idty_index,
+            ))
+            .saturating_add(
+                pallet_distance::Pallet::<Runtime>::cancel_pending_evaluation(*idty_index),
+            )
     }
 }
 
@@ -105,6 +111,7 @@ impl<
     Runtime: frame_system::Config
         + pallet_identity::Config<IdtyData = IdtyData, IdtyIndex = IdtyIndex>
         + pallet_smith_members::Config<IdtyIndex = IdtyIndex>
+        + pallet_committee_membership::Config<IdtyIndex = IdtyIndex>
         + pallet_duniter_wot::Config
         + pallet_quota::Config
         + pallet_universal_dividend::Config,
@@ -130,6 +137,12 @@ impl<
         weight.saturating_add(pallet_quota::Pallet::<Runtime>::on_removed(idty_index));
         weight.saturating_add(Runtime::DbWeight::get().reads_writes(1, 1));
 
+        // When membership is removed, also remove from technical committee.
+        // This is done before the smith removal to record the right removal reason.
+        weight = weight.saturating_add(
+            pallet_committee_membership::Pallet::<Runtime>::on_removed_wot_member(*idty_index),
+        );
+
         // When membership is removed, also remove from smith member.
         weight.saturating_add(
             pallet_smith_members::Pallet::<Runtime>::on_removed_wot_member(*idty_index),
@@ -158,9 +171,15 @@ pub struct OnSmithDeletedHandler<Runtime>(core::marker::PhantomData<Runtime>);
 impl<Runtime> pallet_smith_members::traits::OnSmithDelete<Runtime::MemberId>
     for OnSmithDeletedHandler<Runtime>
 where
-    Runtime: pallet_authority_members::Config,
+    Runtime: pallet_authority_members::Config
+        + pallet_committee_membership::Config<IdtyIndex = Runtime::MemberId>,
 {
-    fn on_smith_delete(idty_index: Runtime::MemberId, _reason: SmithRemovalReason) {
+    fn on_smith_delete(idty_index: Runtime::MemberId, _reason: SmithRemovalReason) -> Weight {
+        use pallet_authority_members::WeightInfo;
+        let weight = pallet_committee_membership::Pallet::<Runtime>::on_smith_removed(idty_index)
+            .saturating_add(
+                <Runtime as pallet_authority_members::Config>::WeightInfo::remove_member(),
+            );
         let call = pallet_authority_members::Call::<Runtime>::remove_member {
             member_id: idty_index,
         };
@@ -168,6 +187,7 @@ where
             #[cfg(feature = "std")]
             println!("fail to remove member: {_e:?}")
         }
+        weight
     }
 }
 
@@ -179,7 +199,8 @@ impl<
     Runtime: frame_system::Config<AccountId = AccountId>
         + pallet_identity::Config<IdtyIndex = IdtyIndex>
         + pallet_authority_members::Config<MemberId = IdtyIndex>
-        + pallet_smith_members::Config<IdtyIndex = IdtyIndex>,
+        + pallet_smith_members::Config<IdtyIndex = IdtyIndex>
+        + pallet_committee_membership::Config<IdtyIndex = IdtyIndex>,
     ReportLongevity: Get<BlockNumberFor<Runtime>>,
 > pallet_identity::traits::KeyChange<Runtime> for KeyChangeHandler<Runtime, ReportLongevity>
 {
@@ -217,6 +238,10 @@ impl<
                 .map_err(|e| e.error)?;
             }
         }
+        // The new owner key seats in the technical committee.
+        pallet_committee_membership::Pallet::<Runtime>::on_owner_key_changed(
+            idty_index, account_id,
+        );
         Ok(())
     }
 }
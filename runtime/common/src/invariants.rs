@@ -0,0 +1,190 @@
+// Copyright 2021-2023 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime invariants.
+//!
+//! Every Duniter pallet checks the invariants of its own storage in a `do_try_state` function,
+//! which is also its `try_state` hook. This module runs all of them together with the invariants
+//! spanning several pallets, and exposes the result through the [`InvariantsApi`] runtime API
+//! and the [`CheckInvariants`] migration.
+
+use codec::{Decode, Encode};
+use core::marker::PhantomData;
+use frame_support::{ensure, traits::PalletInfoAccess};
+use pallet_identity::IdtyStatus;
+use scale_info::{TypeInfo, prelude::vec::Vec};
+use sp_runtime::{RuntimeDebug, SaturatedConversion, TryRuntimeError};
+
+sp_api::decl_runtime_apis! {
+    /// Runtime API checking the runtime invariants.
+    pub trait InvariantsApi {
+        /// Check all the runtime invariants and return the violated ones.
+        fn check_invariants() -> Vec<InvariantViolation>;
+    }
+}
+
+/// A violated runtime invariant.
+#[derive(Clone, Decode, Encode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct InvariantViolation {
+    /// Name of the pallet owning the invariant.
+    pub pallet: Vec<u8>,
+    /// Description of the violated invariant.
+    pub message: Vec<u8>,
+}
+
+impl InvariantViolation {
+    fn new(pallet: &str, error: TryRuntimeError) -> Self {
+        Self {
+            pallet: pallet.as_bytes().to_vec(),
+            message: <&'static str>::from(error).as_bytes().to_vec(),
+        }
+    }
+}
+
+type Check = fn() -> Result<(), TryRuntimeError>;
+
+/// Check all the runtime invariants and return the violated ones.
+///
+/// Only the first violated invariant of each pallet is reported.
+pub fn check_invariants<T>() -> Vec<InvariantViolation>
+where
+    T: pallet_authority_members::Config
+        + pallet_certification::Config
+        + pallet_committee_membership::Config
+        + pallet_duniter_account::Config
+        + pallet_duniter_wot::Config
+        + pallet_identity::Config
+        + pallet_membership::Config
+        + pallet_oneshot_account::Config
+        + pallet_smith_members::Config
+        + pallet_universal_dividend::Config,
+{
+    let checks: [(&str, Check); 10] = [
+        (
+            <pallet_identity::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_identity::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_membership::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_membership::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_certification::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_certification::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_duniter_wot::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_duniter_wot::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_duniter_account::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_duniter_account::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_smith_members::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_smith_members::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_authority_members::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_authority_members::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_committee_membership::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_committee_membership::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_universal_dividend::Pallet<T> as PalletInfoAccess>::name(),
+            pallet_universal_dividend::Pallet::<T>::do_try_state,
+        ),
+        (
+            <pallet_universal_dividend::Pallet<T> as PalletInfoAccess>::name(),
+            check_monetary_mass::<T>,
+        ),
+    ];
+    checks
+        .into_iter()
+        .filter_map(|(pallet, check)| {
+            check()
+                .err()
+                .map(|error| InvariantViolation::new(pallet, error))
+        })
+        .collect()
+}
+
+/// Check that the money in circulation does not exceed the monetary mass.
+///
+/// The money in circulation is the total issuance of balances, plus the oneshot account
+/// balances and the unclaimed UDs of the members, which are not issued yet. It is not
+/// equal to the monetary mass since money can be destroyed, for instance through dust
+/// removal, treasury burn or UDs left unclaimed beyond the past reevaluations.
+fn check_monetary_mass<T>() -> Result<(), TryRuntimeError>
+where
+    T: pallet_balances::Config
+        + pallet_identity::Config
+        + pallet_oneshot_account::Config
+        + pallet_universal_dividend::Config,
+{
+    let mut circulating = pallet_balances::TotalIssuance::<T>::get().saturated_into::<u128>();
+    for balance in pallet_oneshot_account::OneshotAccounts::<T>::iter_values() {
+        circulating = circulating.saturating_add(balance.saturated_into::<u128>());
+    }
+    for idty_value in pallet_identity::Identities::<T>::iter_values() {
+        if idty_value.status == IdtyStatus::Member {
+            let unclaim_uds =
+                pallet_universal_dividend::Pallet::<T>::account_balances(&idty_value.owner_key)
+                    .unclaim_uds;
+            circulating = circulating.saturating_add(unclaim_uds.saturated_into::<u128>());
+        }
+    }
+    ensure!(
+        circulating <= pallet_universal_dividend::MonetaryMass::<T>::get().saturated_into::<u128>(),
+        "money in circulation exceeds the monetary mass"
+    );
+    Ok(())
+}
+
+/// A migration checking the runtime invariants once the runtime is upgraded.
+///
+/// It does nothing outside of `try-runtime` and should be the last runtime migration.
+pub struct CheckInvariants<T>(PhantomData<T>);
+impl<T> frame_support::traits::OnRuntimeUpgrade for CheckInvariants<T>
+where
+    T: pallet_authority_members::Config
+        + pallet_certification::Config
+        + pallet_committee_membership::Config
+        + pallet_duniter_account::Config
+        + pallet_duniter_wot::Config
+        + pallet_identity::Config
+        + pallet_membership::Config
+        + pallet_oneshot_account::Config
+        + pallet_smith_members::Config
+        + pallet_universal_dividend::Config,
+{
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+        let violations = check_invariants::<T>();
+        for violation in &violations {
+            log::error!(
+                target: "runtime::invariants",
+                "{}: {}",
+                core::str::from_utf8(&violation.pallet).unwrap_or_default(),
+                core::str::from_utf8(&violation.message).unwrap_or_default(),
+            );
+        }
+        ensure!(violations.is_empty(), "runtime invariants are violated");
+        Ok(())
+    }
+}
@@ -18,12 +18,16 @@
 
 mod apis;
 mod benchmarks;
+pub mod check_rate_limit;
+pub mod check_wot_priority;
 pub mod constants;
 pub mod entities;
 pub mod fees;
 pub mod handlers;
+pub mod invariants;
 mod offchain;
 mod pallets_config;
+mod parameters;
 pub mod providers;
 
 pub use pallet_duniter_account::GenesisAccountData;
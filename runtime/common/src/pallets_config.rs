@@ -118,9 +118,21 @@ macro_rules! pallets_config {
             pub const MaxQuota: Balance = 1000; // 10 ĞD
             pub const MaxNominators: u32 = 64;
             pub TreasuryAccount: AccountId = Treasury::account_id();
+            pub const PriorityWindow: BlockNumber = 1 * HOURS;
+            pub const MaxPriorityPerWindow: u32 = 10;
+            // above any fee-based priority so that WoT calls are included first under load
+            pub const PriorityBoost: sp_runtime::transaction_validity::TransactionPriority =
+                sp_runtime::transaction_validity::TransactionPriority::MAX / 2;
         }
         impl pallet_quota::Config for Runtime {
+            type MaxPriorityPerWindow = MaxPriorityPerWindow;
             type MaxQuota = MaxQuota;
+            type PriorityBoost = PriorityBoost;
+            type PriorityWindow = PriorityWindow;
+            type RateLimitMaxPerAccount = RateLimitMaxPerAccount;
+            type RateLimitMaxPerIdentity = RateLimitMaxPerIdentity;
+            type RateLimitMaxPerMemberAccount = RateLimitMaxPerMemberAccount;
+            type RateLimitWindow = RateLimitWindow;
             type RefundAccount = TreasuryAccount;
             type ReloadRate = ReloadRate;
             type WeightInfo = weights::pallet_quota::WeightInfo<Runtime>;
@@ -303,6 +315,33 @@ macro_rules! pallets_config {
             type WeightInfo = weights::pallet_preimage::WeightInfo<Runtime>;
         }
 
+        parameter_types! {
+            pub const UpgradeMinEnactmentDelay: BlockNumber =
+                common_runtime::param_duration!(3 * DAYS, 10 * MINUTES);
+        }
+
+        impl pallet_upgrade_proposal::Config for Runtime {
+            type ApproveOrigin = pallet_collective::EnsureProportionAtLeast<
+                AccountId,
+                TechnicalCommitteeInstance,
+                2,
+                3,
+            >;
+            type CancelOrigin = pallet_collective::EnsureProportionAtLeast<
+                AccountId,
+                TechnicalCommitteeInstance,
+                2,
+                3,
+            >;
+            type MinEnactmentDelay = UpgradeMinEnactmentDelay;
+            type Preimages = Preimage;
+            type ProposeOrigin =
+                pallet_collective::EnsureMember<AccountId, TechnicalCommitteeInstance>;
+            type RuntimeCall = RuntimeCall;
+            type Scheduler = Scheduler;
+            type WeightInfo = weights::pallet_upgrade_proposal::WeightInfo<Runtime>;
+        }
+
         // UTILITIES //
 
         impl pallet_atomic_swap::Config for Runtime {
@@ -491,11 +530,17 @@ macro_rules! pallets_config {
             // With approx. 10% of validators running an oracle, a period of 40 blocks means approx. 99% chance of successful evaluation.
             type EvaluationPeriod = EvaluationPeriod;
             type EvaluationPrice = frame_support::traits::ConstU64<1000>;
+            type MaxEvaluatorHistory = MaxEvaluatorHistory;
+            type MaxOutlierPeriods = MaxOutlierPeriods;
+            type MaxQueuedEvaluations = MaxQueuedEvaluations;
             type MaxRefereeDistance = MaxRefereeDistance;
             type MinAccessibleReferees = MinAccessibleReferees;
             type OnUnbalanced = HandleFees<TreasuryAccount, Balances>;
             type OnValidDistanceStatus = Wot;
+            type OutlierTolerance = OutlierTolerance;
+            type ReportOutlier = Offences;
             type RuntimeHoldReason = RuntimeHoldReason;
+            type ValidatorSet = Historical;
             type WeightInfo = weights::pallet_distance::WeightInfo<Runtime>;
         }
 
@@ -544,8 +589,44 @@ macro_rules! pallets_config {
             type Proposal = RuntimeCall;
             type RuntimeEvent = RuntimeEvent;
             type RuntimeOrigin = RuntimeOrigin;
+            // the members are managed by the committee membership pallet
+            #[cfg(not(feature = "runtime-benchmarks"))]
+            type SetMembersOrigin = frame_system::EnsureNever<AccountId>;
+            #[cfg(feature = "runtime-benchmarks")]
             type SetMembersOrigin = EnsureRoot<AccountId>;
             type WeightInfo = weights::pallet_collective::WeightInfo<Runtime>;
         }
+
+        pub struct TechnicalCommitteeMembers;
+        impl frame_support::traits::Get<Vec<AccountId>> for TechnicalCommitteeMembers {
+            fn get() -> Vec<AccountId> {
+                pallet_collective::Members::<Runtime, Instance2>::get()
+            }
+        }
+        parameter_types! {
+            pub const CommitteeTermDuration: BlockNumber =
+                common_runtime::param_duration!(2 * YEARS, 2 * HOURS);
+            pub const CommitteePrimeRotationPeriod: BlockNumber =
+                common_runtime::param_duration!(MONTHS, 30 * MINUTES);
+        }
+        impl pallet_committee_membership::Config for Runtime {
+            type CollectiveMembers = TechnicalCommitteeMembers;
+            type IdtyAttr = Identity;
+            type IdtyIndex = IdtyIndex;
+            type IsSmith = SmithMembers;
+            type IsWoTMember = common_runtime::providers::IsWoTMemberProvider<Runtime>;
+            type ManagerOrigin = pallet_collective::EnsureProportionAtLeast<
+                AccountId,
+                TechnicalCommitteeInstance,
+                2,
+                3,
+            >;
+            type MaxMembers = frame_support::pallet_prelude::ConstU32<100>;
+            type MembershipChanged = TechnicalCommittee;
+            type PrimeRotationPeriod = CommitteePrimeRotationPeriod;
+            type RequireSmith = frame_support::traits::ConstBool<true>;
+            type TermDuration = CommitteeTermDuration;
+            type WeightInfo = weights::pallet_committee_membership::WeightInfo<Runtime>;
+        }
     };
 }
@@ -0,0 +1,69 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+/// Declares `WotParameterBounds`, the default values and allowed ranges of the Web of Trust
+/// parameters changed through `pallet_duniter_parameters`.
+///
+/// The default values are the `Default` prefixed constants of the runtime.
+#[macro_export]
+macro_rules! wot_parameter_bounds {
+    () => {
+        /// Default values and allowed ranges of the governance-controlled parameters.
+        pub struct WotParameterBounds;
+        impl pallet_duniter_parameters::ParameterBounds for WotParameterBounds {
+            fn bounds(
+                key: pallet_duniter_parameters::ParameterKey,
+            ) -> pallet_duniter_parameters::Bounds {
+                use frame_support::traits::Get;
+                use pallet_duniter_parameters::ParameterKey;
+
+                let (default, min, max) = match key {
+                    ParameterKey::CertPeriod => (
+                        DefaultCertPeriod::get(),
+                        $crate::param_duration!(DAYS, MINUTES),
+                        MONTHS,
+                    ),
+                    ParameterKey::CertMaxByIssuer => (DefaultMaxByIssuer::get(), 10, 1_000),
+                    ParameterKey::CertMinReceivedCertToIssueCert => {
+                        (DefaultMinReceivedCertToBeAbleToIssueCert::get(), 1, 20)
+                    }
+                    ParameterKey::CertValidityPeriod => (
+                        DefaultValidityPeriod::get(),
+                        $crate::param_duration!(6 * MONTHS, 10 * MINUTES),
+                        5 * YEARS,
+                    ),
+                    ParameterKey::MembershipPeriod => (
+                        DefaultMembershipPeriod::get(),
+                        $crate::param_duration!(3 * MONTHS, 10 * MINUTES),
+                        3 * YEARS,
+                    ),
+                    ParameterKey::MembershipRenewalPeriod => (
+                        DefaultMembershipRenewalPeriod::get(),
+                        $crate::param_duration!(DAYS, MINUTES),
+                        6 * MONTHS,
+                    ),
+                    ParameterKey::WotMinCertForMembership => {
+                        (DefaultWotMinCertForMembership::get(), 1, 20)
+                    }
+                    ParameterKey::WotMinCertForCreateIdtyRight => {
+                        (DefaultWotMinCertForCreateIdtyRight::get(), 1, 20)
+                    }
+                };
+                pallet_duniter_parameters::Bounds { default, min, max }
+            }
+        }
+    };
+}
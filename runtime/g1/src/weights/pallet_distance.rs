@@ -21,6 +21,10 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `aynuc`, CPU: `Raspberry Pi 4 Model B Rev 1.4`
 //! WASM-EXECUTION: `Compiled`, CHAIN: `None`, DB CACHE: 1024
+//!
+//! THE WEIGHTS MARKED `ESTIMATED` WERE WRITTEN BY HAND FOR THE CHANGES OF THE PALLET MADE AFTER
+//! THIS RUN. THEY ARE CONSERVATIVE ESTIMATES, NOT MEASUREMENTS, AND MUST BE REPLACED BY RUNNING
+//! THE COMMAND BELOW ON THE REFERENCE HARDWARE.
 
 // Executed Command:
 // target/release/duniter
@@ -57,6 +61,7 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 	/// Proof: `Membership::Membership` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Certification::StorageIdtyCertMeta` (r:1 w:0)
 	/// Proof: `Certification::StorageIdtyCertMeta` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Distance::EvaluationQueue` (r:1 w:0)
 	/// Storage: `Distance::CurrentPeriodIndex` (r:1 w:0)
 	/// Proof: `Distance::CurrentPeriodIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Distance::EvaluationPool2` (r:1 w:1)
@@ -72,7 +77,8 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 433_686_000 picoseconds.
 		Weight::from_parts(441_185_000, 0)
 			.saturating_add(Weight::from_parts(0, 4599))
-			.saturating_add(T::DbWeight::get().reads(9))
+			// ESTIMATED: one read of `Distance::EvaluationQueue` added to the measured weight
+			.saturating_add(T::DbWeight::get().reads(10))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
 	/// Storage: `Identity::IdentityIndexOf` (r:1 w:0)
@@ -85,6 +91,7 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 	/// Proof: `Membership::Membership` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Certification::StorageIdtyCertMeta` (r:1 w:0)
 	/// Proof: `Certification::StorageIdtyCertMeta` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Distance::EvaluationQueue` (r:1 w:0)
 	/// Storage: `Distance::CurrentPeriodIndex` (r:1 w:0)
 	/// Proof: `Distance::CurrentPeriodIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Distance::EvaluationPool2` (r:1 w:1)
@@ -100,9 +107,23 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 		// Minimum execution time: 454_981_000 picoseconds.
 		Weight::from_parts(463_148_000, 0)
 			.saturating_add(Weight::from_parts(0, 7103))
-			.saturating_add(T::DbWeight::get().reads(10))
+			// ESTIMATED: one read of `Distance::EvaluationQueue` added to the measured weight
+			.saturating_add(T::DbWeight::get().reads(11))
 			.saturating_add(T::DbWeight::get().writes(4))
 	}
+	/// Storage: `Distance::PendingEvaluationRequest` (r:1 w:1)
+	/// Storage: `Distance::EvaluationQueue` (r:1 w:1)
+	/// Storage: `Distance::CurrentPeriodIndex` (r:1 w:0)
+	/// Storage: `Distance::EvaluationPool2` (r:1 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	fn cancel_distance_evaluation() -> Weight {
+		// ESTIMATED: conservative estimate, not measured
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 5_000))
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
 	/// Storage: `Distance::DidUpdate` (r:1 w:1)
 	/// Proof: `Distance::DidUpdate` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Session::Validators` (r:1 w:0)
@@ -111,6 +132,7 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 	/// Proof: `Distance::CurrentPeriodIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Distance::EvaluationPool0` (r:1 w:1)
 	/// Proof: `Distance::EvaluationPool0` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Distance::EvaluatorResults` (r:0 w:1)
 	/// The range of component `i` is `[1, 1300]`.
 	fn update_evaluation(i: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -122,13 +144,15 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 			// Standard Error: 1_469
 			.saturating_add(Weight::from_parts(739_812, 0).saturating_mul(i.into()))
 			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(2))
+			// ESTIMATED: one write of `Distance::EvaluatorResults` added to the measured weight
+			.saturating_add(T::DbWeight::get().writes(3))
 			.saturating_add(Weight::from_parts(0, 10).saturating_mul(i.into()))
 	}
 	/// Storage: `Distance::CurrentPeriodIndex` (r:1 w:0)
 	/// Proof: `Distance::CurrentPeriodIndex` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Distance::EvaluationPool0` (r:1 w:1)
 	/// Proof: `Distance::EvaluationPool0` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Distance::EvaluatorResults` (r:0 w:1)
 	/// The range of component `i` is `[1, 1300]`.
 	fn force_update_evaluation(i: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -140,7 +164,8 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 			// Standard Error: 1_153
 			.saturating_add(Weight::from_parts(730_129, 0).saturating_mul(i.into()))
 			.saturating_add(T::DbWeight::get().reads(2))
-			.saturating_add(T::DbWeight::get().writes(1))
+			// ESTIMATED: one write of `Distance::EvaluatorResults` added to the measured weight
+			.saturating_add(T::DbWeight::get().writes(2))
 			.saturating_add(Weight::from_parts(0, 10).saturating_mul(i.into()))
 	}
 	/// Storage: `Identity::Identities` (r:1 w:0)
@@ -233,6 +258,34 @@ impl<T: frame_system::Config> pallet_distance::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `Distance::EvaluatorHistory` (r:1 w:1)
+	/// Storage: `Session::Validators` (r:1 w:0)
+	/// Storage: `Session::CurrentIndex` (r:1 w:0)
+	/// Storage: `Offences::ConcurrentReportsIndex` (r:1 w:1)
+	/// Storage: `Offences::Reports` (r:1 w:1)
+	/// Storage: `AuthorityMembers::OnlineAuthorities` (r:1 w:1)
+	/// Storage: `AuthorityMembers::OutgoingAuthorities` (r:1 w:1)
+	/// The range of component `i` is `[1, 1300]`.
+	fn record_evaluator_deviation(i: u32, ) -> Weight {
+		// ESTIMATED: conservative estimate, not measured
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 5_000))
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `Distance::EvaluationQueue` (r:1 w:1)
+	/// Storage: `Distance::EvaluationPool2` (r:1 w:1)
+	/// The range of component `i` is `[0, 1300]`.
+	fn schedule_queued_evaluations(i: u32, ) -> Weight {
+		// ESTIMATED: conservative estimate, not measured
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 2_000))
+			.saturating_add(Weight::from_parts(250_000, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(Weight::from_parts(0, 50).saturating_mul(i.into()))
+	}
 	/// Storage: `Distance::DidUpdate` (r:1 w:1)
 	/// Proof: `Distance::DidUpdate` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	fn on_finalize() -> Weight {
@@ -0,0 +1,63 @@
+// Copyright 2021-2022 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_duniter_parameters`
+//!
+//! THESE WEIGHTS ARE ESTIMATED FROM THE SCHEDULER AND QUOTA BENCHMARKS ON THE SAME HARDWARE,
+//! THEY MUST BE REPLACED BY THE OUTPUT OF THE SUBSTRATE BENCHMARK CLI:
+//! target/release/duniter benchmark pallet --genesis-builder=spec-genesis --pallet=pallet_duniter_parameters
+//!   --extrinsic=* --steps=50 --repeat=20 --wasm-execution=compiled --heap-pages=4096
+//!   --header=./file_header.txt --output=./runtime/g1/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_duniter_parameters`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_duniter_parameters::WeightInfo for WeightInfo<T> {
+	/// Storage: `Parameters::PendingChanges` (r:1 w:1)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	fn schedule_change() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 40_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Parameters::PendingChanges` (r:1 w:1)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	fn cancel_change() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 40_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `Parameters::Values` (r:1 w:1)
+	/// Storage: `Parameters::PendingChanges` (r:1 w:1)
+	/// Storage: `Parameters::History` (r:1 w:1)
+	fn enact_change() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 2_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+}
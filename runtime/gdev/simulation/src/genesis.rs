@@ -0,0 +1,215 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Genesis state of the simulated network.
+
+use crate::{ACCOUNTS, INITIAL_MEMBERS, account_id};
+use common_runtime::{GenesisAccountData, GenesisIdty, IdtyName, IdtyStatus, IdtyValue};
+use frame_support::traits::Get;
+use gdev_runtime::{opaque::SessionKeys, *};
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_core::{Pair, Public};
+use sp_membership::MembershipData;
+use std::collections::BTreeMap;
+
+/// Initial balance of every simulated account.
+pub const INITIAL_BALANCE: Balance = 1_000_000;
+
+/// Parameters of the simulated network.
+///
+/// Periods are short so that certifications and memberships expire within a simulation.
+pub fn parameters() -> GenesisParameters<u32, u32, Balance, u32> {
+    GenesisParameters {
+        babe_epoch_duration: 25,
+        cert_period: 15,
+        cert_max_by_issuer: 10,
+        cert_min_received_cert_to_issue_cert: 2,
+        cert_validity_period: 600,
+        idty_confirm_period: 40,
+        idty_creation_period: 50,
+        membership_period: 300,
+        membership_renewal_period: 30,
+        ud_creation_period: 60_000,
+        ud_reeval_period: 60_000 * 20,
+        smith_cert_max_by_issuer: 8,
+        smith_wot_min_cert_for_membership: 2,
+        smith_inactivity_max_duration: 48,
+        wot_first_cert_issuable_on: 20,
+        wot_min_cert_for_create_idty_right: 2,
+        wot_min_cert_for_membership: 2,
+        evaluation_period: 7,
+        rate_limit_window: 100,
+        rate_limit_max_per_account: 1_000,
+        rate_limit_max_per_member_account: 1_000,
+        rate_limit_max_per_identity: 1_000,
+    }
+}
+
+/// Build the genesis storage of the simulated network.
+///
+/// The first [`INITIAL_MEMBERS`] accounts are members certifying each other, the first one
+/// being the only smith and authority. All the [`ACCOUNTS`] are endowed.
+pub fn build_storage() -> sp_runtime::Storage {
+    let parameters = parameters();
+    let initial_members = 1..=INITIAL_MEMBERS as IdtyIndex;
+    let treasury_balance = <Runtime as pallet_balances::Config>::ExistentialDeposit::get();
+    // the treasury endowment is part of the monetary mass
+    let monetary_mass = INITIAL_BALANCE * ACCOUNTS.len() as Balance + treasury_balance;
+
+    let mut t = frame_system::GenesisConfig::<Runtime>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_authority_members::GenesisConfig::<Runtime> {
+        initial_authorities: BTreeMap::from([(1, (account_id(0), true))]),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_duniter_account::GenesisConfig::<Runtime> {
+        accounts: (0..ACCOUNTS.len())
+            .map(|i| {
+                (
+                    account_id(i),
+                    GenesisAccountData {
+                        balance: INITIAL_BALANCE,
+                        idty_id: (i < INITIAL_MEMBERS).then_some(i as IdtyIndex + 1),
+                    },
+                )
+            })
+            .collect(),
+        treasury_balance,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    // Necessary to initialize TotalIssuance
+    pallet_balances::GenesisConfig::<Runtime> {
+        total_issuance: monetary_mass,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_duniter_test_parameters::GenesisConfig::<Runtime> {
+        parameters: parameters.clone(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_session::GenesisConfig::<Runtime> {
+        keys: vec![(
+            account_id(0),
+            account_id(0),
+            SessionKeys {
+                babe: public::<BabeId>(ACCOUNTS[0]),
+                grandpa: public::<GrandpaId>(ACCOUNTS[0]),
+                im_online: public::<ImOnlineId>(ACCOUNTS[0]),
+                authority_discovery: public::<AuthorityDiscoveryId>(ACCOUNTS[0]),
+            },
+        )],
+        non_authority_keys: Vec::new(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_identity::GenesisConfig::<Runtime> {
+        identities: initial_members
+            .clone()
+            .map(|index| GenesisIdty {
+                index,
+                name: IdtyName::from(ACCOUNTS[index as usize - 1]),
+                value: IdtyValue {
+                    data: IdtyData {
+                        first_eligible_ud: pallet_universal_dividend::FirstEligibleUd::min(),
+                    },
+                    next_creatable_identity_on: Default::default(),
+                    owner_key: account_id(index as usize - 1),
+                    old_owner_key: None,
+                    next_scheduled: 0,
+                    status: IdtyStatus::Member,
+                },
+            })
+            .collect(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_quota::GenesisConfig::<Runtime> {
+        identities: initial_members.clone().collect(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_membership::GenesisConfig::<Runtime> {
+        memberships: initial_members
+            .clone()
+            .map(|index| {
+                (
+                    index,
+                    MembershipData {
+                        expire_on: parameters.membership_period,
+                    },
+                )
+            })
+            .collect(),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    // clique of certifications between the initial members
+    pallet_certification::GenesisConfig::<Runtime> {
+        certs_by_receiver: initial_members
+            .clone()
+            .map(|receiver| {
+                (
+                    receiver,
+                    initial_members
+                        .clone()
+                        .filter(|issuer| *issuer != receiver)
+                        .map(|issuer| (issuer, Some(parameters.cert_validity_period)))
+                        .collect(),
+                )
+            })
+            .collect(),
+        apply_cert_period_at_genesis: false,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_smith_members::GenesisConfig::<Runtime> {
+        initial_smiths: BTreeMap::from([(1, (true, Vec::new()))]),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    pallet_universal_dividend::GenesisConfig::<Runtime> {
+        first_reeval: Some(600_000),
+        first_ud: Some(24_000),
+        initial_monetary_mass: monetary_mass,
+        ud: 1_000,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    t
+}
+
+/// Generate a public key from the name of an account.
+fn public<TPublic: Public>(name: &str) -> <TPublic::Pair as Pair>::Public {
+    crate::pair::<TPublic::Pair>(name).public()
+}
@@ -0,0 +1,324 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! # ĞDev Web of Trust Simulation
+//!
+//! Drives the ĞDev runtime with sequences of actions on the web of trust: create and confirm
+//! identities, certify, renew certifications, request distance evaluations, revoke identities,
+//! claim UDs and let time pass. The runtime invariants are checked after each block.
+//!
+//! The sequences are generated by [`proptest`] with the [`action`] strategy, which shrinks a
+//! failing sequence to a minimal reproducer. Actions are drawn among a fixed set of accounts,
+//! so some of them are rejected by the runtime: a rejected call is skipped, which keeps every
+//! subsequence of a sequence meaningful.
+//!
+//! Distance evaluations are computed by the algorithm of the distance oracle on the simulated
+//! state, and published one evaluation period later as the inherent would, so that identities
+//! go through the whole membership lifecycle.
+
+pub mod genesis;
+
+use codec::Encode;
+use common_runtime::IdtyName;
+use frame_support::traits::{Get, OnFinalize, OnInitialize};
+use gdev_runtime::*;
+use proptest::prelude::*;
+use sp_core::{Pair, sr25519};
+use sp_runtime::traits::Dispatchable;
+
+/// Names of the simulated accounts, the first [`INITIAL_MEMBERS`] being members at genesis.
+pub const ACCOUNTS: [&str; 10] = [
+    "Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie", "Gina", "Hugo", "Iris", "Jules",
+];
+/// Number of members at genesis.
+pub const INITIAL_MEMBERS: usize = 4;
+
+/// Block time of the simulated network in milliseconds.
+const BLOCK_TIME: u64 = 6_000;
+
+/// An action of the simulation, referring to the accounts by their index in [`ACCOUNTS`].
+#[derive(Clone, Debug)]
+pub enum Action {
+    /// Create an identity for `owner`.
+    CreateIdentity { creator: usize, owner: usize },
+    /// Confirm the identity of `who` with the name of its account.
+    ConfirmIdentity { who: usize },
+    /// Add a certification from the identity of `issuer` to the identity of `receiver`.
+    AddCert { issuer: usize, receiver: usize },
+    /// Renew a certification from the identity of `issuer` to the identity of `receiver`.
+    RenewCert { issuer: usize, receiver: usize },
+    /// Request a distance evaluation of the identity of `target`.
+    RequestDistance { requester: usize, target: usize },
+    /// Revoke the identity of `who`.
+    RevokeIdentity { who: usize },
+    /// Claim the UDs of `who`.
+    ClaimUds { who: usize },
+    /// Produce the given number of blocks.
+    PassBlocks(u32),
+}
+
+/// Strategy generating the actions of a simulation.
+pub fn action() -> impl Strategy<Value = Action> {
+    let account = || 0..ACCOUNTS.len();
+    prop_oneof![
+        2 => (account(), account())
+            .prop_map(|(creator, owner)| Action::CreateIdentity { creator, owner }),
+        2 => account().prop_map(|who| Action::ConfirmIdentity { who }),
+        4 => (account(), account())
+            .prop_map(|(issuer, receiver)| Action::AddCert { issuer, receiver }),
+        1 => (account(), account())
+            .prop_map(|(issuer, receiver)| Action::RenewCert { issuer, receiver }),
+        2 => (account(), account())
+            .prop_map(|(requester, target)| Action::RequestDistance { requester, target }),
+        1 => account().prop_map(|who| Action::RevokeIdentity { who }),
+        1 => account().prop_map(|who| Action::ClaimUds { who }),
+        4 => (1..=30u32).prop_map(Action::PassBlocks),
+    ]
+}
+
+/// Generate a key pair from the name of an account.
+pub fn pair<P: Pair>(name: &str) -> P {
+    P::from_string(&format!("//{name}"), None).expect("static values are valid; qed")
+}
+
+/// Account id of the simulated account at the given index.
+pub fn account_id(index: usize) -> AccountId {
+    pair::<sr25519::Pair>(ACCOUNTS[index]).public().into()
+}
+
+/// Externalities of a new simulated network.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut ext = sp_io::TestExternalities::new(genesis::build_storage());
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Run the actions on a new network, checking the runtime invariants after each block.
+pub fn run(actions: &[Action]) -> Result<(), String> {
+    new_test_ext().execute_with(|| run_actions(actions))
+}
+
+/// Run the actions in the current externalities, checking the runtime invariants after each
+/// block.
+///
+/// Returns the first violated invariants, or the error of a distance evaluation rejected by
+/// the runtime.
+pub fn run_actions(actions: &[Action]) -> Result<(), String> {
+    let mut simulation = Simulation::default();
+    check_invariants()?;
+    for action in actions {
+        simulation.apply(action)?;
+    }
+    Ok(())
+}
+
+/// State of the simulation outside of the runtime.
+#[derive(Default)]
+struct Simulation {
+    /// Distance evaluation computed by the oracle, to be published in the next period.
+    oracle_result: Option<pallet_distance::ComputationResult>,
+}
+
+impl Simulation {
+    fn apply(&mut self, action: &Action) -> Result<(), String> {
+        let idty_index = |account: usize| Identity::identity_index_of(account_id(account));
+        let (who, call) = match *action {
+            Action::CreateIdentity { creator, owner } => (
+                creator,
+                RuntimeCall::Identity(pallet_identity::Call::create_identity {
+                    owner_key: account_id(owner),
+                }),
+            ),
+            Action::ConfirmIdentity { who } => (
+                who,
+                RuntimeCall::Identity(pallet_identity::Call::confirm_identity {
+                    idty_name: IdtyName::from(ACCOUNTS[who]),
+                }),
+            ),
+            Action::AddCert { issuer, receiver } => {
+                let Some(receiver) = idty_index(receiver) else {
+                    return Ok(());
+                };
+                (
+                    issuer,
+                    RuntimeCall::Certification(pallet_certification::Call::add_cert { receiver }),
+                )
+            }
+            Action::RenewCert { issuer, receiver } => {
+                let Some(receiver) = idty_index(receiver) else {
+                    return Ok(());
+                };
+                (
+                    issuer,
+                    RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver }),
+                )
+            }
+            Action::RequestDistance { requester, target } => {
+                let call = if requester == target {
+                    pallet_distance::Call::request_distance_evaluation {}
+                } else {
+                    let Some(target) = idty_index(target) else {
+                        return Ok(());
+                    };
+                    pallet_distance::Call::request_distance_evaluation_for { target }
+                };
+                (requester, RuntimeCall::Distance(call))
+            }
+            Action::RevokeIdentity { who } => {
+                let Some(idty_index) = idty_index(who) else {
+                    return Ok(());
+                };
+                let revocation_payload = pallet_identity::RevocationPayload {
+                    genesis_hash: System::block_hash(0),
+                    idty_index,
+                };
+                let revocation_sig = (
+                    pallet_identity::REVOCATION_PAYLOAD_PREFIX,
+                    revocation_payload,
+                )
+                    .using_encoded(|bytes| pair::<sr25519::Pair>(ACCOUNTS[who]).sign(bytes));
+                (
+                    who,
+                    RuntimeCall::Identity(pallet_identity::Call::revoke_identity {
+                        idty_index,
+                        revocation_key: account_id(who),
+                        revocation_sig: revocation_sig.into(),
+                    }),
+                )
+            }
+            Action::ClaimUds { who } => (
+                who,
+                RuntimeCall::UniversalDividend(pallet_universal_dividend::Call::claim_uds {}),
+            ),
+            Action::PassBlocks(blocks) => {
+                for _ in 0..blocks {
+                    self.next_block()?;
+                    check_invariants()?;
+                }
+                return Ok(());
+            }
+        };
+        // a call rejected by the runtime leaves the state unchanged and is skipped
+        let _ = call.dispatch(RuntimeOrigin::signed(account_id(who)));
+        Ok(())
+    }
+
+    /// Produce the next block.
+    fn next_block(&mut self) -> Result<(), String> {
+        // Finalize the previous block
+        Babe::on_finalize(System::block_number());
+        Distance::on_finalize(System::block_number());
+        TransactionPayment::on_finalize(System::block_number());
+        Authorship::on_finalize(System::block_number());
+        Grandpa::on_finalize(System::block_number());
+
+        // Set the new block number
+        System::reset_events();
+        System::set_block_number(System::block_number() + 1);
+        System::set_block_consumed_resources(Weight::zero(), 0_usize);
+        pallet_babe::CurrentSlot::<Runtime>::put(pallet_babe::CurrentSlot::<Runtime>::get() + 1);
+
+        // Initialize the new block
+        Account::on_initialize(System::block_number());
+        Scheduler::on_initialize(System::block_number());
+        Session::on_initialize(System::block_number());
+        pallet_babe::EpochIndex::<Runtime>::put(Session::current_index() as u64);
+        Authorship::on_initialize(System::block_number());
+        UniversalDividend::on_initialize(System::block_number());
+        Wot::on_initialize(System::block_number());
+        Identity::on_initialize(System::block_number());
+        Membership::on_initialize(System::block_number());
+        Certification::on_initialize(System::block_number());
+        CommitteeMembership::on_initialize(System::block_number());
+        Timestamp::set_timestamp(System::block_number() as u64 * BLOCK_TIME);
+        Distance::on_initialize(System::block_number());
+
+        let evaluation_period: u32 = <Runtime as pallet_distance::Config>::EvaluationPeriod::get();
+        if System::block_number() % evaluation_period == 0 {
+            self.run_distance_oracle()?;
+        }
+        Ok(())
+    }
+
+    /// Publish the evaluation of the previous period and evaluate the pending requests.
+    ///
+    /// The oracle evaluates the state at the beginning of a period, and its result is published
+    /// during the next period.
+    fn run_distance_oracle(&mut self) -> Result<(), String> {
+        if let Some(computation_result) = self.oracle_result.take() {
+            pallet_distance::Pallet::<Runtime>::force_update_evaluation(
+                RuntimeOrigin::root(),
+                account_id(0),
+                computation_result,
+            )
+            .map_err(|e| format!("distance evaluation rejected: {e:?}"))?;
+        }
+
+        // pool read by the oracle, see `distance_oracle::api::current_pool`
+        let evaluation_pool = match Distance::current_period_index() % 3 {
+            0 => Distance::evaluation_pool_1(),
+            1 => Distance::evaluation_pool_2(),
+            2 => Distance::evaluation_pool_0(),
+            _ => unreachable!("n<3"),
+        };
+        if evaluation_pool.evaluations.is_empty() {
+            return Ok(());
+        }
+        let idties: Vec<IdtyIndex> = evaluation_pool
+            .evaluations
+            .iter()
+            .map(|(idty, _)| *idty)
+            .collect();
+        let distances = dc_distance_rule::evaluate_distances(
+            pallet_membership::Membership::<Runtime>::iter_keys(),
+            pallet_certification::CertsByReceiver::<Runtime>::iter().map(|(receiver, issuers)| {
+                (
+                    receiver,
+                    issuers
+                        .into_iter()
+                        .map(|(issuer, _removable_on)| issuer)
+                        .collect(),
+                )
+            }),
+            <Runtime as pallet_distance::Config>::MaxRefereeDistance::get(),
+            &idties,
+        );
+        self.oracle_result = Some(pallet_distance::ComputationResult { distances });
+        Ok(())
+    }
+}
+
+/// Check the runtime invariants at the current block.
+fn check_invariants() -> Result<(), String> {
+    let violations = common_runtime::invariants::check_invariants::<Runtime>();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "invariants violated at block #{}: {}",
+            System::block_number(),
+            violations
+                .iter()
+                .map(|violation| format!(
+                    "{}: {}",
+                    String::from_utf8_lossy(&violation.pallet),
+                    String::from_utf8_lossy(&violation.message)
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+}
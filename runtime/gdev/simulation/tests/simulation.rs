@@ -0,0 +1,72 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use common_runtime::IdtyStatus;
+use gdev_runtime::*;
+use gdev_simulation::{Action, account_id, action, new_test_ext, run, run_actions};
+use proptest::prelude::*;
+
+proptest! {
+    // each case simulates hundreds of blocks
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    /// This test checks that the runtime invariants hold after each block of a random
+    /// simulation of the web of trust.
+    #[test]
+    fn invariants_hold(actions in prop::collection::vec(action(), 1..200)) {
+        prop_assert_eq!(run(&actions), Ok(()));
+    }
+}
+
+/// This test checks that an identity created and certified during the simulation becomes
+/// member once its distance is evaluated by the oracle.
+#[test]
+fn membership_lifecycle() {
+    let actions = [
+        Action::PassBlocks(20),
+        Action::CreateIdentity {
+            creator: 0,
+            owner: 4,
+        },
+        Action::ConfirmIdentity { who: 4 },
+        Action::AddCert {
+            issuer: 1,
+            receiver: 4,
+        },
+        Action::AddCert {
+            issuer: 2,
+            receiver: 4,
+        },
+        Action::RequestDistance {
+            requester: 4,
+            target: 4,
+        },
+        // the oracle evaluates the request in the next period
+        // and its result is applied two periods later
+        Action::PassBlocks(30),
+    ];
+    new_test_ext().execute_with(|| {
+        assert_eq!(run_actions(&actions), Ok(()));
+        let idty_index = Identity::identity_index_of(account_id(4)).unwrap();
+        assert_eq!(
+            Identity::identity(idty_index).unwrap().status,
+            IdtyStatus::Member
+        );
+        assert!(pallet_membership::Membership::<Runtime>::contains_key(
+            idty_index
+        ));
+    });
+}
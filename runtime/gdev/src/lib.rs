@@ -86,10 +86,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 1000,
+    spec_version: 1001,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     system_version: 1,
 };
 
@@ -115,6 +115,8 @@ pub type TxExtension = (
     frame_system::CheckWeight<Runtime>,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
     frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
+    common_runtime::check_wot_priority::CheckWotPriority<Runtime>,
+    common_runtime::check_rate_limit::CheckRateLimit<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -127,8 +129,26 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
+parameter_types! {
+    /// Rate limit parameters written to storage by the test parameters migration.
+    pub const DefaultRateLimits: (u32, u32, u32, u32) = (
+        RATE_LIMIT_WINDOW,
+        RATE_LIMIT_MAX_PER_ACCOUNT,
+        RATE_LIMIT_MAX_PER_MEMBER_ACCOUNT,
+        RATE_LIMIT_MAX_PER_IDENTITY,
+    );
+}
+
+/// Runtime migrations to apply on upgrade.
+type Migrations = (
+    pallet_duniter_test_parameters::migrations::v2::MigrateV1ToV2<Runtime, DefaultRateLimits>,
+    pallet_committee_membership::migrations::ImportCollectiveMembers<Runtime>,
+    common_runtime::invariants::CheckInvariants<Runtime>,
+);
+
 pub type TechnicalCommitteeInstance = Instance2;
 
 pub struct BaseCallFilter;
@@ -164,6 +184,9 @@ pub enum ProxyType {
     TransferOnly = 1,
     CancelProxy = 2,
     TechnicalCommitteePropose = 3,
+    CertificationRenewal = 4,
+    DistanceRequest = 5,
+    SmithOperations = 6,
 }
 impl Default for ProxyType {
     fn default() -> Self {
@@ -200,6 +223,32 @@ impl frame_support::traits::InstanceFilter<RuntimeCall> for ProxyType {
                     RuntimeCall::TechnicalCommittee(pallet_collective::Call::propose { .. })
                 )
             }
+            ProxyType::CertificationRenewal => {
+                matches!(
+                    c,
+                    RuntimeCall::Certification(pallet_certification::Call::renew_cert { .. })
+                )
+            }
+            ProxyType::DistanceRequest => {
+                matches!(
+                    c,
+                    RuntimeCall::Distance(
+                        pallet_distance::Call::request_distance_evaluation { .. }
+                            | pallet_distance::Call::request_distance_evaluation_for { .. }
+                            | pallet_distance::Call::cancel_distance_evaluation { .. }
+                    )
+                )
+            }
+            ProxyType::SmithOperations => {
+                matches!(
+                    c,
+                    RuntimeCall::AuthorityMembers(
+                        pallet_authority_members::Call::go_online { .. }
+                            | pallet_authority_members::Call::go_offline { .. }
+                            | pallet_authority_members::Call::set_session_keys { .. }
+                    )
+                )
+            }
         }
     }
 }
@@ -227,6 +276,11 @@ pub type SmithWotMinCertForMembership =
 pub type SmithInactivityMaxDuration =
     pallet_duniter_test_parameters::SmithInactivityMaxDuration<Runtime>;
 pub type EvaluationPeriod = pallet_duniter_test_parameters::EvaluationPeriod<Runtime>;
+pub type RateLimitWindow = pallet_duniter_test_parameters::RateLimitWindow<Runtime>;
+pub type RateLimitMaxPerAccount = pallet_duniter_test_parameters::RateLimitMaxPerAccount<Runtime>;
+pub type RateLimitMaxPerMemberAccount =
+    pallet_duniter_test_parameters::RateLimitMaxPerMemberAccount<Runtime>;
+pub type RateLimitMaxPerIdentity = pallet_duniter_test_parameters::RateLimitMaxPerIdentity<Runtime>;
 
 impl pallet_duniter_test_parameters::Config for Runtime {
     type BlockNumber = u32;
@@ -272,6 +326,8 @@ construct_runtime!(
         UpgradeOrigin: pallet_upgrade_origin = 21,
         Preimage: pallet_preimage = 22,
         TechnicalCommittee: pallet_collective::<Instance2> = 23,
+        CommitteeMembership: pallet_committee_membership = 24,
+        UpgradeProposal: pallet_upgrade_proposal = 25,
 
         // Universal dividend
         UniversalDividend: pallet_universal_dividend = 30,
@@ -54,6 +54,10 @@ parameter_types! {
     pub const MinAccessibleReferees: Perbill = Perbill::from_percent(80);
     pub const MaxRefereeDistance: u32 = 5;
     pub const EvaluationPeriod: u32 = 40;
+    pub const MaxEvaluatorHistory: u32 = 20;
+    pub const MaxOutlierPeriods: u32 = 10;
+    pub const MaxQueuedEvaluations: u32 = 2 * pallet_distance::MAX_EVALUATIONS_PER_SESSION;
+    pub const OutlierTolerance: Perbill = Perbill::from_percent(5);
 }
 
 // Babe
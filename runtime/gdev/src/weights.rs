@@ -39,6 +39,7 @@ pub mod pallet_timestamp;
 pub mod pallet_treasury;
 pub mod pallet_universal_dividend;
 pub mod pallet_upgrade_origin;
+pub mod pallet_upgrade_proposal;
 pub mod pallet_provide_randomness;
 pub mod pallet_identity;
 pub mod pallet_preimage;
@@ -47,6 +48,7 @@ pub mod pallet_duniter_account;
 pub mod pallet_quota;
 pub mod pallet_oneshot_account;
 pub mod pallet_certification;
+pub mod pallet_committee_membership;
 pub mod pallet_membership;
 pub mod pallet_smith_members;
 pub mod pallet_sudo;
@@ -0,0 +1,74 @@
+// Copyright 2021-2022 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_upgrade_proposal`
+//!
+//! THESE WEIGHTS ARE ESTIMATED FROM THE PREIMAGE AND SCHEDULER BENCHMARKS ON THE SAME HARDWARE,
+//! THEY MUST BE REPLACED BY THE OUTPUT OF THE SUBSTRATE BENCHMARK CLI:
+//! target/release/duniter benchmark pallet --genesis-builder=spec-genesis --pallet=pallet_upgrade_proposal
+//!   --extrinsic=* --steps=50 --repeat=20 --wasm-execution=compiled --heap-pages=4096
+//!   --header=./file_header.txt --output=./runtime/gdev/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_upgrade_proposal`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_upgrade_proposal::WeightInfo for WeightInfo<T> {
+	/// Storage: `UpgradeProposal::Proposal` (r:1 w:1)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Preimage::StatusFor` (r:1 w:0)
+	fn propose_upgrade() -> Weight {
+		Weight::from_parts(35_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `UpgradeProposal::Proposal` (r:1 w:1)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	fn approve_upgrade() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 40_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: `UpgradeProposal::Proposal` (r:1 w:1)
+	/// Storage: `Scheduler::Lookup` (r:1 w:1)
+	/// Storage: `Scheduler::Agenda` (r:1 w:1)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	fn cancel_upgrade() -> Weight {
+		Weight::from_parts(50_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 40_000))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Storage: `UpgradeProposal::Proposal` (r:1 w:1)
+	/// Storage: `Preimage::RequestStatusFor` (r:1 w:1)
+	/// Storage: `Preimage::PreimageFor` (r:1 w:0)
+	/// Storage: `UpgradeProposal::EnactedUpgrades` (r:0 w:1)
+	fn enact_upgrade() -> Weight {
+		Weight::from_parts(3_500_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 4_200_000))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+}
@@ -0,0 +1,121 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use frame_support::assert_ok;
+use gdev_runtime::*;
+use pallet_membership::MembershipRemovalReason;
+use sp_keyring::sr25519::Keyring;
+
+/// Origin of a motion approved by the technical committee.
+fn committee_origin() -> RuntimeOrigin {
+    pallet_collective::RawOrigin::<AccountId, TechnicalCommitteeInstance>::Members(2, 3).into()
+}
+
+/// This test checks that a committee member losing its WoT membership
+/// is removed from the technical committee.
+#[test]
+fn test_committee_member_removed_on_membership_loss() {
+    ExtBuilder::new(1, 3, 4).build().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(CommitteeMembership::add_member(committee_origin(), 1));
+        assert_ok!(CommitteeMembership::add_member(committee_origin(), 2));
+        assert_eq!(
+            pallet_collective::Members::<Runtime, TechnicalCommitteeInstance>::get().len(),
+            2
+        );
+        assert_eq!(
+            pallet_collective::Prime::<Runtime, TechnicalCommitteeInstance>::get(),
+            Some(Keyring::Alice.to_account_id())
+        );
+
+        Membership::do_remove_membership(1, MembershipRemovalReason::System);
+        System::assert_has_event(RuntimeEvent::CommitteeMembership(
+            pallet_committee_membership::Event::MemberRemoved {
+                idty_index: 1,
+                reason: pallet_committee_membership::RemovalReason::LostMembership,
+            },
+        ));
+        assert_eq!(
+            pallet_collective::Members::<Runtime, TechnicalCommitteeInstance>::get(),
+            vec![Keyring::Bob.to_account_id()]
+        );
+        assert_eq!(
+            pallet_collective::Prime::<Runtime, TechnicalCommitteeInstance>::get(),
+            Some(Keyring::Bob.to_account_id())
+        );
+    });
+}
+
+/// This test checks that only smiths can join the technical committee.
+#[test]
+fn test_committee_member_must_be_smith() {
+    ExtBuilder::new(1, 3, 4).build().execute_with(|| {
+        run_to_block(1);
+        // Dave is a member but not a smith
+        assert_eq!(
+            CommitteeMembership::add_member(committee_origin(), 4),
+            Err(pallet_committee_membership::Error::<Runtime>::NotSmith.into())
+        );
+    });
+}
+
+/// This test checks that the collective members are imported into the committee
+/// when the committee membership pallet is added by a runtime upgrade.
+#[test]
+fn test_import_collective_members_on_runtime_upgrade() {
+    ExtBuilder::new(1, 3, 4).build().execute_with(|| {
+        use frame_support::traits::GetStorageVersion;
+
+        run_to_block(1);
+        // the pallet is not deployed yet and the collective is managed on its own,
+        // Dave is not a smith and Ferdie has no identity
+        let _ =
+            sp_io::storage::clear_prefix(&sp_io::hashing::twox_128(b"CommitteeMembership"), None);
+        pallet_collective::Members::<Runtime, TechnicalCommitteeInstance>::put(vec![
+            Keyring::Bob.to_account_id(),
+            Keyring::Alice.to_account_id(),
+            Keyring::Dave.to_account_id(),
+            Keyring::Ferdie.to_account_id(),
+        ]);
+
+        Executive::execute_on_runtime_upgrade();
+
+        assert_eq!(CommitteeMembership::on_chain_storage_version(), 1);
+        assert_eq!(pallet_committee_membership::Members::<Runtime>::count(), 2);
+        assert_eq!(
+            pallet_committee_membership::Prime::<Runtime>::get(),
+            Some(1)
+        );
+        let mut collective_members =
+            vec![Keyring::Alice.to_account_id(), Keyring::Bob.to_account_id()];
+        collective_members.sort();
+        assert_eq!(
+            pallet_collective::Members::<Runtime, TechnicalCommitteeInstance>::get(),
+            collective_members
+        );
+        assert_eq!(
+            pallet_collective::Prime::<Runtime, TechnicalCommitteeInstance>::get(),
+            Some(Keyring::Alice.to_account_id())
+        );
+        assert_eq!(
+            common_runtime::invariants::check_invariants::<Runtime>(),
+            vec![]
+        );
+    });
+}
@@ -113,6 +113,10 @@ impl ExtBuilder {
                 wot_min_cert_for_create_idty_right: 2,
                 wot_min_cert_for_membership: 2,
                 evaluation_period: 7,
+                rate_limit_window: 100,
+                rate_limit_max_per_account: 1_000,
+                rate_limit_max_per_member_account: 1_000,
+                rate_limit_max_per_identity: 1_000,
             },
         }
     }
@@ -374,6 +378,7 @@ pub fn run_to_block(n: u32) {
         Identity::on_initialize(System::block_number());
         Membership::on_initialize(System::block_number());
         Certification::on_initialize(System::block_number());
+        CommitteeMembership::on_initialize(System::block_number());
 
         Timestamp::set_timestamp(System::block_number() as u64 * BLOCK_TIME);
         Distance::on_initialize(System::block_number());
@@ -507,6 +512,8 @@ pub fn get_unchecked_extrinsic(
         frame_system::CheckWeight::<gdev_runtime::Runtime>::new(),
         pallet_transaction_payment::ChargeTransactionPayment::<gdev_runtime::Runtime>::from(tip),
         frame_metadata_hash_extension::CheckMetadataHash::<gdev_runtime::Runtime>::new(false),
+        common_runtime::check_wot_priority::CheckWotPriority::<gdev_runtime::Runtime>::new(),
+        common_runtime::check_rate_limit::CheckRateLimit::<gdev_runtime::Runtime>::new(),
     );
     let payload = SignedPayload::new(call.clone(), tx_ext.clone()).unwrap();
     let origin = signer;
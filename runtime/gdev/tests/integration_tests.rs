@@ -79,6 +79,12 @@ fn verify_pallet_indices() {
 #[test]
 fn verify_proxy_type_indices() {
     assert_eq!(ProxyType::AlmostAny as u8, 0);
+    assert_eq!(ProxyType::TransferOnly as u8, 1);
+    assert_eq!(ProxyType::CancelProxy as u8, 2);
+    assert_eq!(ProxyType::TechnicalCommitteePropose as u8, 3);
+    assert_eq!(ProxyType::CertificationRenewal as u8, 4);
+    assert_eq!(ProxyType::DistanceRequest as u8, 5);
+    assert_eq!(ProxyType::SmithOperations as u8, 6);
 }
 
 #[test]
@@ -0,0 +1,80 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use common_runtime::invariants::{InvariantViolation, check_invariants};
+use frame_support::{assert_ok, traits::Get};
+use gdev_runtime::*;
+use sp_keyring::sr25519::Keyring;
+
+/// The test genesis endows the treasury without counting it in the total issuance
+/// nor in the monetary mass, count it here so that the invariants hold.
+fn count_treasury_endowment() {
+    let endowment = <Runtime as pallet_balances::Config>::ExistentialDeposit::get();
+    pallet_balances::TotalIssuance::<Runtime>::mutate(|total| *total += endowment);
+    pallet_universal_dividend::MonetaryMass::<Runtime>::mutate(|mass| *mass += endowment);
+}
+
+/// This test checks that the runtime invariants hold while UDs are created and claimed.
+#[test]
+fn test_invariants_hold() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![
+            (Keyring::Alice.to_account_id(), 2_000),
+            (Keyring::Bob.to_account_id(), 1_000),
+            (Keyring::Charlie.to_account_id(), 500),
+            (Keyring::Dave.to_account_id(), 500),
+        ])
+        .build()
+        .execute_with(|| {
+            count_treasury_endowment();
+            run_to_block(1);
+            assert_eq!(check_invariants::<Runtime>(), vec![]);
+
+            // first UD creation
+            run_to_block(11);
+            assert_ok!(UniversalDividend::claim_uds(RuntimeOrigin::signed(
+                Keyring::Alice.to_account_id()
+            )));
+            assert_ok!(Balances::transfer_allow_death(
+                RuntimeOrigin::signed(Keyring::Alice.to_account_id()),
+                Keyring::Eve.to_account_id().into(),
+                500
+            ));
+            assert_eq!(check_invariants::<Runtime>(), vec![]);
+        });
+}
+
+/// This test checks that a violated invariant is reported with its pallet.
+#[test]
+fn test_invariant_violation_is_reported() {
+    ExtBuilder::new(1, 3, 4).build().execute_with(|| {
+        count_treasury_endowment();
+        run_to_block(1);
+        assert_eq!(check_invariants::<Runtime>(), vec![]);
+
+        pallet_universal_dividend::MonetaryMass::<Runtime>::mutate(|mass| *mass -= 1);
+        assert_eq!(
+            check_invariants::<Runtime>(),
+            vec![InvariantViolation {
+                pallet: b"UniversalDividend".to_vec(),
+                message: b"money in circulation exceeds the monetary mass".to_vec(),
+            }]
+        );
+    });
+}
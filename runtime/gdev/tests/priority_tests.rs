@@ -0,0 +1,403 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use common_runtime::check_wot_priority::CheckWotPriority;
+use frame_support::{
+    assert_ok,
+    dispatch::GetDispatchInfo,
+    pallet_prelude::{DispatchClass, InvalidTransaction},
+};
+use gdev_runtime::*;
+use sp_keyring::sr25519::Keyring;
+use sp_runtime::transaction_validity::{
+    TransactionPriority, TransactionSource, TransactionValidityError, ValidTransaction,
+};
+
+/// Returns the validity of an extrinsic as computed by `validate_transaction`.
+fn validity_of(xt: UncheckedExtrinsic) -> ValidTransaction {
+    let block_number = System::block_number();
+    let validity =
+        Executive::validate_transaction(TransactionSource::External, xt, System::parent_hash())
+            .expect("transaction should be valid");
+    // validation initializes the next block, restore the current one
+    System::set_block_number(block_number);
+    validity
+}
+
+/// Returns the pool priority of an extrinsic as computed by `validate_transaction`.
+fn priority_of(xt: UncheckedExtrinsic) -> TransactionPriority {
+    validity_of(xt).priority
+}
+
+/// Simulates a chain under load so that fees apply to all transactions.
+fn fill_blocks() {
+    pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(MaxMultiplier::get());
+}
+
+/// This test checks that, under load, a member-signed certification renewal is ordered
+/// before transfers, even tipped ones.
+#[test]
+fn test_wot_call_priority_under_full_block() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![
+            (Keyring::Alice.to_account_id(), 10_000),
+            (Keyring::Bob.to_account_id(), 10_000),
+            (Keyring::Eve.to_account_id(), 10_000),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            fill_blocks();
+
+            let transfer = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+                dest: Keyring::Eve.to_account_id().into(),
+                value: 500,
+            });
+            let transfer_priority = priority_of(get_unchecked_extrinsic(
+                transfer.clone(),
+                4u64,
+                8u64,
+                Keyring::Alice,
+                0u64,
+                0,
+            ));
+            let tipped_transfer_priority = priority_of(get_unchecked_extrinsic(
+                transfer,
+                4u64,
+                8u64,
+                Keyring::Alice,
+                100u64,
+                0,
+            ));
+
+            let renew_cert =
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 3 });
+            let renew_cert_priority = priority_of(get_unchecked_extrinsic(
+                renew_cert,
+                4u64,
+                8u64,
+                Keyring::Bob,
+                0u64,
+                0,
+            ));
+
+            // Eve is not a member, their WoT call is not boosted
+            let add_cert =
+                RuntimeCall::Certification(pallet_certification::Call::add_cert { receiver: 3 });
+            let non_member_priority = priority_of(get_unchecked_extrinsic(
+                add_cert,
+                4u64,
+                8u64,
+                Keyring::Eve,
+                0u64,
+                0,
+            ));
+
+            assert!(tipped_transfer_priority > transfer_priority);
+            assert!(renew_cert_priority >= PriorityBoost::get());
+            assert!(renew_cert_priority > tipped_transfer_priority);
+            assert!(non_member_priority < PriorityBoost::get());
+        })
+}
+
+/// This test checks that distance evaluation requests from members are boosted.
+#[test]
+fn test_distance_request_priority() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![
+            (Keyring::Alice.to_account_id(), 10_000),
+            (Keyring::Bob.to_account_id(), 10_000),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            fill_blocks();
+
+            let transfer = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+                dest: Keyring::Eve.to_account_id().into(),
+                value: 500,
+            });
+            let transfer_priority = priority_of(get_unchecked_extrinsic(
+                transfer,
+                4u64,
+                8u64,
+                Keyring::Alice,
+                100u64,
+                0,
+            ));
+            let request =
+                RuntimeCall::Distance(pallet_distance::Call::request_distance_evaluation_for {
+                    target: 3,
+                });
+            let request_priority = priority_of(get_unchecked_extrinsic(
+                request,
+                4u64,
+                8u64,
+                Keyring::Bob,
+                0u64,
+                0,
+            ));
+
+            assert!(request_priority > transfer_priority);
+        })
+}
+
+/// This test checks that the owner of an unconfirmed identity gets the boost to confirm it.
+#[test]
+fn test_confirm_identity_priority() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![
+            (Keyring::Alice.to_account_id(), 10_000),
+            (Keyring::Eve.to_account_id(), 10_000),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(1);
+            assert_ok!(Identity::create_identity(
+                RuntimeOrigin::signed(Keyring::Alice.to_account_id()),
+                Keyring::Eve.to_account_id(),
+            ));
+            run_to_block(2);
+            fill_blocks();
+
+            let confirm = RuntimeCall::Identity(pallet_identity::Call::confirm_identity {
+                idty_name: "Eeeeeveeeee".into(),
+            });
+            let confirm_priority = priority_of(get_unchecked_extrinsic(
+                confirm,
+                4u64,
+                8u64,
+                Keyring::Eve,
+                0u64,
+                0,
+            ));
+            assert!(confirm_priority >= PriorityBoost::get());
+
+            // an unconfirmed identity does not get the boost for other WoT calls
+            let renew_cert =
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 1 });
+            let renew_cert_priority = priority_of(get_unchecked_extrinsic(
+                renew_cert,
+                4u64,
+                8u64,
+                Keyring::Eve,
+                0u64,
+                0,
+            ));
+            assert!(renew_cert_priority < PriorityBoost::get());
+        })
+}
+
+/// This test checks that the boost is recorded when the extrinsic is applied
+/// and that it is no longer granted once the identity reached its limit.
+#[test]
+fn test_wot_priority_rate_limit() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![(Keyring::Bob.to_account_id(), 10_000)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            let renew_cert =
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 3 });
+            let xt = get_unchecked_extrinsic(renew_cert.clone(), 4u64, 8u64, Keyring::Bob, 0u64, 0);
+            assert!(priority_of(xt.clone()) >= PriorityBoost::get());
+            assert_ok!(Executive::apply_extrinsic(xt));
+            assert_eq!(
+                pallet_quota::IdtyPriorityUsage::<Runtime>::get(2),
+                Some(pallet_quota::PriorityUsage {
+                    window_start: 2,
+                    count: 1
+                })
+            );
+
+            // use the remaining priority transactions of the window
+            for _ in 1..MaxPriorityPerWindow::get() {
+                Quota::note_priority_use(2);
+            }
+            let xt = get_unchecked_extrinsic(renew_cert, 4u64, 8u64, Keyring::Bob, 0u64, 1);
+            assert!(priority_of(xt) < PriorityBoost::get());
+
+            // other members are not impacted
+            let renew_cert =
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 2 });
+            let xt = get_unchecked_extrinsic(renew_cert, 4u64, 8u64, Keyring::Charlie, 0u64, 0);
+            assert!(priority_of(xt) >= PriorityBoost::get());
+        })
+}
+
+/// This test checks that, when the block is full, the boosted WoT calls are included first and
+/// the transfers submitted before them are left out.
+#[test]
+fn test_wot_calls_first_in_full_block() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![
+            (Keyring::Alice.to_account_id(), 1_000_000),
+            (Keyring::Bob.to_account_id(), 1_000_000),
+            (Keyring::Charlie.to_account_id(), 1_000_000),
+            (Keyring::Eve.to_account_id(), 1_000_000),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            fill_blocks();
+
+            // tipped transfers are submitted first, then the WoT calls
+            let transfer = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+                dest: Keyring::Eve.to_account_id().into(),
+                value: 500,
+            });
+            let mut submitted: Vec<UncheckedExtrinsic> = (0..5)
+                .map(|nonce| {
+                    get_unchecked_extrinsic(
+                        transfer.clone(),
+                        4u64,
+                        8u64,
+                        Keyring::Alice,
+                        100u64,
+                        nonce,
+                    )
+                })
+                .collect();
+            let bob_renew_cert = get_unchecked_extrinsic(
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 3 }),
+                4u64,
+                8u64,
+                Keyring::Bob,
+                0u64,
+                0,
+            );
+            let charlie_renew_cert = get_unchecked_extrinsic(
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 2 }),
+                4u64,
+                8u64,
+                Keyring::Charlie,
+                0u64,
+                0,
+            );
+            submitted.push(bob_renew_cert.clone());
+            submitted.push(charlie_renew_cert.clone());
+
+            // the ready queue of the pool yields the transactions by decreasing priority
+            let mut ready: Vec<(TransactionPriority, UncheckedExtrinsic)> = submitted
+                .into_iter()
+                .map(|xt| (priority_of(xt.clone()), xt))
+                .collect();
+            ready.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+            // leave room in the block for the WoT calls and a single transfer
+            let weights = BlockWeights::get();
+            let normal = weights.get(DispatchClass::Normal);
+            let weight_of = |xt: &UncheckedExtrinsic| {
+                xt.get_dispatch_info().total_weight() + normal.base_extrinsic
+            };
+            let room = weight_of(&bob_renew_cert)
+                + weight_of(&charlie_renew_cert)
+                + weight_of(&ready.last().unwrap().1);
+            System::set_block_consumed_resources(
+                normal
+                    .max_total
+                    .unwrap_or(weights.max_block)
+                    .saturating_sub(room),
+                0_usize,
+            );
+
+            // build the block like the block authoring does
+            let mut included = Vec::new();
+            let mut left_out = 0;
+            for (priority, xt) in ready {
+                match Executive::apply_extrinsic(xt) {
+                    Ok(_) => included.push(priority),
+                    Err(err) => {
+                        assert_eq!(
+                            err,
+                            TransactionValidityError::Invalid(
+                                InvalidTransaction::ExhaustsResources
+                            )
+                        );
+                        left_out += 1;
+                    }
+                }
+            }
+
+            assert!(left_out > 0);
+            assert!(included[0] >= PriorityBoost::get());
+            assert!(included[1] >= PriorityBoost::get());
+            assert!(
+                included[2..]
+                    .iter()
+                    .all(|priority| *priority < PriorityBoost::get())
+            );
+            assert_eq!(
+                pallet_quota::IdtyPriorityUsage::<Runtime>::get(2).map(|usage| usage.count),
+                Some(1)
+            );
+            assert_eq!(
+                pallet_quota::IdtyPriorityUsage::<Runtime>::get(3).map(|usage| usage.count),
+                Some(1)
+            );
+        })
+}
+
+/// This test checks that a boosted transaction of an identity provides a tag which only changes
+/// on inclusion, so that the pool keeps one transaction per call, and that the boosted
+/// transactions of different calls are all accepted.
+#[test]
+fn test_wot_priority_reserved_in_pool() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![(Keyring::Bob.to_account_id(), 10_000)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            let renew_cert =
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 3 });
+            let other_renew_cert =
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 1 });
+            let first =
+                get_unchecked_extrinsic(renew_cert.clone(), 4u64, 8u64, Keyring::Bob, 0u64, 0);
+            let second = get_unchecked_extrinsic(
+                other_renew_cert.clone(),
+                4u64,
+                8u64,
+                Keyring::Bob,
+                0u64,
+                1,
+            );
+            let tag = CheckWotPriority::<Runtime>::priority_tag(2, &renew_cert);
+            let other_tag = CheckWotPriority::<Runtime>::priority_tag(2, &other_renew_cert);
+            assert_ne!(tag, other_tag);
+            let first_validity = validity_of(first.clone());
+            let second_validity = validity_of(second.clone());
+            assert!(first_validity.provides.contains(&tag));
+            assert!(first_validity.priority >= PriorityBoost::get());
+            assert!(second_validity.provides.contains(&other_tag));
+            assert!(second_validity.priority >= PriorityBoost::get());
+
+            // the same call provides the same tag until one of them is included
+            let same_call = get_unchecked_extrinsic(renew_cert, 4u64, 8u64, Keyring::Bob, 0u64, 1);
+            assert!(validity_of(same_call).provides.contains(&tag));
+
+            // once the first one is included, the pending one gets a new tag
+            assert_ok!(Executive::apply_extrinsic(first));
+            let next_tag = CheckWotPriority::<Runtime>::priority_tag(2, &other_renew_cert);
+            assert_ne!(next_tag, other_tag);
+            assert!(validity_of(second).provides.contains(&next_tag));
+        })
+}
@@ -0,0 +1,202 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use frame_support::assert_ok;
+use gdev_runtime::*;
+use sp_core::Pair;
+use sp_keyring::sr25519::Keyring;
+use sp_runtime::DispatchResult;
+
+/// Gives Eve a proxy of the given type on the account of `real`.
+fn add_eve_proxy(real: Keyring, proxy_type: ProxyType) {
+    assert_ok!(Proxy::add_proxy(
+        RuntimeOrigin::signed(real.to_account_id()),
+        Keyring::Eve.to_account_id().into(),
+        proxy_type,
+        0
+    ));
+}
+
+/// Dispatches `call` as `real` through Eve's proxy and returns the result of the call.
+fn proxy_call(real: Keyring, call: RuntimeCall) -> DispatchResult {
+    assert_ok!(Proxy::proxy(
+        RuntimeOrigin::signed(Keyring::Eve.to_account_id()),
+        real.to_account_id().into(),
+        None,
+        Box::new(call)
+    ));
+    System::events()
+        .into_iter()
+        .rev()
+        .find_map(|record| match record.event {
+            RuntimeEvent::Proxy(pallet_proxy::Event::ProxyExecuted { result }) => Some(result),
+            _ => None,
+        })
+        .expect("proxy call should be executed")
+}
+
+fn assert_filtered(result: DispatchResult) {
+    assert_eq!(
+        result,
+        Err(frame_system::Error::<Runtime>::CallFiltered.into())
+    );
+}
+
+fn dummy_signature() -> Signature {
+    sp_core::sr25519::Signature::from_raw([0u8; 64]).into()
+}
+
+/// Identity owner actions that no WoT proxy type may perform.
+fn owner_only_calls() -> Vec<RuntimeCall> {
+    vec![
+        RuntimeCall::Identity(pallet_identity::Call::change_owner_key {
+            new_key: Keyring::Eve.to_account_id(),
+            new_key_sig: dummy_signature(),
+        }),
+        RuntimeCall::Identity(pallet_identity::Call::revoke_identity {
+            idty_index: 1,
+            revocation_key: Keyring::Alice.to_account_id(),
+            revocation_sig: dummy_signature(),
+        }),
+        RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+            dest: Keyring::Eve.to_account_id().into(),
+            value: 500,
+        }),
+    ]
+}
+
+/// This test checks that a `CertificationRenewal` proxy can only renew certifications.
+#[test]
+fn test_certification_renewal_proxy() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![(Keyring::Alice.to_account_id(), 10_000)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            add_eve_proxy(Keyring::Alice, ProxyType::CertificationRenewal);
+
+            assert_ok!(proxy_call(
+                Keyring::Alice,
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 2 })
+            ));
+            System::assert_has_event(RuntimeEvent::Certification(
+                pallet_certification::Event::CertRenewed {
+                    issuer: 1,
+                    receiver: 2,
+                },
+            ));
+
+            assert_filtered(proxy_call(
+                Keyring::Alice,
+                RuntimeCall::Certification(pallet_certification::Call::add_cert { receiver: 5 }),
+            ));
+            assert_filtered(proxy_call(
+                Keyring::Alice,
+                RuntimeCall::Distance(pallet_distance::Call::request_distance_evaluation {}),
+            ));
+            for call in owner_only_calls() {
+                assert_filtered(proxy_call(Keyring::Alice, call));
+            }
+        })
+}
+
+/// This test checks that a `DistanceRequest` proxy can only request distance evaluations.
+#[test]
+fn test_distance_request_proxy() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![(Keyring::Alice.to_account_id(), 10_000)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            add_eve_proxy(Keyring::Alice, ProxyType::DistanceRequest);
+
+            assert_ok!(proxy_call(
+                Keyring::Alice,
+                RuntimeCall::Distance(pallet_distance::Call::request_distance_evaluation {})
+            ));
+            assert_eq!(
+                Distance::pending_evaluation_request(1),
+                Some(Keyring::Alice.to_account_id())
+            );
+            // evaluations for another identity are allowed too
+            assert_ne!(
+                proxy_call(
+                    Keyring::Alice,
+                    RuntimeCall::Distance(pallet_distance::Call::request_distance_evaluation_for {
+                        target: 2
+                    })
+                ),
+                Err(frame_system::Error::<Runtime>::CallFiltered.into())
+            );
+
+            assert_filtered(proxy_call(
+                Keyring::Alice,
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 2 }),
+            ));
+            for call in owner_only_calls() {
+                assert_filtered(proxy_call(Keyring::Alice, call));
+            }
+        })
+}
+
+/// This test checks that a `SmithOperations` proxy can only manage the authority of a smith.
+#[test]
+fn test_smith_operations_proxy() {
+    ExtBuilder::new(1, 3, 4)
+        .with_initial_balances(vec![(Keyring::Charlie.to_account_id(), 10_000)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+            add_eve_proxy(Keyring::Charlie, ProxyType::SmithOperations);
+
+            frame_system::Pallet::<Runtime>::inc_providers(&Keyring::Charlie.to_account_id());
+            let session_keys = gdev_runtime::opaque::SessionKeys {
+                grandpa: sp_core::ed25519::Pair::generate().0.public().into(),
+                babe: sp_core::sr25519::Pair::generate().0.public().into(),
+                im_online: sp_core::sr25519::Pair::generate().0.public().into(),
+                authority_discovery: sp_core::sr25519::Pair::generate().0.public().into(),
+            };
+            assert_ok!(proxy_call(
+                Keyring::Charlie,
+                RuntimeCall::AuthorityMembers(pallet_authority_members::Call::set_session_keys {
+                    keys: session_keys,
+                })
+            ));
+            assert_ok!(proxy_call(
+                Keyring::Charlie,
+                RuntimeCall::AuthorityMembers(pallet_authority_members::Call::go_online {})
+            ));
+            assert_ok!(proxy_call(
+                Keyring::Charlie,
+                RuntimeCall::AuthorityMembers(pallet_authority_members::Call::go_offline {})
+            ));
+
+            assert_filtered(proxy_call(
+                Keyring::Charlie,
+                RuntimeCall::SmithMembers(pallet_smith_members::Call::invite_smith { receiver: 4 }),
+            ));
+            assert_filtered(proxy_call(
+                Keyring::Charlie,
+                RuntimeCall::Certification(pallet_certification::Call::renew_cert { receiver: 2 }),
+            ));
+            for call in owner_only_calls() {
+                assert_filtered(proxy_call(Keyring::Charlie, call));
+            }
+        })
+}
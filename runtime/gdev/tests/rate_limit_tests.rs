@@ -0,0 +1,170 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use common_runtime::check_rate_limit::RATE_LIMIT_EXCEEDED;
+use frame_support::{assert_err, assert_ok};
+use gdev_runtime::*;
+use sp_core::Encode;
+use sp_keyring::sr25519::Keyring;
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
+
+/// Builds a transfer from `signer` to Ferdie.
+fn transfer(signer: Keyring, nonce: u32) -> UncheckedExtrinsic {
+    let call = RuntimeCall::Balances(BalancesCall::transfer_allow_death {
+        dest: Keyring::Ferdie.to_account_id().into(),
+        value: 200,
+    });
+    get_unchecked_extrinsic(call, 4u64, 8u64, signer, 0u64, nonce)
+}
+
+const RATE_LIMITED: TransactionValidityError =
+    TransactionValidityError::Invalid(InvalidTransaction::Custom(RATE_LIMIT_EXCEEDED));
+
+/// This test checks that free transactions of an account without identity are limited
+/// and that the limit is lifted once the window has passed.
+#[test]
+fn test_rate_limit_non_member_account() {
+    ExtBuilder::new(1, 3, 4)
+        .change_parameters(|params| {
+            params.rate_limit_window = 8;
+            params.rate_limit_max_per_account = 2;
+        })
+        .with_initial_balances(vec![(Keyring::Eve.to_account_id(), 10_000)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            assert_ok!(Executive::apply_extrinsic(transfer(Keyring::Eve, 0)));
+            assert_ok!(Executive::apply_extrinsic(transfer(Keyring::Eve, 1)));
+            assert_err!(
+                Executive::apply_extrinsic(transfer(Keyring::Eve, 2)),
+                RATE_LIMITED
+            );
+
+            // counts are released once the window has passed
+            run_to_block(12);
+            assert_ok!(Executive::apply_extrinsic(transfer(Keyring::Eve, 2)));
+        })
+}
+
+/// This test checks that accounts linked to a member have a higher limit
+/// and that the identity limit applies to all of its accounts.
+#[test]
+fn test_rate_limit_member_account() {
+    ExtBuilder::new(1, 3, 4)
+        .change_parameters(|params| {
+            params.rate_limit_window = 8;
+            params.rate_limit_max_per_account = 2;
+            params.rate_limit_max_per_member_account = 4;
+            params.rate_limit_max_per_identity = 5;
+        })
+        .with_initial_balances(vec![
+            (Keyring::Bob.to_account_id(), 10_000),
+            (Keyring::Eve.to_account_id(), 10_000),
+        ])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            // Bob is a member, his account gets the member limit
+            for nonce in 0..4 {
+                assert_ok!(Executive::apply_extrinsic(transfer(Keyring::Bob, nonce)));
+            }
+            assert_err!(
+                Executive::apply_extrinsic(transfer(Keyring::Bob, 4)),
+                RATE_LIMITED
+            );
+
+            // Eve's account is linked to Bob's identity which has one transaction left
+            let eve = Keyring::Eve.to_account_id();
+            let payload = (b"link", System::block_hash(0), 2u32, eve.clone()).encode();
+            assert_ok!(Identity::link_account(
+                RuntimeOrigin::signed(Keyring::Bob.to_account_id()),
+                eve,
+                Keyring::Eve.sign(&payload).into()
+            ));
+            assert_ok!(Executive::apply_extrinsic(transfer(Keyring::Eve, 0)));
+            assert_err!(
+                Executive::apply_extrinsic(transfer(Keyring::Eve, 1)),
+                RATE_LIMITED
+            );
+        })
+}
+
+/// This test checks that transactions paying fees are not limited.
+#[test]
+fn test_rate_limit_paid_transactions() {
+    ExtBuilder::new(1, 3, 4)
+        .change_parameters(|params| {
+            params.rate_limit_window = 8;
+            params.rate_limit_max_per_account = 1;
+        })
+        .with_initial_balances(vec![(Keyring::Eve.to_account_id(), 10_000)])
+        .build()
+        .execute_with(|| {
+            run_to_block(2);
+
+            assert_ok!(Executive::apply_extrinsic(transfer(Keyring::Eve, 0)));
+            assert_err!(
+                Executive::apply_extrinsic(transfer(Keyring::Eve, 1)),
+                RATE_LIMITED
+            );
+
+            // under load, fees apply and the transaction is accepted
+            pallet_transaction_payment::NextFeeMultiplier::<Runtime>::put(MaxMultiplier::get());
+            assert_ok!(Executive::apply_extrinsic(transfer(Keyring::Eve, 1)));
+        })
+}
+
+/// This test checks that the test parameters migration keeps the stored parameters
+/// and adds the default rate limit parameters.
+#[test]
+fn test_rate_limit_parameters_migration() {
+    ExtBuilder::new(1, 3, 4).build().execute_with(|| {
+        use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+        type ParametersStorage = pallet_duniter_test_parameters::ParametersStorage<Runtime>;
+        type TestParameters = pallet_duniter_test_parameters::Pallet<Runtime>;
+
+        // store the parameters as version 1 did, without the four rate limit parameters
+        let params = ParametersStorage::get();
+        let mut v1 = params.encode();
+        v1.truncate(v1.len() - 4 * core::mem::size_of::<u32>());
+        frame_support::storage::unhashed::put_raw(&ParametersStorage::hashed_key(), &v1);
+        StorageVersion::new(1).put::<TestParameters>();
+
+        pallet_duniter_test_parameters::migrations::v2::MigrateV1ToV2::<
+            Runtime,
+            DefaultRateLimits,
+        >::on_runtime_upgrade();
+
+        let (window, max_per_account, max_per_member_account, max_per_identity) =
+            DefaultRateLimits::get();
+        assert!(
+            ParametersStorage::get()
+                == GenesisParameters {
+                    rate_limit_window: window,
+                    rate_limit_max_per_account: max_per_account,
+                    rate_limit_max_per_member_account: max_per_member_account,
+                    rate_limit_max_per_identity: max_per_identity,
+                    ..params
+                }
+        );
+        assert_eq!(TestParameters::on_chain_storage_version(), 2);
+    })
+}
@@ -0,0 +1,108 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+mod common;
+
+use codec::Encode;
+use common::*;
+use frame_support::{assert_noop, assert_ok, traits::QueryPreimage};
+use gdev_runtime::*;
+use sp_keyring::sr25519::Keyring;
+use sp_runtime::traits::{BlakeTwo256, Hash as _};
+
+/// Origin of a motion approved by the technical committee.
+fn committee_origin() -> RuntimeOrigin {
+    pallet_collective::RawOrigin::<AccountId, TechnicalCommitteeInstance>::Members(2, 3).into()
+}
+
+/// This test checks that an upgrade proposed by a committee member is exposed
+/// with its enactment block once approved, and can be cancelled.
+#[test]
+fn test_upgrade_proposal_lifecycle() {
+    ExtBuilder::new(1, 3, 4).build().execute_with(|| {
+        run_to_block(1);
+        assert_ok!(CommitteeMembership::add_member(committee_origin(), 1));
+
+        let code = b"runtime code".to_vec();
+        let code_hash = BlakeTwo256::hash(&code);
+        let release_notes_hash = BlakeTwo256::hash(b"release notes");
+        let spec_version = VERSION.spec_version + 1;
+        assert_ok!(Preimage::note_preimage(
+            RuntimeOrigin::signed(Keyring::Alice.to_account_id()),
+            code
+        ));
+
+        // the upgrade is proposed by a committee member through the committee, not with a
+        // plain signed origin
+        assert_noop!(
+            UpgradeProposal::propose_upgrade(
+                RuntimeOrigin::signed(Keyring::Alice.to_account_id()),
+                code_hash,
+                spec_version,
+                release_notes_hash,
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        let propose =
+            RuntimeCall::UpgradeProposal(pallet_upgrade_proposal::Call::propose_upgrade {
+                code_hash,
+                spec_version,
+                release_notes_hash,
+            });
+        let proposal_hash = BlakeTwo256::hash_of(&propose);
+        let length_bound = propose.encoded_size() as u32;
+        // Bob is not a committee member
+        assert_noop!(
+            TechnicalCommittee::execute(
+                RuntimeOrigin::signed(Keyring::Bob.to_account_id()),
+                Box::new(propose.clone()),
+                length_bound,
+            ),
+            pallet_collective::Error::<Runtime, TechnicalCommitteeInstance>::NotMember
+        );
+        assert_ok!(TechnicalCommittee::execute(
+            RuntimeOrigin::signed(Keyring::Alice.to_account_id()),
+            Box::new(propose),
+            length_bound,
+        ));
+        System::assert_has_event(RuntimeEvent::TechnicalCommittee(
+            pallet_collective::Event::MemberExecuted {
+                proposal_hash,
+                result: Ok(()),
+            },
+        ));
+
+        // the enactment must leave time to smiths to update their nodes
+        let enactment = System::block_number() + UpgradeMinEnactmentDelay::get();
+        assert_noop!(
+            UpgradeProposal::approve_upgrade(committee_origin(), code_hash, enactment - 1),
+            pallet_upgrade_proposal::Error::<Runtime>::EnactmentTooEarly
+        );
+        assert_ok!(UpgradeProposal::approve_upgrade(
+            committee_origin(),
+            code_hash,
+            enactment
+        ));
+        let pending = UpgradeProposal::pending_upgrade().expect("upgrade should be pending");
+        assert_eq!(pending.spec_version, spec_version);
+        assert_eq!(pending.enactment(), Some(enactment));
+        assert!(Preimage::is_requested(&code_hash));
+
+        assert_ok!(UpgradeProposal::cancel_upgrade(committee_origin()));
+        assert_eq!(UpgradeProposal::pending_upgrade(), None);
+        assert!(!Preimage::is_requested(&code_hash));
+    });
+}
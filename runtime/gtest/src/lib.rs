@@ -85,10 +85,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     //   `spec_version`, and `authoring_version` are the same between Wasm and native.
     // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
     //   the compatible custom types.
-    spec_version: 1100,
+    spec_version: 1101,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     system_version: 1,
 };
 
@@ -117,6 +117,8 @@ pub type TxExtension = (
     frame_system::CheckWeight<Runtime>,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
     frame_metadata_hash_extension::CheckMetadataHash<Runtime>,
+    common_runtime::check_wot_priority::CheckWotPriority<Runtime>,
+    common_runtime::check_rate_limit::CheckRateLimit<Runtime>,
 );
 
 /// Executive: handles dispatch to the various modules.
@@ -126,8 +128,15 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsWithSystem,
+    Migrations,
 >;
 
+/// Runtime migrations to apply on upgrade.
+type Migrations = (
+    pallet_committee_membership::migrations::ImportCollectiveMembers<Runtime>,
+    common_runtime::invariants::CheckInvariants<Runtime>,
+);
+
 pub type TechnicalCommitteeInstance = Instance2;
 
 pub struct BaseCallFilter;
@@ -158,6 +167,9 @@ pub enum ProxyType {
     TransferOnly = 1,
     CancelProxy = 2,
     TechnicalCommitteePropose = 3,
+    CertificationRenewal = 4,
+    DistanceRequest = 5,
+    SmithOperations = 6,
 }
 impl Default for ProxyType {
     fn default() -> Self {
@@ -194,10 +206,48 @@ impl frame_support::traits::InstanceFilter<RuntimeCall> for ProxyType {
                     RuntimeCall::TechnicalCommittee(pallet_collective::Call::propose { .. })
                 )
             }
+            ProxyType::CertificationRenewal => {
+                matches!(
+                    c,
+                    RuntimeCall::Certification(pallet_certification::Call::renew_cert { .. })
+                )
+            }
+            ProxyType::DistanceRequest => {
+                matches!(
+                    c,
+                    RuntimeCall::Distance(
+                        pallet_distance::Call::request_distance_evaluation { .. }
+                            | pallet_distance::Call::request_distance_evaluation_for { .. }
+                            | pallet_distance::Call::cancel_distance_evaluation { .. }
+                    )
+                )
+            }
+            ProxyType::SmithOperations => {
+                matches!(
+                    c,
+                    RuntimeCall::AuthorityMembers(
+                        pallet_authority_members::Call::go_online { .. }
+                            | pallet_authority_members::Call::go_offline { .. }
+                            | pallet_authority_members::Call::set_session_keys { .. }
+                    )
+                )
+            }
         }
     }
 }
 
+impl pallet_duniter_parameters::Config for Runtime {
+    type Bounds = WotParameterBounds;
+    type ChangeOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCommitteeInstance, 2, 3>;
+    type EnactmentDelay = ParametersEnactmentDelay;
+    type MaxHistoryLen = ParametersMaxHistoryLen;
+    type Preimages = Preimage;
+    type RuntimeCall = RuntimeCall;
+    type Scheduler = Scheduler;
+    type WeightInfo = weights::pallet_duniter_parameters::WeightInfo<Runtime>;
+}
+
 // Create the runtime by composing the pallets that were previously configured.
 construct_runtime!(
     pub enum Runtime
@@ -211,6 +261,9 @@ construct_runtime!(
         Babe: pallet_babe = 3,
         Timestamp: pallet_timestamp = 4,
 
+        // Governance-controlled parameters
+        Parameters: pallet_duniter_parameters = 5,
+
         // Money management
         Balances: pallet_balances = 6,
         TransactionPayment: pallet_transaction_payment = 32,
@@ -233,6 +286,8 @@ construct_runtime!(
         UpgradeOrigin: pallet_upgrade_origin = 21,
         Preimage: pallet_preimage = 22,
         TechnicalCommittee: pallet_collective::<Instance2> = 23,
+        CommitteeMembership: pallet_committee_membership = 24,
+        UpgradeProposal: pallet_upgrade_proposal = 25,
 
         // Universal dividend
         UniversalDividend: pallet_universal_dividend = 30,
@@ -259,6 +314,26 @@ construct_runtime!(
 common_runtime::pallets_config!();
 common_runtime::declare_session_keys! {}
 #[cfg(feature = "runtime-benchmarks")]
-common_runtime::benchmarks_config!();
+common_runtime::benchmarks_config! {
+    [pallet_duniter_parameters, Parameters]
+}
 common_runtime::offchain_config! {}
-common_runtime::runtime_apis! {}
+common_runtime::runtime_apis! {
+    impl pallet_duniter_parameters::DuniterParametersApi<Block, BlockNumber> for Runtime {
+        fn parameter_value(key: pallet_duniter_parameters::ParameterKey) -> u32 {
+            Parameters::value(key)
+        }
+
+        fn pending_change(
+            key: pallet_duniter_parameters::ParameterKey,
+        ) -> Option<pallet_duniter_parameters::PendingChange<BlockNumber>> {
+            Parameters::pending_change(key)
+        }
+
+        fn parameter_history(
+            key: pallet_duniter_parameters::ParameterKey,
+        ) -> Vec<pallet_duniter_parameters::ParameterChange<BlockNumber>> {
+            Parameters::history(key)
+        }
+    }
+}
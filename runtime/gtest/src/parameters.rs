@@ -51,6 +51,10 @@ parameter_types! {
     pub const MinAccessibleReferees: Perbill = Perbill::from_percent(80);
     pub const MaxRefereeDistance: u32 = 5;
     pub const EvaluationPeriod: u32 = common_runtime::param_duration!(40, 5 * MINUTES);
+    pub const MaxEvaluatorHistory: u32 = 20;
+    pub const MaxOutlierPeriods: u32 = 10;
+    pub const MaxQueuedEvaluations: u32 = 2 * pallet_distance::MAX_EVALUATIONS_PER_SESSION;
+    pub const OutlierTolerance: Perbill = Perbill::from_percent(5);
 }
 
 // Babe
@@ -95,15 +99,24 @@ parameter_types! {
         common_runtime::param_duration!(15_778_800_000, 30 * 60_000); // 1/2 year
 }
 
+// Quota
+parameter_types! {
+    // free transactions allowed per rolling window
+    pub const RateLimitWindow: BlockNumber = RATE_LIMIT_WINDOW;
+    pub const RateLimitMaxPerAccount: u32 = RATE_LIMIT_MAX_PER_ACCOUNT;
+    pub const RateLimitMaxPerMemberAccount: u32 = RATE_LIMIT_MAX_PER_MEMBER_ACCOUNT;
+    pub const RateLimitMaxPerIdentity: u32 = RATE_LIMIT_MAX_PER_IDENTITY;
+}
+
 /*******/
 /* WOT */
 /*******/
 
 parameter_types! {
     pub const WotFirstCertIssuableOn: BlockNumber = 0;
-    pub const WotMinCertForMembership: u32 = 5;
-    pub const MinReceivedCertToBeAbleToIssueCert: u32 = 5;
-    pub const WotMinCertForCreateIdtyRight: u32 = 5;
+    pub const DefaultWotMinCertForMembership: u32 = 5;
+    pub const DefaultMinReceivedCertToBeAbleToIssueCert: u32 = 5;
+    pub const DefaultWotMinCertForCreateIdtyRight: u32 = 5;
 }
 
 // Identity
@@ -122,21 +135,43 @@ parameter_types! {
 
 // Membership
 parameter_types! {
-    pub const MembershipPeriod: BlockNumber =
+    pub const DefaultMembershipPeriod: BlockNumber =
         common_runtime::param_duration!(YEARS, 60 * MINUTES);
-    pub const MembershipRenewalPeriod: BlockNumber =
+    pub const DefaultMembershipRenewalPeriod: BlockNumber =
         common_runtime::param_duration!(2 * MONTHS, 20 * MINUTES);
 }
 
 // Certification
 parameter_types! {
-    pub const CertPeriod: BlockNumber =
+    pub const DefaultCertPeriod: BlockNumber =
         common_runtime::param_duration!(5 * DAYS, 15 * MINUTES);
-    pub const MaxByIssuer: u32 = 100;
-    pub const ValidityPeriod: BlockNumber =
+    pub const DefaultMaxByIssuer: u32 = 100;
+    pub const DefaultValidityPeriod: BlockNumber =
         common_runtime::param_duration!(2 * YEARS, 120 * MINUTES);
 }
 
+// Governance-controlled parameters
+// The constants above prefixed with `Default` are the values used until the Technical Committee
+// changes them through the parameters pallet.
+parameter_types! {
+    pub const ParametersEnactmentDelay: BlockNumber =
+        common_runtime::param_duration!(7 * DAYS, 10 * MINUTES);
+    pub const ParametersMaxHistoryLen: u32 = 32;
+}
+
+pub type WotMinCertForMembership = pallet_duniter_parameters::WotMinCertForMembership<Runtime>;
+pub type WotMinCertForCreateIdtyRight =
+    pallet_duniter_parameters::WotMinCertForCreateIdtyRight<Runtime>;
+pub type MembershipPeriod = pallet_duniter_parameters::MembershipPeriod<Runtime>;
+pub type MembershipRenewalPeriod = pallet_duniter_parameters::MembershipRenewalPeriod<Runtime>;
+pub type CertPeriod = pallet_duniter_parameters::CertPeriod<Runtime>;
+pub type MaxByIssuer = pallet_duniter_parameters::CertMaxByIssuer<Runtime>;
+pub type MinReceivedCertToBeAbleToIssueCert =
+    pallet_duniter_parameters::CertMinReceivedCertToIssueCert<Runtime>;
+pub type ValidityPeriod = pallet_duniter_parameters::CertValidityPeriod<Runtime>;
+
+common_runtime::wot_parameter_bounds!();
+
 /******************/
 /* SMITH-MEMBERS */
 /******************/
@@ -0,0 +1,137 @@
+// Copyright 2021-2022 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_committee_membership`
+//!
+//! THESE WEIGHTS ARE ESTIMATED FROM THE SMITH MEMBERS AND COLLECTIVE BENCHMARKS ON THE SAME HARDWARE,
+//! THEY MUST BE REPLACED BY THE OUTPUT OF THE SUBSTRATE BENCHMARK CLI:
+//! target/release/duniter benchmark pallet --genesis-builder=spec-genesis --pallet=pallet_committee_membership
+//!   --extrinsic=* --steps=50 --repeat=20 --wasm-execution=compiled --heap-pages=4096
+//!   --header=./file_header.txt --output=./runtime/gtest/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::Weight};
+use core::marker::PhantomData;
+
+/// Weight functions for `pallet_committee_membership`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_committee_membership::WeightInfo for WeightInfo<T> {
+	/// Storage: `Identity::Identities` (r:1 w:0)
+	/// Storage: `CommitteeMembership::Members` (r:1 w:1)
+	/// Storage: `CommitteeMembership::CounterForMembers` (r:1 w:1)
+	/// Storage: `Membership::Membership` (r:1 w:0)
+	/// Storage: `SmithMembers::Smiths` (r:1 w:0)
+	/// Storage: `CommitteeMembership::TermsEndingOn` (r:1 w:1)
+	/// Storage: `CommitteeMembership::Prime` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Prime` (r:0 w:1)
+	fn add_member() -> Weight {
+		Weight::from_parts(90_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 10_000))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	/// Storage: `Membership::Membership` (r:1 w:0)
+	/// Storage: `SmithMembers::Smiths` (r:1 w:0)
+	/// Storage: `CommitteeMembership::Members` (r:1 w:1)
+	/// Storage: `CommitteeMembership::TermsEndingOn` (r:1 w:1)
+	fn renew_term() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 5_000))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `CommitteeMembership::Members` (r:2 w:1)
+	/// Storage: `CommitteeMembership::CounterForMembers` (r:1 w:1)
+	/// Storage: `CommitteeMembership::Prime` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Voting` (r:20 w:20)
+	/// Storage: `TechnicalCommittee::Prime` (r:0 w:1)
+	fn remove_member() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 60_000))
+			.saturating_add(T::DbWeight::get().reads(26))
+			.saturating_add(T::DbWeight::get().writes(25))
+	}
+	/// Storage: `Identity::IdentityIndexOf` (r:1 w:0)
+	/// Storage: `CommitteeMembership::Members` (r:2 w:1)
+	/// Storage: `CommitteeMembership::CounterForMembers` (r:1 w:1)
+	/// Storage: `CommitteeMembership::Prime` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Voting` (r:20 w:20)
+	/// Storage: `TechnicalCommittee::Prime` (r:0 w:1)
+	fn resign() -> Weight {
+		Weight::from_parts(125_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 60_000))
+			.saturating_add(T::DbWeight::get().reads(27))
+			.saturating_add(T::DbWeight::get().writes(25))
+	}
+	/// Storage: `CommitteeMembership::TermsEndingOn` (r:1 w:1)
+	/// Storage: `CommitteeMembership::Members` (r:100 w:100)
+	/// Storage: `CommitteeMembership::CounterForMembers` (r:1 w:1)
+	/// Storage: `CommitteeMembership::Prime` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Voting` (r:20 w:20)
+	/// Storage: `TechnicalCommittee::Prime` (r:0 w:1)
+	/// The range of component `i` is `[0, 100]`.
+	fn expire_terms(i: u32, ) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3_000))
+			// Standard Error: 20_000
+			.saturating_add(Weight::from_parts(120_000_000, 0).saturating_mul(i.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().reads((26_u64).saturating_mul(i.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((25_u64).saturating_mul(i.into())))
+			.saturating_add(Weight::from_parts(0, 60_000).saturating_mul(i.into()))
+	}
+	/// Storage: `CommitteeMembership::Prime` (r:1 w:1)
+	/// Storage: `CommitteeMembership::Members` (r:101 w:0)
+	/// Storage: `TechnicalCommittee::Prime` (r:0 w:1)
+	fn rotate_prime() -> Weight {
+		Weight::from_parts(60_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 260_000))
+			.saturating_add(T::DbWeight::get().reads(102))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: `CommitteeMembership::Members` (r:2 w:1)
+	/// Storage: `CommitteeMembership::CounterForMembers` (r:1 w:1)
+	/// Storage: `CommitteeMembership::Prime` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Members` (r:1 w:1)
+	/// Storage: `TechnicalCommittee::Proposals` (r:1 w:0)
+	/// Storage: `TechnicalCommittee::Voting` (r:20 w:20)
+	/// Storage: `TechnicalCommittee::Prime` (r:0 w:1)
+	fn on_removed_wot_member() -> Weight {
+		Weight::from_parts(120_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 60_000))
+			.saturating_add(T::DbWeight::get().reads(26))
+			.saturating_add(T::DbWeight::get().writes(25))
+	}
+	/// Storage: `CommitteeMembership::Members` (r:1 w:0)
+	fn on_removed_wot_member_empty() -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(Weight::from_parts(0, 3_000))
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+}
@@ -330,6 +330,8 @@ pub fn get_unchecked_extrinsic(
         frame_system::CheckWeight::<gtest_runtime::Runtime>::new(),
         pallet_transaction_payment::ChargeTransactionPayment::<gtest_runtime::Runtime>::from(tip),
         frame_metadata_hash_extension::CheckMetadataHash::<gtest_runtime::Runtime>::new(false),
+        common_runtime::check_wot_priority::CheckWotPriority::<gtest_runtime::Runtime>::new(),
+        common_runtime::check_rate_limit::CheckRateLimit::<gtest_runtime::Runtime>::new(),
     );
     let payload = SignedPayload::new(call.clone(), tx_ext.clone()).unwrap();
     let sig = payload.using_encoded(|payload| signer.pair().sign(payload));
@@ -20,7 +20,10 @@ mod common;
 
 use codec::Encode;
 use common::*;
-use frame_support::{assert_ok, traits::StoredMap};
+use frame_support::{
+    assert_ok,
+    traits::{OnInitialize, StoredMap},
+};
 use gtest_runtime::*;
 use pallet_identity::IdtyStatus;
 use sp_core::Pair;
@@ -213,3 +216,48 @@ fn test_change_owner_key_reaps_old_old_owner_key_without_balance() {
             assert_eq!(Balances::free_balance(dave), 0);
         })
 }
+
+#[test]
+fn test_parameter_change_by_technical_committee() {
+    ExtBuilder::new(1, 3, 4).build().execute_with(|| {
+        run_to_block(2);
+        let key = pallet_duniter_parameters::ParameterKey::CertValidityPeriod;
+        let new_value = DefaultValidityPeriod::get() + 100;
+        let committee_origin: RuntimeOrigin =
+            pallet_collective::RawOrigin::<AccountId, TechnicalCommitteeInstance>::Members(2, 3)
+                .into();
+
+        // a simple member cannot change parameters
+        assert!(
+            Parameters::schedule_change(
+                RuntimeOrigin::signed(Keyring::Alice.to_account_id()),
+                key,
+                new_value
+            )
+            .is_err()
+        );
+        // values outside of the allowed range are rejected
+        assert!(Parameters::schedule_change(committee_origin.clone(), key, 0).is_err());
+
+        assert_ok!(Parameters::schedule_change(
+            committee_origin,
+            key,
+            new_value
+        ));
+        let pending = Parameters::pending_change(key).expect("change should be scheduled");
+        assert_eq!(pending.enactment, 2 + ParametersEnactmentDelay::get());
+        assert!(!pallet_scheduler::Agenda::<Runtime>::get(pending.enactment).is_empty());
+
+        // the change cannot be enacted before the delay, even with root origin
+        assert!(Parameters::enact_change(RuntimeOrigin::root(), key, new_value).is_err());
+
+        // the change is dispatched with root origin by the scheduler at the enactment block
+        System::set_block_number(pending.enactment);
+        Scheduler::on_initialize(pending.enactment);
+        assert_eq!(
+            <Runtime as pallet_certification::Config>::ValidityPeriod::get(),
+            new_value
+        );
+        assert_eq!(Parameters::history(key).len(), 1);
+    })
+}
@@ -14,6 +14,9 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::translations::{
+    Catalog, SourceEntry, TEMPLATE_CONTEXT, render_template, template_keys, translate_function,
+};
 use anyhow::{Context, Result, bail};
 use codec::Decode;
 use core::hash::Hash;
@@ -21,7 +24,7 @@ use frame_metadata::v16::{StorageEntryModifier, StorageEntryType};
 use scale_info::{PortableRegistry, Type, TypeDef, form::PortableForm};
 use serde::Serialize;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs::File,
     io::{Read, Write},
     path::Path,
@@ -41,12 +44,17 @@ where
 
 // consts
 
-const CALLS_DOC_FILEPATH: &str = "docs/api/runtime-calls.md";
-const EVENTS_DOC_FILEPATH: &str = "docs/api/runtime-events.md";
-const STORAGES_DOC_FILEPATH: &str = "docs/api/runtime-storages.md";
-const CONSTANTS_DOC_FILEPATH: &str = "docs/api/runtime-constants.md";
-const ERRORS_DOC_FILEPATH: &str = "docs/api/runtime-errors.md";
-const ERRORS_PO_FILEPATH: &str = "docs/api/runtime-errors.po";
+const DOC_DIR: &str = "docs/api";
+const CALLS_DOC_FILENAME: &str = "runtime-calls.md";
+const EVENTS_DOC_FILENAME: &str = "runtime-events.md";
+const STORAGES_DOC_FILENAME: &str = "runtime-storages.md";
+const CONSTANTS_DOC_FILENAME: &str = "runtime-constants.md";
+const ERRORS_DOC_FILENAME: &str = "runtime-errors.md";
+const ERRORS_PO_FILENAME: &str = "runtime-errors.po";
+const DOC_JSON_FILEPATH: &str = "docs/api/runtime-doc.json";
+const TRANSLATIONS_DIR: &str = "docs/api/i18n";
+const TRANSLATIONS_TEMPLATE_FILEPATH: &str = "docs/api/i18n/runtime.pot";
+const TEMPLATES_DIR: &str = "xtask/res/templates";
 const TEMPLATES_GLOB: &str = "xtask/res/templates/*.{md,po}";
 const WEIGHT_FILEPATH: &str = "runtime/gdev/src/weights/";
 
@@ -111,6 +119,47 @@ struct Constant {
     type_value: String,
 }
 
+/// Rendered documentation of a runtime, in one language
+struct RuntimeDocs {
+    calls: String,
+    events: String,
+    errors: String,
+    errors_po: String,
+    storages: String,
+    constants: String,
+}
+
+/// Documentation of calls, events and errors in every language, for wallets
+#[derive(Serialize)]
+struct DocExport {
+    languages: Vec<String>,
+    pallets: Vec<PalletDoc>,
+}
+#[derive(Serialize)]
+struct PalletDoc {
+    index: u8,
+    name: String,
+    calls: Vec<ItemDoc>,
+    events: Vec<ItemDoc>,
+    errors: Vec<ItemDoc>,
+}
+#[derive(Serialize)]
+struct ItemDoc {
+    index: u8,
+    name: String,
+    /// language -> documentation, untranslated languages are missing
+    documentation: BTreeMap<String, String>,
+}
+
+/// Number of items without translation, by gettext context
+#[derive(Default)]
+struct MissingTranslations {
+    calls: usize,
+    events: usize,
+    errors: usize,
+    texts: usize,
+}
+
 impl Pallet {
     #![allow(clippy::too_many_arguments)]
     fn new(
@@ -220,7 +269,7 @@ impl From<&scale_info::Variant<PortableForm>> for ErroR {
 }
 
 // classify calls into categories depending on their origin
-enum CallCategory {
+pub(super) enum CallCategory {
     // calls filtered by runtime
     Disabled,
     // inherents
@@ -279,7 +328,7 @@ impl CallCategory {
     }
 
     // only user calls
-    fn is_user(pallet_name: &str, call_name: &str) -> bool {
+    pub(super) fn is_user(pallet_name: &str, call_name: &str) -> bool {
         matches!(Self::is(pallet_name, call_name), Self::User)
     }
 }
@@ -309,16 +358,7 @@ pub(super) fn gen_doc() -> Result<()> {
             bail!("unsuported metadata version")
         };
 
-    let mut weights = get_weights(max_weight)?;
-
-    // Ad hoc names conversion between pallet filename and instance name
-    rename_key(&mut weights, &"FrameSystem".into(), "System".into());
-    rename_key(&mut weights, &"DuniterAccount".into(), "Account".into());
-    rename_key(
-        &mut weights,
-        &"Collective".into(),
-        "TechnicalCommittee".into(),
-    );
+    let mut weights = get_weights(Path::new(WEIGHT_FILEPATH), max_weight)?;
 
     // We enforce weight for each pallet.
     // For pallets with manual or no weight, we define a default value.
@@ -345,8 +385,10 @@ pub(super) fn gen_doc() -> Result<()> {
         })
     });
 
-    let (call_doc, event_doc, error_doc, error_po, storage_doc, constant_doc) =
-        print_runtime(runtime);
+    let catalogs = Catalog::load_all(Path::new(TRANSLATIONS_DIR))?;
+    let mut tera = load_templates();
+    tera.register_function("t", translate_function(HashMap::new()));
+    let docs = print_runtime(&runtime, &tera);
 
     // Generate docs from rust code
     Command::new("cargo")
@@ -377,35 +419,38 @@ pub(super) fn gen_doc() -> Result<()> {
         .status()
         .expect("cargo doc failed to execute");
 
-    let mut file = File::create(CALLS_DOC_FILEPATH)
-        .with_context(|| format!("Failed to create file '{CALLS_DOC_FILEPATH}'"))?;
-    file.write_all(call_doc.as_bytes())
-        .with_context(|| format!("Failed to write to file '{CALLS_DOC_FILEPATH}'"))?;
-    let mut file = File::create(EVENTS_DOC_FILEPATH)
-        .with_context(|| format!("Failed to create file '{EVENTS_DOC_FILEPATH}'"))?;
-    file.write_all(event_doc.as_bytes())
-        .with_context(|| format!("Failed to write to file '{EVENTS_DOC_FILEPATH}'"))?;
-    let mut file = File::create(ERRORS_DOC_FILEPATH)
-        .with_context(|| format!("Failed to create file '{ERRORS_DOC_FILEPATH}'"))?;
-    file.write_all(error_doc.as_bytes())
-        .with_context(|| format!("Failed to write to file '{ERRORS_DOC_FILEPATH}'"))?;
-    let mut file = File::create(STORAGES_DOC_FILEPATH)
-        .with_context(|| format!("Failed to create file '{STORAGES_DOC_FILEPATH}'"))?;
-    file.write_all(storage_doc.as_bytes())
-        .with_context(|| format!("Failed to write to file '{STORAGES_DOC_FILEPATH}'"))?;
-    let mut file = File::create(CONSTANTS_DOC_FILEPATH)
-        .with_context(|| format!("Failed to create file '{CONSTANTS_DOC_FILEPATH}'"))?;
-    file.write_all(constant_doc.as_bytes())
-        .with_context(|| format!("Failed to write to file '{CONSTANTS_DOC_FILEPATH}'"))?;
-    let mut file = File::create(ERRORS_PO_FILEPATH)
-        .with_context(|| format!("Failed to create file '{ERRORS_PO_FILEPATH}'"))?;
-    file.write_all(error_po.as_bytes())
-        .with_context(|| format!("Failed to write to file '{ERRORS_PO_FILEPATH}'"))?;
+    write_docs(Path::new(DOC_DIR), &docs)?;
+
+    // Generate one document set per translation catalog
+    let source_entries = translation_sources(&runtime)?;
+    for catalog in &catalogs {
+        let (pallets, missing) = translate(&runtime, catalog, &source_entries);
+        tera.register_function("t", translate_function(catalog.template_texts()));
+        let docs = print_runtime(&pallets, &tera);
+        write_docs(&Path::new(DOC_DIR).join(&catalog.language), &docs)?;
+        println!(
+            "{}: {} missing translations ({} calls, {} events, {} errors, {} texts)",
+            catalog.language,
+            missing.calls + missing.events + missing.errors + missing.texts,
+            missing.calls,
+            missing.events,
+            missing.errors,
+            missing.texts,
+        );
+    }
+    write_file(
+        Path::new(TRANSLATIONS_TEMPLATE_FILEPATH),
+        &render_template(&source_entries),
+    )?;
+    write_file(
+        Path::new(DOC_JSON_FILEPATH),
+        &serde_json::to_string_pretty(&doc_export(&runtime, &catalogs))?,
+    )?;
 
     Ok(())
 }
 
-fn get_max_weight_from_metadata_v16(
+pub(super) fn get_max_weight_from_metadata_v16(
     metadata_v16: frame_metadata::v16::RuntimeMetadataV16,
 ) -> Result<u128> {
     // Extract the maximal weight available in one block
@@ -505,7 +550,7 @@ fn format_type(ty: &Type<PortableForm>, types: &PortableRegistry) -> Result<Stri
 /// - If successful, calls `format_type()` to get a human-readable format.
 /// - Used internally by `format_type()` when resolving type dependencies.
 ///
-fn resolve_type(type_id: u32, types: &PortableRegistry) -> Result<String> {
+pub(super) fn resolve_type(type_id: u32, types: &PortableRegistry) -> Result<String> {
     types
         .resolve(type_id)
         .map(|t| format_type(t, types))
@@ -693,16 +738,27 @@ fn get_from_metadata_v16(
     Ok(pallets)
 }
 
-fn get_weights(max_weight: u128) -> Result<HashMap<String, HashMap<String, WeightInfo>>> {
-    analyze_weight(
-        Path::new(WEIGHT_FILEPATH),
-        &MaxBlockWeight::new(max_weight as f64),
-    )
-    .map_err(|e| anyhow::anyhow!(e))
+/// Weights of the calls of a weights folder, by pallet instance name then call name
+pub(super) fn get_weights(
+    weight_path: &Path,
+    max_weight: u128,
+) -> Result<HashMap<String, HashMap<String, WeightInfo>>> {
+    let mut weights = analyze_weight(weight_path, &MaxBlockWeight::new(max_weight as f64))
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    // Ad hoc names conversion between pallet filename and instance name
+    rename_key(&mut weights, &"FrameSystem".into(), "System".into());
+    rename_key(&mut weights, &"DuniterAccount".into(), "Account".into());
+    rename_key(
+        &mut weights,
+        &"Collective".into(),
+        "TechnicalCommittee".into(),
+    );
+    Ok(weights)
 }
 
 /// use template to render markdown file with runtime calls documentation
-fn print_runtime(pallets: RuntimePallets) -> (String, String, String, String, String, String) {
+fn print_runtime(pallets: &RuntimePallets, tera: &Tera) -> RuntimeDocs {
     // init variables
     // -- user calls
     let mut user_calls_counter = 0;
@@ -747,15 +803,6 @@ fn print_runtime(pallets: RuntimePallets) -> (String, String, String, String, St
         .iter()
         .for_each(|pallet| constant_counter += pallet.constants.len());
 
-    // compile template
-    let tera = match Tera::new(TEMPLATES_GLOB) {
-        Ok(t) => t,
-        Err(e) => {
-            println!("Parsing error(s): {e}");
-            ::std::process::exit(1);
-        }
-    };
-
     // fills tera context for rendering calls
     let mut context = tera::Context::new();
     context.insert("user_calls_counter", &user_calls_counter);
@@ -766,7 +813,7 @@ fn print_runtime(pallets: RuntimePallets) -> (String, String, String, String, St
         .expect("template error");
 
     // render events
-    context.insert("pallets", &pallets);
+    context.insert("pallets", pallets);
     context.insert("event_counter", &event_counter);
     let event_doc = tera
         .render("runtime-events.md", &context)
@@ -783,25 +830,223 @@ fn print_runtime(pallets: RuntimePallets) -> (String, String, String, String, St
         .expect("template error");
 
     // render storages
-    context.insert("pallets", &pallets);
+    context.insert("pallets", pallets);
     context.insert("storage_counter", &event_counter);
     let storage_doc = tera
         .render("runtime-storages.md", &context)
         .expect("template storage");
 
     // render constant
-    context.insert("pallets", &pallets);
+    context.insert("pallets", pallets);
     context.insert("constant_counter", &constant_counter);
     let constant_doc = tera
         .render("runtime-constants.md", &context)
         .expect("template constant");
 
-    (
-        call_doc,
-        event_doc,
-        error_doc,
-        error_po,
-        storage_doc,
-        constant_doc,
-    )
+    RuntimeDocs {
+        calls: call_doc,
+        events: event_doc,
+        errors: error_doc,
+        errors_po: error_po,
+        storages: storage_doc,
+        constants: constant_doc,
+    }
+}
+
+/// compile templates
+fn load_templates() -> Tera {
+    match Tera::new(TEMPLATES_GLOB) {
+        Ok(t) => t,
+        Err(e) => {
+            println!("Parsing error(s): {e}");
+            ::std::process::exit(1);
+        }
+    }
+}
+
+fn write_docs(dir: &Path, docs: &RuntimeDocs) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create folder '{}'", dir.display()))?;
+    write_file(&dir.join(CALLS_DOC_FILENAME), &docs.calls)?;
+    write_file(&dir.join(EVENTS_DOC_FILENAME), &docs.events)?;
+    write_file(&dir.join(ERRORS_DOC_FILENAME), &docs.errors)?;
+    write_file(&dir.join(STORAGES_DOC_FILENAME), &docs.storages)?;
+    write_file(&dir.join(CONSTANTS_DOC_FILENAME), &docs.constants)?;
+    write_file(&dir.join(ERRORS_PO_FILENAME), &docs.errors_po)
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    let mut file = File::create(path)
+        .with_context(|| format!("Failed to create file '{}'", path.display()))?;
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Failed to write to file '{}'", path.display()))
+}
+
+/// Entries to translate: documented user calls, events and errors, and the texts of the templates
+fn translation_sources(pallets: &RuntimePallets) -> Result<Vec<SourceEntry>> {
+    let mut entries = Vec::new();
+    for pallet in pallets {
+        let items = pallet
+            .calls
+            .iter()
+            .filter(|call| CallCategory::is_user(&pallet.name, &call.name))
+            .map(|call| ("call", &call.name, &call.documentation))
+            .chain(
+                pallet
+                    .events
+                    .iter()
+                    .map(|event| ("event", &event.name, &event.documentation)),
+            )
+            .chain(
+                pallet
+                    .errors
+                    .iter()
+                    .map(|error| ("error", &error.name, &error.documentation)),
+            );
+        for (context, name, documentation) in items {
+            if !documentation.is_empty() {
+                entries.push(SourceEntry {
+                    context,
+                    id: format!("{}.{name}", pallet.name),
+                    source: documentation.clone(),
+                });
+            }
+        }
+    }
+
+    let mut templates = std::fs::read_dir(TEMPLATES_DIR)
+        .with_context(|| format!("Failed to read folder '{TEMPLATES_DIR}'"))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    templates.sort();
+    let mut texts = Vec::<String>::new();
+    for template in templates {
+        let content = std::fs::read_to_string(&template)
+            .with_context(|| format!("Failed to read template '{}'", template.display()))?;
+        for key in template_keys(&content) {
+            if !texts.contains(&key) {
+                texts.push(key);
+            }
+        }
+    }
+    entries.extend(texts.into_iter().map(|text| SourceEntry {
+        context: TEMPLATE_CONTEXT,
+        id: text.clone(),
+        source: text,
+    }));
+    Ok(entries)
+}
+
+/// Replace the documentation of calls, events and errors by its translation, when available
+fn translate(
+    pallets: &RuntimePallets,
+    catalog: &Catalog,
+    source_entries: &[SourceEntry],
+) -> (RuntimePallets, MissingTranslations) {
+    let mut pallets = pallets.clone();
+    for pallet in pallets.iter_mut() {
+        let pallet_name = pallet.name.clone();
+        let translation = |context: &str, name: &str| {
+            catalog
+                .get(context, &format!("{pallet_name}.{name}"))
+                .map(str::to_owned)
+        };
+        for call in pallet.calls.iter_mut() {
+            if let Some(documentation) = translation("call", &call.name) {
+                call.documentation = documentation;
+            }
+        }
+        for event in pallet.events.iter_mut() {
+            if let Some(documentation) = translation("event", &event.name) {
+                event.documentation = documentation;
+            }
+        }
+        for error in pallet.errors.iter_mut() {
+            if let Some(documentation) = translation("error", &error.name) {
+                error.documentation = documentation;
+            }
+        }
+    }
+
+    let mut missing = MissingTranslations::default();
+    for entry in source_entries {
+        if catalog.get(entry.context, &entry.id).is_none() {
+            match entry.context {
+                "call" => missing.calls += 1,
+                "event" => missing.events += 1,
+                "error" => missing.errors += 1,
+                _ => missing.texts += 1,
+            }
+        }
+    }
+    (pallets, missing)
+}
+
+/// Documentation of user calls, events and errors in english and in every translated language
+fn doc_export(pallets: &RuntimePallets, catalogs: &[Catalog]) -> DocExport {
+    let item_doc = |context: &str, pallet: &str, index: u8, name: &str, documentation: &str| {
+        let mut translations = BTreeMap::from([("en".to_owned(), documentation.to_owned())]);
+        for catalog in catalogs {
+            if let Some(translation) = catalog.get(context, &format!("{pallet}.{name}")) {
+                translations.insert(catalog.language.clone(), translation.to_owned());
+            }
+        }
+        ItemDoc {
+            index,
+            name: name.to_owned(),
+            documentation: translations,
+        }
+    };
+    DocExport {
+        languages: std::iter::once("en".to_owned())
+            .chain(catalogs.iter().map(|catalog| catalog.language.clone()))
+            .collect(),
+        pallets: pallets
+            .iter()
+            .map(|pallet| PalletDoc {
+                index: pallet.index,
+                name: pallet.name.clone(),
+                calls: pallet
+                    .calls
+                    .iter()
+                    .filter(|call| CallCategory::is_user(&pallet.name, &call.name))
+                    .map(|call| {
+                        item_doc(
+                            "call",
+                            &pallet.name,
+                            call.index,
+                            &call.name,
+                            &call.documentation,
+                        )
+                    })
+                    .collect(),
+                events: pallet
+                    .events
+                    .iter()
+                    .map(|event| {
+                        item_doc(
+                            "event",
+                            &pallet.name,
+                            event.index,
+                            &event.name,
+                            &event.documentation,
+                        )
+                    })
+                    .collect(),
+                errors: pallet
+                    .errors
+                    .iter()
+                    .map(|error| {
+                        item_doc(
+                            "error",
+                            &pallet.name,
+                            error.index,
+                            &error.name,
+                            &error.documentation,
+                        )
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
 }
@@ -19,7 +19,11 @@ mod gen_doc;
 mod gitlab;
 mod network;
 mod runtime;
+mod runtime_changelog;
+mod spec_diff;
 mod squid;
+mod translations;
+mod weight_check;
 
 use anyhow::{Context, Result};
 use clap::Parser;
@@ -62,6 +66,52 @@ enum DuniterXTaskCommand {
     },
     /// Print the chainSpec published on given Network Release
     PrintSpec { network: String },
+    /// Show a per pallet, per storage item diff between two raw or plain chain specs
+    DiffSpecs {
+        /// Chain spec of reference
+        old: PathBuf,
+        /// Chain spec to review
+        new: PathBuf,
+        #[clap(short, long)]
+        /// Runtime metadata decoding raw storage and constants (ex: resources/gdev_metadata.scale)
+        metadata: Option<PathBuf>,
+        #[clap(long)]
+        /// Runtime metadata of the new chain spec, when its runtime differs
+        new_metadata: Option<PathBuf>,
+    },
+    /// Generate a runtime changelog from two runtime metadata, flagging breaking changes
+    RuntimeChangelog {
+        /// Runtime metadata of reference (ex: resources/gdev_metadata.scale of the last release)
+        old: PathBuf,
+        /// Runtime metadata to review
+        new: PathBuf,
+        #[clap(long)]
+        /// Weights folder of the reference runtime, to compare the weights of user calls
+        old_weights: Option<PathBuf>,
+        #[clap(long)]
+        /// Weights folder of the runtime to review (ex: runtime/gdev/src/weights/)
+        new_weights: Option<PathBuf>,
+        #[clap(short, long)]
+        /// Markdown file to write the changelog to, instead of the standard output
+        output: Option<PathBuf>,
+    },
+    /// Compare weight files against a baseline and check the worst case weight of user calls
+    CheckWeights {
+        /// Weights folder of reference (ex: the weights of the last runtime release)
+        baseline: PathBuf,
+        #[clap(long, default_value = "runtime/gdev/src/weights/")]
+        /// Weights folder to check
+        weights: PathBuf,
+        #[clap(short, long, default_value = "resources/gdev_metadata.scale")]
+        /// Runtime metadata giving the block weight and the user calls
+        metadata: PathBuf,
+        #[clap(long, default_value_t = 0.25)]
+        /// Maximum fraction of the block weight a user call can take
+        max_block_fraction: f64,
+        #[clap(long, default_value_t = 0.2)]
+        /// Maximum relative weight increase of a user call over the baseline
+        max_regression: f64,
+    },
     /// Create asset in a release
     CreateAssetLink {
         tag: String,
@@ -377,6 +427,38 @@ async fn main() -> Result<()> {
             inject_runtime_code(&raw_spec, &runtime)
         }
         DuniterXTaskCommand::PrintSpec { network } => gitlab::print_spec(network).await,
+        DuniterXTaskCommand::DiffSpecs {
+            old,
+            new,
+            metadata,
+            new_metadata,
+        } => spec_diff::diff_specs(&old, &new, metadata.as_deref(), new_metadata.as_deref()),
+        DuniterXTaskCommand::RuntimeChangelog {
+            old,
+            new,
+            old_weights,
+            new_weights,
+            output,
+        } => runtime_changelog::runtime_changelog(
+            &old,
+            &new,
+            old_weights.as_deref(),
+            new_weights.as_deref(),
+            output.as_deref(),
+        ),
+        DuniterXTaskCommand::CheckWeights {
+            baseline,
+            weights,
+            metadata,
+            max_block_fraction,
+            max_regression,
+        } => weight_check::check_weights(
+            &baseline,
+            &weights,
+            &metadata,
+            max_block_fraction,
+            max_regression,
+        ),
         DuniterXTaskCommand::CreateAssetLink {
             tag,
             asset_name,
@@ -0,0 +1,609 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    gen_doc::{CallCategory, get_max_weight_from_metadata_v16, get_weights, resolve_type},
+    spec_diff::{decode_value, load_metadata},
+};
+use anyhow::{Context, Result, bail};
+use frame_metadata::v16::{
+    RuntimeMetadataV16, StorageEntryMetadata, StorageEntryModifier, StorageEntryType,
+};
+use scale_info::{PortableRegistry, TypeDef, form::PortableForm};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Write,
+    path::Path,
+};
+use weightanalyzer::WeightInfo;
+
+/// Items of a runtime which matter to its clients, by pallet name.
+type Runtime = BTreeMap<String, PalletItems>;
+
+/// Weights of the calls, by pallet name then call name.
+type Weights = HashMap<String, HashMap<String, WeightInfo>>;
+
+#[derive(Default)]
+struct PalletItems {
+    index: u8,
+    calls: BTreeMap<String, Variant>,
+    events: BTreeMap<String, Variant>,
+    errors: BTreeMap<String, Variant>,
+    /// storage name -> storage signature
+    storages: BTreeMap<String, String>,
+    constants: BTreeMap<String, Constant>,
+}
+
+/// A call, an event or an error, with its named and typed fields.
+#[derive(Clone, Debug, PartialEq)]
+struct Variant {
+    index: u8,
+    fields: Vec<(String, String)>,
+}
+
+impl Variant {
+    fn signature(&self) -> String {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, type_name)| {
+                if name.is_empty() {
+                    type_name.clone()
+                } else {
+                    format!("{name}: {type_name}")
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("({})", fields.join(", "))
+    }
+
+    fn field_types(&self) -> Vec<&String> {
+        self.fields.iter().map(|(_, type_name)| type_name).collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Constant {
+    type_name: String,
+    value: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Pallet,
+    Calls,
+    Events,
+    Errors,
+    Storages,
+    Constants,
+    Weights,
+}
+
+impl Section {
+    fn title(self) -> &'static str {
+        match self {
+            Self::Pallet => "Pallet",
+            Self::Calls => "Calls",
+            Self::Events => "Events",
+            Self::Errors => "Errors",
+            Self::Storages => "Storages",
+            Self::Constants => "Constants",
+            Self::Weights => "Weights of user calls",
+        }
+    }
+}
+
+/// A change between two runtimes, breaking when existing clients must be updated.
+#[derive(Debug)]
+struct Change {
+    pallet: String,
+    section: Section,
+    description: String,
+    breaking: bool,
+}
+
+/// Generate a markdown changelog of the runtime from two runtime metadata
+pub(super) fn runtime_changelog(
+    old: &Path,
+    new: &Path,
+    old_weights: Option<&Path>,
+    new_weights: Option<&Path>,
+    output: Option<&Path>,
+) -> Result<()> {
+    let old_metadata = load_metadata(old)?;
+    let new_metadata = load_metadata(new)?;
+    let old_runtime = runtime_items(&old_metadata)?;
+    let new_runtime = runtime_items(&new_metadata)?;
+
+    let mut changes = diff_runtimes(&old_runtime, &new_runtime);
+    match (old_weights, new_weights) {
+        (Some(old_weights), Some(new_weights)) => {
+            let old_weights =
+                get_weights(old_weights, get_max_weight_from_metadata_v16(old_metadata)?)?;
+            let new_weights =
+                get_weights(new_weights, get_max_weight_from_metadata_v16(new_metadata)?)?;
+            changes.extend(diff_weights(&old_weights, &new_weights, &new_runtime));
+        }
+        (None, None) => {}
+        _ => bail!("both --old-weights and --new-weights are required to compare weights"),
+    }
+
+    let changelog = render(&changes);
+    match output {
+        Some(path) => std::fs::write(path, changelog)
+            .with_context(|| format!("Failed to write changelog to {}", path.display()))?,
+        None => print!("{changelog}"),
+    }
+    Ok(())
+}
+
+fn runtime_items(metadata: &RuntimeMetadataV16) -> Result<Runtime> {
+    let types = &metadata.types;
+    let mut runtime = Runtime::new();
+    for pallet in &metadata.pallets {
+        let mut items = PalletItems {
+            index: pallet.index,
+            ..Default::default()
+        };
+        if let Some(calls) = &pallet.calls {
+            items.calls = variants(calls.ty.id, types)?;
+        }
+        if let Some(event) = &pallet.event {
+            items.events = variants(event.ty.id, types)?;
+        }
+        if let Some(error) = &pallet.error {
+            items.errors = variants(error.ty.id, types)?;
+        }
+        if let Some(storage) = &pallet.storage {
+            for entry in &storage.entries {
+                items
+                    .storages
+                    .insert(entry.name.clone(), storage_signature(entry, types)?);
+            }
+        }
+        for constant in &pallet.constants {
+            items.constants.insert(
+                constant.name.clone(),
+                Constant {
+                    type_name: resolve_type(constant.ty.id, types)?,
+                    value: decode_value(&constant.value, constant.ty.id, types),
+                },
+            );
+        }
+        runtime.insert(pallet.name.clone(), items);
+    }
+    Ok(runtime)
+}
+
+fn variants(type_id: u32, types: &PortableRegistry) -> Result<BTreeMap<String, Variant>> {
+    let Some(TypeDef::Variant(type_def)) = types.resolve(type_id).map(|ty| &ty.type_def) else {
+        bail!("Invalid metadata: type {type_id} is not an enum")
+    };
+    type_def
+        .variants
+        .iter()
+        .map(|variant| {
+            let fields = variant
+                .fields
+                .iter()
+                .map(|field| {
+                    Ok((
+                        field.name.clone().unwrap_or_default(),
+                        resolve_type(field.ty.id, types)?,
+                    ))
+                })
+                .collect::<Result<_>>()?;
+            Ok((
+                variant.name.clone(),
+                Variant {
+                    index: variant.index,
+                    fields,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Hashers, key and value types of a storage item: what clients need to read it
+fn storage_signature(
+    entry: &StorageEntryMetadata<PortableForm>,
+    types: &PortableRegistry,
+) -> Result<String> {
+    let (prefix, value) = match &entry.ty {
+        StorageEntryType::Plain(value) => (String::new(), value.id),
+        StorageEntryType::Map {
+            hashers,
+            key,
+            value,
+        } => (
+            format!("{hashers:?} {} => ", resolve_type(key.id, types)?),
+            value.id,
+        ),
+    };
+    let value = resolve_type(value, types)?;
+    Ok(match entry.modifier {
+        StorageEntryModifier::Optional => format!("{prefix}Option<{value}>"),
+        StorageEntryModifier::Default => format!("{prefix}{value}"),
+    })
+}
+
+fn diff_runtimes(old: &Runtime, new: &Runtime) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let pallets = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+    for pallet in pallets {
+        let mut change = |section, description, breaking| {
+            changes.push(Change {
+                pallet: pallet.clone(),
+                section,
+                description,
+                breaking,
+            })
+        };
+        let (old_items, new_items) = match (old.get(pallet), new.get(pallet)) {
+            (Some(old_items), Some(new_items)) => (old_items, new_items),
+            (None, Some(new_items)) => {
+                change(
+                    Section::Pallet,
+                    format!("Added pallet (index {})", new_items.index),
+                    false,
+                );
+                continue;
+            }
+            (Some(old_items), None) => {
+                change(
+                    Section::Pallet,
+                    format!("Removed pallet (index {})", old_items.index),
+                    true,
+                );
+                continue;
+            }
+            (None, None) => unreachable!("pallet comes from one of the runtimes"),
+        };
+
+        // The pallet index is part of the encoding of its calls and events
+        if old_items.index != new_items.index {
+            change(
+                Section::Pallet,
+                format!(
+                    "Pallet index changed: {} -> {}",
+                    old_items.index, new_items.index
+                ),
+                true,
+            );
+        }
+        for (section, old_variants, new_variants) in [
+            (Section::Calls, &old_items.calls, &new_items.calls),
+            (Section::Events, &old_items.events, &new_items.events),
+            (Section::Errors, &old_items.errors, &new_items.errors),
+        ] {
+            diff_variants(old_variants, new_variants, |description, breaking| {
+                change(section, description, breaking)
+            });
+        }
+        diff_maps(
+            &old_items.storages,
+            &new_items.storages,
+            |name, value| format!("`{name}`: `{value}`"),
+            |name, old_value, new_value| {
+                Some((format!("`{name}`: `{old_value}` -> `{new_value}`"), true))
+            },
+            |description, breaking| change(Section::Storages, description, breaking),
+        );
+        diff_maps(
+            &old_items.constants,
+            &new_items.constants,
+            |name, constant| format!("`{name}: {}` = {}", constant.type_name, constant.value),
+            |name, old_constant, new_constant| {
+                Some(if old_constant.type_name != new_constant.type_name {
+                    (
+                        format!(
+                            "`{name}`: `{}` = {} -> `{}` = {}",
+                            old_constant.type_name,
+                            old_constant.value,
+                            new_constant.type_name,
+                            new_constant.value
+                        ),
+                        true,
+                    )
+                } else {
+                    (
+                        format!("`{name}`: {} -> {}", old_constant.value, new_constant.value),
+                        false,
+                    )
+                })
+            },
+            |description, breaking| change(Section::Constants, description, breaking),
+        );
+    }
+    changes
+}
+
+/// Compare calls, events or errors: removing one, changing its index or the type of its fields
+/// breaks the clients which encode or decode it, renaming its fields does not
+fn diff_variants(
+    old: &BTreeMap<String, Variant>,
+    new: &BTreeMap<String, Variant>,
+    change: impl FnMut(String, bool),
+) {
+    diff_maps(
+        old,
+        new,
+        |name, variant| format!("`{name}{}` (index {})", variant.signature(), variant.index),
+        |name, old_variant, new_variant| {
+            let mut details = Vec::new();
+            if old_variant.index != new_variant.index {
+                details.push(format!(
+                    "index {} -> {}",
+                    old_variant.index, new_variant.index
+                ));
+            }
+            if old_variant.fields != new_variant.fields {
+                details.push(format!(
+                    "`{}` -> `{}`",
+                    old_variant.signature(),
+                    new_variant.signature()
+                ));
+            }
+            (!details.is_empty()).then(|| {
+                (
+                    format!("`{name}`: {}", details.join(", ")),
+                    old_variant.index != new_variant.index
+                        || old_variant.field_types() != new_variant.field_types(),
+                )
+            })
+        },
+        change,
+    );
+}
+
+/// Report the added, removed and changed items of a pallet, a removed item is always breaking
+fn diff_maps<T: PartialEq>(
+    old: &BTreeMap<String, T>,
+    new: &BTreeMap<String, T>,
+    describe: impl Fn(&str, &T) -> String,
+    compare: impl Fn(&str, &T, &T) -> Option<(String, bool)>,
+    mut change: impl FnMut(String, bool),
+) {
+    for (name, new_item) in new {
+        if !old.contains_key(name) {
+            change(format!("Added {}", describe(name, new_item)), false);
+        }
+    }
+    for (name, old_item) in old {
+        match new.get(name) {
+            None => change(format!("Removed {}", describe(name, old_item)), true),
+            Some(new_item) if new_item != old_item => {
+                if let Some((description, breaking)) = compare(name, old_item, new_item) {
+                    change(format!("Changed {description}"), breaking);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// Compare the weights of the user calls of the new runtime, as percentages of the block weight
+fn diff_weights(old: &Weights, new: &Weights, runtime: &Runtime) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for (pallet, items) in runtime {
+        for call in items.calls.keys() {
+            if !CallCategory::is_user(pallet, call) {
+                continue;
+            }
+            let weight = |weights: &Weights| {
+                weights
+                    .get(pallet)
+                    .and_then(|calls| calls.get(call))
+                    .map(|weight| weight.relative_weight)
+            };
+            if let (Some(old_weight), Some(new_weight)) = (weight(old), weight(new))
+                && format!("{old_weight:.4}") != format!("{new_weight:.4}")
+            {
+                changes.push(Change {
+                    pallet: pallet.clone(),
+                    section: Section::Weights,
+                    description: format!(
+                        "`{call}`: {old_weight:.4}% -> {new_weight:.4}% ({:+.1}%)",
+                        (new_weight - old_weight) / old_weight * 100.
+                    ),
+                    breaking: false,
+                });
+            }
+        }
+    }
+    changes
+}
+
+fn render(changes: &[Change]) -> String {
+    let mut changelog = String::from("# Runtime changelog\n");
+    if changes.is_empty() {
+        changelog.push_str("\nNo difference between the runtimes.\n");
+        return changelog;
+    }
+
+    changelog.push_str("\n## Breaking changes\n\n");
+    if !changes.iter().any(|change| change.breaking) {
+        changelog.push_str("None.\n");
+    }
+    for change in changes.iter().filter(|change| change.breaking) {
+        let _ = writeln!(changelog, "- {}: {}", change.pallet, change.description);
+    }
+
+    let mut by_pallet = BTreeMap::<&str, BTreeMap<Section, Vec<&Change>>>::new();
+    for change in changes {
+        by_pallet
+            .entry(&change.pallet)
+            .or_default()
+            .entry(change.section)
+            .or_default()
+            .push(change);
+    }
+    for (pallet, sections) in by_pallet {
+        let _ = write!(changelog, "\n## {pallet}\n");
+        for (section, changes) in sections {
+            let _ = write!(changelog, "\n### {}\n\n", section.title());
+            for change in changes {
+                let flag = if change.breaking { "**BREAKING** " } else { "" };
+                let _ = writeln!(changelog, "- {flag}{}", change.description);
+            }
+        }
+    }
+    changelog
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Constant, PalletItems, Runtime, Section, Variant, diff_runtimes, render};
+
+    fn call(index: u8, fields: &[(&str, &str)]) -> Variant {
+        Variant {
+            index,
+            fields: fields
+                .iter()
+                .map(|(name, type_name)| (name.to_string(), type_name.to_string()))
+                .collect(),
+        }
+    }
+
+    fn runtime(
+        calls: &[(&str, Variant)],
+        storages: &[(&str, &str)],
+        constants: &[(&str, &str)],
+    ) -> Runtime {
+        let mut items = PalletItems {
+            index: 41,
+            ..Default::default()
+        };
+        for (name, variant) in calls {
+            items.calls.insert(name.to_string(), variant.clone());
+        }
+        for (name, signature) in storages {
+            items
+                .storages
+                .insert(name.to_string(), signature.to_string());
+        }
+        for (name, value) in constants {
+            items.constants.insert(
+                name.to_string(),
+                Constant {
+                    type_name: "u32".to_string(),
+                    value: value.to_string(),
+                },
+            );
+        }
+        Runtime::from([("Identity".to_string(), items)])
+    }
+
+    #[test]
+    fn breaking_changes_are_flagged() {
+        let old = runtime(
+            &[
+                ("create_identity", call(0, &[("owner_key", "AccountId32")])),
+                ("confirm_identity", call(1, &[("idty_name", "IdtyName")])),
+                ("revoke_identity", call(2, &[("idty_index", "u32")])),
+            ],
+            &[("Identities", "[Twox64Concat] u32 => Option<IdtyValue>")],
+            &[("ConfirmPeriod", "14400")],
+        );
+        let new = runtime(
+            &[
+                ("create_identity", call(0, &[("owner", "AccountId32")])),
+                ("confirm_identity", call(3, &[("idty_name", "IdtyName")])),
+                ("link_account", call(4, &[("account_id", "AccountId32")])),
+            ],
+            &[
+                ("Identities", "[Twox64Concat] u32 => Option<IdtyValue>"),
+                ("CounterForIdentities", "u32"),
+            ],
+            &[("ConfirmPeriod", "28800")],
+        );
+
+        let changes = diff_runtimes(&old, &new);
+        let changes = changes
+            .iter()
+            .map(|change| (change.section, change.description.as_str(), change.breaking))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    Section::Calls,
+                    "Added `link_account(account_id: AccountId32)` (index 4)",
+                    false
+                ),
+                (
+                    Section::Calls,
+                    "Changed `confirm_identity`: index 1 -> 3",
+                    true
+                ),
+                (
+                    Section::Calls,
+                    "Changed `create_identity`: `(owner_key: AccountId32)` -> `(owner: AccountId32)`",
+                    false
+                ),
+                (
+                    Section::Calls,
+                    "Removed `revoke_identity(idty_index: u32)` (index 2)",
+                    true
+                ),
+                (
+                    Section::Storages,
+                    "Added `CounterForIdentities`: `u32`",
+                    false
+                ),
+                (
+                    Section::Constants,
+                    "Changed `ConfirmPeriod`: 14400 -> 28800",
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn argument_type_change_is_breaking() {
+        let old = runtime(&[("transfer", call(0, &[("value", "u64")]))], &[], &[]);
+        let new = runtime(
+            &[("transfer", call(0, &[("value", "Compact<u64>")]))],
+            &[],
+            &[],
+        );
+
+        let changes = diff_runtimes(&old, &new);
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].breaking);
+        assert!(
+            render(&changes).contains(
+                "## Breaking changes\n\n- Identity: Changed `transfer`: `(value: u64)` -> `(value: Compact<u64>)`\n"
+            )
+        );
+    }
+
+    #[test]
+    fn same_runtimes_have_no_changes() {
+        let runtime = runtime(&[("transfer", call(0, &[("value", "u64")]))], &[], &[]);
+
+        let changes = diff_runtimes(&runtime, &runtime);
+
+        assert!(changes.is_empty());
+        assert_eq!(
+            render(&changes),
+            "# Runtime changelog\n\nNo difference between the runtimes.\n"
+        );
+    }
+}
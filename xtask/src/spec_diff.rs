@@ -0,0 +1,428 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use anyhow::{Context, Result, bail};
+use codec::Decode;
+use frame_metadata::{
+    RuntimeMetadata, RuntimeMetadataPrefixed,
+    v16::{RuntimeMetadataV16, StorageEntryMetadata, StorageEntryType, StorageHasher},
+};
+use scale_info::{PortableRegistry, TypeDef, form::PortableForm};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::BufReader,
+    path::Path,
+};
+
+/// Decoded values of a chain spec, by pallet, then by storage item, then by key.
+type Storage = BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>;
+
+/// Values longer than this are summarized by their length and hash.
+const MAX_VALUE_LEN: usize = 256;
+
+#[derive(Default)]
+struct ItemDiff {
+    added: Vec<(String, String)>,
+    removed: Vec<(String, String)>,
+    changed: Vec<(String, String, String)>,
+}
+
+impl ItemDiff {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Print a per pallet, per storage item diff between two raw or plain chain specs
+pub(super) fn diff_specs(
+    old: &Path,
+    new: &Path,
+    metadata: Option<&Path>,
+    new_metadata: Option<&Path>,
+) -> Result<()> {
+    let old_metadata = metadata.map(load_metadata).transpose()?;
+    let new_metadata = match new_metadata {
+        Some(path) => Some(load_metadata(path)?),
+        None => old_metadata.clone(),
+    };
+    let old_spec = load_spec(old)?;
+    let new_spec = load_spec(new)?;
+
+    let raw_top = |spec: &serde_json::Value| spec.pointer("/genesis/raw/top").cloned();
+    let (mut old_storage, mut new_storage) = match (raw_top(&old_spec), raw_top(&new_spec)) {
+        (Some(old_top), Some(new_top)) => {
+            let (Some(old_metadata), Some(new_metadata)) = (&old_metadata, &new_metadata) else {
+                bail!("runtime metadata is required to decode raw chain specs")
+            };
+            (
+                decode_raw_storage(&old_top, old_metadata)?,
+                decode_raw_storage(&new_top, new_metadata)?,
+            )
+        }
+        (None, None) => (plain_storage(&old_spec)?, plain_storage(&new_spec)?),
+        _ => bail!("cannot compare a raw chain spec with a plain one"),
+    };
+    if let (Some(old_metadata), Some(new_metadata)) = (&old_metadata, &new_metadata) {
+        add_constants(&mut old_storage, old_metadata);
+        add_constants(&mut new_storage, new_metadata);
+    }
+
+    let diff = diff_storage(&old_storage, &new_storage);
+    if diff.is_empty() {
+        println!("No difference between the chain specs");
+        return Ok(());
+    }
+    for ((pallet, item), item_diff) in &diff {
+        println!(
+            "{pallet}.{item}: {} added, {} removed, {} changed",
+            item_diff.added.len(),
+            item_diff.removed.len(),
+            item_diff.changed.len()
+        );
+    }
+    for ((pallet, item), item_diff) in &diff {
+        println!("\n== {pallet}.{item} ==");
+        for (key, value) in &item_diff.added {
+            println!("+ {}", format_entry(key, value));
+        }
+        for (key, value) in &item_diff.removed {
+            println!("- {}", format_entry(key, value));
+        }
+        for (key, old_value, new_value) in &item_diff.changed {
+            println!("~ {}", format_entry(key, old_value));
+            println!("  -> {new_value}");
+        }
+    }
+    Ok(())
+}
+
+fn format_entry(key: &str, value: &str) -> String {
+    match (key.is_empty(), value.is_empty()) {
+        (true, _) => value.to_owned(),
+        (false, true) => key.to_owned(),
+        (false, false) => format!("{key}: {value}"),
+    }
+}
+
+pub(super) fn load_metadata(path: &Path) -> Result<RuntimeMetadataV16> {
+    let bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read metadata file {}", path.display()))?;
+    let metadata = RuntimeMetadataPrefixed::decode(&mut &bytes[..])
+        .with_context(|| "Failed to decode metadata")?;
+    if let RuntimeMetadata::V16(metadata_v16) = metadata.1 {
+        Ok(metadata_v16)
+    } else {
+        bail!("unsupported metadata version")
+    }
+}
+
+fn load_spec(path: &Path) -> Result<serde_json::Value> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open chain spec {}", path.display()))?;
+    serde_json::from_reader(BufReader::new(file))
+        .with_context(|| format!("Failed to parse chain spec {}", path.display()))
+}
+
+fn diff_storage(old: &Storage, new: &Storage) -> BTreeMap<(String, String), ItemDiff> {
+    let empty = BTreeMap::new();
+    let mut diff = BTreeMap::new();
+    let pallets = old.keys().chain(new.keys()).collect::<BTreeSet<_>>();
+    for pallet in pallets {
+        let old_items = old.get(pallet).unwrap_or(&empty);
+        let new_items = new.get(pallet).unwrap_or(&empty);
+        let items = old_items
+            .keys()
+            .chain(new_items.keys())
+            .collect::<BTreeSet<_>>();
+        for item in items {
+            let old_entries = old_items.get(item).cloned().unwrap_or_default();
+            let new_entries = new_items.get(item).cloned().unwrap_or_default();
+            let mut item_diff = ItemDiff::default();
+            for (key, old_value) in &old_entries {
+                match new_entries.get(key) {
+                    None => item_diff.removed.push((key.clone(), old_value.clone())),
+                    Some(new_value) if new_value != old_value => {
+                        item_diff
+                            .changed
+                            .push((key.clone(), old_value.clone(), new_value.clone()))
+                    }
+                    Some(_) => {}
+                }
+            }
+            for (key, new_value) in &new_entries {
+                if !old_entries.contains_key(key) {
+                    item_diff.added.push((key.clone(), new_value.clone()));
+                }
+            }
+            if !item_diff.is_empty() {
+                diff.insert((pallet.clone(), item.clone()), item_diff);
+            }
+        }
+    }
+    diff
+}
+
+/// Decode the raw storage of a chain spec, keys which are not part of the metadata are kept raw
+fn decode_raw_storage(top: &serde_json::Value, metadata: &RuntimeMetadataV16) -> Result<Storage> {
+    let top = top
+        .as_object()
+        .with_context(|| "invalid raw spec file: top is not an object")?;
+    // storage entries by their prefix
+    let mut entries = BTreeMap::new();
+    for pallet in &metadata.pallets {
+        if let Some(storage) = &pallet.storage {
+            for entry in &storage.entries {
+                let mut prefix = sp_crypto_hashing::twox_128(storage.prefix.as_bytes()).to_vec();
+                prefix.extend(sp_crypto_hashing::twox_128(entry.name.as_bytes()));
+                entries.insert(prefix, (pallet.name.clone(), entry));
+            }
+        }
+    }
+
+    let mut decoded = Storage::new();
+    for (key, value) in top {
+        let key_bytes = hex::decode(key.trim_start_matches("0x"))
+            .with_context(|| format!("invalid raw storage key {key}"))?;
+        let value = value
+            .as_str()
+            .with_context(|| format!("invalid raw storage value at {key}"))?;
+        let value_bytes = hex::decode(value.trim_start_matches("0x"))
+            .with_context(|| format!("invalid raw storage value at {key}"))?;
+        let (pallet, item, key, value) =
+            match key_bytes.get(..32).and_then(|prefix| entries.get(prefix)) {
+                Some((pallet, entry)) => (
+                    pallet.clone(),
+                    entry.name.clone(),
+                    decode_key(&key_bytes[32..], entry, &metadata.types),
+                    decode_value(&value_bytes, value_type_id(entry), &metadata.types),
+                ),
+                None => (
+                    "Raw".to_owned(),
+                    String::from_utf8(key_bytes.clone())
+                        .ok()
+                        .filter(|key| key.starts_with(':'))
+                        .unwrap_or_else(|| "unknown".to_owned()),
+                    key.clone(),
+                    summarize(value.to_owned()),
+                ),
+            };
+        decoded
+            .entry(pallet)
+            .or_default()
+            .entry(item)
+            .or_default()
+            .insert(key, value);
+    }
+    Ok(decoded)
+}
+
+fn value_type_id(entry: &StorageEntryMetadata<PortableForm>) -> u32 {
+    match &entry.ty {
+        StorageEntryType::Plain(ty) => ty.id,
+        StorageEntryType::Map { value, .. } => value.id,
+    }
+}
+
+/// Decode the keys of a storage map, the part which cannot be decoded is kept raw
+fn decode_key(
+    mut key: &[u8],
+    entry: &StorageEntryMetadata<PortableForm>,
+    types: &PortableRegistry,
+) -> String {
+    let StorageEntryType::Map {
+        hashers,
+        key: key_ty,
+        ..
+    } = &entry.ty
+    else {
+        return String::new();
+    };
+    let key_types = if hashers.len() == 1 {
+        vec![key_ty.id]
+    } else {
+        match types.resolve(key_ty.id).map(|ty| &ty.type_def) {
+            Some(TypeDef::Tuple(tuple)) => tuple.fields.iter().map(|field| field.id).collect(),
+            _ => Vec::new(),
+        }
+    };
+    let mut parts = Vec::new();
+    for (hasher, ty) in hashers.iter().zip(key_types) {
+        let hash_len = match hasher {
+            StorageHasher::Blake2_128Concat => 16,
+            StorageHasher::Twox64Concat => 8,
+            StorageHasher::Identity => 0,
+            // the key cannot be recovered from its hash
+            _ => break,
+        };
+        if key.len() < hash_len {
+            break;
+        }
+        key = &key[hash_len..];
+        match scale_value::scale::decode_as_type(&mut key, &ty, types) {
+            Ok(value) => parts.push(value.to_string()),
+            Err(_) => break,
+        }
+    }
+    if !key.is_empty() {
+        parts.push(format!("0x{}", hex::encode(key)));
+    }
+    parts.join(", ")
+}
+
+pub(super) fn decode_value(bytes: &[u8], ty: u32, types: &PortableRegistry) -> String {
+    let value = scale_value::scale::decode_as_type(&mut &*bytes, &ty, types)
+        .map(|value| value.to_string())
+        .unwrap_or_else(|_| format!("0x{}", hex::encode(bytes)));
+    summarize(value)
+}
+
+fn summarize(value: String) -> String {
+    if value.len() > MAX_VALUE_LEN {
+        format!(
+            "<{} bytes, blake2_256 0x{}>",
+            value.len(),
+            hex::encode(sp_crypto_hashing::blake2_256(value.as_bytes()))
+        )
+    } else {
+        value
+    }
+}
+
+/// Flatten the genesis config of a plain chain spec: array elements are keyed by their value
+fn plain_storage(spec: &serde_json::Value) -> Result<Storage> {
+    let runtime_genesis = spec
+        .pointer("/genesis/runtimeGenesis")
+        .with_context(|| "invalid plain spec file: missing field runtimeGenesis")?;
+    let config = runtime_genesis
+        .get("patch")
+        .or_else(|| runtime_genesis.get("config"))
+        .and_then(|config| config.as_object())
+        .with_context(|| "invalid plain spec file: missing genesis config")?;
+
+    let mut storage = Storage::new();
+    for (pallet, pallet_config) in config {
+        let items = storage.entry(pallet.clone()).or_default();
+        let Some(fields) = pallet_config.as_object() else {
+            items
+                .entry(String::new())
+                .or_default()
+                .insert(String::new(), summarize(pallet_config.to_string()));
+            continue;
+        };
+        for (field, value) in fields {
+            let entries = items.entry(field.clone()).or_default();
+            match value {
+                serde_json::Value::Array(elements) => {
+                    for element in elements {
+                        entries.insert(summarize(element.to_string()), String::new());
+                    }
+                }
+                _ => {
+                    entries.insert(String::new(), summarize(value.to_string()));
+                }
+            }
+        }
+    }
+    if let Some(code) = runtime_genesis.get("code").and_then(|code| code.as_str()) {
+        storage
+            .entry("Raw".to_owned())
+            .or_default()
+            .entry(":code".to_owned())
+            .or_default()
+            .insert(String::new(), summarize(code.to_owned()));
+    }
+    Ok(storage)
+}
+
+fn add_constants(storage: &mut Storage, metadata: &RuntimeMetadataV16) {
+    for pallet in &metadata.pallets {
+        for constant in &pallet.constants {
+            storage
+                .entry(pallet.name.clone())
+                .or_default()
+                .entry("constants".to_owned())
+                .or_default()
+                .insert(
+                    constant.name.clone(),
+                    decode_value(&constant.value, constant.ty.id, &metadata.types),
+                );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Storage, diff_storage, plain_storage};
+
+    fn plain_spec(identities: &[&str], keys: &[&str], ud: u64) -> serde_json::Value {
+        serde_json::json!({
+            "genesis": {
+                "runtimeGenesis": {
+                    "code": "0x00",
+                    "patch": {
+                        "identity": { "identities": identities },
+                        "session": { "keys": keys },
+                        "universalDividend": { "initialMonetaryMass": 0, "ud": ud },
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn diff_plain_specs() {
+        let old: Storage =
+            plain_storage(&plain_spec(&["alice", "bob"], &["alice_keys"], 1000)).unwrap();
+        let new: Storage = plain_storage(&plain_spec(
+            &["alice", "charlie"],
+            &["alice_new_keys"],
+            1000,
+        ))
+        .unwrap();
+
+        let diff = diff_storage(&old, &new);
+
+        let identities = &diff[&("identity".to_owned(), "identities".to_owned())];
+        assert_eq!(
+            identities.added,
+            vec![("\"charlie\"".to_owned(), String::new())]
+        );
+        assert_eq!(
+            identities.removed,
+            vec![("\"bob\"".to_owned(), String::new())]
+        );
+        let keys = &diff[&("session".to_owned(), "keys".to_owned())];
+        assert_eq!(keys.added.len(), 1);
+        assert_eq!(keys.removed.len(), 1);
+        assert!(!diff.contains_key(&("universalDividend".to_owned(), "ud".to_owned())));
+        assert!(!diff.contains_key(&("Raw".to_owned(), ":code".to_owned())));
+    }
+
+    #[test]
+    fn diff_changed_value() {
+        let old: Storage = plain_storage(&plain_spec(&[], &[], 1000)).unwrap();
+        let new: Storage = plain_storage(&plain_spec(&[], &[], 1148)).unwrap();
+
+        let diff = diff_storage(&old, &new);
+
+        assert_eq!(diff.len(), 1);
+        let ud = &diff[&("universalDividend".to_owned(), "ud".to_owned())];
+        assert_eq!(
+            ud.changed,
+            vec![(String::new(), "1000".to_owned(), "1148".to_owned())]
+        );
+    }
+}
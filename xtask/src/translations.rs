@@ -0,0 +1,335 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+//! Gettext translation catalogs of the runtime documentation.
+//!
+//! Entries are keyed by a context (`call`, `event`, `error` or `template`) and an identifier
+//! (`Pallet.item`, or the english text for the texts of the templates). Fuzzy and empty
+//! translations are ignored, like gettext does.
+
+use anyhow::{Context, Result, bail};
+use std::{collections::HashMap, fmt::Write, path::Path};
+
+/// Context of the fixed texts of the documentation templates
+pub(super) const TEMPLATE_CONTEXT: &str = "template";
+
+/// Translations of one language
+pub(super) struct Catalog {
+    pub(super) language: String,
+    /// (context, identifier) -> translation
+    entries: HashMap<(String, String), String>,
+}
+
+/// An entry of the catalog template, with the english text to translate
+pub(super) struct SourceEntry {
+    pub(super) context: &'static str,
+    pub(super) id: String,
+    pub(super) source: String,
+}
+
+impl Catalog {
+    /// Load the `<language>.po` catalogs of a folder, sorted by language
+    pub(super) fn load_all(dir: &Path) -> Result<Vec<Self>> {
+        let mut catalogs = Vec::new();
+        if !dir.exists() {
+            return Ok(catalogs);
+        }
+        for entry in std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read folder {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "po") {
+                let language = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .with_context(|| format!("Invalid catalog name {}", path.display()))?
+                    .to_owned();
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read catalog {}", path.display()))?;
+                catalogs.push(
+                    Self::parse(language, &content)
+                        .with_context(|| format!("Invalid catalog {}", path.display()))?,
+                );
+            }
+        }
+        catalogs.sort_by(|a, b| a.language.cmp(&b.language));
+        Ok(catalogs)
+    }
+
+    pub(super) fn parse(language: String, content: &str) -> Result<Self> {
+        #[derive(Default)]
+        struct Entry {
+            context: String,
+            id: Option<String>,
+            translation: String,
+            fuzzy: bool,
+        }
+        #[derive(Clone, Copy)]
+        enum Field {
+            Context,
+            Id,
+            Translation,
+        }
+
+        let mut entries = HashMap::new();
+        let mut push = |entry: Entry| {
+            if let Some(id) = entry.id
+                && !id.is_empty()
+                && !entry.translation.is_empty()
+                && !entry.fuzzy
+            {
+                entries.insert((entry.context, id), entry.translation);
+            }
+        };
+
+        let mut entry = Entry::default();
+        let mut field = None;
+        // flags are written before the entry they apply to
+        let mut fuzzy = false;
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                if comment.starts_with(',') && comment.contains("fuzzy") {
+                    fuzzy = true;
+                }
+                continue;
+            }
+            let (keyword, value) = match line.split_once(' ') {
+                Some((keyword, value)) if keyword.starts_with("msg") => (Some(keyword), value),
+                _ => (None, line),
+            };
+            let value = unquote(value)
+                .with_context(|| format!("line {}: invalid string", line_number + 1))?;
+            // an entry starts with its context, or with its identifier when it has no context
+            if keyword == Some("msgctxt") || (keyword == Some("msgid") && entry.id.is_some()) {
+                push(std::mem::take(&mut entry));
+                entry.fuzzy = std::mem::take(&mut fuzzy);
+            }
+            match keyword {
+                Some("msgctxt") => {
+                    entry.context = value;
+                    field = Some(Field::Context);
+                }
+                Some("msgid") => {
+                    entry.id = Some(value);
+                    field = Some(Field::Id);
+                }
+                Some("msgstr") => {
+                    entry.translation = value;
+                    field = Some(Field::Translation);
+                }
+                Some(keyword) => bail!("line {}: unknown keyword {keyword}", line_number + 1),
+                // continuation of the previous string
+                None => match field {
+                    Some(Field::Context) => entry.context.push_str(&value),
+                    Some(Field::Id) => entry.id.get_or_insert_default().push_str(&value),
+                    Some(Field::Translation) => entry.translation.push_str(&value),
+                    None => bail!("line {}: string outside of an entry", line_number + 1),
+                },
+            }
+        }
+        push(entry);
+
+        Ok(Self { language, entries })
+    }
+
+    /// Translation of an entry, if any
+    pub(super) fn get(&self, context: &str, id: &str) -> Option<&str> {
+        self.entries
+            .get(&(context.to_owned(), id.to_owned()))
+            .map(String::as_str)
+    }
+
+    /// Translations of the fixed texts of the templates, by english text
+    pub(super) fn template_texts(&self) -> HashMap<String, String> {
+        self.entries
+            .iter()
+            .filter(|((context, _), _)| context == TEMPLATE_CONTEXT)
+            .map(|((_, id), translation)| (id.clone(), translation.clone()))
+            .collect()
+    }
+}
+
+/// Tera function translating the fixed texts of the templates: `t(key="english text")`.
+///
+/// `{name}` placeholders of the text are replaced by the other arguments of the function.
+pub(super) fn translate_function(
+    texts: HashMap<String, String>,
+) -> impl Fn(&HashMap<String, tera::Value>) -> tera::Result<tera::Value> + Send + Sync {
+    move |args: &HashMap<String, tera::Value>| {
+        let key = args
+            .get("key")
+            .and_then(|key| key.as_str())
+            .ok_or_else(|| tera::Error::msg("t: missing string argument `key`"))?;
+        let mut text = texts.get(key).cloned().unwrap_or_else(|| key.to_owned());
+        for (name, value) in args.iter().filter(|(name, _)| *name != "key") {
+            let value = match value {
+                tera::Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            text = text.replace(&format!("{{{name}}}"), &value);
+        }
+        Ok(tera::Value::String(text))
+    }
+}
+
+/// Texts of the `t(key="...")` calls of a template
+pub(super) fn template_keys(template: &str) -> Vec<String> {
+    template
+        .split("t(key=\"")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('"').map(|(key, _)| key.to_owned()))
+        .collect()
+}
+
+/// Render the catalog template (`.pot`) from which translators create or update their catalog
+pub(super) fn render_template(entries: &[SourceEntry]) -> String {
+    let mut pot =
+        String::from("msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+    for entry in entries {
+        pot.push('\n');
+        for line in entry.source.lines() {
+            let _ = writeln!(pot, "#. {line}");
+        }
+        let _ = writeln!(pot, "msgctxt {}", quote(entry.context));
+        let _ = writeln!(pot, "msgid {}", quote(&entry.id));
+        pot.push_str("msgstr \"\"\n");
+    }
+    pot
+}
+
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+fn unquote(value: &str) -> Option<String> {
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unquoted = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next()? {
+                'n' => unquoted.push('\n'),
+                't' => unquoted.push('\t'),
+                c => unquoted.push(c),
+            }
+        } else {
+            unquoted.push(c);
+        }
+    }
+    Some(unquoted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Catalog, SourceEntry, render_template, template_keys, translate_function};
+    use std::collections::HashMap;
+
+    #[test]
+    fn parse_catalog() {
+        let catalog = Catalog::parse(
+            "fr".to_owned(),
+            r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+#. Sender account balance is too low.
+msgctxt "error"
+msgid "Balances.InsufficientBalance"
+msgstr ""
+"Le solde du compte est\n"
+"insuffisant."
+
+#, fuzzy
+msgctxt "error"
+msgid "Balances.ExistentialDeposit"
+msgstr "Dépôt existentiel"
+
+msgctxt "event"
+msgid "Balances.Transfer"
+msgstr ""
+
+msgctxt "template"
+msgid "no args"
+msgstr "pas d'arguments"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.get("error", "Balances.InsufficientBalance"),
+            Some("Le solde du compte est\ninsuffisant.")
+        );
+        assert_eq!(catalog.get("error", "Balances.ExistentialDeposit"), None);
+        assert_eq!(catalog.get("event", "Balances.Transfer"), None);
+        assert_eq!(catalog.get("call", "no args"), None);
+        assert_eq!(
+            catalog.template_texts(),
+            HashMap::from([("no args".to_owned(), "pas d'arguments".to_owned())])
+        );
+    }
+
+    #[test]
+    fn rendered_template_can_be_parsed() {
+        let pot = render_template(&[SourceEntry {
+            context: "call",
+            id: "Balances.transfer_keep_alive".to_owned(),
+            source: "Same as the [`transfer_allow_death`] call,\nbut with a \"check\".".to_owned(),
+        }]);
+
+        assert!(pot.contains("#. but with a \"check\".\nmsgctxt \"call\"\n"));
+        let translated = pot.strip_suffix("msgstr \"\"\n").unwrap().to_owned()
+            + "msgstr \"Comme \\\"transfer_allow_death\\\"\"\n";
+        let catalog = Catalog::parse("fr".to_owned(), &translated).unwrap();
+        assert_eq!(
+            catalog.get("call", "Balances.transfer_keep_alive"),
+            Some("Comme \"transfer_allow_death\"")
+        );
+    }
+
+    #[test]
+    fn translate_template_texts() {
+        let template = r#"{{ t(key="There are **{events}** events.", events=event_counter) }} {{ t(key="no args") }}"#;
+        assert_eq!(
+            template_keys(template),
+            vec!["There are **{events}** events.", "no args"]
+        );
+
+        let t = translate_function(HashMap::from([(
+            "There are **{events}** events.".to_owned(),
+            "Il y a **{events}** événements.".to_owned(),
+        )]));
+        let args = |key: &str| {
+            HashMap::from([
+                ("key".to_owned(), tera::Value::from(key)),
+                ("events".to_owned(), tera::Value::from(12)),
+            ])
+        };
+        assert_eq!(
+            t(&args("There are **{events}** events.")).unwrap(),
+            tera::Value::from("Il y a **12** événements.")
+        );
+        assert_eq!(t(&args("no args")).unwrap(), tera::Value::from("no args"));
+    }
+}
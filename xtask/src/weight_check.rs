@@ -0,0 +1,315 @@
+// Copyright 2021 Axiom-Team
+//
+// This file is part of Duniter-v2S.
+//
+// Duniter-v2S is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, version 3 of the License.
+//
+// Duniter-v2S is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with Duniter-v2S. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    gen_doc::{CallCategory, get_max_weight_from_metadata_v16, get_weights},
+    spec_diff::load_metadata,
+};
+use anyhow::{Result, bail};
+use frame_metadata::v16::RuntimeMetadataV16;
+use scale_info::TypeDef;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::Path,
+};
+use weightanalyzer::WeightInfo;
+
+/// Weights of the benchmarked functions, by pallet name then function name.
+type Weights = HashMap<String, HashMap<String, WeightInfo>>;
+
+/// Worst case weight of a benchmarked function in the baseline and in the new weights.
+#[derive(Debug, PartialEq)]
+struct WeightDiff {
+    old: Option<(u128, f64)>,
+    new: Option<(u128, f64)>,
+    user_call: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Failure {
+    /// The call takes more than the allowed fraction of a block
+    TooHeavy { relative_weight: f64 },
+    /// The weight of the call increased more than allowed
+    Regression { increase: f64 },
+}
+
+/// Compare weight files against a baseline, failing when a user call is too heavy or regressed
+pub(super) fn check_weights(
+    baseline: &Path,
+    weights: &Path,
+    metadata: &Path,
+    max_block_fraction: f64,
+    max_regression: f64,
+) -> Result<()> {
+    let metadata = load_metadata(metadata)?;
+    let user_calls = user_calls(&metadata);
+    let max_weight = get_max_weight_from_metadata_v16(metadata)?;
+    let old_weights = get_weights(baseline, max_weight)?;
+    let new_weights = get_weights(weights, max_weight)?;
+
+    let diff = diff_weights(&old_weights, &new_weights, &user_calls);
+    if diff.is_empty() {
+        println!("No weight difference with the baseline");
+    }
+    for ((pallet, function), weight_diff) in &diff {
+        let user_call = if weight_diff.user_call {
+            " (user call)"
+        } else {
+            ""
+        };
+        match (weight_diff.old, weight_diff.new) {
+            (None, Some((_, new))) => println!("+ {pallet}.{function}: {new:.4}%{user_call}"),
+            (Some((_, old)), None) => println!("- {pallet}.{function}: {old:.4}%{user_call}"),
+            (Some((old_weight, old)), Some((new_weight, new))) => println!(
+                "~ {pallet}.{function}: {old:.4}% -> {new:.4}% ({:+.1}%){user_call}",
+                increase(old_weight, new_weight) * 100.
+            ),
+            (None, None) => {}
+        }
+    }
+
+    let failures = check_user_calls(
+        &old_weights,
+        &new_weights,
+        &user_calls,
+        max_block_fraction,
+        max_regression,
+    );
+    if failures.is_empty() {
+        return Ok(());
+    }
+    println!();
+    for ((pallet, call), failure) in &failures {
+        match failure {
+            Failure::TooHeavy { relative_weight } => println!(
+                "FAIL {pallet}.{call}: takes {relative_weight:.4}% of a block, more than {}%",
+                max_block_fraction * 100.
+            ),
+            Failure::Regression { increase } => println!(
+                "FAIL {pallet}.{call}: weight increased by {:.1}%, more than {}%",
+                increase * 100.,
+                max_regression * 100.
+            ),
+        }
+    }
+    bail!("{} weight checks failed", failures.len())
+}
+
+/// User calls of the runtime, by pallet name and call name
+fn user_calls(metadata: &RuntimeMetadataV16) -> BTreeSet<(String, String)> {
+    let mut user_calls = BTreeSet::new();
+    for pallet in &metadata.pallets {
+        let Some(calls) = &pallet.calls else {
+            continue;
+        };
+        if let Some(TypeDef::Variant(calls)) =
+            metadata.types.resolve(calls.ty.id).map(|ty| &ty.type_def)
+        {
+            for call in &calls.variants {
+                if CallCategory::is_user(&pallet.name, &call.name) {
+                    user_calls.insert((pallet.name.clone(), call.name.clone()));
+                }
+            }
+        }
+    }
+    user_calls
+}
+
+/// Added, removed and changed worst case weights of all the benchmarked functions, including
+/// hooks and internal functions like `do_evaluation_overhead`
+fn diff_weights(
+    old: &Weights,
+    new: &Weights,
+    user_calls: &BTreeSet<(String, String)>,
+) -> BTreeMap<(String, String), WeightDiff> {
+    let functions = |weights: &Weights| {
+        weights
+            .iter()
+            .flat_map(|(pallet, functions)| {
+                functions.iter().map(move |(function, weight)| {
+                    (
+                        (pallet.clone(), function.clone()),
+                        (weight.weight, weight.relative_weight),
+                    )
+                })
+            })
+            .collect::<BTreeMap<_, _>>()
+    };
+    let old = functions(old);
+    let new = functions(new);
+    old.keys()
+        .chain(new.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|key| {
+            let (old, new) = (old.get(key).copied(), new.get(key).copied());
+            let changed = old.map(|(weight, _)| weight) != new.map(|(weight, _)| weight);
+            changed.then(|| {
+                (
+                    key.clone(),
+                    WeightDiff {
+                        old,
+                        new,
+                        user_call: user_calls.contains(key),
+                    },
+                )
+            })
+        })
+        .collect()
+}
+
+/// Check the user calls of the new weights against the thresholds
+fn check_user_calls(
+    old: &Weights,
+    new: &Weights,
+    user_calls: &BTreeSet<(String, String)>,
+    max_block_fraction: f64,
+    max_regression: f64,
+) -> Vec<((String, String), Failure)> {
+    let mut failures = Vec::new();
+    for (pallet, call) in user_calls {
+        let weight = |weights: &Weights| {
+            weights
+                .get(pallet)
+                .and_then(|calls| calls.get(call))
+                .cloned()
+        };
+        let Some(new_weight) = weight(new) else {
+            continue;
+        };
+        let key = (pallet.clone(), call.clone());
+        if new_weight.relative_weight > max_block_fraction * 100. {
+            failures.push((
+                key.clone(),
+                Failure::TooHeavy {
+                    relative_weight: new_weight.relative_weight,
+                },
+            ));
+        }
+        if let Some(old_weight) = weight(old) {
+            let increase = increase(old_weight.weight, new_weight.weight);
+            if increase > max_regression {
+                failures.push((key, Failure::Regression { increase }));
+            }
+        }
+    }
+    failures
+}
+
+/// Relative increase from an old weight to a new one
+fn increase(old: u128, new: u128) -> f64 {
+    if old == 0 {
+        return if new == 0 { 0. } else { f64::INFINITY };
+    }
+    (new as f64 - old as f64) / old as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Failure, WeightDiff, Weights, check_user_calls, diff_weights};
+    use std::collections::BTreeSet;
+    use weightanalyzer::WeightInfo;
+
+    /// Weights of a block of 1_000_000
+    fn weights(functions: &[(&str, &str, u128)]) -> Weights {
+        let mut weights = Weights::new();
+        for (pallet, function, weight) in functions {
+            weights.entry(pallet.to_string()).or_default().insert(
+                function.to_string(),
+                WeightInfo {
+                    weight: *weight,
+                    relative_weight: *weight as f64 / 1_000_000. * 100.,
+                },
+            );
+        }
+        weights
+    }
+
+    fn user_calls() -> BTreeSet<(String, String)> {
+        BTreeSet::from([
+            (
+                "Distance".to_owned(),
+                "request_distance_evaluation".to_owned(),
+            ),
+            ("Identity".to_owned(), "create_identity".to_owned()),
+            ("Identity".to_owned(), "confirm_identity".to_owned()),
+        ])
+    }
+
+    #[test]
+    fn diff_all_functions() {
+        let old = weights(&[
+            ("Distance", "request_distance_evaluation", 1_000),
+            ("Distance", "do_evaluation_overhead", 500),
+            ("Identity", "create_identity", 2_000),
+            ("Identity", "on_initialize", 100),
+        ]);
+        let new = weights(&[
+            ("Distance", "request_distance_evaluation", 1_000),
+            ("Distance", "do_evaluation_overhead", 800),
+            ("Identity", "create_identity", 2_100),
+            ("Identity", "confirm_identity", 1_500),
+        ]);
+
+        let diff = diff_weights(&old, &new, &user_calls());
+
+        let key = |pallet: &str, function: &str| (pallet.to_owned(), function.to_owned());
+        assert_eq!(diff.len(), 4);
+        assert_eq!(
+            diff[&key("Distance", "do_evaluation_overhead")],
+            WeightDiff {
+                old: Some((500, 500. / 1_000_000. * 100.)),
+                new: Some((800, 800. / 1_000_000. * 100.)),
+                user_call: false,
+            }
+        );
+        assert!(diff[&key("Identity", "create_identity")].user_call);
+        assert_eq!(diff[&key("Identity", "confirm_identity")].old, None);
+        assert_eq!(diff[&key("Identity", "on_initialize")].new, None);
+    }
+
+    #[test]
+    fn heavy_and_regressed_user_calls_fail() {
+        let old = weights(&[
+            ("Distance", "request_distance_evaluation", 1_000),
+            ("Distance", "do_evaluation_overhead", 500),
+            ("Identity", "create_identity", 2_000),
+        ]);
+        let new = weights(&[
+            ("Distance", "request_distance_evaluation", 1_100),
+            // not a user call
+            ("Distance", "do_evaluation_overhead", 5_000),
+            ("Identity", "create_identity", 300_000),
+            ("Identity", "confirm_identity", 1_500),
+        ]);
+
+        let failures = check_user_calls(&old, &new, &user_calls(), 0.25, 0.2);
+
+        let create_identity = ("Identity".to_owned(), "create_identity".to_owned());
+        assert_eq!(
+            failures,
+            vec![
+                (
+                    create_identity.clone(),
+                    Failure::TooHeavy {
+                        relative_weight: 300_000. / 1_000_000. * 100.
+                    }
+                ),
+                (create_identity, Failure::Regression { increase: 149. }),
+            ]
+        );
+    }
+}